@@ -0,0 +1,355 @@
+//! Times the breakdown and offscreen drawing of each mode with criterion, run with `cargo bench`
+//!
+//! Save a baseline with `cargo bench -- --save-baseline main` and compare a branch against it
+//! with `cargo bench -- --baseline main`, criterion reports each mode that got slower. Pass a
+//! name to only run those, i.e. `cargo bench -- split`. `cargo test --benches` runs each once,
+//! to check they still draw
+
+use buffer_graphics_lib::prelude::*;
+use chrono::{Duration, NaiveDate};
+use countup::count::{
+    CalendarProgress, Fiscal, Intro, IsoWeeks, PreciseAge, Timeline, Totals, Weekdays, YearProgress,
+};
+use countup::event::Event;
+use countup::font::{Glyphs, NumberFont};
+use countup::i18n::strings;
+use countup::render::*;
+use countup::report::{Counting, MonthLength, Moons, Report, Split, Upcoming};
+use countup::roman::roman_date;
+use countup::term::TermProgress;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use std::time::Duration as Elapsed;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("bench dates are valid")
+}
+
+fn modes(c: &mut Criterion) {
+    let start = date(2020, 2, 29);
+    let today = date(2026, 10, 14);
+    c.bench_function("breakdown/report", |b| {
+        b.iter(|| {
+            black_box(Report::new_with(
+                black_box(start),
+                today,
+                None,
+                Counting::Exclusive,
+            ));
+        })
+    });
+    c.bench_function("breakdown/calendar", |b| {
+        b.iter(|| {
+            black_box(Split::between(black_box(start), today));
+            black_box(MonthLength::Calendar.months(black_box(start), 2419));
+        })
+    });
+    c.bench_function("breakdown/weeks", |b| {
+        b.iter(|| {
+            black_box(IsoWeeks::new(black_box(start), today));
+            black_box(Fiscal::new(black_box(start), today, 4));
+            black_box(YearProgress::new(black_box(start), today));
+        })
+    });
+    c.bench_function("breakdown/moons", |b| {
+        b.iter(|| {
+            black_box(Moons::new(black_box(start), today));
+        })
+    });
+
+    let strings = strings("en");
+    let reveal = Reveal {
+        intro: Intro::CountUp,
+        progress: 1.0,
+    };
+    //mid count, as it's drawn during the intro
+    let rolling = Reveal {
+        intro: Intro::CountUp,
+        progress: 0.5,
+    };
+    let palette = Palette::default();
+    let texts = Texts::new(strings);
+    //a gradient photo, bigger than the window so it's shrunk
+    let photo = Image::new(
+        (0..640 * 480)
+            .map(|i| Color::rgb((i % 640 / 3) as u8, (i / 640 / 2) as u8, 128))
+            .collect(),
+        640,
+        480,
+    )
+    .expect("photo");
+    let backdrop = Backdrop::new(photo);
+    let phased: Event = serde_json::from_value(serde_json::json!({
+        "name": "Training",
+        "date": "2026-09-01",
+        "phases": [
+            {"name": "Base", "end": "2026-10-01"},
+            {"name": "Build", "end": "2026-11-01"},
+        ],
+    }))
+    .expect("bench event is valid");
+    let noon = |date: NaiveDate| date.and_hms_opt(12, 0, 0).expect("noon is valid");
+    let age = PreciseAge::new(noon(start), noon(today));
+    let age_lines = age.lines("%d/%m/%Y %H:%M", strings);
+    for scale in [1, 4] {
+        let font = NumberFont::new(Glyphs::large(), scale);
+        let (width, height) = canvas_size(&font, false);
+        let mut buffer = vec![0; width * height * 4];
+        let mut graphics = Graphics::new(&mut buffer, width, height).expect("canvas");
+        let screensaver = Screensaver::new((width, height));
+        let mut mode = |name: &str, draw: &mut dyn FnMut(&mut Graphics)| {
+            c.bench_function(&format!("{name}/x{scale}"), |b| {
+                b.iter(|| draw(&mut graphics))
+            });
+        };
+        mode("split", &mut |graphics| {
+            render_split(
+                graphics,
+                Split::new(2419),
+                Some(SplitRow::Anniversary(138)),
+                "29/02/2020",
+                reveal,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("split-backdrop", &mut |graphics| {
+            render_split(
+                graphics,
+                Split::new(2419),
+                Some(SplitRow::Anniversary(138)),
+                "29/02/2020",
+                reveal,
+                &font,
+                palette,
+                &texts,
+            );
+            backdrop.draw(graphics, palette.background);
+        });
+        for style in [BackgroundStyle::Gradient, BackgroundStyle::Starfield] {
+            mode(&format!("split-{}", style.name()), &mut |graphics| {
+                render_split(
+                    graphics,
+                    Split::new(2419),
+                    Some(SplitRow::Anniversary(138)),
+                    "29/02/2020",
+                    reveal,
+                    &font,
+                    palette,
+                    &texts,
+                );
+                style.draw(graphics, palette.background, 1_792_000_000.5);
+            });
+        }
+        mode("split-screensaver", &mut |graphics| {
+            screensaver.draw(graphics, palette.background, 1_792_000_000.5, |count| {
+                render_split(
+                    count,
+                    Split::new(2419),
+                    Some(SplitRow::Anniversary(138)),
+                    "29/02/2020",
+                    reveal,
+                    &font,
+                    palette,
+                    &texts,
+                )
+            });
+        });
+        mode("split-rolling", &mut |graphics| {
+            render_split(
+                graphics,
+                Split::new(1209),
+                None,
+                "29/02/2020",
+                rolling,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("diff", &mut |graphics| {
+            render_diff(
+                graphics,
+                2419,
+                86,
+                "29/02/2020",
+                reveal,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("segments", &mut |graphics| {
+            render_segments(graphics, 2419, height, palette, &texts)
+        });
+        mode("tminus", &mut |graphics| {
+            render_tminus(
+                graphics,
+                Duration::seconds(1_234_567),
+                "01/01/2030",
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("dual", &mut |graphics| {
+            let side = |days, label| Side {
+                days,
+                reveal,
+                palette,
+                label,
+            };
+            render_dual(
+                graphics,
+                [side(2419, "29/02/2020"), side(53, "01/01/2030")],
+                &font,
+                &texts,
+            )
+        });
+        mode("weeks", &mut |graphics| {
+            render_weeks(
+                graphics,
+                IsoWeeks::new(start, today),
+                reveal,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("fiscal", &mut |graphics| {
+            render_fiscal(
+                graphics,
+                Fiscal::new(start, today, 4),
+                "FY27, started 01/04/2026",
+                reveal,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("moons", &mut |graphics| {
+            render_moons(
+                graphics,
+                Moons::new(start, today),
+                "29/02/2020",
+                reveal,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("year", &mut |graphics| {
+            render_year(
+                graphics,
+                YearProgress::new(start, today),
+                "2026, from 01/01/2026",
+                reveal,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("totals", &mut |graphics| {
+            let totals = Totals::new(
+                start
+                    .and_hms_opt(0, 0, 0)
+                    .expect("midnight is valid")
+                    .and_utc(),
+                today
+                    .and_hms_opt(12, 0, 0)
+                    .expect("noon is valid")
+                    .and_utc(),
+            );
+            render_totals(graphics, totals, "29/02/2020", reveal, palette, &texts)
+        });
+        mode("upcoming", &mut |graphics| {
+            let upcoming = Upcoming::new(start, today, 2419);
+            let dates = [upcoming.hundred, upcoming.thousand, upcoming.anniversary]
+                .map(|ahead| ahead.date.format("%d/%m/%Y").to_string());
+            render_upcoming(graphics, upcoming, &dates, reveal, palette, &texts)
+        });
+        mode("weekdays", &mut |graphics| {
+            let weekdays = Weekdays::new(start, today);
+            let header = strings.started_on(weekdays.start);
+            render_weekdays(graphics, weekdays, &header, reveal, palette, &texts)
+        });
+        mode("calendar", &mut |graphics| {
+            let noon = today.and_hms_opt(12, 0, 0).expect("noon is valid");
+            render_calendar(graphics, CalendarProgress::new(noon), palette, &texts)
+        });
+        mode("term", &mut |graphics| {
+            let progress = TermProgress::InTerm {
+                week: 6,
+                weeks: 13,
+                days_left: 34,
+                on_break: false,
+            };
+            render_term(graphics, progress, "01/09/2026 - 18/12/2026", &texts)
+        });
+        mode("goal", &mut |graphics| {
+            render_goal(graphics, 2419, 3000, "3000 days", palette, &texts)
+        });
+        mode("weeks-and-days", &mut |graphics| {
+            render_weeks_and_days(graphics, 2419, "29/02/2020", reveal, &font, palette, &texts)
+        });
+        mode("timeline", &mut |graphics| {
+            let labels = [
+                String::from("29/02/2020"),
+                String::from("24/07/2027"),
+                String::from("2500 days"),
+            ];
+            render_timeline(
+                graphics,
+                Timeline::new(start, today, date(2027, 7, 24)),
+                &labels,
+                reveal,
+                palette,
+                &texts,
+            )
+        });
+        mode("bases", &mut |graphics| {
+            render_bases(graphics, 2419, "29/02/2020", reveal, &font, palette, &texts)
+        });
+        mode("roman", &mut |graphics| {
+            render_roman(
+                graphics,
+                Split::new(2419),
+                &roman_date(start),
+                reveal,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("age", &mut |graphics| {
+            render_age(
+                graphics,
+                age,
+                "Born 29/02/2020",
+                &age_lines,
+                reveal,
+                &font,
+                palette,
+                &texts,
+            )
+        });
+        mode("recap", &mut |graphics| {
+            render_recap(graphics, 6, 2192, 9, &font, palette, &texts)
+        });
+        mode("phases", &mut |graphics| {
+            let progress = phased.phase_progress(today);
+            render_phases(graphics, &phased, &progress, today, palette, &texts)
+        });
+    }
+}
+
+criterion_group! {
+    name = benches;
+    //shorter than criterion's defaults, there are over 50 to get through
+    config = Criterion::default()
+        .warm_up_time(Elapsed::from_millis(500))
+        .measurement_time(Elapsed::from_secs(2));
+    targets = modes
+}
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+/* C ABI for countup, build with `cargo build --lib --release --features ffi` */
+
+#ifndef COUNTUP_H
+#define COUNTUP_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Dates can be yyyy-mm-dd, yyyy/mm/dd, dd-mm-yyyy, yyyymmdd or a unix timestamp,
+ * a NULL today means the current UTC date */
+
+typedef struct CountupBreakdown {
+    uint64_t days;
+    uint64_t weeks;
+    uint64_t months;
+    uint64_t years;
+    uint64_t split_years;
+    uint64_t split_months;
+    uint64_t split_days;
+    /* -1 unless the start is in the future */
+    int64_t days_until;
+    uint64_t next_milestone_days;
+    uint64_t days_until_next_milestone;
+} CountupBreakdown;
+
+/* Fills out with the count from start to today, false if either date is invalid */
+bool countup_breakdown(const char *start, const char *today, CountupBreakdown *out);
+
+/* Years, months and days as one line in lang (i.e. "de", NULL for English),
+ * NULL if either date is invalid, free with countup_free_string */
+char *countup_summary(const char *start, const char *today, const char *lang);
+
+/* Name of the milestone reached after days, i.e. "1 year", NULL if it isn't one,
+ * free with countup_free_string */
+char *countup_milestone(uint64_t days);
+
+void countup_free_string(char *text);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
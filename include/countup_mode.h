@@ -0,0 +1,33 @@
+/* A mode for the countup window from a shared library, add its path to `plugins` in the
+ * config. It's shown after the built in modes, Space switches to it as usual */
+
+#ifndef COUNTUP_MODE_H
+#define COUNTUP_MODE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include "countup.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Shown in the help and sent over D-Bus as the mode, must stay valid while loaded */
+const char *countup_mode_name(void);
+
+/* Optional, called every frame while the mode is shown, delta is the seconds since the
+ * last, for animating */
+void countup_mode_update(const CountupBreakdown *breakdown, double delta);
+
+/* Draws the mode into pixels, width * height colours as 0xRRGGBBAA, left to right and top
+ * to bottom, which are transparent to start with and blended over the background.
+ * background and accent are the event's colours in the same format */
+void countup_mode_render(const CountupBreakdown *breakdown, uint32_t background, uint32_t accent,
+                         uint32_t *pixels, size_t width, size_t height);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
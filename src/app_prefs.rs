@@ -0,0 +1,77 @@
+use crate::count::Mode;
+use crate::paths;
+use chrono::{DateTime, NaiveDate, Utc};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use pixels_graphics_lib::prefs::preferences::Preferences;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::debug;
+
+const PREF_FILE: &str = "app.pref";
+const PREF_APP: &str = "app";
+
+/// Runtime choices remembered between runs
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppPref {
+    pub mode: Option<Mode>,
+    pub event: Option<String>,
+    /// Last date passed with `--date`
+    pub date: Option<NaiveDate>,
+    /// Day the weekly digest was last opened on
+    pub digest_shown: Option<NaiveDate>,
+    /// When `countup stopwatch` was started, so reopening it carries on
+    pub stopwatch: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppPreferences {
+    preferences: Preferences<AppPref>,
+    /// False if they couldn't be loaded, so they're only kept until the app closes
+    saving: bool,
+}
+
+impl AppPreferences {
+    pub fn load() -> Result<Self> {
+        let dir = paths::prefs_dir()?;
+        let mut preferences = Preferences::new(dir.clone(), PREF_FILE);
+        preferences
+            .load()
+            .map_err(|err| eyre!("Unable to load prefs: {err}"))?;
+        let prefs = AppPreferences {
+            preferences,
+            saving: true,
+        };
+        debug!(dir = %dir.display(), pref = ?prefs.get(), "loaded prefs");
+        Ok(prefs)
+    }
+
+    /// Empty prefs that are never saved, for when they can't be loaded
+    pub fn unsaved() -> Self {
+        AppPreferences {
+            preferences: Preferences::new(PathBuf::new(), PREF_FILE),
+            saving: false,
+        }
+    }
+
+    pub fn get(&self) -> AppPref {
+        self.preferences.get(PREF_APP).cloned().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, pref: AppPref) {
+        self.preferences.set(PREF_APP, pref);
+    }
+
+    /// Errors are only printed as losing prefs shouldn't stop the app
+    pub fn save(&self) {
+        if !self.saving {
+            return;
+        }
+        debug!(pref = ?self.get(), "saving prefs");
+        let _ = self
+            .preferences
+            .save()
+            .map_err(|err| eprintln!("Unable to save prefs: {err:?}"));
+    }
+}
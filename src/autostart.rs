@@ -0,0 +1,178 @@
+#[cfg(not(any(target_os = "macos", windows)))]
+use crate::paths;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use std::env;
+#[cfg(not(windows))]
+use std::fs;
+#[cfg(not(windows))]
+use std::path::{Path, PathBuf};
+#[cfg(windows)]
+use std::process::Command;
+
+/// Name of the autostart entry on every platform
+const NAME: &str = "countup";
+
+/// Starts countup with `args` when the user logs in, replacing any entry from before,
+/// returns where the entry was put
+pub fn enable(args: &[String]) -> Result<String> {
+    let exe = env::current_exe().wrap_err("Finding the countup executable")?;
+    let mut command = vec![exe.display().to_string()];
+    command.extend(args.iter().cloned());
+    install(&command)
+}
+
+/// Removes the entry added by [enable], false if there wasn't one
+pub fn disable() -> Result<bool> {
+    uninstall()
+}
+
+/// XDG autostart desktop file, for Linux and the BSDs
+#[cfg(not(any(target_os = "macos", windows)))]
+fn entry_path() -> Result<PathBuf> {
+    let config = paths::xdg_config_home().ok_or_else(|| eyre!("Unable to find the home dir"))?;
+    Ok(config.join("autostart").join(format!("{NAME}.desktop")))
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn install(command: &[String]) -> Result<String> {
+    //quoted as the desktop entry spec says, % is also doubled as it starts field codes
+    let exec: Vec<String> = command
+        .iter()
+        .map(|arg| {
+            let arg = arg.replace('%', "%%");
+            if arg.contains(|c: char| " \t\"'\\><~|&;$*?#()`".contains(c)) {
+                let escaped: String = arg
+                    .chars()
+                    .flat_map(|c| match c {
+                        '"' | '`' | '$' | '\\' => vec!['\\', c],
+                        c => vec![c],
+                    })
+                    .collect();
+                format!("\"{escaped}\"")
+            } else {
+                arg
+            }
+        })
+        .collect();
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=countup\nComment=Counts up from a specified date\nExec={}\nTerminal=false\nX-GNOME-Autostart-enabled=true\n",
+        exec.join(" ")
+    );
+    write_entry(&entry_path()?, &entry)
+}
+
+/// LaunchAgent that runs once at login
+#[cfg(target_os = "macos")]
+fn entry_path() -> Result<PathBuf> {
+    let home = env::home_dir().ok_or_else(|| eyre!("Unable to find the home dir"))?;
+    Ok(home
+        .join("Library/LaunchAgents")
+        .join(format!("com.emmabritton.{NAME}.plist")))
+}
+
+#[cfg(target_os = "macos")]
+fn install(command: &[String]) -> Result<String> {
+    let args: String = command
+        .iter()
+        .map(|arg| {
+            let arg = arg
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            format!("        <string>{arg}</string>\n")
+        })
+        .collect();
+    let entry = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.emmabritton.{NAME}</string>
+    <key>ProgramArguments</key>
+    <array>
+{args}    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>
+"#
+    );
+    write_entry(&entry_path()?, &entry)
+}
+
+#[cfg(not(windows))]
+fn write_entry(path: &Path, entry: &str) -> Result<String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).wrap_err_with(|| format!("Creating {}", dir.display()))?;
+    }
+    fs::write(path, entry).wrap_err_with(|| format!("Writing {}", path.display()))?;
+    Ok(path.display().to_string())
+}
+
+#[cfg(not(windows))]
+fn uninstall() -> Result<bool> {
+    let path = entry_path()?;
+    if !path.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(&path).wrap_err_with(|| format!("Removing {}", path.display()))?;
+    Ok(true)
+}
+
+/// The current user's Run key, changed with `reg` so no registry crate is needed
+#[cfg(windows)]
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+#[cfg(windows)]
+fn install(command: &[String]) -> Result<String> {
+    let line: Vec<String> = command
+        .iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains([' ', '\t', '"']) {
+                format!("\"{}\"", arg.replace('"', "\\\""))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect();
+    reg(&[
+        "add",
+        RUN_KEY,
+        "/v",
+        NAME,
+        "/t",
+        "REG_SZ",
+        "/d",
+        &line.join(" "),
+        "/f",
+    ])?;
+    Ok(format!(r"{RUN_KEY}\{NAME}"))
+}
+
+#[cfg(windows)]
+fn uninstall() -> Result<bool> {
+    //query fails if the value doesn't exist
+    if reg(&["query", RUN_KEY, "/v", NAME]).is_err() {
+        return Ok(false);
+    }
+    reg(&["delete", RUN_KEY, "/v", NAME, "/f"])?;
+    Ok(true)
+}
+
+#[cfg(windows)]
+fn reg(args: &[&str]) -> Result<()> {
+    let output = Command::new("reg")
+        .args(args)
+        .output()
+        .wrap_err("Running reg")?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "reg {}: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use pixels_graphics_lib::prelude::Timing;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Frames slower than this (in seconds) turn the optional drawing off, about 30 fps
+const SLOW_FRAME: f64 = 1.0 / 30.0;
+/// Frames have to be faster than this again before it's turned back on, the gap stops it
+/// flickering on and off around the limit
+const FAST_FRAME: f64 = 1.0 / 45.0;
+/// How much each frame moves the average, so a single slow frame doesn't count
+const SMOOTHING: f64 = 0.05;
+/// How often the frame rate is logged
+const LOG_EVERY: Duration = Duration::from_secs(10);
+
+/// Watches how long frames take and turns off work that isn't needed to show the count
+/// (frame capture) while the machine can't keep up, and logs the frame rate for `--verbose`
+#[derive(Debug)]
+pub struct FrameBudget {
+    /// Moving average of the frame time, in seconds
+    average: f64,
+    /// Render count when last sampled, as update can run more than once per frame
+    renders: usize,
+    reduced: bool,
+    /// Slowest frame since the frame rate was last logged, in seconds
+    slowest: f64,
+    logged_at: Option<Instant>,
+}
+
+impl Default for FrameBudget {
+    fn default() -> Self {
+        FrameBudget {
+            average: 0.0,
+            renders: 0,
+            reduced: false,
+            slowest: 0.0,
+            logged_at: None,
+        }
+    }
+}
+
+impl FrameBudget {
+    /// Adds the last frame's time, called from update
+    pub fn record(&mut self, timing: &Timing) {
+        if timing.renders == self.renders {
+            return;
+        }
+        self.renders = timing.renders;
+        self.average += (timing.delta - self.average) * SMOOTHING;
+        if !self.reduced && self.average > SLOW_FRAME {
+            self.reduced = true;
+            eprintln!(
+                "Frames are taking {:.0}ms, pausing frame capture",
+                self.average * 1000.0
+            );
+        } else if self.reduced && self.average < FAST_FRAME {
+            self.reduced = false;
+            eprintln!("Frames are fast again, resuming frame capture");
+        }
+        self.slowest = self.slowest.max(timing.delta);
+        let logged_at = *self.logged_at.get_or_insert(timing.now);
+        if timing.now.duration_since(logged_at) >= LOG_EVERY {
+            debug!(
+                fps = timing.stats.fps,
+                average_ms = self.average * 1000.0,
+                slowest_ms = self.slowest * 1000.0,
+                "frame rate"
+            );
+            self.slowest = 0.0;
+            self.logged_at = Some(timing.now);
+        }
+    }
+
+    /// If optional drawing is turned off
+    pub fn reduced(&self) -> bool {
+        self.reduced
+    }
+}
@@ -0,0 +1,82 @@
+use crate::render::{Backdrop, BackgroundStyle, Palette};
+use buffer_graphics_lib::prelude::*;
+use chrono::Utc;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use std::path::Path;
+
+/// Cards are scaled up to at least this many pixels wide, so they're the same size
+/// whatever the window would be
+const CARD_WIDTH: usize = 1200;
+/// Space around the count, at 1x
+const MARGIN: usize = 8;
+/// Room for the label above the count, at 1x
+const LABEL_HEIGHT: usize = 12;
+
+/// Look of a card from `countup card`
+pub struct Card<'a> {
+    /// Drawn above the count, i.e. the event name
+    pub label: Option<String>,
+    pub palette: Palette,
+    pub backdrop: Option<&'a Backdrop>,
+    pub background_style: BackgroundStyle,
+}
+
+impl Card<'_> {
+    /// Draws the count with `draw` on a canvas of `size`, framed with the label and
+    /// background, scales it up and writes it to `path` as a PNG
+    pub fn save(
+        &self,
+        path: &Path,
+        size: (usize, usize),
+        draw: impl FnOnce(&mut Graphics),
+    ) -> Result<()> {
+        let top = MARGIN + self.label.as_ref().map_or(0, |_| LABEL_HEIGHT);
+        let (width, height) = (size.0 + MARGIN * 2, size.1 + top + MARGIN);
+        let mut buffer = vec![0; width * height * 4];
+        let mut graphics = Graphics::new(&mut buffer, width, height)
+            .map_err(|err| eyre!("Creating {width}x{height} card: {err:?}"))?;
+        graphics.set_translate(Coord::from((MARGIN, top)));
+        draw(&mut graphics);
+        graphics.set_translate(Coord::default());
+        let background = self.palette.background;
+        if let Some(backdrop) = self.backdrop {
+            backdrop.draw(&mut graphics, background);
+        }
+        let seconds = Utc::now().timestamp_millis() as f64 / 1000.0;
+        self.background_style
+            .draw(&mut graphics, background, seconds);
+        if let Some(label) = &self.label {
+            graphics.draw_text(
+                label,
+                Px(MARGIN as isize + 4, MARGIN as isize),
+                (self.palette.accent, Large),
+            );
+        }
+        //a thin frame halfway into the margin
+        let inset = MARGIN / 2;
+        graphics.draw_rect(
+            Rect::new_with_size(
+                (inset, inset),
+                width - inset * 2 - 1,
+                height - inset * 2 - 1,
+            ),
+            stroke(self.palette.accent),
+        );
+
+        let frame = graphics.copy_to_image();
+        let scale = CARD_WIDTH.div_ceil(width).max(1);
+        let bytes: Vec<u8> = (0..height * scale)
+            .flat_map(|y| (0..width * scale).map(move |x| (x / scale, y / scale)))
+            .flat_map(|(x, y)| frame.get_pixel(x, y).as_array())
+            .collect();
+        image::save_buffer(
+            path,
+            &bytes,
+            (width * scale) as u32,
+            (height * scale) as u32,
+            image::ColorType::Rgba8,
+        )
+        .wrap_err_with(|| format!("Writing {}", path.display()))
+    }
+}
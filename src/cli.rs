@@ -0,0 +1,640 @@
+use crate::count::{Easing, Intro};
+use crate::event::SnoozeLength;
+use crate::i18n::{cli_strings, detect_lang, CliStrings, NumberFormat, Numerals, LANGUAGES};
+use crate::milestones::Pattern;
+use crate::placement::Position;
+use crate::render::BackgroundStyle;
+use crate::report::MonthLength;
+use crate::shared::DEFAULT_ADDR;
+use crate::tokens::Role;
+use crate::DEFAULT_DATE_FORMAT;
+use chrono_tz::Tz;
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{arg, command, value_parser, Arg, ArgMatches, Command};
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::exit;
+use std::sync::OnceLock;
+
+static TEXT: OnceLock<&'static CliStrings> = OnceLock::new();
+
+/// Command line text in the language picked by [parse], English until then
+pub fn text() -> &'static CliStrings {
+    TEXT.get().copied().unwrap_or_else(|| cli_strings("en"))
+}
+
+/// Parses the command line, with the help and errors in the language from `--lang`,
+/// `COUNTUP_LANG` or the locale
+pub fn parse() -> ArgMatches {
+    let args: Vec<OsString> = env::args_os().collect();
+    let text = *TEXT.get_or_init(|| cli_strings(&pick_lang(&args)));
+    let mut command = build();
+    if !text.help.is_empty() {
+        command.build();
+        command = localize(command, "", text);
+    }
+    match command.try_get_matches_from_mut(args) {
+        Ok(matches) => matches,
+        Err(err) => match translate(&err, text) {
+            Some(message) => {
+                eprintln!("{message}");
+                exit(2);
+            }
+            None => err.exit(),
+        },
+    }
+}
+
+/// Env vars read by args, i.e. `COUNTUP_MONTH_LENGTH`, which the config leaves to clap as
+/// they can share a name with a config option but not its format
+pub fn env_vars() -> &'static HashSet<String> {
+    static VARS: OnceLock<HashSet<String>> = OnceLock::new();
+    VARS.get_or_init(|| {
+        fn collect(command: &Command, vars: &mut HashSet<String>) {
+            vars.extend(
+                command
+                    .get_arguments()
+                    .filter_map(Arg::get_env)
+                    .map(|var| var.to_string_lossy().into_owned()),
+            );
+            for sub in command.get_subcommands() {
+                collect(sub, vars);
+            }
+        }
+        let mut vars = HashSet::new();
+        collect(&build(), &mut vars);
+        vars
+    })
+}
+
+/// Finds the language before parsing, so it can be used for the help and any errors
+fn pick_lang(args: &[OsString]) -> String {
+    let args: Vec<&str> = args.iter().filter_map(|arg| arg.to_str()).collect();
+    args.iter()
+        .enumerate()
+        .find_map(|(i, arg)| match arg.strip_prefix("--lang=") {
+            Some(lang) => Some(lang.to_string()),
+            None if *arg == "--lang" || *arg == "-l" => args.get(i + 1).map(|s| s.to_string()),
+            None => None,
+        })
+        .or_else(|| env::var("COUNTUP_LANG").ok())
+        .filter(|lang| LANGUAGES.contains(&lang.as_str()))
+        .unwrap_or_else(detect_lang)
+}
+
+/// Replaces the help for `command`, its args and subcommands, `command` must be built so
+/// the generated help args and subcommands exist
+fn localize(mut command: Command, path: &str, text: &'static CliStrings) -> Command {
+    let join = |name: &str| match path {
+        "" => name.to_string(),
+        _ => format!("{path} {name}"),
+    };
+    if let Some(about) = text.help(path) {
+        command = command.about(about);
+    }
+    let args: Vec<(String, bool)> = command
+        .get_arguments()
+        .map(|arg| (arg.get_id().to_string(), arg.is_positional()))
+        .collect();
+    for (id, positional) in &args {
+        let key = if *positional {
+            id.clone()
+        } else {
+            format!("--{id}")
+        };
+        let help = match id.as_str() {
+            "help" => Some(text.print_help),
+            "version" => Some(text.print_version),
+            _ => text.help(&join(&key)).or_else(|| text.help(&key)),
+        };
+        //every arg is replaced, even without help, as mut_arg moves it to the end and the
+        //built command finds args by position
+        command = command.mut_arg(id, |arg| match help {
+            Some(help) => arg.help(help),
+            None => arg,
+        });
+    }
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in &subcommands {
+        command = command.mut_subcommand(name, |sub| match name.as_str() {
+            "help" => sub.about(text.help_subcommand),
+            _ => localize(sub, &join(name), text),
+        });
+    }
+
+    let mut template = format!("{{about-with-newline}}\n{}: {{usage}}\n", text.usage);
+    if args.iter().any(|(_, positional)| *positional) {
+        template.push_str(&format!("\n{}:\n{{positionals}}\n", text.arguments));
+    }
+    template.push_str(&format!("\n{}:\n{{options}}\n", text.options));
+    if !subcommands.is_empty() {
+        template.push_str(&format!("\n{}:\n{{subcommands}}\n", text.commands));
+    }
+    template.push_str("{after-help}");
+    command.help_template(template)
+}
+
+/// Message for the common errors in the chosen language, none to let clap print it
+fn translate(err: &clap::Error, text: &CliStrings) -> Option<String> {
+    let get = |kind| match err.get(kind) {
+        Some(ContextValue::String(value)) => Some(value.clone()),
+        Some(ContextValue::Strings(values)) => Some(values.join(", ")),
+        _ => None,
+    };
+    let message = match err.kind() {
+        ErrorKind::InvalidValue | ErrorKind::ValueValidation => {
+            let mut message = text.invalid_value(
+                &get(ContextKind::InvalidValue)?,
+                &get(ContextKind::InvalidArg)?,
+            );
+            if let Some(valid) = get(ContextKind::ValidValue) {
+                message.push_str(&format!("\n  [{}: {valid}]", text.possible_values));
+            }
+            if let Some(source) = std::error::Error::source(err) {
+                message.push_str(&format!(": {source}"));
+            }
+            message
+        }
+        ErrorKind::UnknownArgument => text.unexpected_argument(&get(ContextKind::InvalidArg)?),
+        ErrorKind::MissingRequiredArgument => match err.get(ContextKind::InvalidArg) {
+            Some(ContextValue::Strings(args)) => {
+                format!("{}:\n  {}", text.missing_required, args.join("\n  "))
+            }
+            _ => return None,
+        },
+        ErrorKind::ArgumentConflict => {
+            text.conflict(&get(ContextKind::InvalidArg)?, &get(ContextKind::PriorArg)?)
+        }
+        ErrorKind::InvalidSubcommand => {
+            text.unknown_subcommand(&get(ContextKind::InvalidSubcommand)?)
+        }
+        _ => return None,
+    };
+    let usage = match err.get(ContextKind::Usage) {
+        Some(ContextValue::StyledStr(usage)) => {
+            let usage = usage.to_string();
+            let usage = usage.trim().trim_start_matches("Usage:").trim();
+            format!("\n\n{}: {usage}", text.usage)
+        }
+        _ => String::new(),
+    };
+    Some(format!(
+        "{}: {message}{usage}\n\n{}",
+        text.error, text.more_info
+    ))
+}
+
+pub fn build() -> Command {
+    command!()
+        .args(display_args())
+        .arg(
+            arg!(-e --event <NAME> "Name of an event in the config file to count from")
+                .conflicts_with_all(["date", "date-file", "from", "birthday", "windows", "calendar"])
+                .env("COUNTUP_EVENT"),
+        )
+        .arg(
+            arg!(-c --config <PATH> "Path to config file, defaults to config.json in the app's pref dir")
+                .value_parser(value_parser!(PathBuf))
+                .env("COUNTUP_CONFIG")
+                .global(true),
+        )
+        .arg(
+            arg!(--"data-dir" <PATH> "Dir for prefs, the outbox and secrets (and the default config), defaults to the app's pref dir")
+                .value_parser(value_parser!(PathBuf))
+                .env("COUNTUP_DATA_DIR")
+                .global(true),
+        )
+        .arg(
+            arg!(--"prefs-dir" <PATH> "Dir for the remembered mode, event and date, i.e. next to the binary for a portable install, defaults to the data dir. The window position isn't remembered with it")
+                .value_parser(value_parser!(PathBuf))
+                .env("COUNTUP_PREFS_DIR")
+                .global(true),
+        )
+        .arg(
+            arg!(--connect <ADDR> "Use the events from a countup daemon, i.e. 192.168.1.20:7878")
+                .env("COUNTUP_CONNECT")
+                .global(true),
+        )
+        .arg(
+            arg!(--token <TOKEN> "Token for the daemon, defaults to the `token` secret")
+                .env("COUNTUP_TOKEN")
+                .hide_env_values(true)
+                .global(true),
+        )
+        .arg(
+            arg!(--verbose "Log what was parsed and worked out, prefs loading and saving, and frame rates to stderr")
+                .env("COUNTUP_VERBOSE")
+                .global(true),
+        )
+        .arg(
+            arg!(--"log-file" <PATH> "Write the log to this file instead of stderr, implies --verbose")
+                .value_parser(value_parser!(PathBuf))
+                .env("COUNTUP_LOG_FILE")
+                .global(true),
+        )
+        .subcommand(
+            Command::new("show")
+                .about("Open the counter, this is the default")
+                .args(display_args())
+                .arg(
+                    arg!([NAME] "Name of an event to count from")
+                        .conflicts_with_all(["date", "date-file", "from", "birthday", "windows", "calendar"]),
+                ),
+        )
+        .subcommand(
+            Command::new("card")
+                .about("Save the count as a large image, i.e. to share on an anniversary")
+                .args(display_args())
+                .arg(
+                    arg!([NAME] "Name of an event to count from")
+                        .conflicts_with_all(["date", "date-file", "from", "birthday", "windows", "calendar"]),
+                )
+                .arg(
+                    arg!(-o --out <PATH> "PNG file to write")
+                        .value_parser(value_parser!(PathBuf))
+                        .required(true),
+                )
+                .arg(arg!(--label <TEXT> "Text above the count, defaults to the event name")),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Exit with 0 if today is a milestone and 1 if not, for cron jobs and scripts")
+                .args(display_args())
+                .arg(
+                    arg!([NAME] "Name of an event to count from")
+                        .conflicts_with_all(["date", "date-file", "from", "birthday", "windows", "calendar"]),
+                )
+                .arg(
+                    arg!(--milestone <DAYS> "Only this many days, i.e. 1000, rather than any milestone")
+                        .value_parser(value_parser!(u64).range(1..)),
+                ),
+        )
+        .subcommand(
+            Command::new("stopwatch")
+                .about("Count up to the second from now, carrying on from the same moment when reopened")
+                .args(display_args())
+                .arg(arg!(--restart "Start again from now")),
+        )
+        .subcommand(
+            Command::new("add")
+                .about("Save an event to the config file")
+                .arg(arg!(<NAME> "Name of the event"))
+                .arg(arg!(<DATE> "Date of the event, format yyyy-mm-dd"))
+                .arg(
+                    arg!(--timezone <TZ> "Timezone the days go up in, i.e. Asia/Tokyo, defaults to UTC")
+                        .value_parser(parse_timezone),
+                ),
+        )
+        .subcommand(Command::new("list").about("List events in the config file"))
+        .subcommand(
+            Command::new("remove")
+                .about("Remove an event from the config file")
+                .arg(arg!(<NAME> "Name of the event")),
+        )
+        .subcommand(
+            Command::new("reset")
+                .about("Set an event's date to today")
+                .arg(arg!(<NAME> "Name of the event")),
+        )
+        .subcommand(
+            Command::new("snooze")
+                .about("Stop an event's milestones chiming and running hooks for a while")
+                .arg(arg!(<NAME> "Name of the event"))
+                .arg(
+                    arg!(<LENGTH> "How long for, starting today, off to unsnooze")
+                        .value_parser(SnoozeLength::NAMES),
+                ),
+        )
+        .subcommand(
+            Command::new("history")
+                .about("Show who changed the events on the daemon, newest first")
+                .arg(arg!([NAME] "Only show changes to this event"))
+                .arg(
+                    arg!(-n --limit <COUNT> "Number of changes to show")
+                        .value_parser(value_parser!(u64).range(1..))
+                        .default_value("20"),
+                ),
+        )
+        .subcommand(
+            Command::new("conflicts")
+                .about("Review edits that conflicted with changes on the daemon")
+                .subcommand(
+                    Command::new("keep-mine")
+                        .about("Apply your edit, replacing the daemon's change")
+                        .arg(conflict_number()),
+                )
+                .subcommand(
+                    Command::new("keep-theirs")
+                        .about("Discard your edit")
+                        .arg(conflict_number()),
+                ),
+        )
+        .subcommand(
+            Command::new("daemon")
+                .about("Share the events in the config file with other countup instances")
+                .arg(
+                    arg!(--listen <ADDR> "Address to listen on, use 0.0.0.0:7878 for other machines")
+                        .default_value(DEFAULT_ADDR),
+                ),
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Show the count as a web page and JSON")
+                .arg(
+                    arg!([NAME] "Name of an event to count from")
+                        .conflicts_with_all(["date", "date-file"]),
+                )
+                .arg(arg!(-d --date <DATE> "Date to count from, format yyyy-mm-dd, - reads it from stdin"))
+                .arg(
+                    arg!(--"date-file" <PATH> "File to read the date from")
+                        .value_parser(value_parser!(PathBuf))
+                        .conflicts_with("date"),
+                )
+                .arg(
+                    arg!(-l --lang <LANG> "Language for the page, defaults to the system locale")
+                        .value_parser(LANGUAGES),
+                )
+                .arg(
+                    arg!(-f --"date-format" <FORMAT> "Format for the date, as a strftime pattern")
+                        .default_value(DEFAULT_DATE_FORMAT),
+                )
+                .arg(
+                    arg!(-p --port <PORT> "Port to listen on")
+                        .value_parser(value_parser!(u16))
+                        .default_value("8080"),
+                )
+                .arg(arg!(--bind <ADDR> "Address to listen on").default_value("0.0.0.0")),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Add events from a CSV file with name,date rows, an ICS calendar or a habit app's CSV or JSON export")
+                .arg(
+                    arg!(<FILE> "CSV, .ics or .json file to import").value_parser(value_parser!(PathBuf)),
+                )
+                .arg(arg!(--filter <PATTERN> "Only import events with names matching this pattern, * matches anything")),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Write the events' upcoming milestones to a file, for a calendar app")
+                .arg(
+                    arg!(--milestones "The milestones and anniversaries in the next year")
+                        .required(true),
+                )
+                .arg(
+                    arg!(--ics <PATH> "ICS calendar file to write, - prints it")
+                        .value_parser(value_parser!(PathBuf))
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("auth")
+                .about("Manage secrets stored in the system keyring")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("set")
+                        .about("Save a secret, read from stdin")
+                        .arg(arg!(<NAME> "Name of the secret, i.e. proxy")),
+                )
+                .subcommand(
+                    Command::new("clear")
+                        .about("Delete a secret")
+                        .arg(arg!(<NAME> "Name of the secret")),
+                ),
+        )
+        .subcommand(
+            Command::new("monitors")
+                .about("List the monitors, with the numbers for --monitor")
+                .arg(arg!(--json "Print them as JSON")),
+        )
+        .subcommand(
+            Command::new("autostart")
+                .about("Start countup when you log in, or stop it")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("enable")
+                        .about("Add the login entry, replacing any from before")
+                        .arg(
+                            arg!([ARGS] ... "Arguments to start countup with, after --, i.e. -- --event Sober")
+                                .last(true),
+                        ),
+                )
+                .subcommand(Command::new("disable").about("Remove the login entry")),
+        )
+        .subcommand(
+            Command::new("selftest")
+                .about("Check the date maths, config, stored files and drawing, for provisioning scripts"),
+        )
+        .subcommand(
+            Command::new("update")
+                .about("Replace this binary with the latest release, if it's newer")
+                .arg(arg!(--"check-only" "Only say whether there's a newer release")),
+        )
+        .subcommand(
+            Command::new("token")
+                .about("Manage tokens for accessing the daemon and server on this machine")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("add")
+                        .about("Create a token, replacing any with the same name")
+                        .arg(arg!(<NAME> "Name of the token, i.e. lobby-display"))
+                        .arg(
+                            arg!(-r --role <ROLE> "What the token can do, viewers can only read, editors can also add and reset events, admins can do anything")
+                                .value_parser(Role::NAMES)
+                                .default_value("viewer"),
+                        ),
+                )
+                .subcommand(Command::new("list").about("List tokens"))
+                .subcommand(
+                    Command::new("remove")
+                        .about("Delete a token")
+                        .arg(arg!(<NAME> "Name of the token")),
+                ),
+        )
+}
+
+fn conflict_number() -> Arg {
+    arg!(<NUMBER> "Number of the conflict, from `countup conflicts`")
+        .value_parser(value_parser!(u64).range(1..))
+}
+
+fn parse_anim_speed(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(secs) if secs.is_finite() && secs >= 0.0 => Ok(secs),
+        _ => Err(String::from("must be a number of seconds, 0 or more")),
+    }
+}
+
+fn parse_position(value: &str) -> Result<Position, String> {
+    Position::parse(value)
+        .ok_or_else(|| format!("must be x,y or one of {}", Position::PRESETS.join(", ")))
+}
+
+fn parse_timezone(value: &str) -> Result<Tz, String> {
+    value
+        .parse()
+        .map_err(|_| String::from("must be a timezone name, i.e. Europe/London"))
+}
+
+/// Args for opening the counter
+fn display_args() -> Vec<Arg> {
+    vec![
+        arg!(-d --date <DATE> "Date to count from (or down to, if in the future), format yyyy-mm-dd, - reads it from stdin")
+            .env("COUNTUP_DATE"),
+        arg!(--"date-file" <PATH> "File to read the date from, i.e. written by a script")
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with("date")
+            .env("COUNTUP_DATE_FILE"),
+        arg!(--from <DATE> "Show the time between this date and --to, without going up as the days pass")
+            .conflicts_with_all(["date", "date-file", "slideshow"]),
+        arg!(--to <DATE> "End of the time shown with --from, defaults to today")
+            .requires("from"),
+        arg!(--now <TIME> "Count as if it's this date, or RFC 3339 time like 2024-06-01T09:30:00Z, instead of now, for screenshots, demos and tests")
+            .env("COUNTUP_NOW"),
+        arg!(-l --lang <LANG> "Language for the UI, defaults to the system locale")
+            .value_parser(LANGUAGES)
+            .env("COUNTUP_LANG"),
+        arg!(-f --"date-format" <FORMAT> "Format for the date in the header, as a strftime pattern")
+            .default_value(DEFAULT_DATE_FORMAT)
+            .env("COUNTUP_DATE_FORMAT"),
+        arg!(--template <TEMPLATE> "Text for the header, supports {years}, {months} and {days} as in split mode, {total}, {label} and those for --statusbar-template")
+            .env("COUNTUP_TEMPLATE"),
+        arg!(-t --term "Start in term mode, requires a term in the config file")
+            .env("COUNTUP_TERM_MODE"),
+        arg!(--"anim-speed" <SECONDS> "Seconds to count up each year of days when opening, 0 to skip counting")
+            .value_parser(parse_anim_speed)
+            .env("COUNTUP_ANIM_SPEED"),
+        arg!(--"no-animation" "Show the count straight away, the same as --anim-speed 0")
+            .env("COUNTUP_NO_ANIMATION"),
+        arg!(--"reduced-motion" "Skip counting up and the flash when the day changes, and keep the background still, on by default if the OS asks for less motion")
+            .conflicts_with("full-motion")
+            .env("COUNTUP_REDUCED_MOTION"),
+        arg!(--"full-motion" "Animate as usual, even if the OS or the config asks for less motion")
+            .env("COUNTUP_FULL_MOTION"),
+        arg!(--easing <CURVE> "How the count speeds up and slows down while counting, defaults to linear")
+            .value_parser(Easing::NAMES)
+            .env("COUNTUP_EASING"),
+        arg!(--intro <STYLE> "How the number appears when opening, defaults to count-up")
+            .value_parser(Intro::NAMES)
+            .env("COUNTUP_INTRO"),
+        arg!(--sound "Tick while counting up and chime when done or at a milestone")
+            .conflicts_with("mute")
+            .env("COUNTUP_SOUND"),
+        arg!(--mute "Turn off sounds, even if on in the config").env("COUNTUP_MUTE"),
+        arg!(--font <PATH> "Bitmap font for the numbers, a PNG of the printable ASCII characters in rows of 16")
+            .value_parser(value_parser!(PathBuf))
+            .env("COUNTUP_FONT"),
+        arg!(--background <PATH> "Image to show behind the count, a PNG scaled to fit the window and dimmed so the text can be read")
+            .value_parser(value_parser!(PathBuf))
+            .env("COUNTUP_BACKGROUND"),
+        arg!(--"background-style" <STYLE> "What's drawn behind the count, a slowly shifting gradient or drifting stars instead of flat, defaults to flat")
+            .value_parser(BackgroundStyle::NAMES)
+            .env("COUNTUP_BACKGROUND_STYLE"),
+        arg!(--"text-scale" <SCALE> "Draw the numbers this many times bigger, the window grows to fit")
+            .value_parser(value_parser!(u64).range(1..=8))
+            .env("COUNTUP_TEXT_SCALE"),
+        arg!(--size <WxH> "Window size, i.e. 540x180, the numbers are drawn as big as fit unless --text-scale is set")
+            .env("COUNTUP_SIZE"),
+        arg!(--scale <FACTOR> "Make the window this many times bigger, on top of the screen's scaling, for high DPI screens")
+            .value_parser(value_parser!(u64).range(1..=8))
+            .env("COUNTUP_SCALE"),
+        arg!(--"min-text-size" <PIXELS> "Make the window big enough that the smallest text is at least this many pixels tall, on top of the screen's scaling")
+            .value_parser(value_parser!(u64).range(5..=40))
+            .env("COUNTUP_MIN_TEXT_SIZE"),
+        arg!(--position <POSITION> "Open the window here instead of where it was last time, x,y in pixels from the top left of the monitor or top-left, top-right, bottom-left, bottom-right or center")
+            .value_parser(parse_position)
+            .conflicts_with("windows")
+            .env("COUNTUP_POSITION"),
+        arg!(--monitor <NUMBER> "Open the window on this monitor instead of where it was last time, from `countup monitors`, centered unless --position is set")
+            .value_parser(value_parser!(u64))
+            .conflicts_with("windows")
+            .env("COUNTUP_MONITOR"),
+        arg!(--"start-hidden" "Don't open the window until it's shown with the Show D-Bus method, for starting on login, Linux only")
+            .conflicts_with_all(["windows", "headless", "json", "statusbar"])
+            .env("COUNTUP_START_HIDDEN"),
+        arg!(--"new-instance" "Open another window even if countup is already running, instead of bringing that one to the front")
+            .conflicts_with_all(["windows", "headless", "json", "statusbar", "watch"])
+            .env("COUNTUP_NEW_INSTANCE"),
+        arg!(--screensaver "Fill the monitor with black and slowly bounce the count around it, so an OLED screen left on the counter doesn't burn in")
+            .conflicts_with_all(["windows", "headless", "json", "statusbar", "watch"])
+            .env("COUNTUP_SCREENSAVER"),
+        arg!(--desktop "Put the window on the desktop, below the other windows and without a title bar, with clicks going through it, like conky, X11 and Windows only")
+            .conflicts_with_all(["windows", "headless", "json", "statusbar", "watch"])
+            .env("COUNTUP_DESKTOP"),
+        arg!(--numerals <SYSTEM> "Digits for the big numbers, defaults to the language's")
+            .value_parser(Numerals::NAMES)
+            .env("COUNTUP_NUMERALS"),
+        arg!(--"number-format" <FORMAT> "Separator between each group of three digits in the numbers and text, locale for the language's, defaults to plain")
+            .value_parser(NumberFormat::NAMES)
+            .env("COUNTUP_NUMBER_FORMAT"),
+        arg!(--"month-length" <DAYS> "Days in a month for the months in diff mode, or calendar months, defaults to 28")
+            .value_parser(MonthLength::NAMES)
+            .env("COUNTUP_MONTH_LENGTH"),
+        arg!(--clock "Show the date and time in the corner, for a counter that's always on")
+            .env("COUNTUP_CLOCK"),
+        arg!(--calendar "Show how far through the year, month and day it is, without counting from a date")
+            .conflicts_with_all(["date", "date-file", "from", "birthday", "pair", "goal", "minimal", "slideshow", "windows", "headless"])
+            .env("COUNTUP_CALENDAR"),
+        arg!(--minimal "Only show the numbers, without the header and labels, in a smaller window")
+            .env("COUNTUP_MINIMAL"),
+        arg!(--"high-contrast" "Draw on black with white text, or the event's colour if it's still easy to read, and no background image, gradient or stars")
+            .env("COUNTUP_HIGH_CONTRAST"),
+        arg!(--hours <HOURS> "12 or 24 hour times, for --clock and history mode, defaults to the language's")
+            .value_parser(["12", "24"])
+            .env("COUNTUP_HOURS"),
+        arg!(--"capture-frames" <COUNT> "Frames to keep for saving as PNGs with Ctrl+F or on a crash, 0 to turn off, defaults to 120")
+            .value_parser(value_parser!(u64))
+            .env("COUNTUP_CAPTURE_FRAMES"),
+        arg!(--slideshow <SECONDS> "Show each event in the config for this many seconds in turn, replaying the count for each")
+            .value_parser(value_parser!(u64).range(1..))
+            .env("COUNTUP_SLIDESHOW"),
+        arg!(--windows "Open a window for each event in the config, each remembering its own position")
+            .conflicts_with_all(["date", "date-file", "from", "slideshow", "headless", "json", "statusbar", "watch"]),
+        //set on the processes --windows starts, one per event
+        arg!(--window <NAME>).hide(true),
+        arg!(--birthday <DATE> "Show an age from this birth date, with the days until the next birthday")
+            .conflicts_with_all(["date", "date-file", "from", "slideshow", "windows"])
+            .env("COUNTUP_BIRTHDAY"),
+        arg!(--"born-at" <TIME> "Time of birth for --birthday as HH:MM, to show the age to the minute and the hours milestones")
+            .requires("birthday")
+            .env("COUNTUP_BORN_AT"),
+        arg!(--inclusive "Count the start day itself, so it's day 1 rather than 0 on the day")
+            .conflicts_with("exclusive")
+            .env("COUNTUP_INCLUSIVE"),
+        arg!(--exclusive "Don't count the start day, this is the default unless changed in the config")
+            .env("COUNTUP_EXCLUSIVE"),
+        arg!(--goal <GOAL> "Show a progress bar towards a number of days or a date under the count")
+            .env("COUNTUP_GOAL"),
+        arg!(--"fiscal-year" <MONTH> "Add fiscal mode, for a fiscal year starting on the 1st of this month, i.e. 4 for April")
+            .value_parser(value_parser!(u64).range(1..=12))
+            .env("COUNTUP_FISCAL_YEAR"),
+        arg!(--patterns <PATTERNS> "Also count these day counts as milestones, from palindrome, repdigit, power-of-two and pi, i.e. palindrome,pi")
+            .value_parser(Pattern::NAMES)
+            .value_delimiter(',')
+            .env("COUNTUP_PATTERNS"),
+        arg!(--pair <TARGET> "Show the days until this event or date next to the count, in dual mode")
+            .env("COUNTUP_PAIR"),
+        arg!(--anniversary "Also show the days until the next anniversary of the date in split mode")
+            .env("COUNTUP_ANNIVERSARY"),
+        arg!(--"leap-days" "Show the 29 Februaries since the date in split mode, instead of the days until the anniversary")
+            .conflicts_with("anniversary")
+            .env("COUNTUP_LEAP_DAYS"),
+        arg!(--roman "Add a mode with the years, months and days in Roman numerals, just for fun")
+            .env("COUNTUP_ROMAN"),
+        arg!(--forget "Forget the remembered date and event"),
+        arg!(--headless "Print the count instead of opening a window"),
+        arg!(--json "Print the count as JSON, implies --headless"),
+        arg!(--statusbar <FORMAT> "Print a single line for status bars, implies --headless")
+            .value_parser(["plain", "waybar"]),
+        arg!(--"statusbar-template" <TEMPLATE> "Text for --statusbar, supports {days}, {weeks}, {months}, {years}, {until}, {start}, {event} and {milestone}")
+            .default_value("{days} days"),
+        arg!(-w --watch "Keep running and reprint the count, implies --headless"),
+        arg!(--interval <SECONDS> "Seconds between updates with --watch")
+            .value_parser(value_parser!(u64).range(1..))
+            .default_value("1"),
+    ]
+}
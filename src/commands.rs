@@ -0,0 +1,950 @@
+use crate::autostart;
+use crate::cli;
+use crate::config::Config;
+use crate::credentials::{self, Store};
+use crate::digest;
+use crate::event::{Event, Snooze, SnoozeLength};
+use crate::offline::{self, Outcome};
+use crate::placement;
+use crate::report::Report;
+use crate::shared::{self, Change};
+use crate::tokens::{Role, Tokens};
+use crate::{parse_date, DEFAULT_DATE_FORMAT};
+use chrono::{DateTime, Days, Local, NaiveDate, Utc};
+use chrono_tz::Tz;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde_json::{Map, Value};
+use std::fs;
+use std::io::{stdin, stdout, IsTerminal, Write};
+use std::path::Path;
+
+/// Days ahead `countup export --milestones` covers
+const EXPORT_DAYS: i64 = 365;
+
+/// Commands that edit or list events use the daemon at `remote` if set, otherwise the
+/// config file
+pub fn add(
+    config_path: &Path,
+    remote: Option<&str>,
+    name: &str,
+    date: &str,
+    timezone: Option<Tz>,
+) -> Result<()> {
+    add_event(
+        config_path,
+        remote,
+        new_event(name, parse_date(date)?, timezone),
+    )
+}
+
+/// Event with only a name and date, as `countup add` and the window's event editor make
+pub fn new_event(name: &str, date: NaiveDate, timezone: Option<Tz>) -> Event {
+    Event {
+        name: name.to_string(),
+        date,
+        phases: vec![],
+        pauses: vec![],
+        timezone,
+        resets: vec![],
+        background: None,
+        accent: None,
+        anniversary: None,
+        streak: None,
+        note_pauses: None,
+        snooze: None,
+        milestones: vec![],
+    }
+}
+
+pub fn add_event(config_path: &Path, remote: Option<&str>, event: Event) -> Result<()> {
+    let name = event.name.clone();
+    if let Some(addr) = remote {
+        return remote_edit(addr, &name, Change::Add { event }, "Added");
+    }
+    let mut config = Config::load(config_path)?;
+    if config.event(&name).is_some() {
+        return Err(eyre!(cli::text().event_exists(&name)));
+    }
+    config.events.push(event);
+    config.save(config_path)?;
+    println!("Added {name}");
+    Ok(())
+}
+
+/// Renames `event` to `name`, keeping everything else about it
+pub fn rename(config_path: &Path, remote: Option<&str>, event: &Event, name: &str) -> Result<()> {
+    let renamed = Event {
+        name: name.to_string(),
+        ..event.clone()
+    };
+    if let Some(addr) = remote {
+        let change = Change::Rename { name: renamed.name };
+        return remote_edit(addr, &event.name, change, &format!("Renamed to {name}:"));
+    }
+    let mut config = Config::load(config_path)?;
+    //only changing the case is fine
+    if !name.eq_ignore_ascii_case(&event.name) && config.event(name).is_some() {
+        return Err(eyre!(cli::text().event_exists(name)));
+    }
+    let listed = config
+        .events
+        .iter_mut()
+        .find(|listed| listed.name.eq_ignore_ascii_case(&event.name))
+        .ok_or_else(|| eyre!(cli::text().no_event(&event.name)))?;
+    listed.name = renamed.name;
+    config.save(config_path)?;
+    println!("Renamed {} to {name}", event.name);
+    Ok(())
+}
+
+/// Lists the monitors with the numbers for `--monitor`, `json` is read by the window's own
+/// process to place it
+pub fn monitors(json: bool) -> Result<()> {
+    let monitors = placement::list()?;
+    if json {
+        println!("{}", serde_json::to_string(&monitors)?);
+        return Ok(());
+    }
+    if monitors.is_empty() {
+        println!("No monitors found");
+        return Ok(());
+    }
+    for (i, monitor) in monitors.iter().enumerate() {
+        println!(
+            "{i}  {}  {}x{} at {},{}, {}x{}",
+            monitor.name,
+            monitor.width,
+            monitor.height,
+            monitor.x,
+            monitor.y,
+            monitor.scale,
+            if monitor.primary { ", primary" } else { "" }
+        );
+    }
+    Ok(())
+}
+
+/// Adds or removes the login entry, `args` are passed to countup when it starts
+pub fn autostart(enable: bool, args: &[String]) -> Result<()> {
+    if enable {
+        let entry = autostart::enable(args)?;
+        println!("countup will start when you log in, from {entry}");
+    } else if autostart::disable()? {
+        println!("countup won't start when you log in");
+    } else {
+        println!("countup wasn't set to start when you log in");
+    }
+    Ok(())
+}
+
+/// Lists the events with their counts as the window would show them, with the config's
+/// `counting` and less their pauses
+pub fn list(config_path: &Path, remote: Option<&str>, now: DateTime<Utc>) -> Result<()> {
+    let config = Config::load_with_env(config_path);
+    let events = match remote {
+        Some(addr) => shared::list(addr)?,
+        None => config
+            .as_ref()
+            .map_err(|err| eyre!("{err:#}"))?
+            .events
+            .clone(),
+    };
+    if events.is_empty() {
+        println!("No events, add one with `countup add <NAME> <DATE>`");
+        return Ok(());
+    }
+    let counting = config
+        .ok()
+        .and_then(|config| config.counting)
+        .unwrap_or_default();
+    let width = events
+        .iter()
+        .map(|event| event.name.len())
+        .max()
+        .unwrap_or_default();
+    for event in &events {
+        let today = event.today(now);
+        let report = Report::new_with(event.date, today, None, counting)
+            .with_paused(event.paused_days(today, counting));
+        let count = match report.days_until {
+            Some(days) => format!("in {days} days"),
+            None => format!("{} days", report.days),
+        };
+        println!(
+            "{:width$}  {}  {count}",
+            event.name,
+            event.date.format(DEFAULT_DATE_FORMAT),
+        );
+    }
+    Ok(())
+}
+
+pub fn remove(config_path: &Path, remote: Option<&str>, name: &str) -> Result<()> {
+    if let Some(addr) = remote {
+        return remote_edit(addr, name, Change::Remove, "Removed");
+    }
+    let mut config = Config::load(config_path)?;
+    let count = config.events.len();
+    config
+        .events
+        .retain(|event| !event.name.eq_ignore_ascii_case(name));
+    if config.events.len() == count {
+        return Err(eyre!(cli::text().no_event(name)));
+    }
+    config.save(config_path)?;
+    println!("Removed {name}");
+    Ok(())
+}
+
+/// Starts `name` again from today (at `now`) in its timezone, so it's 0 until its next
+/// midnight
+pub fn reset(
+    config_path: &Path,
+    remote: Option<&str>,
+    name: &str,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    if let Some(addr) = remote {
+        let today = remote_today(addr, name, now);
+        return remote_edit(addr, name, Change::SetDate { date: today }, "Reset");
+    }
+    let mut config = Config::load(config_path)?;
+    let event = config
+        .events
+        .iter_mut()
+        .find(|event| event.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| eyre!(cli::text().no_event(name)))?;
+    event.reset(event.today(now));
+    config.save(config_path)?;
+    println!("Reset {name}");
+    Ok(())
+}
+
+/// Snoozes `name`'s milestones for `length`, starting `today`
+pub fn snooze(
+    config_path: &Path,
+    remote: Option<&str>,
+    name: &str,
+    length: SnoozeLength,
+    today: NaiveDate,
+) -> Result<()> {
+    let snooze = length.starting(today);
+    let done = match snooze {
+        Some(Snooze::Until(until)) => {
+            format!("Snoozed until {}:", until.format(DEFAULT_DATE_FORMAT))
+        }
+        Some(Snooze::Forever) => String::from("Snoozed forever:"),
+        None => String::from("Unsnoozed"),
+    };
+    if let Some(addr) = remote {
+        return remote_edit(addr, name, Change::Snooze { snooze }, &done);
+    }
+    let mut config = Config::load(config_path)?;
+    let event = config
+        .events
+        .iter_mut()
+        .find(|event| event.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| eyre!(cli::text().no_event(name)))?;
+    event.snooze = snooze;
+    config.save(config_path)?;
+    println!("{done} {name}");
+    Ok(())
+}
+
+/// Today in the timezone of the daemon's copy of `name`, in UTC if it can't be reached so
+/// the edit can still be queued
+pub fn remote_today(addr: &str, name: &str, now: DateTime<Utc>) -> NaiveDate {
+    shared::list(addr)
+        .ok()
+        .and_then(|events| {
+            events
+                .into_iter()
+                .find(|event| event.name.eq_ignore_ascii_case(name))
+        })
+        .map_or(now.date_naive(), |event| event.today(now))
+}
+
+/// How a snooze is listed in the history and conflicts
+fn snooze_text(snooze: Option<Snooze>) -> String {
+    match snooze {
+        Some(Snooze::Until(until)) => {
+            format!("snoozed until {}", until.format(DEFAULT_DATE_FORMAT))
+        }
+        Some(Snooze::Forever) => String::from("snoozed forever"),
+        None => String::from("unsnoozed"),
+    }
+}
+
+fn remote_edit(addr: &str, name: &str, change: Change, done: &str) -> Result<()> {
+    match offline::edit(addr, name, change)? {
+        Outcome::Applied => println!("{done} {name}"),
+        Outcome::Queued => {
+            println!("Unable to reach the daemon, the change to {name} will be sent next time")
+        }
+        Outcome::Conflict(reason) => {
+            println!("Not applied as {reason}, review with `countup conflicts`")
+        }
+    }
+    Ok(())
+}
+
+pub fn history(remote: Option<&str>, name: Option<&str>, limit: usize) -> Result<()> {
+    let addr = remote.ok_or_else(|| eyre!(cli::text().history_needs_daemon))?;
+    let entries = shared::history(addr, name, limit)?;
+    if entries.is_empty() {
+        println!("No changes");
+        return Ok(());
+    }
+    for entry in entries {
+        let change = match &entry.change {
+            Change::Add { event } => format!("added on {}", event.date.format(DEFAULT_DATE_FORMAT)),
+            Change::Remove => String::from("removed"),
+            Change::SetDate { date } => format!("reset to {}", date.format(DEFAULT_DATE_FORMAT)),
+            Change::Snooze { snooze } => snooze_text(*snooze),
+            Change::Rename { name } => format!("renamed to {name}"),
+        };
+        println!(
+            "{}  {} ({})  {}  {change}",
+            entry.at.with_timezone(&Local).format("%F %R"),
+            entry.client,
+            entry.addr,
+            entry.event
+        );
+    }
+    Ok(())
+}
+
+pub fn conflicts(remote: Option<&str>) -> Result<()> {
+    let addr = remote.ok_or_else(|| eyre!(cli::text().needs_daemon))?;
+    let conflicts = offline::conflicts(addr)?;
+    if conflicts.is_empty() {
+        println!("No conflicts");
+        return Ok(());
+    }
+    for (i, conflict) in conflicts.iter().enumerate() {
+        let mine = match &conflict.edit.change {
+            Change::Add { event } => format!(
+                "add {} on {}",
+                event.name,
+                event.date.format(DEFAULT_DATE_FORMAT)
+            ),
+            Change::Remove => format!("remove {}", conflict.edit.name),
+            Change::SetDate { date } => format!(
+                "set {} to {}",
+                conflict.edit.name,
+                date.format(DEFAULT_DATE_FORMAT)
+            ),
+            Change::Snooze { snooze } => format!("{} {}", conflict.edit.name, snooze_text(*snooze)),
+            Change::Rename { name } => format!("rename {} to {name}", conflict.edit.name),
+        };
+        let theirs = match &conflict.theirs {
+            Some(event) => format!(
+                "{} on {}",
+                event.name,
+                event.date.format(DEFAULT_DATE_FORMAT)
+            ),
+            None => String::from("removed"),
+        };
+        println!(
+            "{}. {}\n   yours:  {mine} (made {})\n   theirs: {theirs}",
+            i + 1,
+            conflict.reason,
+            conflict.edit.made_at.format("%F %R")
+        );
+    }
+    println!("Use `countup conflicts keep-mine <NUMBER>` or `keep-theirs <NUMBER>` to resolve");
+    Ok(())
+}
+
+pub fn resolve_conflict(remote: Option<&str>, number: usize, keep_mine: bool) -> Result<()> {
+    let addr = remote.ok_or_else(|| eyre!(cli::text().needs_daemon))?;
+    match offline::resolve(addr, number - 1, keep_mine)? {
+        Outcome::Queued => println!("Unable to reach the daemon, your edit will be sent next time"),
+        _ if keep_mine => println!("Applied your edit"),
+        _ => println!("Discarded your edit"),
+    }
+    Ok(())
+}
+
+/// Adds events from a CSV file of `name,date` rows or an ICS calendar, skipping (and
+/// reporting) invalid rows and names that already exist
+///
+/// If `filter` is set only events with a matching name are imported
+pub fn import(config_path: &Path, path: &Path, filter: Option<&str>) -> Result<()> {
+    let text = fs::read_to_string(path).wrap_err_with(|| format!("Reading {}", path.display()))?;
+    let is_ics = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ics"));
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        || text.trim_start().starts_with(['{', '[']);
+    let rows = if is_ics {
+        parse_ics(&text)
+    } else if is_json {
+        parse_json(&text).wrap_err_with(|| format!("Reading {}", path.display()))?
+    } else {
+        parse_csv(&text)
+    };
+    let rows = merge_resets(rows);
+    let mut config = Config::load(config_path)?;
+    let mut imported = 0;
+    let mut skipped = vec![];
+    for row in rows {
+        let matches = |pattern| {
+            row.name
+                .as_deref()
+                .is_some_and(|name| matches_pattern(pattern, name))
+        };
+        if filter.is_some_and(|pattern| !matches(pattern)) {
+            continue;
+        }
+        let date = match row.date {
+            Ok(date) => date,
+            Err(reason) => {
+                skipped.push(format!("{}: {reason}", row.label));
+                continue;
+            }
+        };
+        let name = row.name.expect("rows with dates have names");
+        if config.event(&name).is_some() {
+            skipped.push(format!("{}: {name} already exists", row.label));
+            continue;
+        }
+        config.events.push(Event {
+            name,
+            date,
+            phases: vec![],
+            pauses: vec![],
+            timezone: None,
+            resets: row.resets,
+            background: None,
+            accent: None,
+            anniversary: None,
+            streak: None,
+            note_pauses: None,
+            snooze: None,
+            milestones: vec![],
+        });
+        imported += 1;
+    }
+    if imported > 0 {
+        config.save(config_path)?;
+    }
+    println!("Imported {imported} event(s)");
+    if !skipped.is_empty() {
+        println!("Skipped {} row(s):", skipped.len());
+        for reason in skipped {
+            println!("  {reason}");
+        }
+    }
+    Ok(())
+}
+
+/// Writes the next year of milestones and anniversaries across all events to `path` as an
+/// ICS calendar, `-` prints it
+pub fn export_milestones(config_path: &Path, remote: Option<&str>, path: &Path) -> Result<()> {
+    let config = Config::load_with_env(config_path)?;
+    let events = match remote {
+        Some(addr) => shared::list(addr)?,
+        None => config.events,
+    };
+    let today = Utc::now().date_naive();
+    let entries = digest::upcoming(&events, &config.patterns, today, EXPORT_DAYS);
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//emmabritton//countup//EN"),
+    ];
+    for entry in &entries {
+        let milestone = entry.milestone.as_deref().unwrap_or_default();
+        //the milestone too as an anniversary can be on the same day as one
+        let uid: String = format!(
+            "{}-{}-{milestone}",
+            entry.event,
+            entry.date.format("%Y%m%d")
+        )
+        .chars()
+        .map(|chr| {
+            if chr.is_ascii_alphanumeric() {
+                chr
+            } else {
+                '-'
+            }
+        })
+        .collect();
+        lines.extend([
+            String::from("BEGIN:VEVENT"),
+            format!("UID:{}@countup", uid.to_lowercase()),
+            format!("DTSTAMP:{stamp}"),
+            format!("DTSTART;VALUE=DATE:{}", entry.date.format("%Y%m%d")),
+            format!(
+                "DTEND;VALUE=DATE:{}",
+                (entry.date + Days::new(1)).format("%Y%m%d")
+            ),
+            format!(
+                "SUMMARY:{}",
+                escape_ics(&format!("{}: {milestone}", entry.event))
+            ),
+            String::from("TRANSP:TRANSPARENT"),
+            String::from("END:VEVENT"),
+        ]);
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    let ics: String = lines.iter().map(|line| fold_ics(line) + "\r\n").collect();
+    if path == Path::new("-") {
+        print!("{ics}");
+        return Ok(());
+    }
+    fs::write(path, ics).wrap_err_with(|| format!("Writing {}", path.display()))?;
+    println!(
+        "Exported {} milestones to {}",
+        entries.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+struct ImportRow {
+    /// Where the row was in the file, i.e. `row 3`
+    label: String,
+    name: Option<String>,
+    date: std::result::Result<NaiveDate, String>,
+    /// Earlier start dates, oldest first
+    resets: Vec<NaiveDate>,
+}
+
+/// Headers for the name column of habit and counter app exports, after lower casing and
+/// replacing spaces and dashes with `_`
+const NAME_COLUMNS: [&str; 6] = ["name", "event", "title", "label", "habit", "counter"];
+/// Headers for the date column, the first found is used
+const DATE_COLUMNS: [&str; 8] = [
+    "date",
+    "start",
+    "start_date",
+    "started",
+    "since",
+    "last_reset",
+    "reset",
+    "timestamp",
+];
+/// Keys for the earlier start dates in JSON exports
+const RESET_KEYS: [&str; 4] = ["resets", "reset_history", "history", "reset_dates"];
+
+/// Reads a date from an export, as [crate::date::parse_date] or the date part of an ISO
+/// 8601 time, i.e. `2022-11-25T08:00:00Z`
+fn import_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    crate::date::parse_date(text).or_else(|| {
+        text.get(..10)
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+    })
+}
+
+/// Combines rows with the same name, as habit apps export a row per reset, the latest date
+/// is kept as the start and the others become resets
+fn merge_resets(rows: Vec<ImportRow>) -> Vec<ImportRow> {
+    let mut merged: Vec<ImportRow> = vec![];
+    for row in rows {
+        let existing = match (&row.name, &row.date) {
+            (Some(name), Ok(_)) => merged.iter_mut().find(|other| {
+                other.date.is_ok()
+                    && other
+                        .name
+                        .as_ref()
+                        .is_some_and(|other| other.eq_ignore_ascii_case(name))
+            }),
+            _ => None,
+        };
+        match existing {
+            Some(existing) => {
+                let (Ok(date), Ok(other)) = (row.date, &mut existing.date) else {
+                    unreachable!("only rows with dates are merged");
+                };
+                existing.resets.extend(row.resets);
+                existing.resets.push(date.min(*other));
+                *other = date.max(*other);
+            }
+            None => merged.push(row),
+        }
+    }
+    for row in &mut merged {
+        if let Ok(date) = row.date {
+            row.resets.retain(|reset| *reset < date);
+        }
+        row.resets.sort();
+        row.resets.dedup();
+    }
+    merged
+}
+
+/// Columns of the name and date if `header` is from a habit or counter app export
+fn csv_columns(header: &[String]) -> Option<(usize, usize)> {
+    let header: Vec<String> = header
+        .iter()
+        .map(|field| field.trim().to_lowercase().replace([' ', '-'], "_"))
+        .collect();
+    let find = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| header.iter().position(|field| field == name))
+    };
+    Some((find(&NAME_COLUMNS)?, find(&DATE_COLUMNS)?))
+}
+
+/// Reads `name,date` rows, or the name and date columns of a habit app export with a header
+fn parse_csv(csv: &str) -> Vec<ImportRow> {
+    let mut rows = vec![];
+    let mut columns = None;
+    for (i, line) in csv.lines().enumerate() {
+        let row = i + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let label = format!("row {row}");
+        let fields = parse_csv_line(line);
+        if row == 1 {
+            columns = csv_columns(&fields);
+            if columns.is_some() {
+                continue;
+            }
+        }
+        let (name, date) = match columns {
+            Some((name, date)) => (fields.get(name), fields.get(date)),
+            None if fields.len() == 2 => (fields.first(), fields.get(1)),
+            None => (None, None),
+        };
+        let (Some(name), Some(date)) = (name, date) else {
+            let expected = columns.map_or(2, |(name, date)| name.max(date) + 1);
+            rows.push(ImportRow {
+                label,
+                name: None,
+                date: Err(format!(
+                    "expected {expected} columns, found {}",
+                    fields.len()
+                )),
+                resets: vec![],
+            });
+            continue;
+        };
+        let name = name.trim();
+        let date = date.trim();
+        rows.push(ImportRow {
+            label,
+            name: Some(name.to_string()).filter(|name| !name.is_empty()),
+            date: import_date(date)
+                .ok_or_else(|| format!("invalid date {date}, must be yyyy-mm-dd"))
+                .and_then(|date| {
+                    if name.is_empty() {
+                        Err(String::from("missing name"))
+                    } else {
+                        Ok(date)
+                    }
+                }),
+            resets: vec![],
+        });
+    }
+    rows
+}
+
+/// Reads a date from a JSON export, either text or a unix timestamp in seconds or
+/// milliseconds
+fn json_date(value: &Value) -> Option<NaiveDate> {
+    match value {
+        Value::String(text) => import_date(text),
+        Value::Number(number) => {
+            let secs = number.as_i64()?;
+            //anything after 5138 as seconds is milliseconds
+            let secs = if secs > 100_000_000_000 {
+                secs / 1000
+            } else {
+                secs
+            };
+            DateTime::from_timestamp(secs, 0).map(|time| time.date_naive())
+        }
+        //reset history entries can be objects, i.e. `{"date": "2022-11-25", "note": ".."}`
+        Value::Object(object) => DATE_COLUMNS
+            .iter()
+            .find_map(|key| object.get(*key))
+            .and_then(json_date),
+        _ => None,
+    }
+}
+
+/// Reads a habit or counter app's JSON export, a list of objects (or an object with one)
+/// with a name, date and optionally a list of earlier resets
+fn parse_json(json: &str) -> Result<Vec<ImportRow>> {
+    let value: Value = serde_json::from_str(json)?;
+    let items = match &value {
+        Value::Array(items) => items,
+        Value::Object(object) => object
+            .values()
+            .find_map(Value::as_array)
+            .ok_or_else(|| eyre!("No list of events"))?,
+        _ => return Err(eyre!("No list of events")),
+    };
+    Ok(items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            //keys are matched like CSV headers, so `startDate` and `start_date` both work
+            let object: Map<String, Value> = item
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| (json_key(key), value.clone()))
+                .collect();
+            let name = NAME_COLUMNS
+                .iter()
+                .find_map(|key| object.get(*key))
+                .and_then(Value::as_str)
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty());
+            let date = match (&name, json_date(&Value::Object(object.clone()))) {
+                (None, _) => Err(String::from("missing name")),
+                (Some(name), None) => Err(format!("{name} has no valid date")),
+                (Some(_), Some(date)) => Ok(date),
+            };
+            let resets = RESET_KEYS
+                .iter()
+                .find_map(|key| object.get(*key))
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(json_date)
+                .collect();
+            ImportRow {
+                label: format!("item {}", i + 1),
+                name,
+                date,
+                resets,
+            }
+        })
+        .collect())
+}
+
+/// Converts `camelCase`, `kebab-case` and spaced keys to `snake_case`
+fn json_key(key: &str) -> String {
+    let mut snake = String::new();
+    for chr in key.trim().chars() {
+        match chr {
+            ' ' | '-' => snake.push('_'),
+            chr if chr.is_uppercase() => {
+                if !snake.is_empty() && !snake.ends_with('_') {
+                    snake.push('_');
+                }
+                snake.extend(chr.to_lowercase());
+            }
+            chr => snake.push(chr),
+        }
+    }
+    snake
+}
+
+/// Reads the `SUMMARY` and `DTSTART` of each `VEVENT`, for recurring events (i.e. birthdays)
+/// this is the first occurrence
+fn parse_ics(ics: &str) -> Vec<ImportRow> {
+    //long lines are folded by starting the next line with a space or tab
+    let mut lines: Vec<String> = vec![];
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    let mut rows = vec![];
+    let mut event: Option<(Option<String>, Option<String>)> = None;
+    let mut count = 0;
+    for line in lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        //parameters such as `;VALUE=DATE` or `;TZID=...` aren't needed
+        let key = key.split(';').next().unwrap_or_default();
+        match (key.to_ascii_uppercase().as_str(), &mut event) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                event = Some((None, None));
+                count += 1;
+            }
+            ("SUMMARY", Some((summary, _))) => *summary = Some(unescape_ics(value)),
+            ("DTSTART", Some((_, start))) => *start = Some(value.trim().to_string()),
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                if let Some((summary, start)) = event.take() {
+                    rows.push(ics_row(count, summary, start));
+                }
+            }
+            _ => {}
+        }
+    }
+    rows
+}
+
+fn ics_row(count: usize, summary: Option<String>, start: Option<String>) -> ImportRow {
+    let name = summary
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    let date = match (&name, start) {
+        (None, _) => Err(String::from("missing summary")),
+        (Some(name), None) => Err(format!("{name} has no start date")),
+        //dates are `yyyymmdd`, optionally followed by a time
+        (Some(name), Some(start)) => start
+            .get(..8)
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+            .ok_or_else(|| format!("{name} has invalid start {start}")),
+    };
+    ImportRow {
+        label: format!("event {count}"),
+        name,
+        date,
+        resets: vec![],
+    }
+}
+
+/// Undoes ICS text escaping, i.e. `\,` and `\n`
+fn unescape_ics(value: &str) -> String {
+    let mut text = String::new();
+    let mut chars = value.chars();
+    while let Some(chr) = chars.next() {
+        match (chr, chars.clone().next()) {
+            ('\\', Some('n' | 'N')) => {
+                text.push(' ');
+                chars.next();
+            }
+            ('\\', Some(escaped @ ('\\' | ',' | ';'))) => {
+                text.push(escaped);
+                chars.next();
+            }
+            _ => text.push(chr),
+        }
+    }
+    text
+}
+
+/// Escapes text for ICS, the opposite of [unescape_ics]
+fn escape_ics(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds lines longer than the 75 bytes ICS allows, continuing with a space
+fn fold_ics(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for chr in line.chars() {
+        if length + chr.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(chr);
+        length += chr.len_utf8();
+    }
+    folded
+}
+
+/// Case insensitive match where `*` matches any text, without a `*` the pattern can be
+/// anywhere in `name`
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    if !pattern.contains('*') {
+        return name.contains(&pattern);
+    }
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, rest) = parts.split_first().expect("split always has one part");
+    let Some(mut remaining) = name.strip_prefix(first) else {
+        return false;
+    };
+    let (last, middle) = rest.split_last().expect("pattern contains *");
+    for part in middle {
+        match remaining.find(part) {
+            Some(idx) => remaining = &remaining[idx + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+/// Saves a secret read from the first line of stdin
+pub fn auth_set(name: &str) -> Result<()> {
+    if stdin().is_terminal() {
+        print!("Secret for {name}: ");
+        stdout().flush()?;
+    }
+    let mut secret = String::new();
+    stdin().read_line(&mut secret)?;
+    let secret = secret.trim_end_matches(['\r', '\n']);
+    if secret.is_empty() {
+        return Err(eyre!("No secret entered"));
+    }
+    match credentials::set(name, secret)? {
+        Store::Keyring => println!("Saved {name} to the keyring"),
+        Store::File => println!("Saved {name} to the credentials file"),
+    }
+    Ok(())
+}
+
+pub fn auth_clear(name: &str) -> Result<()> {
+    if !credentials::clear(name)? {
+        return Err(eyre!("No secret named {name}"));
+    }
+    println!("Cleared {name}");
+    Ok(())
+}
+
+pub fn token_add(name: &str, role: &str) -> Result<()> {
+    let role = Role::from_name(role).ok_or_else(|| eyre!("Unknown role {role}"))?;
+    let token = Tokens::add(name, role)?;
+    println!("Created {role} token {name}, it won't be shown again:");
+    println!("{}", token.token);
+    Ok(())
+}
+
+pub fn token_list() -> Result<()> {
+    let tokens = Tokens::load()?;
+    if tokens.list().is_empty() {
+        println!("No tokens, the daemon and server can be used without one");
+        return Ok(());
+    }
+    for token in tokens.list() {
+        println!(
+            "{}: {} (created {})",
+            token.name,
+            token.role,
+            token.created.format(DEFAULT_DATE_FORMAT)
+        );
+    }
+    Ok(())
+}
+
+pub fn token_remove(name: &str) -> Result<()> {
+    if !Tokens::remove(name)? {
+        return Err(eyre!("No token named {name}"));
+    }
+    println!("Removed {name}");
+    Ok(())
+}
+
+/// Splits a CSV line into fields, supporting quoted fields with commas and `""` escapes
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(chr) = chars.next() {
+        match chr {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(chr),
+        }
+    }
+    fields.push(field);
+    fields
+}
@@ -0,0 +1,277 @@
+use crate::cli;
+use crate::count::{Easing, Intro};
+use crate::event::Event;
+use crate::hooks::Hooks;
+use crate::i18n::{NumberFormat, Numerals};
+use crate::keys::KeysConfig;
+use crate::milestones::Pattern;
+use crate::mqtt::MqttConfig;
+use crate::net::NetworkConfig;
+use crate::paths;
+use crate::render::BackgroundStyle;
+use crate::report::{Counting, MonthLength};
+use crate::term::Term;
+use crate::units::CustomUnit;
+use color_eyre::eyre::WrapErr;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+const CONFIG_FILE: &str = "config.json";
+const ENV_PREFIX: &str = "COUNTUP_";
+/// Separates nested keys in env var names, i.e. `COUNTUP_NETWORK__CA_BUNDLE`
+const ENV_NESTING: &str = "__";
+
+/// Settings read from the config file, everything is optional
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<Term>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<Event>,
+    /// See `--lang`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// See `--date-format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    /// See `--template`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(skip_serializing_if = "Hooks::is_empty")]
+    pub hooks: Hooks,
+    #[serde(skip_serializing_if = "NetworkConfig::is_empty")]
+    pub network: NetworkConfig,
+    /// Publishes the count for home dashboards, see [MqttConfig]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mqtt: Option<MqttConfig>,
+    /// Seconds to count up a year of days, see `--anim-speed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anim_speed: Option<f64>,
+    /// See `--reduced-motion`, the OS's setting is used if not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduced_motion: Option<bool>,
+    /// See `--easing`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub easing: Option<Easing>,
+    /// See `--intro`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intro: Option<Intro>,
+    /// Play sounds while counting, see `--sound`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sound: Option<bool>,
+    /// Open on the digest the first time the counter is opened each week
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weekly_digest: Option<bool>,
+    /// See `--font`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<PathBuf>,
+    /// See `--background`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_image: Option<PathBuf>,
+    /// See `--background-style`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_style: Option<BackgroundStyle>,
+    /// See `--text-scale`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_scale: Option<usize>,
+    /// Window size as `WIDTHxHEIGHT`, see `--size`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    /// See `--scale`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<usize>,
+    /// See `--min-text-size`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_text_size: Option<usize>,
+    /// See `--numerals`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numerals: Option<Numerals>,
+    /// See `--number-format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_format: Option<NumberFormat>,
+    /// See `--capture-frames`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_frames: Option<usize>,
+    /// See `--clock`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock: Option<bool>,
+    /// See `--minimal`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimal: Option<bool>,
+    /// See `--high-contrast`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_contrast: Option<bool>,
+    /// See `--hours`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hours: Option<u8>,
+    /// Month the fiscal year starts in, see `--fiscal-year`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fiscal_year: Option<u32>,
+    /// See `--inclusive` and `--exclusive`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counting: Option<Counting>,
+    /// See `--month-length`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub month_length: Option<MonthLength>,
+    /// See `--patterns`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<Pattern>,
+    /// Shared libraries with extra modes, see `include/countup_mode.h`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PathBuf>,
+    /// Extra rows in diff mode, see [CustomUnit]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub units: Vec<CustomUnit>,
+    /// Changed shortcuts, see [KeysConfig]
+    #[serde(skip_serializing_if = "KeysConfig::is_empty")]
+    pub keys: KeysConfig,
+}
+
+/// Default location of the config file, `config.json` in the XDG config dir if there's one
+/// there (and `--data-dir` wasn't passed), otherwise in the data dir
+pub fn default_path() -> Result<PathBuf> {
+    if !paths::has_data_dir() {
+        if let Some(path) = paths::xdg_config_dir().map(|dir| dir.join(CONFIG_FILE)) {
+            if path.exists() {
+                return Ok(path);
+            }
+        }
+    }
+    let mut path = paths::data_dir()?;
+    path.push(CONFIG_FILE);
+    Ok(path)
+}
+
+impl Config {
+    pub fn event(&self, name: &str) -> Option<&Event> {
+        self.events
+            .iter()
+            .find(|event| event.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Loads the config from `path`, a missing file is treated as an empty config
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let json = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Reading config from {}", path.display()))?;
+        serde_json::from_str(&json)
+            .wrap_err_with(|| format!("Parsing config from {}", path.display()))
+    }
+
+    /// Loads the config from `path` and then applies any `COUNTUP_*` env vars over it
+    ///
+    /// This shouldn't be used when the config is going to be saved, as the env values
+    /// would be written to the file
+    pub fn load_with_env(path: &Path) -> Result<Self> {
+        let value = if path.exists() {
+            let json = fs::read_to_string(path)
+                .wrap_err_with(|| format!("Reading config from {}", path.display()))?;
+            serde_json::from_str(&json)
+                .wrap_err_with(|| format!("Parsing config from {}", path.display()))?
+        } else {
+            Value::Object(Map::new())
+        };
+        let config = with_env(value, env::vars())?;
+        debug!(path = %path.display(), events = config.events.len(), "loaded config");
+        Ok(config)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).wrap_err_with(|| format!("Writing config to {}", path.display()))
+    }
+}
+
+/// `config` with the `COUNTUP_*` vars in `vars` applied, other than the CLI ones
+fn with_env(mut config: Value, vars: impl IntoIterator<Item = (String, String)>) -> Result<Config> {
+    let cli_vars = cli::env_vars();
+    for (key, value) in vars {
+        if cli_vars.contains(&key) {
+            continue;
+        }
+        if let Some(key) = key.strip_prefix(ENV_PREFIX) {
+            apply_env(&mut config, key, &value);
+        }
+    }
+    serde_json::from_value(config).wrap_err("Applying COUNTUP_* env vars to config")
+}
+
+/// Sets the value at `key` (a `__` separated path) in `config`, `value` is parsed as JSON
+/// if possible otherwise it's used as a string
+///
+/// Vars that don't match a config option end up as unknown fields and are ignored when
+/// deserializing, the CLI ones are skipped before this
+fn apply_env(config: &mut Value, key: &str, value: &str) {
+    let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+    let mut target = config;
+    for part in key.to_lowercase().split(ENV_NESTING) {
+        if !target.is_object() {
+            *target = Value::Object(Map::new());
+        }
+        target = target
+            .as_object_mut()
+            .expect("just made into an object")
+            .entry(part)
+            .or_insert(Value::Null);
+    }
+    *target = value;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn config_options_from_env() {
+        let config = with_env(
+            Value::Object(Map::new()),
+            vars(&[
+                ("COUNTUP_COUNTING", "\"inclusive\""),
+                ("COUNTUP_NETWORK__CA_BUNDLE", "/etc/ca.pem"),
+                ("HOME", "/root"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.counting, Some(Counting::Inclusive));
+        assert_eq!(config.network.ca_bundle, Some(PathBuf::from("/etc/ca.pem")));
+    }
+
+    #[test]
+    fn cli_env_vars_are_left_to_clap() {
+        let config = with_env(
+            Value::Object(Map::new()),
+            vars(&[("COUNTUP_MONTH_LENGTH", "30")]),
+        )
+        .unwrap();
+        assert_eq!(config.month_length, None);
+    }
+
+    #[test]
+    fn patterns_env_var_is_the_cli_list() {
+        let config = with_env(
+            Value::Object(Map::new()),
+            vars(&[("COUNTUP_PATTERNS", "palindrome,repdigit")]),
+        )
+        .unwrap();
+        assert!(config.patterns.is_empty());
+        let matches = cli::build()
+            .try_get_matches_from(["countup", "--patterns", "palindrome,repdigit"])
+            .unwrap();
+        let patterns: Vec<&String> = matches.get_many("patterns").unwrap().collect();
+        assert_eq!(patterns, ["palindrome", "repdigit"]);
+    }
+}
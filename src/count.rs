@@ -0,0 +1,951 @@
+use crate::i18n::Strings;
+use crate::report::{Counting, Split};
+use chrono::{
+    DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Default seconds taken to count up a year of days, shorter counts take the same time
+pub const COUNT_TIME_PER_YEAR: f64 = 1.0;
+/// Counts longer than this many years take as long as it, so a date centuries ago doesn't
+/// take minutes to count up
+pub const MAX_COUNT_YEARS: f64 = 30.0;
+/// Seconds the numbers are highlighted for when the day changes while the window is open
+pub const ROLLOVER_FLASH: f64 = 1.5;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Mode {
+    Split,
+    Diff,
+    /// Whole weeks and the days left over, i.e. for a baby's age or a training plan
+    WeeksAndDays,
+    /// Total days in large seven segment digits, for reading from across the room
+    Segments,
+    /// Launch style countdown, used when the date is in the future
+    TMinus,
+    /// Progress through the configured school term
+    Term,
+    /// Progress through the phases of the selected event
+    Phases,
+    /// Recent edits to the selected event, when connected to a daemon
+    History,
+    /// Summary of the last completed year
+    Recap,
+    /// Milestones from the past week and what's coming up, across all events
+    Digest,
+    /// Lengths of the selected event's past streaks, once it's been reset
+    Streaks,
+    /// The count next to the days until `--pair`'s target
+    Dual,
+    /// ISO week numbers and the full weeks since the start, for planning in week numbers
+    Weeks,
+    /// Day of the fiscal year and the fiscal years since the start, from `--fiscal-year`
+    Fiscal,
+    /// Lunar cycles and full moons since the start
+    Moons,
+    /// Day and week of the year and how far through it it is, with a progress bar
+    Year,
+    /// How far through the year, month and day it is, the only mode with `--calendar`
+    Calendar,
+    /// Age to the minute, from `--birthday` with `--born-at`
+    Age,
+    /// Seconds, minutes, hours, days and weeks since the start, all at once
+    Totals,
+    /// The next round 100 and 1000 days and the next anniversary, with their dates
+    Upcoming,
+    /// The weekday of the start and how many of each weekday there's been since
+    Weekdays,
+    /// Years, months and days in Roman numerals, from `--roman`
+    Roman,
+    /// The total days in decimal, hexadecimal and binary
+    Bases,
+    /// A line from the start to the next milestone, with a tick for each anniversary
+    Timeline,
+    /// An added [crate::plugin::DisplayMode], by its index in the [crate::plugin::Registry]
+    Plugin(u8),
+}
+
+impl Mode {
+    /// Modes that only show the count, the ones kept by `--minimal`
+    pub fn is_count(self) -> bool {
+        matches!(
+            self,
+            Mode::Split
+                | Mode::Diff
+                | Mode::WeeksAndDays
+                | Mode::Segments
+                | Mode::TMinus
+                | Mode::Dual
+        )
+    }
+}
+
+/// Where the current time comes from, the system clock unless it's fixed with `--now`
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum TimeSource {
+    #[default]
+    System,
+    /// Always this time, for screenshots, demos and tests
+    Fixed(DateTime<Utc>),
+}
+
+impl TimeSource {
+    pub fn now(&self) -> DateTime<Utc> {
+        match self {
+            TimeSource::System => Utc::now(),
+            TimeSource::Fixed(time) => *time,
+        }
+    }
+
+    /// [now](TimeSource::now) in the system timezone
+    pub fn local(&self) -> DateTime<Local> {
+        self.now().with_timezone(&Local)
+    }
+}
+
+/// Modes available for any date, before any from the config
+pub fn date_modes(start_date: DateTime<Utc>, time: TimeSource) -> Vec<Mode> {
+    if start_date > time.now() {
+        vec![Mode::TMinus]
+    } else {
+        vec![
+            Mode::Split,
+            Mode::Diff,
+            Mode::WeeksAndDays,
+            Mode::Segments,
+            Mode::Weeks,
+            Mode::Moons,
+            Mode::Year,
+            Mode::Totals,
+            Mode::Upcoming,
+            Mode::Weekdays,
+            Mode::Bases,
+            Mode::Timeline,
+        ]
+    }
+}
+
+/// Returns the start of `date` and the days since, which go up at midnight in `timezone`
+/// (UTC if not set), 0 days for future dates
+pub fn calc_days_since(
+    date: NaiveDate,
+    timezone: Option<Tz>,
+    counting: Counting,
+    time: TimeSource,
+) -> (DateTime<Utc>, usize) {
+    let today = local_date(time.now(), timezone);
+    let days = if today < date {
+        0
+    } else {
+        (today - date).num_days() as usize + counting.offset()
+    };
+    (midnight(date, timezone), days)
+}
+
+/// Target for `--goal`, a number of days or a date
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Goal {
+    Days(usize),
+    Date(NaiveDate),
+}
+
+impl Goal {
+    /// Days from `start` to the goal, at least 1
+    pub fn days(&self, start: NaiveDate) -> usize {
+        let days = match self {
+            Goal::Days(days) => *days,
+            Goal::Date(date) => (*date - start).num_days().max(0) as usize,
+        };
+        days.max(1)
+    }
+}
+
+/// Days from `today` until the next anniversary of `start`, 0 on the day, anniversaries of
+/// 29 February are on the 28th in other years
+pub fn days_to_anniversary(start: NaiveDate, today: NaiveDate) -> usize {
+    let on = |year| {
+        start
+            .with_year(year)
+            .or_else(|| NaiveDate::from_ymd_opt(year, 2, 28))
+            .expect("28 February is valid")
+    };
+    let mut next = on(today.year());
+    if next < today {
+        next = on(today.year() + 1);
+    }
+    (next - today).num_days().max(0) as usize
+}
+
+/// ISO weeks for weeks mode, weeks start on Monday and week 1 has the year's first Thursday
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct IsoWeeks {
+    /// Week and ISO year the start is in, near new year this can differ from the calendar year
+    pub start: (u32, i32),
+    /// Week `today` is in
+    pub week: u32,
+    /// Monday to Sunday weeks from the start to `today`, not counting the week the start
+    /// is in unless it's a Monday
+    pub full: usize,
+}
+
+impl IsoWeeks {
+    pub fn new(start: NaiveDate, today: NaiveDate) -> Self {
+        let monday =
+            |date: NaiveDate| date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        let first = monday(start + Duration::days(6));
+        let full = (monday(today) - first).num_days().max(0) as usize / 7;
+        let iso = start.iso_week();
+        IsoWeeks {
+            start: (iso.week(), iso.year()),
+            week: today.iso_week().week(),
+            full,
+        }
+    }
+}
+
+/// Where `today` is in its calendar year for year mode, from the start if that's this year
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct YearProgress {
+    pub year: i32,
+    /// 1 January, or the start if it's later in the year
+    pub from: NaiveDate,
+    /// Day of the year, 1 on 1 January
+    pub day: u32,
+    /// ISO week `today` is in
+    pub week: u32,
+    /// Days from `from` to `today`
+    pub elapsed: usize,
+    /// Days from `from` to the end of the year
+    pub length: usize,
+}
+
+impl YearProgress {
+    pub fn new(start: NaiveDate, today: NaiveDate) -> Self {
+        let first = |year: i32| NaiveDate::from_ymd_opt(year, 1, 1).expect("1 January is valid");
+        let from = start.clamp(first(today.year()), today);
+        YearProgress {
+            year: today.year(),
+            from,
+            day: today.ordinal(),
+            week: today.iso_week().week(),
+            elapsed: (today - from).num_days() as usize,
+            length: (first(today.year() + 1) - from).num_days() as usize,
+        }
+    }
+
+    /// Whole percent of the year gone, from `from`
+    pub fn percent(&self) -> usize {
+        self.elapsed * 100 / self.length.max(1)
+    }
+}
+
+/// Hours between the milestones in age mode, i.e. 300000 hours old
+pub const HOURS_MILESTONE: usize = 10_000;
+
+/// Age to the minute for age mode, from the birth date and time
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct PreciseAge {
+    /// Calendar years, months and days to the last time it was the time of birth
+    pub split: Split,
+    /// Since the last time it was the time of birth
+    pub hours: u32,
+    pub minutes: u32,
+    pub total_hours: usize,
+    pub total_minutes: usize,
+    /// Next multiple of [HOURS_MILESTONE] hours old
+    pub next_hours: usize,
+    pub next_at: NaiveDateTime,
+}
+
+impl PreciseAge {
+    pub fn new(born: NaiveDateTime, now: NaiveDateTime) -> Self {
+        let total_minutes = (now - born).num_minutes().max(0) as usize;
+        let total_hours = total_minutes / 60;
+        let mut day = now.date();
+        if now.time() < born.time() {
+            day = day.pred_opt().unwrap_or(day);
+        }
+        let day = day.max(born.date());
+        let since = (now - day.and_time(born.time())).num_minutes().max(0);
+        let next_hours = (total_hours / HOURS_MILESTONE + 1) * HOURS_MILESTONE;
+        PreciseAge {
+            split: Split::between(born.date(), day),
+            hours: (since / 60) as u32,
+            minutes: (since % 60) as u32,
+            total_hours,
+            total_minutes,
+            next_hours,
+            next_at: born + Duration::hours(next_hours as i64),
+        }
+    }
+
+    /// Years, months, days, hours and minutes, i.e. `34 YEARS, 5 MONTHS, 12 DAYS, 07:23`
+    pub fn summary(&self, strings: &Strings) -> String {
+        format!(
+            "{} {}, {} {}, {} {}, {:02}:{:02}",
+            self.split.years,
+            strings.years,
+            self.split.months,
+            strings.months,
+            self.split.days,
+            strings.days,
+            self.hours,
+            self.minutes
+        )
+    }
+
+    /// [PreciseAge::summary] and when it's next [HOURS_MILESTONE] hours old, `format` is
+    /// used for the date and time of that
+    pub fn lines(&self, format: &str, strings: &Strings) -> [String; 2] {
+        [
+            self.summary(strings),
+            strings.hours_old(self.next_hours, &self.next_at.format(format).to_string()),
+        ]
+    }
+}
+
+/// Time from `start` to `now` in every unit for totals mode, each rounded down, without
+/// taking out any pauses
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Totals {
+    pub seconds: u64,
+    pub minutes: u64,
+    pub hours: u64,
+    pub days: u64,
+    pub weeks: u64,
+}
+
+impl Totals {
+    pub fn new(start: DateTime<Utc>, now: DateTime<Utc>) -> Self {
+        let seconds = (now - start).num_seconds().max(0) as u64;
+        Totals {
+            seconds,
+            minutes: seconds / 60,
+            hours: seconds / 3600,
+            days: seconds / 86400,
+            weeks: seconds / (86400 * 7),
+        }
+    }
+}
+
+/// Each weekday there's been after the start up to `today`, for weekdays mode
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Weekdays {
+    /// Weekday of the start
+    pub start: Weekday,
+    /// From Monday, they add up to the days since the start
+    pub counts: [usize; 7],
+}
+
+impl Weekdays {
+    pub fn new(start: NaiveDate, today: NaiveDate) -> Self {
+        let days = (today - start).num_days().max(0) as usize;
+        let first = start.weekday().succ().num_days_from_monday() as usize;
+        let mut counts = [days / 7; 7];
+        //the days left over from the full weeks start the day after the start
+        for extra in 0..days % 7 {
+            counts[(first + extra) % 7] += 1;
+        }
+        Weekdays {
+            start: start.weekday(),
+            counts,
+        }
+    }
+}
+
+/// The start to the next milestone, with today and the anniversaries between, for timeline
+/// mode
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Timeline {
+    pub start: NaiveDate,
+    pub today: NaiveDate,
+    /// The next milestone, where the line ends
+    pub end: NaiveDate,
+    /// Anniversaries up to today
+    pub years: u32,
+}
+
+impl Timeline {
+    pub fn new(start: NaiveDate, today: NaiveDate, milestone: NaiveDate) -> Self {
+        let today = today.max(start);
+        let mut years = (today.year() - start.year()).max(0) as u32;
+        if years > 0 && anniversary(start, years) > today {
+            years -= 1;
+        }
+        Timeline {
+            start,
+            today,
+            end: milestone.max(today),
+            years,
+        }
+    }
+
+    /// How far along the line `date` is, from 0 at the start to 1 at the end
+    pub fn position(&self, date: NaiveDate) -> f64 {
+        let length = (self.end - self.start).num_days().max(1) as f64;
+        ((date - self.start).num_days() as f64 / length).clamp(0.0, 1.0)
+    }
+
+    /// The date of each anniversary up to today, every `step` years
+    pub fn anniversaries(&self, step: u32) -> impl Iterator<Item = (u32, NaiveDate)> + '_ {
+        (step.max(1)..=self.years)
+            .step_by(step.max(1) as usize)
+            .map(|year| (year, anniversary(self.start, year)))
+    }
+}
+
+/// `years` after `start`, on 28 February for a 29 February start in other years
+fn anniversary(start: NaiveDate, years: u32) -> NaiveDate {
+    start
+        .checked_add_months(Months::new(years * 12))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// How far through the year, month and day `now` is for calendar mode, each from 0 to 1
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CalendarProgress {
+    pub year: f64,
+    pub month: f64,
+    pub day: f64,
+}
+
+impl CalendarProgress {
+    pub fn new(now: NaiveDateTime) -> Self {
+        let today = now.date();
+        let through = |start: NaiveDate, end: NaiveDate| {
+            let start = start.and_hms_opt(0, 0, 0).expect("midnight is valid");
+            let end = end.and_hms_opt(0, 0, 0).expect("midnight is valid");
+            (now - start).num_seconds() as f64 / (end - start).num_seconds() as f64
+        };
+        let new_year = |year: i32| NaiveDate::from_ymd_opt(year, 1, 1).expect("1 January is valid");
+        let first = today.with_day(1).expect("the 1st is valid");
+        CalendarProgress {
+            year: through(new_year(today.year()), new_year(today.year() + 1)),
+            month: through(first, first + Months::new(1)),
+            day: through(today, today + Duration::days(1)),
+        }
+    }
+}
+
+/// Where `today` is in the fiscal year for `--fiscal-year`, which starts on the 1st of a
+/// month and is named after the calendar year it ends in, i.e. FY25 from April 2024
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct Fiscal {
+    /// Calendar year the fiscal year ends in
+    pub year: i32,
+    pub started: NaiveDate,
+    /// Day of the fiscal year, 1 on its first day
+    pub day: usize,
+    /// Days after today until the next fiscal year starts
+    pub days_left: usize,
+    /// Whole fiscal years from the start to `today`
+    pub years: usize,
+}
+
+impl Fiscal {
+    /// `start_month` is 1 to 12
+    pub fn new(start: NaiveDate, today: NaiveDate, start_month: u32) -> Self {
+        let year_start = |date: NaiveDate| {
+            let year = if date.month() >= start_month {
+                date.year()
+            } else {
+                date.year() - 1
+            };
+            NaiveDate::from_ymd_opt(year, start_month, 1).expect("the 1st is valid")
+        };
+        let started = year_start(today);
+        let next = started + Months::new(12);
+        //the first whole year is the one the start is on the first day of, or the next
+        let first = year_start(start);
+        let first = if first == start {
+            first
+        } else {
+            first + Months::new(12)
+        };
+        Fiscal {
+            year: (next - Duration::days(1)).year(),
+            started,
+            day: (today - started).num_days() as usize + 1,
+            days_left: (next - today).num_days() as usize - 1,
+            years: (started.year() - first.year()).max(0) as usize,
+        }
+    }
+}
+
+/// Date at `at` in `timezone`, or UTC if not set
+pub fn local_date(at: DateTime<Utc>, timezone: Option<Tz>) -> NaiveDate {
+    match timezone {
+        Some(timezone) => at.with_timezone(&timezone).date_naive(),
+        None => at.date_naive(),
+    }
+}
+
+/// Start of `date` in `timezone`, or UTC if not set
+pub fn midnight(date: NaiveDate, timezone: Option<Tz>) -> DateTime<Utc> {
+    let naive = date.and_hms_opt(0, 0, 0).expect("midnight is valid");
+    timezone
+        //a few timezones skip midnight when the clocks change, so use the UTC time instead
+        .and_then(|timezone| timezone.from_local_datetime(&naive).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+}
+
+/// First midnight after `now` in `timezone`, when counts in that timezone go up
+pub fn next_midnight(now: DateTime<Utc>, timezone: Option<Tz>) -> DateTime<Utc> {
+    midnight(local_date(now, timezone) + Duration::days(1), timezone)
+}
+
+/// How the count speeds up or slows down while counting
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Easing {
+    /// Same speed throughout
+    #[default]
+    Linear,
+    /// Starts fast and slows down
+    EaseOut,
+    /// Starts and ends slowly
+    EaseInOut,
+    /// Races through most of the count then crawls to the end
+    Exponential,
+}
+
+impl Easing {
+    pub const NAMES: [&'static str; 4] = ["linear", "ease-out", "ease-in-out", "exponential"];
+
+    pub fn from_name(name: &str) -> Option<Easing> {
+        match name {
+            "linear" => Some(Easing::Linear),
+            "ease-out" => Some(Easing::EaseOut),
+            "ease-in-out" => Some(Easing::EaseInOut),
+            "exponential" => Some(Easing::Exponential),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        Easing::NAMES[self as usize]
+    }
+
+    /// Fraction of the count to show after `progress` (0 to 1) of the time
+    pub fn apply(self, progress: f64) -> f64 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t.powi(3),
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+            Easing::Exponential if t >= 1.0 => 1.0,
+            Easing::Exponential => 1.0 - 2_f64.powf(-10.0 * t),
+        }
+    }
+}
+
+/// How the numbers appear when the counter is opened
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Intro {
+    /// Counts up from zero
+    #[default]
+    CountUp,
+    /// Types out the final numbers, one row after another
+    Typewriter,
+    /// Fades in each row
+    Fade,
+    /// Spins the digits then settles on the final numbers
+    Slot,
+    /// Rolls each digit up into the next, like a mechanical odometer
+    Odometer,
+}
+
+impl Intro {
+    pub const NAMES: [&'static str; 5] = ["count-up", "typewriter", "fade", "slot", "odometer"];
+
+    pub fn from_name(name: &str) -> Option<Intro> {
+        match name {
+            "count-up" => Some(Intro::CountUp),
+            "typewriter" => Some(Intro::Typewriter),
+            "fade" => Some(Intro::Fade),
+            "slot" => Some(Intro::Slot),
+            "odometer" => Some(Intro::Odometer),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        Intro::NAMES[self as usize]
+    }
+
+    /// The style after this one, wrapping round
+    pub fn next(self) -> Intro {
+        match self {
+            Intro::CountUp => Intro::Typewriter,
+            Intro::Typewriter => Intro::Fade,
+            Intro::Fade => Intro::Slot,
+            Intro::Slot => Intro::Odometer,
+            Intro::Odometer => Intro::CountUp,
+        }
+    }
+}
+
+/// How the count rolls up
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Animation {
+    /// Seconds to count up a year, counts under a year take the same time and counts over
+    /// [MAX_COUNT_YEARS] take the time for that, 0 shows the days immediately
+    pub time_per_year: f64,
+    pub easing: Easing,
+    pub intro: Intro,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Animation {
+            time_per_year: COUNT_TIME_PER_YEAR,
+            easing: Easing::default(),
+            intro: Intro::default(),
+        }
+    }
+}
+
+/// Days shown, counts up from zero to `days` when first shown
+#[derive(Debug, Clone)]
+pub struct Count {
+    pub days: usize,
+    pub current: usize,
+    animation: Animation,
+    /// Seconds the count up takes
+    duration: f64,
+    /// Time the count up started, from the clock passed to [Count::update]
+    started: Option<f64>,
+    /// 0 to 1, after easing
+    progress: f64,
+    /// Time passed to the last [Count::update]
+    last_update: Option<f64>,
+    /// While paused the start time moves forward with updates so the count resumes from
+    /// the same place
+    paused: bool,
+    /// Highlighted after [Count::roll_over], until [ROLLOVER_FLASH] after the next update
+    flashing: bool,
+    /// Time of the first update after rolling over
+    flash_started: Option<f64>,
+}
+
+impl Count {
+    pub fn new(days: usize, animation: Animation) -> Self {
+        let years = days as f64 / 365.0;
+        Self {
+            days,
+            current: 0,
+            animation,
+            duration: years.clamp(1.0, MAX_COUNT_YEARS) * animation.time_per_year,
+            started: None,
+            progress: 0.0,
+            last_update: None,
+            paused: false,
+            flashing: false,
+            flash_started: None,
+        }
+    }
+
+    /// Counts up to `days` from zero, keeping the animation
+    pub fn count_to(&mut self, days: usize) {
+        *self = Count::new(days, self.animation);
+    }
+
+    pub fn is_counting(&self) -> bool {
+        self.current < self.days
+    }
+
+    pub fn intro(&self) -> Intro {
+        self.animation.intro
+    }
+
+    /// Days to draw, only counting up shows the days before the intro has finished
+    pub fn shown(&self) -> usize {
+        match self.animation.intro {
+            Intro::CountUp => self.current,
+            _ => self.days,
+        }
+    }
+
+    /// How far through the intro the count is, from 0 to 1
+    pub fn progress(&self) -> f64 {
+        if self.is_counting() {
+            self.progress
+        } else {
+            1.0
+        }
+    }
+
+    /// Counts up from zero again
+    pub fn restart(&mut self) {
+        self.current = 0;
+        self.started = None;
+        self.progress = 0.0;
+        self.paused = false;
+    }
+
+    /// Swaps to the intro style after the current one and plays it from the start
+    pub fn next_intro(&mut self) {
+        self.animation.intro = self.animation.intro.next();
+        self.restart();
+    }
+
+    /// Freezes the count where it is, or carries on counting if already paused
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Stops counting and shows the final number
+    pub fn skip(&mut self) {
+        self.current = self.days;
+    }
+
+    /// Jumps straight to `days`, i.e. when the day changes
+    pub fn set(&mut self, days: usize) {
+        self.count_to(days);
+        self.current = days;
+    }
+
+    /// As [Count::set], then highlights the numbers so the new day is noticed, keep calling
+    /// [Count::update] while [Count::is_flashing]
+    pub fn roll_over(&mut self, days: usize) {
+        self.set(days);
+        self.flashing = true;
+    }
+
+    pub fn is_flashing(&self) -> bool {
+        self.flashing
+    }
+
+    /// How highlighted the numbers are, 1 as the day changes fading to 0
+    pub fn flash(&self) -> f64 {
+        match (self.flashing, self.flash_started, self.last_update) {
+            (true, Some(started), Some(now)) => 1.0 - (now - started) / ROLLOVER_FLASH,
+            (true, _, _) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Updates the count for `now`, in seconds from any clock that doesn't go backwards,
+    /// returns true if it finished in this update
+    ///
+    /// The count starts from the first update so the duration is the same however often
+    /// this is called
+    pub fn update(&mut self, now: f64) -> bool {
+        let last_update = self.last_update.replace(now);
+        if self.flashing && now - *self.flash_started.get_or_insert(now) >= ROLLOVER_FLASH {
+            self.flashing = false;
+            self.flash_started = None;
+        }
+        if !self.is_counting() {
+            return false;
+        }
+        if self.paused {
+            if let (Some(started), Some(last_update)) = (self.started.as_mut(), last_update) {
+                *started += now - last_update;
+            }
+            return false;
+        }
+        let started = *self.started.get_or_insert(now);
+        let progress = if self.duration > 0.0 {
+            (now - started) / self.duration
+        } else {
+            1.0
+        };
+        self.progress = self.animation.easing.apply(progress);
+        self.current = if progress >= 1.0 {
+            self.days
+        } else {
+            ((self.days as f64 * self.progress).round() as usize).min(self.days)
+        };
+        self.current == self.days
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Report;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("test dates are valid")
+    }
+
+    #[test]
+    fn iso_weeks_from_a_monday() {
+        let weeks = IsoWeeks::new(date(2024, 1, 1), date(2024, 1, 8));
+        assert_eq!(weeks.full, 1);
+        assert_eq!(weeks.week, 2);
+    }
+
+    #[test]
+    fn iso_weeks_starting_in_the_previous_year() {
+        //2021 started on a Friday, in the last week of 2020
+        let weeks = IsoWeeks::new(date(2021, 1, 1), date(2021, 1, 11));
+        assert_eq!(weeks.start, (53, 2020));
+        assert_eq!(weeks.full, 1);
+    }
+
+    #[test]
+    fn fiscal_year_from_april() {
+        let fiscal = Fiscal::new(date(2023, 4, 1), date(2026, 10, 14), 4);
+        assert_eq!(fiscal.year, 2027);
+        assert_eq!(fiscal.day, 197);
+        assert_eq!(fiscal.years, 3);
+    }
+
+    #[test]
+    fn last_day_of_a_fiscal_year_from_january() {
+        let fiscal = Fiscal::new(date(2024, 1, 1), date(2024, 12, 31), 1);
+        assert_eq!(fiscal.year, 2024);
+        assert_eq!(fiscal.days_left, 0);
+    }
+
+    #[test]
+    fn half_way_through_a_leap_year() {
+        let progress = YearProgress::new(date(2020, 1, 1), date(2024, 7, 2));
+        assert_eq!(progress.day, 184);
+        assert_eq!(progress.week, 27);
+        assert_eq!(progress.percent(), 50);
+    }
+
+    #[test]
+    fn year_progress_from_a_start_this_year() {
+        let progress = YearProgress::new(date(2024, 10, 1), date(2024, 11, 16));
+        assert_eq!(progress.from, date(2024, 10, 1));
+        assert_eq!(progress.percent(), 50);
+    }
+
+    #[test]
+    fn calendar_progress_at_noon() {
+        let noon = date(2024, 7, 2)
+            .and_hms_opt(12, 0, 0)
+            .expect("noon is valid");
+        let progress = CalendarProgress::new(noon);
+        assert_eq!(progress.day, 0.5);
+        assert_eq!(progress.month, 1.5 / 31.0);
+        assert_eq!(progress.year, 183.5 / 366.0);
+    }
+
+    fn at(day: NaiveDate, hour: u32, minute: u32) -> NaiveDateTime {
+        day.and_hms_opt(hour, minute, 0)
+            .expect("test times are valid")
+    }
+
+    #[test]
+    fn age_the_minute_before_a_day() {
+        let age = PreciseAge::new(at(date(2000, 1, 1), 12, 0), at(date(2000, 1, 2), 11, 30));
+        assert_eq!(age.split.days, 0);
+        assert_eq!((age.hours, age.minutes), (23, 30));
+        assert_eq!(age.total_minutes, 1410);
+    }
+
+    #[test]
+    fn next_hours_milestone() {
+        let age = PreciseAge::new(at(date(2000, 1, 1), 0, 0), at(date(2000, 6, 1), 0, 0));
+        assert_eq!(age.next_at, at(date(2001, 2, 20), 16, 0));
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        text.parse().expect("test times are valid")
+    }
+
+    #[test]
+    fn a_billion_seconds() {
+        //a billion seconds is a bit under 31.7 years
+        let totals = Totals::new(utc("1990-01-01T00:00:00Z"), utc("2021-09-09T01:46:40Z"));
+        assert_eq!(totals.seconds, 1_000_000_000);
+        assert_eq!(totals.hours, 277_777);
+        assert_eq!(totals.weeks, 1653);
+    }
+
+    #[test]
+    fn totals_before_the_start() {
+        let totals = Totals::new(utc("2024-01-02T00:00:00Z"), utc("2024-01-01T00:00:00Z"));
+        assert_eq!(totals.seconds, 0);
+    }
+
+    #[test]
+    fn weekdays_since_a_saturday() {
+        let weekdays = Weekdays::new(date(2020, 2, 29), date(2020, 3, 9));
+        assert_eq!(weekdays.start, Weekday::Sat);
+        //the start day isn't one of them
+        assert_eq!(weekdays.counts, [2, 1, 1, 1, 1, 1, 2]);
+        let weekdays = Weekdays::new(date(2020, 2, 29), date(2026, 10, 14));
+        assert_eq!(weekdays.counts[0], 346);
+        assert_eq!(weekdays.counts.iter().sum::<usize>(), 2419);
+    }
+
+    #[test]
+    fn weekdays_before_the_start() {
+        assert_eq!(
+            Weekdays::new(date(2024, 1, 2), date(2024, 1, 1)).counts,
+            [0; 7]
+        );
+    }
+
+    #[test]
+    fn centuries_ago() {
+        let time = TimeSource::Fixed(utc("2026-10-14T12:00:00Z"));
+        let london = "Europe/London".parse().ok();
+        let (_, days) = calc_days_since(date(1066, 10, 14), london, Counting::Exclusive, time);
+        assert_eq!(days, 350_633);
+        assert_eq!(
+            Report::new(date(1066, 10, 14), date(2026, 10, 14), None).years,
+            960
+        );
+        assert_eq!(
+            Split::between(date(1066, 10, 14), date(2026, 10, 14)).years,
+            960
+        );
+    }
+
+    #[test]
+    fn long_counts_take_the_longest_time() {
+        //counts up in the same time as MAX_COUNT_YEARS rather than 960 seconds
+        let mut count = Count::new(350_633, Animation::default());
+        count.update(0.0);
+        assert!(count.update(MAX_COUNT_YEARS * COUNT_TIME_PER_YEAR));
+    }
+
+    #[test]
+    fn flash_on_rolling_over() {
+        let mut count = Count::new(10, Animation::default());
+        count.skip();
+        count.update(100.0);
+        count.roll_over(11);
+        assert_eq!(count.shown(), 11);
+        assert_eq!(count.flash(), 1.0);
+        count.update(200.0);
+        count.update(200.0 + ROLLOVER_FLASH / 2.0);
+        assert_eq!(count.flash(), 0.5);
+        count.update(200.0 + ROLLOVER_FLASH);
+        assert!(!count.is_flashing());
+        assert_eq!(count.flash(), 0.0);
+    }
+
+    #[test]
+    fn timeline_from_a_leap_day() {
+        let timeline = Timeline::new(date(2020, 2, 29), date(2026, 10, 14), date(2026, 11, 20));
+        assert_eq!(timeline.years, 6);
+        assert_eq!(
+            timeline.anniversaries(1).next(),
+            Some((1, date(2021, 2, 28)))
+        );
+        assert_eq!(
+            timeline.anniversaries(5).collect::<Vec<_>>(),
+            vec![(5, date(2025, 2, 28))]
+        );
+        assert_eq!(timeline.position(timeline.start), 0.0);
+        assert_eq!(timeline.position(timeline.end), 1.0);
+    }
+
+    #[test]
+    fn timeline_before_an_anniversary() {
+        let timeline = Timeline::new(date(2020, 2, 29), date(2021, 2, 27), date(2021, 3, 1));
+        assert_eq!(timeline.years, 0);
+    }
+}
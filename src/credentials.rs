@@ -0,0 +1,83 @@
+use crate::paths;
+use color_eyre::eyre::WrapErr;
+use color_eyre::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+const SERVICE: &str = "countup";
+/// Used when the OS keyring isn't available, i.e. no secret service running
+const FALLBACK_FILE: &str = "credentials.json";
+
+/// Where a secret was stored
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Store {
+    Keyring,
+    File,
+}
+
+/// Reads secret `name`, from the keyring if possible otherwise the fallback file
+pub fn get(name: &str) -> Option<String> {
+    match keyring::Entry::new(SERVICE, name).and_then(|entry| entry.get_password()) {
+        Ok(secret) => Some(secret),
+        Err(_) => read_file().ok()?.remove(name),
+    }
+}
+
+/// Saves secret `name` in the keyring, falling back to the file if the keyring fails
+pub fn set(name: &str, secret: &str) -> Result<Store> {
+    match keyring::Entry::new(SERVICE, name).and_then(|entry| entry.set_password(secret)) {
+        Ok(()) => {
+            //don't leave an old copy in the file
+            remove_from_file(name)?;
+            Ok(Store::Keyring)
+        }
+        Err(err) => {
+            eprintln!("Keyring unavailable ({err}), saving to file instead");
+            let mut secrets = read_file()?;
+            secrets.insert(name.to_string(), secret.to_string());
+            write_file(&secrets)?;
+            Ok(Store::File)
+        }
+    }
+}
+
+/// Removes secret `name` from both the keyring and file, returns false if it didn't exist
+pub fn clear(name: &str) -> Result<bool> {
+    let in_keyring = keyring::Entry::new(SERVICE, name)
+        .and_then(|entry| entry.delete_credential())
+        .is_ok();
+    let in_file = remove_from_file(name)?;
+    Ok(in_keyring || in_file)
+}
+
+fn remove_from_file(name: &str) -> Result<bool> {
+    let mut secrets = read_file()?;
+    if secrets.remove(name).is_none() {
+        return Ok(false);
+    }
+    write_file(&secrets)?;
+    Ok(true)
+}
+
+fn path() -> Result<PathBuf> {
+    let mut path = paths::data_dir()?;
+    path.push(FALLBACK_FILE);
+    Ok(path)
+}
+
+fn read_file() -> Result<BTreeMap<String, String>> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let json = fs::read_to_string(&path).wrap_err_with(|| format!("Reading {}", path.display()))?;
+    serde_json::from_str(&json).wrap_err_with(|| format!("Parsing {}", path.display()))
+}
+
+fn write_file(secrets: &BTreeMap<String, String>) -> Result<()> {
+    let path = path()?;
+    let json = serde_json::to_string_pretty(secrets)?;
+    //only readable by the user, as the secrets are in plaintext
+    paths::write_private(&path, &json).wrap_err_with(|| format!("Writing {}", path.display()))
+}
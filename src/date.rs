@@ -0,0 +1,162 @@
+//! Reading dates typed or picked by people
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate};
+
+/// Formats tried by [parse_date], in order, before unix timestamps
+pub const DATE_FORMATS: [&str; 4] = ["%Y-%m-%d", "%Y/%m/%d", "%d-%m-%Y", "%Y%m%d"];
+
+/// Reads `text` as each of [DATE_FORMATS] and then a unix timestamp (in UTC), returning the
+/// first that fits
+///
+/// The order won't change within a minor version, new formats are only added after timestamps
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+        .or_else(|| {
+            text.bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| text.parse().ok())
+                .flatten()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                .map(|time| time.date_naive())
+        })
+}
+
+/// Part of the date changed by the picker's up and down keys
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DateField {
+    Year,
+    Month,
+    Day,
+}
+
+/// Date being chosen with the keyboard, shown as year, month and day
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DatePicker {
+    pub date: NaiveDate,
+    pub field: DateField,
+}
+
+impl DatePicker {
+    /// Starts on `date` with the day selected
+    pub fn new(date: NaiveDate) -> Self {
+        DatePicker {
+            date,
+            field: DateField::Day,
+        }
+    }
+
+    /// Selects the field to the right (or left), wrapping around
+    pub fn next_field(&mut self, forward: bool) {
+        self.field = match (self.field, forward) {
+            (DateField::Year, true) | (DateField::Day, false) => DateField::Month,
+            (DateField::Month, true) | (DateField::Year, false) => DateField::Day,
+            (DateField::Day, true) | (DateField::Month, false) => DateField::Year,
+        };
+    }
+
+    /// Moves the selected field up (or down) by one, days carry into the month and months
+    /// into the year, a day past the end of the new month is moved back to its last day,
+    /// i.e. 31 January plus a month is 29 February in a leap year
+    pub fn step(&mut self, up: bool) {
+        let months = match self.field {
+            DateField::Year => 12,
+            DateField::Month => 1,
+            DateField::Day => {
+                let days = Duration::days(if up { 1 } else { -1 });
+                self.date = self.date.checked_add_signed(days).unwrap_or(self.date);
+                return;
+            }
+        };
+        let stepped = if up {
+            self.date.checked_add_months(Months::new(months))
+        } else {
+            self.date.checked_sub_months(Months::new(months))
+        };
+        self.date = stepped.unwrap_or(self.date);
+    }
+
+    /// Year, month and day as shown, zero padded
+    pub fn parts(&self) -> [(DateField, String); 3] {
+        [
+            (DateField::Year, format!("{:04}", self.date.year())),
+            (DateField::Month, format!("{:02}", self.date.month())),
+            (DateField::Day, format!("{:02}", self.date.day())),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("test dates are valid")
+    }
+
+    #[test]
+    fn stepping_each_field() {
+        let mut picker = DatePicker::new(date(2024, 1, 31));
+        picker.next_field(false);
+        picker.step(true);
+        //there's no 31 February, so it's the last day of the month
+        assert_eq!(picker.date, date(2024, 2, 29));
+        picker.next_field(false);
+        picker.step(true);
+        assert_eq!(picker.date, date(2025, 2, 28));
+        //wraps around to the day
+        picker.next_field(false);
+        picker.step(false);
+        assert_eq!(picker.date, date(2025, 2, 27));
+        picker.step(true);
+        picker.step(true);
+        assert_eq!(picker.date, date(2025, 3, 1));
+    }
+
+    #[test]
+    fn each_date_format() {
+        let expected = Some(date(2022, 11, 25));
+        for text in ["2022-11-25", "2022/11/25", "25-11-2022", "20221125"] {
+            assert_eq!(parse_date(text), expected, "{text}");
+        }
+        assert_eq!(DATE_FORMATS.len(), 4, "add a case for the new format");
+    }
+
+    #[test]
+    fn surrounding_whitespace() {
+        assert_eq!(parse_date("  2022-11-25\n"), Some(date(2022, 11, 25)));
+        assert_eq!(parse_date("\t1700000000 "), Some(date(2023, 11, 14)));
+    }
+
+    #[test]
+    fn eight_digits_are_a_date_before_a_timestamp() {
+        //also 1970-08-23 as seconds
+        assert_eq!(parse_date("20240101"), Some(date(2024, 1, 1)));
+    }
+
+    #[test]
+    fn unix_timestamps() {
+        assert_eq!(parse_date("1700000000"), Some(date(2023, 11, 14)));
+        assert_eq!(parse_date("0"), Some(date(1970, 1, 1)));
+    }
+
+    #[test]
+    fn invalid_dates() {
+        for text in [
+            "",
+            "today",
+            "2022-13-01",
+            "2023-02-29",
+            "25/11/2022",
+            "-1700000000",
+            "+1700000000",
+            "1.5",
+            "99999999999999999999",
+            "9999999999999999",
+        ] {
+            assert_eq!(parse_date(text), None, "{text}");
+        }
+    }
+}
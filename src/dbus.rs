@@ -0,0 +1,202 @@
+use crate::shutdown;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Bus name, object path and interface of the service
+#[cfg(target_os = "linux")]
+const NAME: &str = "com.emmabritton.Countup";
+#[cfg(target_os = "linux")]
+const PATH: &str = "/com/emmabritton/Countup";
+/// How often a signal is checked for while waiting to be shown
+const SIGNAL_CHECK: Duration = Duration::from_millis(250);
+
+/// Sent from the service to the app
+#[derive(Debug, Clone)]
+pub enum Control {
+    SwitchEvent(String),
+    NextMode,
+    /// Open the window, for `--start-hidden`
+    Show,
+    Quit,
+}
+
+/// What the app is currently showing, updated by the app after every change
+#[derive(Debug, Clone, Default)]
+pub struct Status {
+    pub days: usize,
+    pub start: String,
+    pub event: Option<String>,
+    pub mode: String,
+    /// Names of the events that can be switched to
+    pub events: Vec<String>,
+}
+
+/// Running service, dropping it removes the service from the bus
+pub struct Handle {
+    #[cfg(target_os = "linux")]
+    _connection: zbus::blocking::Connection,
+    receiver: Receiver<Control>,
+    status: Arc<Mutex<Status>>,
+}
+
+impl Handle {
+    pub fn try_recv(&self) -> Option<Control> {
+        self.receiver.try_recv().ok()
+    }
+
+    pub fn publish(&self, status: Status) {
+        *self.status.lock().unwrap_or_else(|err| err.into_inner()) = status;
+    }
+}
+
+/// Registers the service on the session bus, only supported on Linux
+///
+/// Failing to connect isn't an error as the counter works without it
+#[cfg(target_os = "linux")]
+pub fn start(status: Status) -> Option<Handle> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let status = Arc::new(Mutex::new(status));
+    let service = linux::Service {
+        sender,
+        status: status.clone(),
+    };
+    let connection = zbus::blocking::connection::Builder::session()
+        .and_then(|builder| builder.name(NAME))
+        .and_then(|builder| builder.serve_at(PATH, service))
+        .and_then(|builder| builder.build());
+    match connection {
+        Ok(connection) => Some(Handle {
+            _connection: connection,
+            receiver,
+            status,
+        }),
+        Err(err) => {
+            eprintln!("D-Bus unavailable: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn start(_status: Status) -> Option<Handle> {
+    None
+}
+
+/// Shows the window of a countup already on the bus, or only checks it's there if not
+/// `show`, then switches it to `event`, returns false if there isn't one
+#[cfg(target_os = "linux")]
+pub fn hand_off(event: Option<&str>, show: bool) -> bool {
+    let Ok(connection) = zbus::blocking::Connection::session() else {
+        return false;
+    };
+    let method = if show { "Show" } else { "ListEvents" };
+    if connection
+        .call_method(Some(NAME), PATH, Some(NAME), method, &())
+        .is_err()
+    {
+        return false;
+    }
+    if let Some(event) = event {
+        if let Err(err) =
+            connection.call_method(Some(NAME), PATH, Some(NAME), "SwitchEvent", &event)
+        {
+            eprintln!("Unable to switch the running countup to {event}: {err}");
+        }
+    }
+    true
+}
+
+/// Waits for `Show` without a window, for `--start-hidden`, returns false if `Quit` or a
+/// signal came first
+///
+/// Other calls are ignored until then, the service is removed before returning so the
+/// window can register it again
+pub fn wait_until_shown(status: Status) -> bool {
+    let Some(handle) = start(status) else {
+        eprintln!("Nothing can show the window without D-Bus, opening it now");
+        return true;
+    };
+    loop {
+        match handle.receiver.recv_timeout(SIGNAL_CHECK) {
+            Ok(Control::Show) | Err(RecvTimeoutError::Disconnected) => return true,
+            Ok(Control::Quit) => return false,
+            Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+        }
+        if shutdown::requested() {
+            return false;
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{Control, Status};
+    use std::sync::mpsc::Sender;
+    use std::sync::{Arc, Mutex};
+    use zbus::fdo;
+
+    pub struct Service {
+        pub sender: Sender<Control>,
+        pub status: Arc<Mutex<Status>>,
+    }
+
+    impl Service {
+        fn status(&self) -> Status {
+            self.status
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .clone()
+        }
+
+        fn send(&self, control: Control) -> fdo::Result<()> {
+            self.sender
+                .send(control)
+                .map_err(|_| fdo::Error::Failed(String::from("Countup is closing")))
+        }
+    }
+
+    #[zbus::interface(name = "com.emmabritton.Countup")]
+    impl Service {
+        /// Returns the days, start date, event name (empty if none) and mode
+        fn get_count(&self) -> (u64, String, String, String) {
+            let status = self.status();
+            (
+                status.days as u64,
+                status.start,
+                status.event.unwrap_or_default(),
+                status.mode,
+            )
+        }
+
+        fn list_events(&self) -> Vec<String> {
+            self.status().events
+        }
+
+        fn switch_event(&self, name: String) -> fdo::Result<()> {
+            let status = self.status();
+            if !status
+                .events
+                .iter()
+                .any(|event| event.eq_ignore_ascii_case(&name))
+            {
+                return Err(fdo::Error::InvalidArgs(format!("No event named {name}")));
+            }
+            self.send(Control::SwitchEvent(name))
+        }
+
+        /// Same as pressing space
+        fn next_mode(&self) -> fdo::Result<()> {
+            self.send(Control::NextMode)
+        }
+
+        /// Opens the window when started with `--start-hidden`
+        fn show(&self) -> fdo::Result<()> {
+            self.send(Control::Show)
+        }
+
+        fn quit(&self) -> fdo::Result<()> {
+            self.send(Control::Quit)
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to keep looking for the window after it's been asked for, it may take a while
+/// to be shown
+const FIND_FOR: Duration = Duration::from_secs(10);
+const FIND_EVERY: Duration = Duration::from_millis(200);
+
+/// Fails if the window can't be put on the desktop here, call before the window is created
+pub fn check() -> Result<()> {
+    platform_check()
+}
+
+/// Puts the window below the others and lets clicks go through it to the desktop, once
+/// it's open, from a background thread as the window library doesn't give access to it
+pub fn pin() {
+    thread::spawn(|| {
+        let start = Instant::now();
+        loop {
+            match try_pin() {
+                Ok(true) => return,
+                Ok(false) if start.elapsed() < FIND_FOR => thread::sleep(FIND_EVERY),
+                Ok(false) => {
+                    return eprintln!("Unable to put the counter on the desktop: no window found")
+                }
+                Err(err) => return eprintln!("Unable to put the counter on the desktop: {err}"),
+            }
+        }
+    });
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn platform_check() -> Result<()> {
+    use std::env;
+    if env::var_os("DISPLAY").is_none() {
+        return Err(eyre!(
+            "--desktop needs X11 (or XWayland), DISPLAY isn't set"
+        ));
+    }
+    //Wayland doesn't let apps change their layer, so use XWayland instead
+    env::set_var("WINIT_UNIX_BACKEND", "x11");
+    Ok(())
+}
+
+/// The window of process `pid`, found by its `_NET_WM_PID`, none if it isn't open yet
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn x11_window(
+    conn: &impl x11rb::connection::Connection,
+    root: x11rb::protocol::xproto::Window,
+    pid: u32,
+) -> Result<Option<x11rb::protocol::xproto::Window>> {
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+    let atom = |name: &str| conn.intern_atom(false, name.as_bytes());
+    let pid_atom = atom("_NET_WM_PID")?.reply()?.atom;
+    let list = atom("_NET_CLIENT_LIST")?.reply()?.atom;
+    let clients = conn
+        .get_property(false, root, list, AtomEnum::WINDOW, 0, u32::MAX)?
+        .reply()?;
+    let window = clients.value32().into_iter().flatten().find(|window| {
+        conn.get_property(false, *window, pid_atom, AtomEnum::CARDINAL, 0, 1)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| reply.value32()?.next())
+            == Some(pid)
+    });
+    Ok(window)
+}
+
+/// False if the window isn't open yet
+#[cfg(not(any(target_os = "macos", windows)))]
+fn try_pin() -> Result<bool> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::shape::{self, SK, SO};
+    use x11rb::protocol::xproto::{
+        Atom, ClientMessageEvent, ClipOrdering, ConnectionExt, EventMask, PropMode,
+    };
+    use x11rb::wrapper::ConnectionExt as _;
+
+    let (conn, screen) = x11rb::connect(None)?;
+    let root = conn.setup().roots[screen].root;
+    let atom = |name: &str| -> Result<Atom> {
+        Ok(conn.intern_atom(false, name.as_bytes())?.reply()?.atom)
+    };
+    let Some(window) = x11_window(&conn, root, std::process::id())? else {
+        return Ok(false);
+    };
+
+    //an empty input shape, so every click goes to whatever is under it
+    shape::rectangles(
+        &conn,
+        SO::SET,
+        SK::INPUT,
+        ClipOrdering::UNSORTED,
+        window,
+        0,
+        0,
+        &[],
+    )?;
+    //no title bar or border
+    let hints = atom("_MOTIF_WM_HINTS")?;
+    conn.change_property32(PropMode::REPLACE, window, hints, hints, &[2, 0, 0, 0, 0])?;
+    let state = atom("_NET_WM_STATE")?;
+    for [first, second] in [
+        ["_NET_WM_STATE_BELOW", "_NET_WM_STATE_STICKY"],
+        ["_NET_WM_STATE_SKIP_TASKBAR", "_NET_WM_STATE_SKIP_PAGER"],
+    ] {
+        //add both, from an application
+        let event =
+            ClientMessageEvent::new(32, window, state, [1, atom(first)?, atom(second)?, 1, 0]);
+        conn.send_event(
+            false,
+            root,
+            EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+            event,
+        )?;
+    }
+    conn.flush()?;
+    Ok(true)
+}
+
+#[cfg(windows)]
+fn platform_check() -> Result<()> {
+    Ok(())
+}
+
+/// Changes the window's styles with PowerShell, so no Windows API crate is needed
+#[cfg(windows)]
+fn try_pin() -> Result<bool> {
+    use std::process::Command;
+    //layered and transparent lets clicks through, a tool window isn't on the taskbar
+    let script = format!(
+        r#"
+Add-Type @"
+using System;
+using System.Runtime.InteropServices;
+public static class Pin {{
+    [DllImport("user32.dll")] public static extern int GetWindowLong(IntPtr window, int index);
+    [DllImport("user32.dll")] public static extern int SetWindowLong(IntPtr window, int index, int value);
+    [DllImport("user32.dll")] public static extern bool SetLayeredWindowAttributes(IntPtr window, uint key, byte alpha, uint flags);
+    [DllImport("user32.dll")] public static extern bool SetWindowPos(IntPtr window, IntPtr after, int x, int y, int width, int height, uint flags);
+}}
+"@
+$window = (Get-Process -Id {}).MainWindowHandle
+if ($window -eq [IntPtr]::Zero) {{ exit 2 }}
+$style = [Pin]::GetWindowLong($window, -20)
+[Pin]::SetWindowLong($window, -20, $style -bor 0x80000 -bor 0x20 -bor 0x80) | Out-Null
+[Pin]::SetLayeredWindowAttributes($window, 0, 255, 2) | Out-Null
+[Pin]::SetWindowPos($window, [IntPtr]1, 0, 0, 0, 0, 0x13) | Out-Null
+"#,
+        std::process::id()
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", &script])
+        .output()?;
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(2) => Ok(false),
+        _ => Err(eyre!("{}", String::from_utf8_lossy(&output.stderr).trim())),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn platform_check() -> Result<()> {
+    Err(eyre!("--desktop isn't supported on macOS"))
+}
+
+#[cfg(target_os = "macos")]
+fn try_pin() -> Result<bool> {
+    Ok(true)
+}
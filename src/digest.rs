@@ -0,0 +1,138 @@
+use crate::count::days_to_anniversary;
+use crate::event::{Event, Rgb};
+use crate::milestones::{Milestones, Pattern};
+use crate::report::Counting;
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Days either side of today covered by the digest
+const DIGEST_DAYS: i64 = 7;
+
+/// Something that happened in the last week or is coming up in the next
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DigestEntry {
+    pub date: NaiveDate,
+    pub event: String,
+    /// Reached (or will reach) this milestone, otherwise the event starts on `date`
+    pub milestone: Option<String>,
+    /// The event's own colour for the milestone
+    pub color: Option<Rgb>,
+}
+
+/// Milestones from the past week and events and milestones in the next, across all
+/// events, oldest first
+pub fn digest(events: &[Event], today: NaiveDate) -> Vec<DigestEntry> {
+    let mut entries = vec![];
+    for event in events {
+        let milestones = event.milestones(&[]);
+        for offset in 1 - DIGEST_DAYS..=DIGEST_DAYS {
+            let date = today + Duration::days(offset);
+            if offset > 0 && days_on(event, date) == Some(0) {
+                entries.push(DigestEntry {
+                    date,
+                    event: event.name.clone(),
+                    milestone: None,
+                    color: None,
+                });
+            } else if let Some(entry) = milestone_on(event, &milestones, date) {
+                entries.push(entry);
+            }
+        }
+    }
+    entries.sort_by_key(|entry| entry.date);
+    entries
+}
+
+/// Milestones from tomorrow until `days` from now across all events, with `patterns`
+/// turned on, and the anniversaries of events with `"anniversary"` set, oldest first
+pub fn upcoming(
+    events: &[Event],
+    patterns: &[Pattern],
+    today: NaiveDate,
+    days: i64,
+) -> Vec<DigestEntry> {
+    let mut entries = vec![];
+    for event in events {
+        let milestones = event.milestones(patterns);
+        entries.extend(
+            (1..=days).filter_map(|offset| {
+                milestone_on(event, &milestones, today + Duration::days(offset))
+            }),
+        );
+        if event.anniversary == Some(true) {
+            let tomorrow = today + Duration::days(1);
+            let date = tomorrow + Duration::days(days_to_anniversary(event.date, tomorrow) as i64);
+            let years = date.year() - event.date.year();
+            if years > 0 && (date - today).num_days() <= days {
+                entries.push(DigestEntry {
+                    date,
+                    event: event.name.clone(),
+                    milestone: Some(format!("{years} year anniversary")),
+                    color: None,
+                });
+            }
+        }
+    }
+    entries.sort_by_key(|entry| entry.date);
+    entries
+}
+
+/// Days counted on `date`, none while paused as the count doesn't go up, so a milestone
+/// would be repeated for each day
+fn days_on(event: &Event, date: NaiveDate) -> Option<i64> {
+    if event.is_paused(date) {
+        return None;
+    }
+    Some((date - event.date).num_days() - event.paused_days(date, Counting::Exclusive) as i64)
+}
+
+fn milestone_on(event: &Event, milestones: &Milestones, date: NaiveDate) -> Option<DigestEntry> {
+    let days = usize::try_from(days_on(event, date)?).ok()?;
+    Some(DigestEntry {
+        date,
+        event: event.name.clone(),
+        milestone: Some(milestones.milestone(days)?),
+        color: event
+            .custom_milestone(days)
+            .and_then(|milestone| milestone.color),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("test dates are valid")
+    }
+
+    #[test]
+    fn milestones_and_anniversaries_of_a_leap_day() {
+        let event = Event {
+            name: String::from("Leap"),
+            date: date(2024, 2, 29),
+            phases: vec![],
+            pauses: vec![],
+            timezone: None,
+            resets: vec![],
+            background: None,
+            accent: None,
+            anniversary: Some(true),
+            streak: None,
+            note_pauses: None,
+            snooze: None,
+            milestones: vec![],
+        };
+        let upcoming: Vec<(NaiveDate, Option<String>)> =
+            upcoming(&[event], &[], date(2025, 2, 20), 30)
+                .into_iter()
+                .map(|entry| (entry.date, entry.milestone))
+                .collect();
+        assert_eq!(
+            upcoming,
+            vec![
+                (date(2025, 2, 28), Some(String::from("1 year"))),
+                (date(2025, 2, 28), Some(String::from("1 year anniversary"))),
+            ]
+        );
+    }
+}
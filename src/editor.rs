@@ -0,0 +1,276 @@
+use crate::date::DatePicker;
+use crate::event::Event;
+use crate::i18n::Strings;
+use crate::render::{render_date_picker, render_event_list, render_name_entry};
+use chrono::NaiveDate;
+use pixels_graphics_lib::prelude::VirtualKeyCode::*;
+use pixels_graphics_lib::prelude::*;
+
+/// Longest name that can be typed, so it fits in the panel
+const NAME_LENGTH: usize = 24;
+/// Letters, digits and space, typed as they are
+const TYPED: [(VirtualKeyCode, char); 37] = [
+    (A, 'a'),
+    (B, 'b'),
+    (C, 'c'),
+    (D, 'd'),
+    (E, 'e'),
+    (F, 'f'),
+    (G, 'g'),
+    (H, 'h'),
+    (I, 'i'),
+    (J, 'j'),
+    (K, 'k'),
+    (L, 'l'),
+    (M, 'm'),
+    (N, 'n'),
+    (O, 'o'),
+    (P, 'p'),
+    (Q, 'q'),
+    (R, 'r'),
+    (S, 's'),
+    (T, 't'),
+    (U, 'u'),
+    (V, 'v'),
+    (W, 'w'),
+    (X, 'x'),
+    (Y, 'y'),
+    (Z, 'z'),
+    (Key0, '0'),
+    (Key1, '1'),
+    (Key2, '2'),
+    (Key3, '3'),
+    (Key4, '4'),
+    (Key5, '5'),
+    (Key6, '6'),
+    (Key7, '7'),
+    (Key8, '8'),
+    (Key9, '9'),
+    (Space, ' '),
+];
+
+/// Change to make to the events, picked in the editor
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Edit {
+    /// Closes the editor without changing anything
+    Close,
+    /// Closes the editor and shows the event
+    Show(String),
+    Add {
+        name: String,
+        date: NaiveDate,
+    },
+    Rename {
+        from: String,
+        to: String,
+    },
+    Delete(String),
+}
+
+#[derive(Debug, Clone)]
+enum Step {
+    List,
+    /// Asks before deleting the selected event, any key but Delete cancels
+    Deleting,
+    /// Typing the name for a new event, or a new name if `renaming`
+    Naming {
+        renaming: Option<String>,
+        name: String,
+        taken: bool,
+    },
+    /// Picking the date of a new event
+    Dating {
+        name: String,
+        picker: DatePicker,
+    },
+}
+
+/// Keyboard driven panel for adding, renaming and deleting events, opened with L or from
+/// the right click menu
+#[derive(Debug, Clone)]
+pub struct Editor {
+    /// Row in the list, the row after the events is for a new one
+    selected: usize,
+    step: Step,
+    /// Where the date picker starts for a new event
+    today: NaiveDate,
+}
+
+impl Editor {
+    pub fn new(selected: usize, today: NaiveDate) -> Self {
+        Editor {
+            selected,
+            step: Step::List,
+            today,
+        }
+    }
+
+    /// Keys to ask the window for while the editor is open
+    pub fn keys() -> Vec<VirtualKeyCode> {
+        let mut keys: Vec<VirtualKeyCode> = TYPED.iter().map(|(key, _)| *key).collect();
+        keys.extend([
+            Up, Down, Left, Right, Return, Escape, Back, Delete, Insert, F2, LShift, RShift,
+        ]);
+        keys
+    }
+
+    /// Moves through the steps for `keys`, returning the change once one's been picked
+    pub fn key(&mut self, keys: &[VirtualKeyCode], shift: bool, events: &[Event]) -> Option<Edit> {
+        self.selected = self.selected.min(events.len());
+        let current = events.get(self.selected);
+        match &mut self.step {
+            Step::List => {
+                let rows = events.len() + 1;
+                if keys.contains(&Escape) {
+                    return Some(Edit::Close);
+                } else if keys.contains(&Up) {
+                    self.selected = (self.selected + rows - 1) % rows;
+                } else if keys.contains(&Down) {
+                    self.selected = (self.selected + 1) % rows;
+                } else if keys.contains(&Return) && current.is_some() {
+                    return current.map(|event| Edit::Show(event.name.clone()));
+                } else if keys.contains(&Return) || keys.contains(&N) || keys.contains(&Insert) {
+                    self.step = Step::Naming {
+                        renaming: None,
+                        name: String::new(),
+                        taken: false,
+                    };
+                } else if let Some(event) = current.filter(|_| keys.contains(&F2)) {
+                    self.step = Step::Naming {
+                        renaming: Some(event.name.clone()),
+                        name: event.name.clone(),
+                        taken: false,
+                    };
+                } else if current.is_some() && keys.contains(&Delete) {
+                    self.step = Step::Deleting;
+                }
+            }
+            Step::Deleting => {
+                if keys.contains(&Delete) {
+                    self.step = Step::List;
+                    return current.map(|event| Edit::Delete(event.name.clone()));
+                } else if !keys.is_empty() {
+                    self.step = Step::List;
+                }
+            }
+            Step::Naming {
+                renaming,
+                name,
+                taken,
+            } => {
+                if keys.contains(&Escape) {
+                    self.step = Step::List;
+                } else if keys.contains(&Return) {
+                    let typed = name.trim().to_string();
+                    *taken = events.iter().any(|event| {
+                        event.name.eq_ignore_ascii_case(&typed)
+                            && renaming
+                                .as_ref()
+                                .is_none_or(|from| !from.eq_ignore_ascii_case(&typed))
+                    });
+                    if typed.is_empty() || *taken {
+                        return None;
+                    }
+                    match renaming.take() {
+                        Some(from) => {
+                            self.step = Step::List;
+                            return Some(Edit::Rename { from, to: typed });
+                        }
+                        None => {
+                            self.step = Step::Dating {
+                                name: typed,
+                                picker: DatePicker::new(self.today),
+                            };
+                        }
+                    }
+                } else if keys.contains(&Back) {
+                    name.pop();
+                    *taken = false;
+                } else {
+                    for (_, char) in TYPED.iter().filter(|(key, _)| keys.contains(key)) {
+                        if name.chars().count() < NAME_LENGTH {
+                            name.push(if shift {
+                                char.to_ascii_uppercase()
+                            } else {
+                                *char
+                            });
+                            *taken = false;
+                        }
+                    }
+                }
+            }
+            Step::Dating { name, picker } => {
+                if keys.contains(&Escape) {
+                    self.step = Step::List;
+                } else if keys.contains(&Return) {
+                    let edit = Edit::Add {
+                        name: name.clone(),
+                        date: picker.date,
+                    };
+                    self.step = Step::List;
+                    return Some(edit);
+                } else if keys.contains(&Left) || keys.contains(&Right) {
+                    picker.next_field(keys.contains(&Right));
+                } else if keys.contains(&Up) || keys.contains(&Down) {
+                    picker.step(keys.contains(&Up));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn render(
+        &self,
+        graphics: &mut Graphics,
+        events: &[Event],
+        date_format: &str,
+        strings: &Strings,
+    ) {
+        match &self.step {
+            Step::List | Step::Deleting => {
+                let mut rows: Vec<(String, String)> = events
+                    .iter()
+                    .map(|event| {
+                        let date = event.date.format(date_format).to_string();
+                        (event.name.clone(), date)
+                    })
+                    .collect();
+                rows.push((format!("+ {}", strings.new_event), String::new()));
+                let hint = match self.step {
+                    Step::Deleting => strings.confirm_delete,
+                    _ => strings.edit_keys,
+                };
+                render_event_list(
+                    graphics,
+                    &rows,
+                    self.selected.min(events.len()),
+                    hint,
+                    strings,
+                );
+            }
+            Step::Naming {
+                renaming,
+                name,
+                taken,
+            } => {
+                let title = match renaming {
+                    Some(_) => strings.rename_event,
+                    None => strings.new_event,
+                };
+                let hint = if *taken {
+                    strings.name_taken
+                } else {
+                    strings.type_name
+                };
+                render_name_entry(graphics, title, name, hint, strings.escape_back);
+            }
+            Step::Dating { name, picker } => render_date_picker(
+                graphics,
+                picker,
+                name,
+                strings.add_date,
+                strings.escape_back,
+            ),
+        }
+    }
+}
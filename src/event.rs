@@ -0,0 +1,360 @@
+use crate::count::local_date;
+use crate::milestones::{Milestones, Pattern};
+use crate::report::Counting;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Named date from the config file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub name: String,
+    pub date: NaiveDate,
+    /// Ordered phases, the first starts on `date` and each following one starts when the previous ends
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<Phase>,
+    /// Times the count stopped, i.e. a career break, their days aren't counted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pauses: Vec<Pause>,
+    /// Adds the paused days to the title, i.e. `(excluding 14 days)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note_pauses: Option<bool>,
+    /// Timezone the days go up in, i.e. `Asia/Tokyo`, defaults to UTC
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
+    /// Previous values of `date`, oldest first, added each time the event is reset
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resets: Vec<NaiveDate>,
+    /// Background for the event's modes, defaults to dark gray
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<Rgb>,
+    /// Colour of the numbers in the event's modes, defaults to white
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<Rgb>,
+    /// Also show the days until the next anniversary of `date` in split mode, i.e. for
+    /// birthdays
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anniversary: Option<bool>,
+    /// Lets the count be reset to today from the window with Backspace, i.e. for a streak
+    /// that's broken by a slip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streak: Option<bool>,
+    /// Milestones don't chime or run the milestone hooks while snoozed, see `countup snooze`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snooze: Option<Snooze>,
+    /// The event's own milestones, celebrated the same as the usual ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub milestones: Vec<CustomMilestone>,
+}
+
+/// A milestone set for one event, i.e. `500` days with the message `Halfway mark`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomMilestone {
+    pub days: usize,
+    pub message: String,
+    /// Colour of the message on the day, defaults to the event's accent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Rgb>,
+}
+
+/// How long an event's milestones are snoozed for
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Snooze {
+    /// To the end of this day
+    Until(NaiveDate),
+    Forever,
+}
+
+/// Lengths for `countup snooze` and the right click menu
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SnoozeLength {
+    Day,
+    Week,
+    Forever,
+    /// Unsnoozes
+    Off,
+}
+
+impl SnoozeLength {
+    pub const NAMES: [&'static str; 4] = ["day", "week", "forever", "off"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "day" => Some(SnoozeLength::Day),
+            "week" => Some(SnoozeLength::Week),
+            "forever" => Some(SnoozeLength::Forever),
+            "off" => Some(SnoozeLength::Off),
+            _ => None,
+        }
+    }
+
+    /// Snooze starting `today`, the day counts as one of them
+    pub fn starting(self, today: NaiveDate) -> Option<Snooze> {
+        match self {
+            SnoozeLength::Day => Some(Snooze::Until(today)),
+            SnoozeLength::Week => Some(Snooze::Until(today + Days::new(6))),
+            SnoozeLength::Forever => Some(Snooze::Forever),
+            SnoozeLength::Off => None,
+        }
+    }
+}
+
+/// Colour written as `#rrggbb`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let hex = text.strip_prefix('#').unwrap_or(text);
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|c| u8::from_str_radix(c, 16).ok())
+        };
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Rgb(r, g, b)),
+            _ => Err(format!("Invalid colour {text}, must be #rrggbb")),
+        }
+    }
+}
+
+impl Display for Rgb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl Serialize for Rgb {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Phase {
+    pub name: String,
+    /// First day after the phase
+    pub end: NaiveDate,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pause {
+    pub start: NaiveDate,
+    /// First day counting again
+    pub end: NaiveDate,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PhaseProgress {
+    NotStarted {
+        days_until: i64,
+    },
+    InPhase {
+        idx: usize,
+        days_in: i64,
+        days_left: i64,
+    },
+    Complete,
+}
+
+impl Event {
+    /// The event's milestones with `patterns` turned on
+    pub fn milestones(&self, patterns: &[Pattern]) -> Milestones {
+        Milestones {
+            patterns: patterns.to_vec(),
+            custom: self
+                .milestones
+                .iter()
+                .map(|milestone| (milestone.days, milestone.message.clone()))
+                .collect(),
+        }
+    }
+
+    /// The event's own milestone at `days`, if it has one
+    pub fn custom_milestone(&self, days: usize) -> Option<&CustomMilestone> {
+        self.milestones
+            .iter()
+            .find(|milestone| milestone.days == days)
+    }
+
+    /// If milestones shouldn't chime or run hooks on `today`
+    pub fn is_snoozed(&self, today: NaiveDate) -> bool {
+        match self.snooze {
+            Some(Snooze::Until(until)) => today <= until,
+            Some(Snooze::Forever) => true,
+            None => false,
+        }
+    }
+
+    /// Date at `now` in the event's timezone, where it rolls over
+    pub fn today(&self, now: DateTime<Utc>) -> NaiveDate {
+        local_date(now, self.timezone)
+    }
+
+    /// Starts counting again from `today`, keeping the old date in `resets`
+    pub fn reset(&mut self, today: NaiveDate) {
+        if self.date != today {
+            self.resets.push(self.date);
+            self.date = today;
+        }
+    }
+
+    pub fn is_paused(&self, date: NaiveDate) -> bool {
+        self.pauses
+            .iter()
+            .any(|pause| pause.start <= date && date < pause.end)
+    }
+
+    /// [Event::paused_days] if they're noted in the title, otherwise 0
+    pub fn noted_pauses(&self, today: NaiveDate, counting: Counting) -> usize {
+        match self.note_pauses {
+            Some(true) => self.paused_days(today, counting),
+            _ => 0,
+        }
+    }
+
+    /// Days counted so far that are in a pause, overlapping pauses are only counted once
+    pub fn paused_days(&self, today: NaiveDate, counting: Counting) -> usize {
+        let counted_to = today + Days::new(counting.offset() as u64);
+        let mut pauses: Vec<(NaiveDate, NaiveDate)> = self
+            .pauses
+            .iter()
+            .map(|pause| (pause.start.max(self.date), pause.end.min(counted_to)))
+            .filter(|(start, end)| start < end)
+            .collect();
+        pauses.sort();
+        let mut days = 0;
+        let mut paused_to = NaiveDate::MIN;
+        for (start, end) in pauses {
+            days += (end - start.max(paused_to)).num_days().max(0);
+            paused_to = paused_to.max(end);
+        }
+        days as usize
+    }
+
+    /// Days in each finished streak, oldest first, from each reset date to the next (or to
+    /// `date` for the last)
+    pub fn past_streaks(&self) -> Vec<usize> {
+        self.resets
+            .iter()
+            .zip(self.resets.iter().skip(1).chain([&self.date]))
+            .map(|(start, end)| (*end - *start).num_days().max(0) as usize)
+            .collect()
+    }
+
+    /// Start and end of each phase
+    pub fn phase_ranges(&self) -> Vec<(NaiveDate, NaiveDate)> {
+        let mut start = self.date;
+        self.phases
+            .iter()
+            .map(|phase| {
+                let range = (start, phase.end);
+                start = phase.end;
+                range
+            })
+            .collect()
+    }
+
+    pub fn phase_progress(&self, today: NaiveDate) -> PhaseProgress {
+        if today < self.date {
+            return PhaseProgress::NotStarted {
+                days_until: (self.date - today).num_days(),
+            };
+        }
+        let ranges = self.phase_ranges();
+        ranges
+            .iter()
+            .position(|(_, end)| today < *end)
+            .map(|idx| {
+                let (start, end) = ranges[idx];
+                PhaseProgress::InPhase {
+                    idx,
+                    days_in: (today - start).num_days(),
+                    days_left: (end - today).num_days(),
+                }
+            })
+            .unwrap_or(PhaseProgress::Complete)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Report;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("test dates are valid")
+    }
+
+    /// Started 2024-01-01, with overlapping pauses and one before the start
+    fn paused() -> Event {
+        let pause = |start, end| Pause { start, end };
+        Event {
+            name: String::from("Work"),
+            date: date(2024, 1, 1),
+            phases: vec![],
+            pauses: vec![
+                pause(date(2024, 3, 1), date(2024, 3, 11)),
+                pause(date(2024, 3, 6), date(2024, 3, 16)),
+                pause(date(2023, 12, 25), date(2024, 1, 2)),
+            ],
+            timezone: None,
+            resets: vec![],
+            background: None,
+            accent: None,
+            anniversary: None,
+            streak: None,
+            note_pauses: None,
+            snooze: None,
+            milestones: vec![],
+        }
+    }
+
+    #[test]
+    fn overlapping_pauses_are_counted_once() {
+        let today = date(2024, 3, 8);
+        assert_eq!(paused().paused_days(today, Counting::Exclusive), 8);
+        assert_eq!(paused().paused_days(today, Counting::Inclusive), 9);
+    }
+
+    #[test]
+    fn pauses_are_only_noted_with_note_pauses() {
+        let today = date(2024, 3, 8);
+        assert_eq!(paused().noted_pauses(today, Counting::Exclusive), 0);
+        let noted = Event {
+            note_pauses: Some(true),
+            ..paused()
+        };
+        assert_eq!(noted.noted_pauses(today, Counting::Exclusive), 8);
+    }
+
+    #[test]
+    fn reset_in_a_timezone_ahead_of_utc() {
+        let event = Event {
+            timezone: "Asia/Tokyo".parse().ok(),
+            ..paused()
+        };
+        //05:00 on the 9th in Tokyo
+        let now = "2024-03-08T20:00:00Z"
+            .parse()
+            .expect("test times are valid");
+        let mut reset = event.clone();
+        reset.reset(reset.today(now));
+        assert_eq!(reset.date, date(2024, 3, 9));
+        assert_eq!(reset.resets, [event.date]);
+        let report = Report::new_with(reset.date, reset.today(now), None, Counting::Exclusive);
+        assert_eq!(report.days, 0);
+    }
+}
@@ -0,0 +1,98 @@
+//! C ABI for the breakdown and text, see `include/countup.h`
+//!
+//! Dates are passed as strings in any format [parse_date] accepts, a null `today` means the
+//! current UTC date. Strings returned must be freed with [countup_free_string]
+
+use crate::date::parse_date;
+use crate::i18n::strings;
+use crate::milestones;
+use crate::report::{CountupBreakdown, Report};
+use chrono::{NaiveDate, Utc};
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+/// Reads a date, none if `text` is null, not UTF-8 or not a date
+///
+/// # Safety
+/// `text` must be null or a valid C string
+unsafe fn read_date(text: *const c_char) -> Option<NaiveDate> {
+    if text.is_null() {
+        return None;
+    }
+    parse_date(CStr::from_ptr(text).to_str().ok()?)
+}
+
+/// # Safety
+/// `start` and `today` must be null or valid C strings
+unsafe fn report(start: *const c_char, today: *const c_char) -> Option<Report> {
+    let start = read_date(start)?;
+    let today = if today.is_null() {
+        Utc::now().date_naive()
+    } else {
+        read_date(today)?
+    };
+    Some(Report::new(start, today, None))
+}
+
+fn into_c_string(text: String) -> *mut c_char {
+    CString::new(text).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Fills `out` with the count from `start` to `today`, returns false if either date is invalid
+///
+/// # Safety
+/// `start` and `today` must be null or valid C strings, `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn countup_breakdown(
+    start: *const c_char,
+    today: *const c_char,
+    out: *mut CountupBreakdown,
+) -> bool {
+    let Some(report) = report(start, today) else {
+        return false;
+    };
+    if out.is_null() {
+        return false;
+    }
+    *out = CountupBreakdown::from(&report);
+    true
+}
+
+/// Years, months and days as one line in `lang` (i.e. `de`, null for English), the same as
+/// the second line of `--headless`, null if either date is invalid
+///
+/// # Safety
+/// `start`, `today` and `lang` must be null or valid C strings
+#[no_mangle]
+pub unsafe extern "C" fn countup_summary(
+    start: *const c_char,
+    today: *const c_char,
+    lang: *const c_char,
+) -> *mut c_char {
+    let lang = if lang.is_null() {
+        "en"
+    } else {
+        CStr::from_ptr(lang).to_str().unwrap_or("en")
+    };
+    match report(start, today) {
+        Some(report) => into_c_string(report.summary(strings(lang))),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Name of the milestone reached after `days`, i.e. `1 year`, null if it isn't one
+#[no_mangle]
+pub extern "C" fn countup_milestone(days: u64) -> *mut c_char {
+    milestones::milestone(days as usize).map_or(ptr::null_mut(), into_c_string)
+}
+
+/// Frees a string returned by this library
+///
+/// # Safety
+/// `text` must be null or returned by this library, and not freed already
+#[no_mangle]
+pub unsafe extern "C" fn countup_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
@@ -0,0 +1,389 @@
+use crate::i18n::Numerals;
+use buffer_graphics_lib::prelude::*;
+use std::ops::Range;
+
+/// Characters in a bitmap font, printable ASCII from space to `~`
+const FIRST_CHAR: u8 = b' ';
+const LAST_CHAR: u8 = b'~';
+/// Characters per row in a bitmap font image
+const FONT_COLUMNS: usize = 16;
+
+/// Large font versions of the non Western digits in [Numerals]
+const EXTRA_DIGITS: [(char, [&str; 10]); 20] = [
+    (
+        '٠',
+        [
+            "........", "........", "........", "...##...", "..####..", "..####..", "...##...",
+            "........", "........", "........",
+        ],
+    ),
+    (
+        '١',
+        [
+            "..##....", "..###...", "...##...", "...##...", "...##...", "...##...", "...##...",
+            "...##...", "...##...", "...##...",
+        ],
+    ),
+    (
+        '٢',
+        [
+            "##....##", "##...###", ".######.", "..##....", "..##....", "..##....", "..##....",
+            "..##....", "..##....", "..##....",
+        ],
+    ),
+    (
+        '٣',
+        [
+            "##.##.##", "##.##.##", ".######.", "..##....", "..##....", "..##....", "..##....",
+            "..##....", "..##....", "..##....",
+        ],
+    ),
+    (
+        '٤',
+        [
+            "...#####", "..##....", "..##....", "...####.", "..##....", ".##.....", ".##.....",
+            "..######", "...#####", "........",
+        ],
+    ),
+    (
+        '٥',
+        [
+            "...##...", "..####..", ".##..##.", "##....##", "##....##", "##....##", "##....##",
+            ".##..##.", "..####..", "........",
+        ],
+    ),
+    (
+        '٦',
+        [
+            "##......", ".######.", "......##", "......##", "......##", "......##", "......##",
+            "......##", "......##", "......##",
+        ],
+    ),
+    (
+        '٧',
+        [
+            "##....##", "##....##", ".##..##.", ".##..##.", ".##..##.", "..####..", "..####..",
+            "..####..", "...##...", "...##...",
+        ],
+    ),
+    (
+        '٨',
+        [
+            "...##...", "...##...", "..####..", "..####..", "..####..", ".##..##.", ".##..##.",
+            ".##..##.", "##....##", "##....##",
+        ],
+    ),
+    (
+        '٩',
+        [
+            ".#####..", "##...##.", "##...##.", ".######.", ".....##.", ".....##.", ".....##.",
+            ".....##.", ".....##.", ".....##.",
+        ],
+    ),
+    (
+        '०',
+        [
+            "..####..", ".######.", "##....##", "##....##", "##....##", "##....##", "##....##",
+            ".######.", "..####..", "........",
+        ],
+    ),
+    (
+        '१',
+        [
+            ".####...", "##..##..", "##..##..", ".#####..", "....##..", "....##..", "...##...",
+            "..##....", ".##.....", "##......",
+        ],
+    ),
+    (
+        '२',
+        [
+            ".#####..", "##...##.", ".....##.", "....##..", "...##...", "...##...", "....##..",
+            ".....##.", "......##", "........",
+        ],
+    ),
+    (
+        '३',
+        [
+            ".#####..", "##...##.", ".....##.", "..####..", ".....##.", ".....##.", "##...##.",
+            ".#####..", "......##", ".......#",
+        ],
+    ),
+    (
+        '४',
+        [
+            ".##..##.", "##....##", "##....##", ".##..##.", "..####..", ".##..##.", "##....##",
+            "##....##", ".######.", "..####..",
+        ],
+    ),
+    (
+        '५',
+        [
+            "##......", "##......", "##..##..", "##..##..", ".#####..", "....##..", "...##...",
+            "..##.##.", ".##...##", "##.....#",
+        ],
+    ),
+    (
+        '६',
+        [
+            ".#####..", "##......", "##......", ".####...", "....##..", ".....##.", ".....##.",
+            "##...##.", ".#####..", "........",
+        ],
+    ),
+    (
+        '७',
+        [
+            "##....##", "##....##", ".##..##.", "..####..", "...##...", "....##..", ".....##.",
+            "......##", ".......#", "........",
+        ],
+    ),
+    (
+        '८',
+        [
+            "..####..", ".##..##.", "##......", "##......", "##......", ".##.....", "..##....",
+            "...##...", "....##..", ".....##.",
+        ],
+    ),
+    (
+        '९',
+        [
+            ".#####..", "##...##.", "##...##.", ".######.", ".....##.", ".....##.", "....##..",
+            "...##...", ".###....", "##......",
+        ],
+    ),
+];
+
+/// Pixels of each printable ASCII character, all the same size
+#[derive(Debug, Clone)]
+pub struct Glyphs {
+    width: usize,
+    height: usize,
+    /// Gap after each character
+    spacing: usize,
+    masks: Vec<Vec<bool>>,
+    /// Characters outside of ASCII, i.e. other digits
+    extra: Vec<(char, Vec<bool>)>,
+}
+
+impl Glyphs {
+    /// The built in large font
+    pub fn large() -> Self {
+        let (width, height) = Large.get_size();
+        let mut buffer = vec![0; width * height * 4];
+        let masks = (FIRST_CHAR..=LAST_CHAR)
+            .map(|chr| {
+                buffer.fill(0);
+                let mut graphics =
+                    Graphics::new(&mut buffer, width, height).expect("buffer is the right size");
+                graphics.draw_letter((0, 0), chr as char, Large, WHITE);
+                buffer.chunks_exact(4).map(|px| px[3] > 0).collect()
+            })
+            .collect();
+        let extra = EXTRA_DIGITS
+            .iter()
+            .map(|(chr, rows)| (*chr, rows.concat().chars().map(|px| px == '#').collect()))
+            .collect();
+        Glyphs {
+            width,
+            height,
+            spacing: Large.get_spacing(),
+            masks,
+            extra,
+        }
+    }
+
+    /// Reads a bitmap font, an image of the printable ASCII characters (space to `~`) in
+    /// rows of 16, where any pixel that isn't black or transparent is part of a character
+    pub fn from_image(image: &Image) -> Result<Self, String> {
+        let count = (LAST_CHAR - FIRST_CHAR + 1) as usize;
+        let rows = count.div_ceil(FONT_COLUMNS);
+        if image.width() < FONT_COLUMNS
+            || image.height() < rows
+            || !image.width().is_multiple_of(FONT_COLUMNS)
+            || !image.height().is_multiple_of(rows)
+        {
+            return Err(format!(
+                "Image is {}x{}, it must be {FONT_COLUMNS} characters wide and {rows} tall",
+                image.width(),
+                image.height()
+            ));
+        }
+        let width = image.width() / FONT_COLUMNS;
+        let height = image.height() / rows;
+        let masks = (0..count)
+            .map(|i| {
+                let left = (i % FONT_COLUMNS) * width;
+                let top = (i / FONT_COLUMNS) * height;
+                (0..width * height)
+                    .map(|px| {
+                        let color = image.get_pixel(left + px % width, top + px / width);
+                        color.a > 0 && (color.r > 0 || color.g > 0 || color.b > 0)
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Glyphs {
+            width,
+            height,
+            spacing: (width / 4).max(1),
+            masks,
+            extra: vec![],
+        })
+    }
+
+    fn mask(&self, chr: char) -> Option<&[bool]> {
+        if let Some((_, mask)) = self.extra.iter().find(|(extra, _)| *extra == chr) {
+            return Some(mask);
+        }
+        let idx = (chr as u32).checked_sub(FIRST_CHAR as u32)?;
+        self.masks.get(idx as usize).map(|mask| mask.as_slice())
+    }
+}
+
+/// Font for the big numbers, drawn at a whole number multiple of its size
+#[derive(Debug, Clone)]
+pub struct NumberFont {
+    glyphs: Glyphs,
+    scale: usize,
+    numerals: Numerals,
+}
+
+impl Default for NumberFont {
+    fn default() -> Self {
+        NumberFont::new(Glyphs::large(), 1)
+    }
+}
+
+impl NumberFont {
+    pub fn new(glyphs: Glyphs, scale: usize) -> Self {
+        NumberFont {
+            glyphs,
+            scale: scale.max(1),
+            numerals: Numerals::default(),
+        }
+    }
+
+    /// Draws digits with `numerals`, fonts without them (bitmap fonts) use Western digits
+    pub fn with_numerals(self, numerals: Numerals) -> Self {
+        NumberFont { numerals, ..self }
+    }
+
+    /// Width and height of `text` in pixels, the width includes the gap after the last
+    /// character, the same as [TextSize::measure]
+    pub fn measure(&self, text: &str) -> (usize, usize) {
+        let advance = self.glyphs.width + self.glyphs.spacing;
+        (
+            text.chars().count() * advance * self.scale,
+            self.glyphs.height * self.scale,
+        )
+    }
+
+    /// Draws `text` with its top right corner at `xy`
+    pub fn draw_right_aligned(
+        &self,
+        graphics: &mut Graphics,
+        text: &str,
+        xy: (isize, isize),
+        color: Color,
+    ) {
+        let (width, height) = self.measure(text);
+        let advance = self.advance();
+        let mut x = xy.0 - width as isize;
+        let rows = xy.1..xy.1 + height as isize;
+        for chr in text.chars() {
+            self.draw_char(graphics, chr, (x, xy.1), &rows, color);
+            x += advance;
+        }
+    }
+
+    /// Draws `value` as `digits` digits with its top right corner at `xy`, each digit
+    /// rolling up into the next as the one to its right goes from 9 to 0, leading zeros
+    /// are left blank, `separator` is drawn between each group of three
+    pub fn draw_odometer(
+        &self,
+        graphics: &mut Graphics,
+        value: f64,
+        digits: usize,
+        separator: &str,
+        xy: (isize, isize),
+        color: Color,
+    ) {
+        let (_, height) = self.measure("0");
+        let rows = xy.1..xy.1 + height as isize;
+        let advance = self.advance();
+        let mut place = 1.0;
+        let mut x = xy.0;
+        for i in 1..=digits {
+            let whole = (value / place).floor();
+            let below = value - whole * place;
+            //only moves while everything to the right is rolling over from 9s
+            let roll = (below - (place - 1.0)).max(0.0);
+            let offset = (roll * height as f64).round() as isize;
+            if i > 1 && (i - 1).is_multiple_of(3) {
+                for chr in separator.chars().rev() {
+                    x -= advance;
+                    if whole > 0.0 {
+                        self.draw_char(graphics, chr, (x, xy.1), &rows, color);
+                    }
+                }
+            }
+            x -= advance;
+            let digit = whole as u64 % 10;
+            if whole > 0.0 || i == 1 {
+                self.draw_digit(graphics, digit, (x, xy.1 - offset), &rows, color);
+            }
+            let next = (digit + 1) % 10;
+            self.draw_digit(
+                graphics,
+                next,
+                (x, xy.1 + height as isize - offset),
+                &rows,
+                color,
+            );
+            place *= 10.0;
+        }
+    }
+
+    fn advance(&self) -> isize {
+        ((self.glyphs.width + self.glyphs.spacing) * self.scale) as isize
+    }
+
+    fn draw_digit(
+        &self,
+        graphics: &mut Graphics,
+        digit: u64,
+        xy: (isize, isize),
+        rows: &Range<isize>,
+        color: Color,
+    ) {
+        let chr = char::from_digit(digit as u32, 10).expect("less than 10");
+        self.draw_char(graphics, chr, xy, rows, color);
+    }
+
+    /// Draws `chr` with its top left corner at `xy`, only the pixels in `rows`
+    fn draw_char(
+        &self,
+        graphics: &mut Graphics,
+        chr: char,
+        xy: (isize, isize),
+        rows: &Range<isize>,
+        color: Color,
+    ) {
+        let scale = self.scale as isize;
+        let mask = self
+            .glyphs
+            .mask(self.numerals.map(chr))
+            .or_else(|| self.glyphs.mask(chr));
+        if let Some(mask) = mask {
+            for (i, _) in mask.iter().enumerate().filter(|(_, set)| **set) {
+                let px = xy.0 + (i % self.glyphs.width) as isize * scale;
+                let py = xy.1 + (i / self.glyphs.width) as isize * scale;
+                for offset in 0..scale * scale {
+                    let y = py + offset / scale;
+                    if rows.contains(&y) {
+                        graphics.set_pixel(px + offset % scale, y, color);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::paths;
+use buffer_graphics_lib::prelude::*;
+use chrono::Local;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use std::collections::VecDeque;
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Frames kept by default, a couple of seconds
+pub const DEFAULT_CAPACITY: usize = 120;
+
+static FRAMES: Mutex<VecDeque<Image>> = Mutex::new(VecDeque::new());
+static CAPACITY: AtomicUsize = AtomicUsize::new(0);
+
+/// Starts keeping the last `capacity` frames, and saving them if the app panics
+pub fn start(capacity: usize) {
+    CAPACITY.store(capacity, Ordering::SeqCst);
+    if capacity == 0 {
+        return;
+    }
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        match dump() {
+            Ok(dir) => eprintln!("Saved the last frames to {}", dir.display()),
+            Err(err) => eprintln!("Unable to save the last frames: {err:#}"),
+        }
+        previous(info);
+    }));
+}
+
+/// Copies the frame just drawn, dropping the oldest if full
+pub fn record(graphics: &Graphics) {
+    let capacity = CAPACITY.load(Ordering::SeqCst);
+    if capacity == 0 {
+        return;
+    }
+    let mut frames = frames();
+    while frames.len() >= capacity {
+        frames.pop_front();
+    }
+    frames.push_back(graphics.copy_to_image());
+}
+
+/// Writes the kept frames as numbered PNGs, oldest first, to a new dir in `frames` in
+/// the data dir and returns the dir
+pub fn dump() -> Result<PathBuf> {
+    let frames: Vec<Image> = frames().iter().cloned().collect();
+    if frames.is_empty() {
+        return Err(eyre!("No frames have been kept"));
+    }
+    let dir = paths::data_dir()?
+        .join("frames")
+        .join(Local::now().format("%Y%m%d-%H%M%S").to_string());
+    fs::create_dir_all(&dir).wrap_err_with(|| format!("Creating {}", dir.display()))?;
+    for (i, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("{:04}.png", i + 1));
+        let bytes: Vec<u8> = (0..frame.height())
+            .flat_map(|y| (0..frame.width()).map(move |x| (x, y)))
+            .flat_map(|(x, y)| frame.get_pixel(x, y).as_array())
+            .collect();
+        image::save_buffer(
+            &path,
+            &bytes,
+            frame.width() as u32,
+            frame.height() as u32,
+            image::ColorType::Rgba8,
+        )
+        .wrap_err_with(|| format!("Writing {}", path.display()))?;
+    }
+    Ok(dir)
+}
+
+/// Frames are still readable after a panic while recording
+fn frames() -> MutexGuard<'static, VecDeque<Image>> {
+    FRAMES.lock().unwrap_or_else(|err| err.into_inner())
+}
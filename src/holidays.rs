@@ -0,0 +1,81 @@
+use crate::net;
+use crate::paths;
+use chrono::{Datelike, NaiveDate};
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Public holidays by year and country, from Nager.Date
+const API: &str = "https://date.nager.at/api/v3/PublicHolidays";
+/// Downloaded years are kept here in the data dir, one file per country and year
+const CACHE_DIR: &str = "holidays";
+
+#[derive(Debug, Deserialize)]
+struct Holiday {
+    date: NaiveDate,
+    /// False if it's only in some regions
+    #[serde(default = "yes")]
+    global: bool,
+    /// ISO 3166-2 codes of the regions, if it isn't global
+    #[serde(default)]
+    counties: Option<Vec<String>>,
+}
+
+fn yes() -> bool {
+    true
+}
+
+/// Public holidays in `region` from `start` to `end`, a country code (`GB`) for the ones
+/// everywhere or a region of one (`GB-SCT`) to add that region's, each year is downloaded
+/// the first time it's needed and then kept
+pub fn between(region: &str, start: NaiveDate, end: NaiveDate) -> Result<Vec<NaiveDate>> {
+    let region = region.to_ascii_uppercase();
+    let country = region.split('-').next().unwrap_or_default();
+    if country.len() != 2 || !country.chars().all(|chr| chr.is_ascii_alphabetic()) {
+        return Err(eyre!(
+            "Invalid holidays {region}, use a country code like GB or a region like GB-SCT"
+        ));
+    }
+    let mut dates = vec![];
+    for year in start.year()..=end.year() {
+        for holiday in year_of(country, year)? {
+            let in_region = holiday.global
+                || holiday
+                    .counties
+                    .iter()
+                    .flatten()
+                    .any(|county| *county == region);
+            if in_region && start <= holiday.date && holiday.date <= end {
+                dates.push(holiday.date);
+            }
+        }
+    }
+    Ok(dates)
+}
+
+fn year_of(country: &str, year: i32) -> Result<Vec<Holiday>> {
+    let path = cache_path(country, year)?;
+    if let Ok(text) = fs::read_to_string(&path) {
+        return serde_json::from_str(&text).wrap_err_with(|| format!("Reading {}", path.display()));
+    }
+    let url = format!("{API}/{year}/{country}");
+    let text = net::agent(&url)
+        .get(&url)
+        .call()
+        .wrap_err_with(|| format!("Downloading the {year} holidays for {country}"))?
+        .into_string()
+        .wrap_err_with(|| format!("Downloading the {year} holidays for {country}"))?;
+    //unknown countries get an empty response rather than an error
+    let holidays = serde_json::from_str(&text)
+        .map_err(|_| eyre!("No holidays for {country}, is it a country code?"))?;
+    fs::write(&path, &text).wrap_err_with(|| format!("Writing {}", path.display()))?;
+    Ok(holidays)
+}
+
+fn cache_path(country: &str, year: i32) -> Result<PathBuf> {
+    let dir = paths::data_dir()?.join(CACHE_DIR);
+    fs::create_dir_all(&dir).wrap_err_with(|| format!("Creating {}", dir.display()))?;
+    Ok(dir.join(format!("{country}-{year}.json")))
+}
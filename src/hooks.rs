@@ -0,0 +1,227 @@
+use crate::outbox::{deliver, Message};
+use countup::i18n::Strings;
+use countup::report::Report;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+
+/// Actions to run when something happens in the app, set in the config file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    /// Announces each day rollover and milestone to this URL, i.e. a Slack or Discord
+    /// incoming webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub on_animation_complete: Vec<Action>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub on_day_rollover: Vec<Action>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub on_milestone: Vec<Action>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Run with the system shell, details are passed as `COUNTUP_*` env vars
+    Command(String),
+    /// Play an audio file with the platform's player
+    Sound(PathBuf),
+    /// POST the details as JSON to the URL, retrying and queueing if the network is down
+    Webhook(String),
+    /// [Action::Webhook] with the text as `content` too, for [Hooks::webhook]
+    #[serde(skip)]
+    Announce(String),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hook {
+    AnimationComplete,
+    DayRollover,
+    Milestone,
+}
+
+/// Details sent to each action
+#[derive(Debug, Clone, Serialize)]
+pub struct HookPayload {
+    pub hook: Hook,
+    pub event: Option<String>,
+    pub start: String,
+    pub days: usize,
+    pub weeks: usize,
+    pub months: usize,
+    pub years: usize,
+    pub milestone: Option<String>,
+    /// One line to post in a chat, i.e. `Sober: 100 days`
+    pub text: String,
+}
+
+impl HookPayload {
+    /// Details from `report`, `title` starts the text when there's no milestone
+    pub fn new(
+        hook: Hook,
+        report: &Report,
+        start: String,
+        milestone: Option<String>,
+        title: &str,
+        strings: &Strings,
+    ) -> Self {
+        let message = match &milestone {
+            Some(milestone) => milestone.clone(),
+            None => report.sentence(title, strings),
+        };
+        HookPayload {
+            hook,
+            event: report.event.clone(),
+            start,
+            days: report.days,
+            weeks: report.weeks,
+            months: report.months,
+            years: report.years,
+            text: match &report.event {
+                Some(event) => format!("{event}: {message}"),
+                None => message,
+            },
+            milestone,
+        }
+    }
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.webhook.is_none()
+            && self.on_animation_complete.is_empty()
+            && self.on_day_rollover.is_empty()
+            && self.on_milestone.is_empty()
+    }
+
+    pub fn actions(&self, hook: Hook) -> &[Action] {
+        match hook {
+            Hook::AnimationComplete => &self.on_animation_complete,
+            Hook::DayRollover => &self.on_day_rollover,
+            Hook::Milestone => &self.on_milestone,
+        }
+    }
+
+    /// Runs every action for `payload.hook` on a background thread so rendering isn't blocked
+    pub fn fire(&self, payload: HookPayload) {
+        let announce = self
+            .webhook
+            .iter()
+            .filter(|_| payload.hook != Hook::AnimationComplete)
+            .map(|url| Action::Announce(url.clone()));
+        for action in self.actions(payload.hook).iter().cloned().chain(announce) {
+            let payload = payload.clone();
+            thread::spawn(move || {
+                if let Err(err) = action.run(&payload) {
+                    eprintln!("Hook {:?} failed: {err}", payload.hook);
+                }
+            });
+        }
+    }
+}
+
+impl Action {
+    fn run(&self, payload: &HookPayload) -> Result<(), String> {
+        match self {
+            Action::Command(cmd) => {
+                let mut command = shell(cmd);
+                command
+                    .env("COUNTUP_HOOK", hook_name(payload.hook))
+                    .env("COUNTUP_EVENT", payload.event.clone().unwrap_or_default())
+                    .env("COUNTUP_START", &payload.start)
+                    .env("COUNTUP_DAYS", payload.days.to_string())
+                    .env(
+                        "COUNTUP_MILESTONE",
+                        payload.milestone.clone().unwrap_or_default(),
+                    );
+                wait(command)
+            }
+            Action::Sound(file) => wait(sound_player(file)),
+            Action::Webhook(url) => {
+                let body = serde_json::to_value(payload).map_err(|err| err.to_string())?;
+                deliver(Message::Webhook {
+                    url: url.clone(),
+                    body,
+                })
+                .map_err(|err| err.to_string())
+            }
+            Action::Announce(url) => {
+                //Slack shows `text` and Discord `content`
+                let mut body = serde_json::to_value(payload).map_err(|err| err.to_string())?;
+                body["content"] = Value::String(payload.text.clone());
+                deliver(Message::Webhook {
+                    url: url.clone(),
+                    body,
+                })
+                .map_err(|err| err.to_string())
+            }
+        }
+    }
+}
+
+fn hook_name(hook: Hook) -> &'static str {
+    match hook {
+        Hook::AnimationComplete => "animation_complete",
+        Hook::DayRollover => "day_rollover",
+        Hook::Milestone => "milestone",
+    }
+}
+
+fn wait(mut command: Command) -> Result<(), String> {
+    let status = command.status().map_err(|err| err.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("exited with {status}"))
+    }
+}
+
+#[cfg(windows)]
+fn shell(cmd: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.args(["/C", cmd]);
+    command
+}
+
+#[cfg(not(windows))]
+fn shell(cmd: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.args(["-c", cmd]);
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn sound_player(file: &Path) -> Command {
+    let mut command = Command::new("afplay");
+    command.arg(file);
+    command
+}
+
+#[cfg(windows)]
+fn sound_player(file: &Path) -> Command {
+    let mut command = Command::new("powershell");
+    command.args([
+        "-NoProfile",
+        "-Command",
+        &format!(
+            "(New-Object Media.SoundPlayer '{}').PlaySync()",
+            file.display()
+        ),
+    ]);
+    command
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn sound_player(file: &Path) -> Command {
+    //paplay handles most formats, aplay is the fallback for systems without pulseaudio
+    let mut command = Command::new("sh");
+    command
+        .args(["-c", "paplay \"$0\" || aplay \"$0\""])
+        .arg(file);
+    command
+}
@@ -0,0 +1,1196 @@
+use crate::report::MonthLength;
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+mod cli;
+
+pub use cli::{cli_strings, CliStrings};
+
+/// Languages with a translation table, the first is used if the locale is unknown
+pub const LANGUAGES: [&str; 6] = ["en", "de", "fr", "it", "nl", "pt"];
+
+/// Digits used for the big numbers
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Numerals {
+    /// 0123456789
+    #[default]
+    Western,
+    /// Eastern Arabic digits
+    ArabicIndic,
+    Devanagari,
+}
+
+impl Numerals {
+    pub const NAMES: [&'static str; 3] = ["western", "arabic-indic", "devanagari"];
+
+    pub fn from_name(name: &str) -> Option<Numerals> {
+        match name {
+            "western" => Some(Numerals::Western),
+            "arabic-indic" => Some(Numerals::ArabicIndic),
+            "devanagari" => Some(Numerals::Devanagari),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        Numerals::NAMES[self as usize]
+    }
+
+    /// Characters for 0 to 9
+    pub fn digits(self) -> [char; 10] {
+        match self {
+            Numerals::Western => ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'],
+            Numerals::ArabicIndic => ['٠', '١', '٢', '٣', '٤', '٥', '٦', '٧', '٨', '٩'],
+            Numerals::Devanagari => ['०', '१', '२', '३', '४', '५', '६', '७', '८', '९'],
+        }
+    }
+
+    /// Swaps an ASCII digit for this system's, anything else is unchanged
+    pub fn map(self, chr: char) -> char {
+        chr.to_digit(10)
+            .filter(|_| chr.is_ascii_digit())
+            .map(|digit| self.digits()[digit as usize])
+            .unwrap_or(chr)
+    }
+}
+
+/// What goes between each group of three digits, from `--number-format`
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NumberFormat {
+    /// 12345
+    #[default]
+    Plain,
+    /// The language's, i.e. 12,345 in English and 12.345 in German
+    Locale,
+    Comma,
+    Period,
+    Space,
+}
+
+impl NumberFormat {
+    pub const NAMES: [&'static str; 5] = ["plain", "locale", "comma", "period", "space"];
+
+    pub fn from_name(name: &str) -> Option<NumberFormat> {
+        match name {
+            "plain" => Some(NumberFormat::Plain),
+            "locale" => Some(NumberFormat::Locale),
+            "comma" => Some(NumberFormat::Comma),
+            "period" => Some(NumberFormat::Period),
+            "space" => Some(NumberFormat::Space),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        NumberFormat::NAMES[self as usize]
+    }
+
+    /// Separator for `strings`' language, empty for plain numbers
+    pub fn separator(self, strings: &Strings) -> &'static str {
+        match self {
+            NumberFormat::Plain => "",
+            NumberFormat::Locale => strings.thousands,
+            NumberFormat::Comma => ",",
+            NumberFormat::Period => ".",
+            NumberFormat::Space => " ",
+        }
+    }
+}
+
+/// `value` with `separator` between each group of three digits, i.e. `1,000,000`
+pub fn group_digits(value: impl ToString, separator: &str) -> String {
+    let text = value.to_string();
+    let digits = text.trim_start_matches('-');
+    let mut grouped = text[..text.len() - digits.len()].to_string();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// All user visible text in the UI
+///
+/// The built in font only supports ASCII so translations must avoid accented characters
+#[derive(Debug)]
+pub struct Strings {
+    /// `{date}` is replaced with the start date
+    pub since: &'static str,
+    /// `{date}` is replaced with the target date
+    pub until: &'static str,
+    /// `{date}` and `{end}` are replaced with the dates from `--from` and `--to`
+    pub between: &'static str,
+    /// `{date}` is replaced with the birth date from `--birthday`
+    pub age: &'static str,
+    pub years: &'static str,
+    pub months: &'static str,
+    pub weeks: &'static str,
+    pub days: &'static str,
+    /// Label for the days until the next anniversary, under the days in split mode
+    pub to_anniversary: &'static str,
+    /// Label for the days until the next birthday with `--birthday`
+    pub to_birthday: &'static str,
+    /// Label for the 29 Februaries since the start with `--leap-days`
+    pub leap_days: &'static str,
+    /// Labels in bases mode
+    pub hex: &'static str,
+    pub binary: &'static str,
+    /// Header for timeline mode
+    pub timeline: &'static str,
+    /// Lowercase days, used in the countdown
+    pub days_lower: &'static str,
+    /// Singular and plural, lowercase, for the copied summary
+    pub year_words: (&'static str, &'static str),
+    pub month_words: (&'static str, &'static str),
+    pub day_words: (&'static str, &'static str),
+    /// `{percent}` and `{goal}` are replaced with the progress and the days or date from
+    /// `--goal`
+    pub goal: &'static str,
+    pub or: &'static str,
+    /// After the months in diff mode, `{days}` is replaced with the days in a month,
+    /// i.e. `(28 d)`
+    pub month_of: &'static str,
+    /// After the months in diff mode with calendar months
+    pub calendar_months: &'static str,
+    pub this_term: &'static str,
+    pub week: &'static str,
+    /// Header for weeks mode, `{week}` and `{year}` are replaced with the start's ISO week
+    pub iso_since: &'static str,
+    pub this_week: &'static str,
+    pub this_year: &'static str,
+    pub this_month: &'static str,
+    pub today: &'static str,
+    pub full_weeks: &'static str,
+    pub total_hours: &'static str,
+    pub total_minutes: &'static str,
+    pub total_seconds: &'static str,
+    /// Between each group of three digits in totals mode
+    pub thousands: &'static str,
+    /// Age milestone in age mode, `{hours}` and `{date}` are replaced
+    pub hours_old: &'static str,
+    pub lunar_cycles: &'static str,
+    pub full_moons: &'static str,
+    /// Header for year mode, `{year}` and `{date}` are replaced with the year and where it's
+    /// counted from
+    pub year_from: &'static str,
+    pub day_of_year: &'static str,
+    pub week_of_year: &'static str,
+    pub percent_of_year: &'static str,
+    /// Header for fiscal mode, `{year}` is replaced with the last two digits of the year it
+    /// ends in and `{date}` with the day it started
+    pub fiscal: &'static str,
+    pub day_of_fiscal: &'static str,
+    pub fiscal_years: &'static str,
+    /// Teaching days remaining in the term
+    pub days_left: &'static str,
+    pub days_to_start: &'static str,
+    /// Under each number in dual mode, above the event or date
+    pub days_since: &'static str,
+    pub days_until: &'static str,
+    pub on_break: &'static str,
+    pub term_over: &'static str,
+    /// Days into the current phase
+    pub days_in: &'static str,
+    pub phases_done: &'static str,
+    /// Header for the edits made to a shared event
+    pub history: &'static str,
+    pub no_history: &'static str,
+    pub added: &'static str,
+    pub removed: &'static str,
+    pub reset: &'static str,
+    /// Milestones snoozed, in the history
+    pub snoozed: &'static str,
+    pub renamed: &'static str,
+    /// Header for the recap, `{year}` is replaced with the year number
+    pub year_complete: &'static str,
+    pub milestones: &'static str,
+    /// Header for upcoming mode
+    pub coming_up: &'static str,
+    /// `{days}` is replaced with the days until a milestone, with [Strings::day_words]
+    pub in_days: &'static str,
+    /// After the title for an event's `note_pauses`, `{days}` is replaced with the paused
+    /// days, with [Strings::day_words]
+    pub excluding: &'static str,
+    /// Header for weekdays mode, `{day}` is replaced with the start's [Strings::weekday_names]
+    pub started_on: &'static str,
+    /// From Monday
+    pub weekday_names: [&'static str; 7],
+    /// Plural, from Monday, next to how many there's been in weekdays mode
+    pub weekdays: [&'static str; 7],
+    /// Header for the weekly digest
+    pub digest: &'static str,
+    pub no_digest: &'static str,
+    /// Header for the help overlay
+    pub shortcuts: &'static str,
+    pub close_help: &'static str,
+    /// Closes the event editor, or goes back to its list
+    pub escape_back: &'static str,
+    /// What each key does, in the help overlay
+    pub next_mode: &'static str,
+    pub skip: &'static str,
+    pub pause: &'static str,
+    pub restart: &'static str,
+    pub next_intro: &'static str,
+    pub copy_summary: &'static str,
+    pub save_frames: &'static str,
+    pub quit: &'static str,
+    /// Header for the version and paths, opened from the help overlay
+    pub about: &'static str,
+    /// Header for streaks mode
+    pub streaks: &'static str,
+    /// `{days}` is replaced with the longest streak, including the current one
+    pub best_streak: &'static str,
+    /// `{days}` is replaced with the average length of the finished streaks
+    pub average_streak: &'static str,
+    /// Header for confirming a streak reset, and how to confirm it
+    pub reset_streak: &'static str,
+    pub confirm_reset: &'static str,
+    /// Header for the date picker, and how to use it
+    pub change_date: &'static str,
+    pub pick_date: &'static str,
+    /// Header for the event editor, also its right click menu item and key in the help
+    pub edit_events: &'static str,
+    /// Last row of the event editor's list, and the header for naming it
+    pub new_event: &'static str,
+    /// Keys for the event editor's list
+    pub edit_keys: &'static str,
+    pub confirm_delete: &'static str,
+    /// Header for typing a new name for an event
+    pub rename_event: &'static str,
+    pub type_name: &'static str,
+    pub name_taken: &'static str,
+    /// How to pick the date of a new event
+    pub add_date: &'static str,
+    /// Right click menu item, copies the count as text
+    pub copy: &'static str,
+    /// Right click menu item, opens the lengths in `snooze_for`
+    pub snooze_milestones: &'static str,
+    /// Snooze for a day, a week, and forever
+    pub snooze_for: [&'static str; 3],
+    pub unsnooze_milestones: &'static str,
+    /// Digits for the big numbers, unless changed with `--numerals`
+    pub numerals: Numerals,
+    /// 12 or 24 hour times, unless changed with `--hours`
+    pub hours: u8,
+}
+
+impl Strings {
+    /// `count` and the singular or plural from `words`, i.e. [Strings::year_words]
+    pub fn counted(&self, count: usize, (one, many): (&str, &str)) -> String {
+        format!("{count} {}", if count == 1 { one } else { many })
+    }
+
+    pub fn since(&self, date: &str) -> String {
+        self.since.replace("{date}", date)
+    }
+
+    pub fn until(&self, date: &str) -> String {
+        self.until.replace("{date}", date)
+    }
+
+    pub fn between(&self, date: &str, end: &str) -> String {
+        self.between.replace("{date}", date).replace("{end}", end)
+    }
+
+    pub fn age(&self, date: &str) -> String {
+        self.age.replace("{date}", date)
+    }
+
+    pub fn hours_old(&self, hours: usize, date: &str) -> String {
+        self.hours_old
+            .replace("{hours}", &hours.to_string())
+            .replace("{date}", date)
+    }
+
+    pub fn month_length(&self, length: MonthLength) -> String {
+        match length {
+            MonthLength::Calendar => self.calendar_months.to_string(),
+            _ => self.month_of.replace("{days}", length.name()),
+        }
+    }
+
+    pub fn goal(&self, percent: usize, goal: &str) -> String {
+        self.goal
+            .replace("{percent}", &percent.to_string())
+            .replace("{goal}", goal)
+    }
+
+    pub fn in_days(&self, days: usize) -> String {
+        self.in_days
+            .replace("{days}", &self.counted(days, self.day_words))
+    }
+
+    pub fn excluding(&self, days: usize) -> String {
+        self.excluding
+            .replace("{days}", &self.counted(days, self.day_words))
+    }
+
+    pub fn started_on(&self, weekday: Weekday) -> String {
+        let day = self.weekday_names[weekday.num_days_from_monday() as usize];
+        self.started_on.replace("{day}", day)
+    }
+
+    pub fn best_streak(&self, days: usize) -> String {
+        self.best_streak.replace("{days}", &days.to_string())
+    }
+
+    pub fn average_streak(&self, days: usize) -> String {
+        self.average_streak.replace("{days}", &days.to_string())
+    }
+
+    pub fn year_from(&self, year: i32, date: &str) -> String {
+        self.year_from
+            .replace("{year}", &year.to_string())
+            .replace("{date}", date)
+    }
+
+    pub fn iso_since(&self, week: u32, year: i32) -> String {
+        self.iso_since
+            .replace("{week}", &week.to_string())
+            .replace("{year}", &year.to_string())
+    }
+
+    pub fn fiscal(&self, year: i32, date: &str) -> String {
+        self.fiscal
+            .replace("{year}", &format!("{:02}", year.rem_euclid(100)))
+            .replace("{date}", date)
+    }
+
+    pub fn year_complete(&self, year: usize) -> String {
+        self.year_complete.replace("{year}", &year.to_string())
+    }
+}
+
+const EN: Strings = Strings {
+    since: "Since {date} it's been",
+    until: "Counting down to {date}",
+    between: "From {date} to {end} it's been",
+    age: "Born {date}, you are",
+    years: "YEARS",
+    months: "MONTHS",
+    weeks: "WEEKS",
+    days: "DAYS",
+    to_anniversary: "TO ANNIVERSARY",
+    to_birthday: "TO BIRTHDAY",
+    leap_days: "LEAP DAYS",
+    hex: "HEX",
+    binary: "BINARY",
+    timeline: "TIMELINE",
+    days_lower: "days",
+    year_words: ("year", "years"),
+    month_words: ("month", "months"),
+    day_words: ("day", "days"),
+    goal: "{percent}% of the way to {goal}",
+    or: "or",
+    month_of: "({days} d)",
+    calendar_months: "(calendar)",
+    this_term: "This term",
+    week: "WEEK",
+    iso_since: "Since week {week} of {year}",
+    this_week: "THIS WEEK",
+    this_year: "THIS YEAR",
+    this_month: "THIS MONTH",
+    today: "TODAY",
+    full_weeks: "FULL WEEKS",
+    total_hours: "HOURS",
+    total_minutes: "MINUTES",
+    total_seconds: "SECONDS",
+    thousands: ",",
+    hours_old: "{hours} hours old on {date}",
+    lunar_cycles: "LUNAR CYCLES",
+    full_moons: "FULL MOONS",
+    year_from: "{year}, from {date}",
+    day_of_year: "DAY OF YEAR",
+    week_of_year: "WEEK OF YEAR",
+    percent_of_year: "% OF YEAR",
+    fiscal: "FY{year}, started {date}",
+    day_of_fiscal: "DAY OF FY",
+    fiscal_years: "FISCAL YEARS",
+    days_left: "DAYS LEFT",
+    days_to_start: "DAYS TO START",
+    days_since: "DAYS SINCE",
+    days_until: "DAYS UNTIL",
+    on_break: "ON BREAK",
+    term_over: "TERM OVER",
+    days_in: "DAYS IN",
+    phases_done: "ALL DONE",
+    history: "History",
+    no_history: "No changes yet",
+    added: "added",
+    removed: "removed",
+    reset: "reset",
+    snoozed: "snoozed",
+    renamed: "renamed",
+    year_complete: "Year {year} complete",
+    milestones: "MILESTONES",
+    coming_up: "Coming up",
+    in_days: "in {days}",
+    excluding: "(excluding {days})",
+    started_on: "Started on a {day}",
+    weekday_names: [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ],
+    weekdays: [
+        "MONDAYS",
+        "TUESDAYS",
+        "WEDNESDAYS",
+        "THURSDAYS",
+        "FRIDAYS",
+        "SATURDAYS",
+        "SUNDAYS",
+    ],
+    digest: "This week",
+    no_digest: "Nothing this week",
+    shortcuts: "Shortcuts",
+    close_help: "Press any key to close",
+    escape_back: "Esc to go back",
+    next_mode: "Next mode",
+    skip: "Skip counting",
+    pause: "Pause",
+    restart: "Count again",
+    next_intro: "Next intro",
+    copy_summary: "Copy summary",
+    save_frames: "Save frames",
+    quit: "Quit",
+    about: "About",
+    streaks: "Streaks",
+    best_streak: "Best {days} days",
+    average_streak: "Average {days} days",
+    reset_streak: "Reset the streak?",
+    confirm_reset: "Press Backspace again to start over from today",
+    change_date: "Change start date",
+    pick_date: "Arrows change, Enter counts from it",
+    edit_events: "Edit events",
+    new_event: "New event",
+    edit_keys: "Enter shows, N adds, F2 renames, Del deletes",
+    confirm_delete: "Press Delete again to delete it",
+    rename_event: "Rename event",
+    type_name: "Type the name, then press Enter",
+    name_taken: "There's already an event called that",
+    add_date: "Arrows change, Enter adds it",
+    copy: "Copy",
+    snooze_milestones: "Snooze milestones",
+    snooze_for: ["For a day", "For a week", "Forever"],
+    unsnooze_milestones: "Unsnooze milestones",
+    numerals: Numerals::Western,
+    hours: 12,
+};
+
+const DE: Strings = Strings {
+    since: "Seit dem {date} sind es",
+    until: "Countdown bis {date}",
+    between: "Vom {date} bis {end} sind es",
+    age: "Geboren am {date}, du bist",
+    years: "JAHRE",
+    months: "MONATE",
+    weeks: "WOCHEN",
+    days: "TAGE",
+    to_anniversary: "BIS JAHRESTAG",
+    to_birthday: "BIS GEBURTSTAG",
+    leap_days: "SCHALTTAGE",
+    hex: "HEX",
+    binary: "BINAER",
+    timeline: "ZEITLEISTE",
+    days_lower: "Tage",
+    year_words: ("Jahr", "Jahre"),
+    month_words: ("Monat", "Monate"),
+    day_words: ("Tag", "Tage"),
+    goal: "{percent}% des Weges bis {goal}",
+    or: "oder",
+    month_of: "({days} T)",
+    calendar_months: "(Kalender)",
+    this_term: "Dieses Halbjahr",
+    week: "WOCHE",
+    iso_since: "Seit Woche {week} von {year}",
+    this_week: "DIESE WOCHE",
+    this_year: "DIESES JAHR",
+    this_month: "DIESER MONAT",
+    today: "HEUTE",
+    full_weeks: "VOLLE WOCHEN",
+    total_hours: "STUNDEN",
+    total_minutes: "MINUTEN",
+    total_seconds: "SEKUNDEN",
+    thousands: ".",
+    hours_old: "{hours} Stunden alt am {date}",
+    lunar_cycles: "MONDZYKLEN",
+    full_moons: "VOLLMONDE",
+    year_from: "{year}, ab {date}",
+    day_of_year: "TAG IM JAHR",
+    week_of_year: "KALENDERWOCHE",
+    percent_of_year: "% DES JAHRES",
+    fiscal: "GJ{year}, seit {date}",
+    day_of_fiscal: "TAG IM GJ",
+    fiscal_years: "GESCH.-JAHRE",
+    days_left: "TAGE UEBRIG",
+    days_to_start: "TAGE BIS START",
+    days_since: "TAGE SEIT",
+    days_until: "TAGE BIS",
+    on_break: "FERIEN",
+    term_over: "VORBEI",
+    days_in: "TAGE BISHER",
+    phases_done: "ALLES FERTIG",
+    history: "Verlauf",
+    no_history: "Noch keine Aenderungen",
+    added: "angelegt",
+    removed: "entfernt",
+    reset: "zurueckgesetzt",
+    snoozed: "stumm",
+    renamed: "umbenannt",
+    year_complete: "Jahr {year} geschafft",
+    milestones: "MEILENSTEINE",
+    coming_up: "Demnaechst",
+    in_days: "in {days}",
+    excluding: "(ohne {days})",
+    started_on: "Begann an einem {day}",
+    weekday_names: [
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+        "Sonntag",
+    ],
+    weekdays: [
+        "MONTAGE",
+        "DIENSTAGE",
+        "MITTWOCHE",
+        "DONNERSTAGE",
+        "FREITAGE",
+        "SAMSTAGE",
+        "SONNTAGE",
+    ],
+    digest: "Diese Woche",
+    no_digest: "Nichts diese Woche",
+    shortcuts: "Tasten",
+    close_help: "Beliebige Taste schliesst",
+    escape_back: "Esc geht zurueck",
+    next_mode: "Naechster Modus",
+    skip: "Ueberspringen",
+    pause: "Pause",
+    restart: "Neu zaehlen",
+    next_intro: "Naechstes Intro",
+    copy_summary: "Text kopieren",
+    save_frames: "Bilder speichern",
+    quit: "Beenden",
+    about: "Info",
+    streaks: "Serien",
+    best_streak: "Beste {days} Tage",
+    average_streak: "Schnitt {days} Tage",
+    reset_streak: "Neu beginnen?",
+    confirm_reset: "Nochmal Ruecktaste, um heute neu zu beginnen",
+    change_date: "Startdatum aendern",
+    pick_date: "Pfeile aendern, Enter zaehlt ab dort",
+    edit_events: "Ereignisse bearbeiten",
+    new_event: "Neues Ereignis",
+    edit_keys: "Enter zeigt, N neu, F2 umbenennen, Entf loescht",
+    confirm_delete: "Nochmal Entf zum Loeschen",
+    rename_event: "Ereignis umbenennen",
+    type_name: "Name eingeben, dann Enter",
+    name_taken: "Diesen Namen gibt es schon",
+    add_date: "Pfeile aendern, Enter fuegt hinzu",
+    copy: "Kopieren",
+    snooze_milestones: "Meilensteine stumm",
+    snooze_for: ["Einen Tag", "Eine Woche", "Fuer immer"],
+    unsnooze_milestones: "Meilensteine an",
+    numerals: Numerals::Western,
+    hours: 24,
+};
+
+const FR: Strings = Strings {
+    since: "Depuis le {date}",
+    until: "Jusqu'au {date}",
+    between: "Du {date} au {end}",
+    age: "Ne(e) le {date}, vous avez",
+    years: "ANNEES",
+    months: "MOIS",
+    weeks: "SEMAINES",
+    days: "JOURS",
+    to_anniversary: "AVANT ANNIV.",
+    to_birthday: "AVANT ANNIV.",
+    leap_days: "JOURS BISSEXT.",
+    hex: "HEXA",
+    binary: "BINAIRE",
+    timeline: "CHRONOLOGIE",
+    days_lower: "jours",
+    year_words: ("an", "ans"),
+    month_words: ("mois", "mois"),
+    day_words: ("jour", "jours"),
+    goal: "{percent}% du chemin vers {goal}",
+    or: "ou",
+    month_of: "({days} j)",
+    calendar_months: "(calendrier)",
+    this_term: "Ce trimestre",
+    week: "SEMAINE",
+    iso_since: "Depuis la sem. {week} de {year}",
+    this_week: "CETTE SEMAINE",
+    this_year: "CETTE ANNEE",
+    this_month: "CE MOIS",
+    today: "AUJOURD'HUI",
+    full_weeks: "SEM. COMPLETES",
+    total_hours: "HEURES",
+    total_minutes: "MINUTES",
+    total_seconds: "SECONDES",
+    thousands: " ",
+    hours_old: "{hours} heures le {date}",
+    lunar_cycles: "LUNAISONS",
+    full_moons: "PLEINES LUNES",
+    year_from: "{year}, depuis le {date}",
+    day_of_year: "QUANTIEME",
+    week_of_year: "NO DE SEMAINE",
+    percent_of_year: "% DE L'ANNEE",
+    fiscal: "EX{year}, depuis le {date}",
+    day_of_fiscal: "JOUR DE L'EX.",
+    fiscal_years: "EXERCICES",
+    days_left: "JOURS RESTANTS",
+    days_to_start: "JOURS AVANT",
+    days_since: "JOURS DEPUIS",
+    days_until: "JOURS AVANT",
+    on_break: "VACANCES",
+    term_over: "TERMINE",
+    days_in: "JOURS ECOULES",
+    phases_done: "TERMINE",
+    history: "Historique",
+    no_history: "Aucun changement",
+    added: "ajoute",
+    removed: "supprime",
+    reset: "remis a zero",
+    snoozed: "en sourdine",
+    renamed: "renomme",
+    year_complete: "Annee {year} terminee",
+    milestones: "ETAPES",
+    coming_up: "A venir",
+    in_days: "dans {days}",
+    excluding: "(hors {days})",
+    started_on: "Commence un {day}",
+    weekday_names: [
+        "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+    ],
+    weekdays: [
+        "LUNDIS",
+        "MARDIS",
+        "MERCREDIS",
+        "JEUDIS",
+        "VENDREDIS",
+        "SAMEDIS",
+        "DIMANCHES",
+    ],
+    digest: "Cette semaine",
+    no_digest: "Rien cette semaine",
+    shortcuts: "Raccourcis",
+    close_help: "Une touche pour fermer",
+    escape_back: "Echap pour revenir",
+    next_mode: "Mode suivant",
+    skip: "Passer le comptage",
+    pause: "Pause",
+    restart: "Recompter",
+    next_intro: "Intro suivante",
+    copy_summary: "Copier le texte",
+    save_frames: "Sauver les images",
+    quit: "Quitter",
+    about: "A propos",
+    streaks: "Series",
+    best_streak: "Record {days} jours",
+    average_streak: "Moyenne {days} jours",
+    reset_streak: "Remettre a zero ?",
+    confirm_reset: "Retour arriere encore pour repartir d'aujourd'hui",
+    change_date: "Changer la date de debut",
+    pick_date: "Fleches changent, Entree compte",
+    edit_events: "Modifier les evenements",
+    new_event: "Nouvel evenement",
+    edit_keys: "Entree voir, N ajouter, F2 renommer, Suppr effacer",
+    confirm_delete: "Suppr encore pour effacer",
+    rename_event: "Renommer l'evenement",
+    type_name: "Tapez le nom, puis Entree",
+    name_taken: "Ce nom existe deja",
+    add_date: "Fleches changent, Entree ajoute",
+    copy: "Copier",
+    snooze_milestones: "Sourdine des etapes",
+    snooze_for: ["Un jour", "Une semaine", "Pour toujours"],
+    unsnooze_milestones: "Reactiver les etapes",
+    numerals: Numerals::Western,
+    hours: 24,
+};
+
+const IT: Strings = Strings {
+    since: "Dal {date} sono passati",
+    until: "Fino al {date}",
+    between: "Dal {date} al {end} sono passati",
+    age: "Nato il {date}, hai",
+    years: "ANNI",
+    months: "MESI",
+    weeks: "SETTIMANE",
+    days: "GIORNI",
+    to_anniversary: "A ANNIVERSARIO",
+    to_birthday: "AL COMPLEANNO",
+    leap_days: "GIORNI BISEST.",
+    hex: "ESADEC.",
+    binary: "BINARIO",
+    timeline: "CRONOLOGIA",
+    days_lower: "giorni",
+    year_words: ("anno", "anni"),
+    month_words: ("mese", "mesi"),
+    day_words: ("giorno", "giorni"),
+    goal: "{percent}% della strada verso {goal}",
+    or: "o",
+    month_of: "({days} g)",
+    calendar_months: "(calendario)",
+    this_term: "Questo periodo",
+    week: "SETTIMANA",
+    iso_since: "Dalla sett. {week} del {year}",
+    this_week: "SETTIMANA ORA",
+    this_year: "QUEST'ANNO",
+    this_month: "QUESTO MESE",
+    today: "OGGI",
+    full_weeks: "SETT. INTERE",
+    total_hours: "ORE",
+    total_minutes: "MINUTI",
+    total_seconds: "SECONDI",
+    thousands: ".",
+    hours_old: "{hours} ore di vita il {date}",
+    lunar_cycles: "CICLI LUNARI",
+    full_moons: "LUNE PIENE",
+    year_from: "{year}, dal {date}",
+    day_of_year: "GG DELL'ANNO",
+    week_of_year: "SETTIMANA",
+    percent_of_year: "% DELL'ANNO",
+    fiscal: "AF{year}, dal {date}",
+    day_of_fiscal: "GIORNO DELL'AF",
+    fiscal_years: "ANNI FISCALI",
+    days_left: "GIORNI RIMASTI",
+    days_to_start: "GIORNI AL VIA",
+    days_since: "GIORNI DA",
+    days_until: "GIORNI A",
+    on_break: "VACANZA",
+    term_over: "FINITO",
+    days_in: "GIORNI FATTI",
+    phases_done: "COMPLETATO",
+    history: "Cronologia",
+    no_history: "Nessuna modifica",
+    added: "aggiunto",
+    removed: "rimosso",
+    reset: "azzerato",
+    snoozed: "silenziato",
+    renamed: "rinominato",
+    year_complete: "Anno {year} completato",
+    milestones: "TRAGUARDI",
+    coming_up: "In arrivo",
+    in_days: "tra {days}",
+    excluding: "(esclusi {days})",
+    started_on: "Iniziato di {day}",
+    weekday_names: [
+        "lunedi",
+        "martedi",
+        "mercoledi",
+        "giovedi",
+        "venerdi",
+        "sabato",
+        "domenica",
+    ],
+    weekdays: [
+        "LUNEDI",
+        "MARTEDI",
+        "MERCOLEDI",
+        "GIOVEDI",
+        "VENERDI",
+        "SABATI",
+        "DOMENICHE",
+    ],
+    digest: "Questa settimana",
+    no_digest: "Niente questa settimana",
+    shortcuts: "Scorciatoie",
+    close_help: "Un tasto per chiudere",
+    escape_back: "Esc per tornare",
+    next_mode: "Modo successivo",
+    skip: "Salta il conteggio",
+    pause: "Pausa",
+    restart: "Ricomincia",
+    next_intro: "Intro successiva",
+    copy_summary: "Copia il testo",
+    save_frames: "Salva i fotogrammi",
+    quit: "Esci",
+    about: "Informazioni",
+    streaks: "Serie",
+    best_streak: "Record {days} giorni",
+    average_streak: "Media {days} giorni",
+    reset_streak: "Azzerare la serie?",
+    confirm_reset: "Premi di nuovo Backspace per ripartire da oggi",
+    change_date: "Cambia data di inizio",
+    pick_date: "Frecce cambiano, Invio conta da li",
+    edit_events: "Modifica eventi",
+    new_event: "Nuovo evento",
+    edit_keys: "Invio mostra, N nuovo, F2 rinomina, Canc elimina",
+    confirm_delete: "Canc di nuovo per eliminare",
+    rename_event: "Rinomina evento",
+    type_name: "Scrivi il nome, poi Invio",
+    name_taken: "Questo nome esiste gia",
+    add_date: "Frecce cambiano, Invio aggiunge",
+    copy: "Copia",
+    snooze_milestones: "Silenzia traguardi",
+    snooze_for: ["Un giorno", "Una settimana", "Per sempre"],
+    unsnooze_milestones: "Riattiva traguardi",
+    numerals: Numerals::Western,
+    hours: 24,
+};
+
+const NL: Strings = Strings {
+    since: "Sinds {date} is het",
+    until: "Aftellen tot {date}",
+    between: "Van {date} tot {end} is het",
+    age: "Geboren op {date}, je bent",
+    years: "JAAR",
+    months: "MAANDEN",
+    weeks: "WEKEN",
+    days: "DAGEN",
+    to_anniversary: "TOT JUBILEUM",
+    to_birthday: "TOT VERJAARDAG",
+    leap_days: "SCHRIKKELDAGEN",
+    hex: "HEX",
+    binary: "BINAIR",
+    timeline: "TIJDLIJN",
+    days_lower: "dagen",
+    year_words: ("jaar", "jaar"),
+    month_words: ("maand", "maanden"),
+    day_words: ("dag", "dagen"),
+    goal: "{percent}% van de weg naar {goal}",
+    or: "of",
+    month_of: "({days} d)",
+    calendar_months: "(kalender)",
+    this_term: "Deze periode",
+    week: "WEEK",
+    iso_since: "Sinds week {week} van {year}",
+    this_week: "DEZE WEEK",
+    this_year: "DIT JAAR",
+    this_month: "DEZE MAAND",
+    today: "VANDAAG",
+    full_weeks: "HELE WEKEN",
+    total_hours: "UUR",
+    total_minutes: "MINUTEN",
+    total_seconds: "SECONDEN",
+    thousands: ".",
+    hours_old: "{hours} uur oud op {date}",
+    lunar_cycles: "MAANCYCLI",
+    full_moons: "VOLLE MANEN",
+    year_from: "{year}, vanaf {date}",
+    day_of_year: "DAGNUMMER",
+    week_of_year: "WEEKNUMMER",
+    percent_of_year: "% VAN HET JAAR",
+    fiscal: "BJ{year}, sinds {date}",
+    day_of_fiscal: "DAG VAN HET BJ",
+    fiscal_years: "BOEKJAREN",
+    days_left: "DAGEN OVER",
+    days_to_start: "DAGEN TE GAAN",
+    days_since: "DAGEN SINDS",
+    days_until: "DAGEN TOT",
+    on_break: "VAKANTIE",
+    term_over: "AFGELOPEN",
+    days_in: "DAGEN BEZIG",
+    phases_done: "AFGEROND",
+    history: "Geschiedenis",
+    no_history: "Nog geen wijzigingen",
+    added: "toegevoegd",
+    removed: "verwijderd",
+    reset: "gereset",
+    snoozed: "gedempt",
+    renamed: "hernoemd",
+    year_complete: "Jaar {year} voltooid",
+    milestones: "MIJLPALEN",
+    coming_up: "Binnenkort",
+    in_days: "over {days}",
+    excluding: "(zonder {days})",
+    started_on: "Begonnen op een {day}",
+    weekday_names: [
+        "maandag",
+        "dinsdag",
+        "woensdag",
+        "donderdag",
+        "vrijdag",
+        "zaterdag",
+        "zondag",
+    ],
+    weekdays: [
+        "MAANDAGEN",
+        "DINSDAGEN",
+        "WOENSDAGEN",
+        "DONDERDAGEN",
+        "VRIJDAGEN",
+        "ZATERDAGEN",
+        "ZONDAGEN",
+    ],
+    digest: "Deze week",
+    no_digest: "Niets deze week",
+    shortcuts: "Sneltoetsen",
+    close_help: "Druk een toets om te sluiten",
+    escape_back: "Esc om terug te gaan",
+    next_mode: "Volgende modus",
+    skip: "Tellen overslaan",
+    pause: "Pauze",
+    restart: "Opnieuw tellen",
+    next_intro: "Volgende intro",
+    copy_summary: "Tekst kopieren",
+    save_frames: "Frames opslaan",
+    quit: "Afsluiten",
+    about: "Over",
+    streaks: "Reeksen",
+    best_streak: "Beste {days} dagen",
+    average_streak: "Gemiddeld {days} dagen",
+    reset_streak: "Reeks resetten?",
+    confirm_reset: "Nogmaals Backspace om vandaag opnieuw te beginnen",
+    change_date: "Startdatum wijzigen",
+    pick_date: "Pijlen wijzigen, Enter telt vanaf",
+    edit_events: "Gebeurtenissen bewerken",
+    new_event: "Nieuwe gebeurtenis",
+    edit_keys: "Enter toont, N nieuw, F2 hernoemen, Del wist",
+    confirm_delete: "Nogmaals Delete om te wissen",
+    rename_event: "Gebeurtenis hernoemen",
+    type_name: "Typ de naam, dan Enter",
+    name_taken: "Die naam bestaat al",
+    add_date: "Pijlen wijzigen, Enter voegt toe",
+    copy: "Kopieren",
+    snooze_milestones: "Mijlpalen dempen",
+    snooze_for: ["Een dag", "Een week", "Voor altijd"],
+    unsnooze_milestones: "Mijlpalen weer aan",
+    numerals: Numerals::Western,
+    hours: 24,
+};
+
+const PT: Strings = Strings {
+    since: "Desde {date} passaram",
+    until: "Contagem para {date}",
+    between: "De {date} a {end} passaram",
+    age: "Nascido a {date}, tens",
+    years: "ANOS",
+    months: "MESES",
+    weeks: "SEMANAS",
+    days: "DIAS",
+    to_anniversary: "ATE ANIVERS.",
+    to_birthday: "ATE ANIVERS.",
+    leap_days: "DIAS BISSEXTOS",
+    hex: "HEXA",
+    binary: "BINARIO",
+    timeline: "CRONOLOGIA",
+    days_lower: "dias",
+    year_words: ("ano", "anos"),
+    month_words: ("mes", "meses"),
+    day_words: ("dia", "dias"),
+    goal: "{percent}% do caminho ate {goal}",
+    or: "ou",
+    month_of: "({days} d)",
+    calendar_months: "(calendario)",
+    this_term: "Este periodo",
+    week: "SEMANA",
+    iso_since: "Desde a semana {week} de {year}",
+    this_week: "ESTA SEMANA",
+    this_year: "ESTE ANO",
+    this_month: "ESTE MES",
+    today: "HOJE",
+    full_weeks: "SEM. COMPLETAS",
+    total_hours: "HORAS",
+    total_minutes: "MINUTOS",
+    total_seconds: "SEGUNDOS",
+    thousands: " ",
+    hours_old: "{hours} horas de vida a {date}",
+    lunar_cycles: "CICLOS LUNARES",
+    full_moons: "LUAS CHEIAS",
+    year_from: "{year}, desde {date}",
+    day_of_year: "DIA DO ANO",
+    week_of_year: "SEMANA DO ANO",
+    percent_of_year: "% DO ANO",
+    fiscal: "AF{year}, desde {date}",
+    day_of_fiscal: "DIA DO AF",
+    fiscal_years: "ANOS FISCAIS",
+    days_left: "DIAS RESTANTES",
+    days_to_start: "DIAS AO INICIO",
+    days_since: "DIAS DESDE",
+    days_until: "DIAS ATE",
+    on_break: "FERIAS",
+    term_over: "TERMINADO",
+    days_in: "DIAS PASSADOS",
+    phases_done: "CONCLUIDO",
+    history: "Historico",
+    no_history: "Sem alteracoes",
+    added: "adicionado",
+    removed: "removido",
+    reset: "reiniciado",
+    snoozed: "silenciado",
+    renamed: "renomeado",
+    year_complete: "Ano {year} concluido",
+    milestones: "MARCOS",
+    coming_up: "Em breve",
+    in_days: "em {days}",
+    excluding: "(excluindo {days})",
+    started_on: "Comecou numa {day}",
+    weekday_names: [
+        "segunda-feira",
+        "terca-feira",
+        "quarta-feira",
+        "quinta-feira",
+        "sexta-feira",
+        "sabado",
+        "domingo",
+    ],
+    weekdays: [
+        "SEGUNDAS", "TERCAS", "QUARTAS", "QUINTAS", "SEXTAS", "SABADOS", "DOMINGOS",
+    ],
+    digest: "Esta semana",
+    no_digest: "Nada esta semana",
+    shortcuts: "Atalhos",
+    close_help: "Qualquer tecla fecha",
+    escape_back: "Esc para voltar",
+    next_mode: "Modo seguinte",
+    skip: "Saltar contagem",
+    pause: "Pausa",
+    restart: "Contar de novo",
+    next_intro: "Intro seguinte",
+    copy_summary: "Copiar texto",
+    save_frames: "Guardar imagens",
+    quit: "Sair",
+    about: "Sobre",
+    streaks: "Series",
+    best_streak: "Recorde {days} dias",
+    average_streak: "Media {days} dias",
+    reset_streak: "Reiniciar a serie?",
+    confirm_reset: "Backspace outra vez para recomecar hoje",
+    change_date: "Mudar data de inicio",
+    pick_date: "Setas mudam, Enter conta desde ai",
+    edit_events: "Editar eventos",
+    new_event: "Novo evento",
+    edit_keys: "Enter mostra, N novo, F2 renomear, Del apaga",
+    confirm_delete: "Delete outra vez para apagar",
+    rename_event: "Renomear evento",
+    type_name: "Escreva o nome e Enter",
+    name_taken: "Esse nome ja existe",
+    add_date: "Setas mudam, Enter adiciona",
+    copy: "Copiar",
+    snooze_milestones: "Silenciar marcos",
+    snooze_for: ["Um dia", "Uma semana", "Para sempre"],
+    unsnooze_milestones: "Reativar marcos",
+    numerals: Numerals::Western,
+    hours: 24,
+};
+
+/// Returns the strings for `lang` (a code from [LANGUAGES]), falling back to English
+pub fn strings(lang: &str) -> &'static Strings {
+    match lang {
+        "de" => &DE,
+        "fr" => &FR,
+        "it" => &IT,
+        "nl" => &NL,
+        "pt" => &PT,
+        _ => &EN,
+    }
+}
+
+/// strftime format for times of day in 12 or 24 hour time, i.e. `3:42 PM` or `15:42`
+pub fn time_format(hours: u8) -> &'static str {
+    if hours == 12 {
+        "%-I:%M %p"
+    } else {
+        "%H:%M"
+    }
+}
+
+/// `date_format` without the year, for dates close to today in the digest and history,
+/// i.e. `%m/%d` from `%m/%d/%Y`, the separator next to the year goes with it
+pub fn day_month_format(date_format: &str) -> String {
+    let expanded = date_format
+        .replace("%F", "%Y-%m-%d")
+        .replace("%D", "%m/%d/%y")
+        .replace("%x", "%m/%d/%y");
+    //specifiers (with any padding flag) and the text between them
+    let mut parts: Vec<String> = vec![];
+    let mut chars = expanded.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char == '%' {
+            let mut spec = String::from('%');
+            while let Some(flag) = chars.next_if(|next| "-_0^#:".contains(*next)) {
+                spec.push(flag);
+            }
+            spec.extend(chars.next());
+            parts.push(spec);
+        } else {
+            match parts.last_mut() {
+                Some(text) if !text.starts_with('%') => text.push(char),
+                _ => parts.push(char.to_string()),
+            }
+        }
+    }
+    let is_year =
+        |part: &String| part.starts_with('%') && part.ends_with(['Y', 'y', 'C', 'G', 'g']);
+    let is_text = |part: &String| !part.starts_with('%');
+    while let Some(idx) = parts.iter().position(is_year) {
+        if idx > 0 && is_text(&parts[idx - 1]) {
+            parts.drain(idx - 1..=idx);
+        } else if parts.get(idx + 1).is_some_and(is_text) {
+            parts.drain(idx..=idx + 1);
+        } else {
+            parts.remove(idx);
+        }
+    }
+    parts.concat()
+}
+
+/// Reads the language from the standard locale env vars, i.e. `de_DE.UTF-8` becomes `de`
+pub fn detect_lang() -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|key| env::var(key).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .split(['_', '.', '-'])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase()
+        })
+        .filter(|lang| LANGUAGES.contains(&lang.as_str()))
+        .unwrap_or_else(|| LANGUAGES[0].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grouping_digits() {
+        assert_eq!(group_digits(1234567, ","), "1,234,567");
+        assert_eq!(group_digits(123, ","), "123");
+        assert_eq!(group_digits(-1234, "."), "-1.234");
+        assert_eq!(group_digits(12345, ""), "12345");
+    }
+
+    #[test]
+    fn note_for_paused_days() {
+        assert_eq!(strings("en").excluding(8), "(excluding 8 days)");
+        assert_eq!(strings("en").excluding(1), "(excluding 1 day)");
+    }
+
+    #[test]
+    fn day_and_month_without_the_year() {
+        for (format, expected) in [
+            ("%d/%m/%Y", "%d/%m"),
+            ("%m/%d/%Y", "%m/%d"),
+            ("%Y-%m-%d", "%m-%d"),
+            ("%d.%m.%Y", "%d.%m"),
+            ("%-d %B %Y", "%-d %B"),
+            ("%B %-d, %Y", "%B %-d"),
+            ("%F", "%m-%d"),
+            ("%D", "%m/%d"),
+            ("%d/%m", "%d/%m"),
+            ("%y%m%d", "%m%d"),
+        ] {
+            assert_eq!(day_month_format(format), expected, "{format}");
+        }
+    }
+}
@@ -0,0 +1,948 @@
+//! Text for the command line, only used by the app
+//!
+//! The English help is written with the args in `cli.rs`, the other languages replace it by
+//! path, i.e. `serve --port` for `--port` on `countup serve`. Terminals can show accents so,
+//! unlike [super::Strings], these use them
+
+/// Headings, errors and help for the command line
+#[derive(Debug)]
+pub struct CliStrings {
+    pub usage: &'static str,
+    pub arguments: &'static str,
+    pub options: &'static str,
+    pub commands: &'static str,
+    pub print_help: &'static str,
+    pub print_version: &'static str,
+    /// The generated `help` subcommand
+    pub help_subcommand: &'static str,
+    pub error: &'static str,
+    pub more_info: &'static str,
+    /// `{value}` and `{arg}` are replaced
+    pub invalid_value: &'static str,
+    pub possible_values: &'static str,
+    /// `{arg}` is replaced
+    pub unexpected_argument: &'static str,
+    pub missing_required: &'static str,
+    /// `{arg}` and `{other}` are replaced
+    pub conflict: &'static str,
+    /// `{name}` is replaced
+    pub unknown_subcommand: &'static str,
+    pub need_date: &'static str,
+    /// `{date}` is replaced
+    pub invalid_date: &'static str,
+    /// `{format}` is replaced
+    pub invalid_date_format: &'static str,
+    /// `{to}` and `{from}` are replaced
+    pub to_before_from: &'static str,
+    pub no_events: &'static str,
+    /// `{name}` is replaced
+    pub no_event: &'static str,
+    /// `{name}` is replaced
+    pub event_exists: &'static str,
+    pub needs_daemon: &'static str,
+    pub history_needs_daemon: &'static str,
+    /// Help for each arg and about for each subcommand by path, empty for English
+    ///
+    /// Subcommands are keyed by their path, i.e. `token add`, and args by the path and
+    /// `--long` or `NAME`, i.e. `serve --port`. If missing the arg alone is tried, so args
+    /// shared by subcommands only need to be given once
+    pub help: &'static [(&'static str, &'static str)],
+}
+
+impl CliStrings {
+    /// Help or about text for `path`
+    pub fn help(&self, path: &str) -> Option<&'static str> {
+        self.help
+            .iter()
+            .find(|(key, _)| *key == path)
+            .map(|(_, text)| *text)
+    }
+
+    pub fn invalid_value(&self, value: &str, arg: &str) -> String {
+        self.invalid_value
+            .replace("{value}", value)
+            .replace("{arg}", arg)
+    }
+
+    pub fn unexpected_argument(&self, arg: &str) -> String {
+        self.unexpected_argument.replace("{arg}", arg)
+    }
+
+    pub fn conflict(&self, arg: &str, other: &str) -> String {
+        self.conflict
+            .replace("{arg}", arg)
+            .replace("{other}", other)
+    }
+
+    pub fn unknown_subcommand(&self, name: &str) -> String {
+        self.unknown_subcommand.replace("{name}", name)
+    }
+
+    pub fn invalid_date(&self, date: &str) -> String {
+        self.invalid_date.replace("{date}", date)
+    }
+
+    pub fn invalid_date_format(&self, format: &str) -> String {
+        self.invalid_date_format.replace("{format}", format)
+    }
+
+    pub fn to_before_from(&self, to: &str, from: &str) -> String {
+        self.to_before_from
+            .replace("{to}", to)
+            .replace("{from}", from)
+    }
+
+    pub fn no_event(&self, name: &str) -> String {
+        self.no_event.replace("{name}", name)
+    }
+
+    pub fn event_exists(&self, name: &str) -> String {
+        self.event_exists.replace("{name}", name)
+    }
+}
+
+/// Returns the command line text for `lang` (a code from [super::LANGUAGES]), falling back
+/// to English
+pub fn cli_strings(lang: &str) -> &'static CliStrings {
+    match lang {
+        "de" => &DE,
+        "fr" => &FR,
+        "it" => &IT,
+        "nl" => &NL,
+        "pt" => &PT,
+        _ => &EN,
+    }
+}
+
+const EN: CliStrings = CliStrings {
+    usage: "Usage",
+    arguments: "Arguments",
+    options: "Options",
+    commands: "Commands",
+    print_help: "Print help",
+    print_version: "Print version",
+    help_subcommand: "Print this message or the help of the given subcommand(s)",
+    error: "error",
+    more_info: "For more information, try '--help'.",
+    invalid_value: "invalid value '{value}' for '{arg}'",
+    possible_values: "possible values",
+    unexpected_argument: "unexpected argument '{arg}' found",
+    missing_required: "the following required arguments were not provided",
+    conflict: "the argument '{arg}' cannot be used with '{other}'",
+    unknown_subcommand: "unrecognized subcommand '{name}'",
+    need_date: "Pass a date with --date or the name of an event",
+    invalid_date: "Invalid date {date}, use yyyy-mm-dd, yyyy/mm/dd, dd-mm-yyyy, yyyymmdd or a unix timestamp, tried in that order",
+    invalid_date_format: "Invalid date format: {format}",
+    to_before_from: "--to {to} is before --from {from}",
+    no_events: "No events in the config, add one with countup add",
+    no_event: "No event named {name}",
+    event_exists: "{name} already exists, remove it first",
+    needs_daemon: "Pass the daemon's address with --connect",
+    history_needs_daemon: "History is kept by the daemon, pass its address with --connect",
+    help: &[],
+};
+
+const DE: CliStrings = CliStrings {
+    usage: "Verwendung",
+    arguments: "Argumente",
+    options: "Optionen",
+    commands: "Befehle",
+    print_help: "Hilfe anzeigen",
+    print_version: "Version anzeigen",
+    help_subcommand: "Diese Hilfe oder die Hilfe der angegebenen Befehle anzeigen",
+    error: "Fehler",
+    more_info: "Weitere Informationen mit '--help'.",
+    invalid_value: "ungültiger Wert '{value}' für '{arg}'",
+    possible_values: "mögliche Werte",
+    unexpected_argument: "unerwartetes Argument '{arg}'",
+    missing_required: "diese Argumente fehlen",
+    conflict: "'{arg}' kann nicht zusammen mit '{other}' verwendet werden",
+    unknown_subcommand: "unbekannter Befehl '{name}'",
+    need_date: "Gib ein Datum mit --date oder den Namen eines Ereignisses an",
+    invalid_date: "Ungültiges Datum {date}, nutze jjjj-mm-tt, jjjj/mm/tt, tt-mm-jjjj, jjjjmmtt oder einen Unix-Zeitstempel, in dieser Reihenfolge versucht",
+    invalid_date_format: "Ungültiges Datumsformat: {format}",
+    to_before_from: "--to {to} liegt vor --from {from}",
+    no_events: "Keine Ereignisse in der Konfiguration, füge eins mit countup add hinzu",
+    no_event: "Kein Ereignis namens {name}",
+    event_exists: "{name} gibt es schon, entferne es zuerst",
+    needs_daemon: "Gib die Adresse des Daemons mit --connect an",
+    history_needs_daemon: "Der Verlauf liegt beim Daemon, gib seine Adresse mit --connect an",
+    help: &[
+        ("", "Zählt ab einem Datum hoch"),
+        ("--event", "Name eines Ereignisses aus der Konfigurationsdatei"),
+        ("--config", "Pfad zur Konfigurationsdatei, standardmäßig config.json im Einstellungsordner der App"),
+        ("--data-dir", "Ordner für Einstellungen, Postausgang und Geheimnisse (und die Standardkonfiguration), standardmäßig der Einstellungsordner der App"),
+        ("--prefs-dir", "Ordner für den gemerkten Modus, das Ereignis und das Datum, z.B. neben der Programmdatei für eine portable Installation, standardmäßig der Datenordner. Die Fensterposition wird damit nicht gemerkt"),
+        ("--connect", "Die Ereignisse eines countup-Daemons verwenden, z.B. 192.168.1.20:7878"),
+        ("--token", "Token für den Daemon, standardmäßig das Geheimnis `token`"),
+        ("--verbose", "Protokollieren, was eingelesen und berechnet wurde, das Laden und Speichern der Einstellungen und die Bildraten, nach stderr"),
+        ("--log-file", "Das Protokoll in diese Datei statt nach stderr schreiben, impliziert --verbose"),
+        ("--date", "Datum, ab dem gezählt wird (oder bis zu dem, wenn es in der Zukunft liegt), Format jjjj-mm-tt, - liest es von stdin"),
+        ("--date-file", "Datei, aus der das Datum gelesen wird, z.B. von einem Skript geschrieben"),
+        ("--windows", "Für jedes Ereignis aus der Konfiguration ein Fenster öffnen, jedes merkt sich seine eigene Position"),
+        ("--from", "Die Zeit zwischen diesem Datum und --to zeigen, ohne mit den Tagen weiterzuzählen"),
+        ("--to", "Ende der mit --from gezeigten Zeit, standardmäßig heute"),
+        ("--now", "So zählen, als wäre es dieses Datum oder diese RFC-3339-Zeit wie 2024-06-01T09:30:00Z statt jetzt, für Screenshots, Demos und Tests"),
+        ("--lang", "Sprache der Oberfläche, standardmäßig die des Systems"),
+        ("--date-format", "Format des Datums in der Kopfzeile, als strftime-Muster"),
+        ("--template", "Text der Kopfzeile, unterstützt {years}, {months} und {days} wie im Split-Modus, {total}, {label} und die für --statusbar-template"),
+        ("--term", "Im Halbjahresmodus starten, braucht ein Halbjahr in der Konfigurationsdatei"),
+        ("--anim-speed", "Sekunden für das Hochzählen jedes Jahres beim Öffnen, 0 überspringt das Zählen"),
+        ("--no-animation", "Die Zahl sofort anzeigen, wie --anim-speed 0"),
+        ("--reduced-motion", "Das Hochzählen und das Aufleuchten beim Tageswechsel überspringen und den Hintergrund stillhalten, standardmäßig an, wenn das System weniger Bewegung wünscht"),
+        ("--full-motion", "Wie gewohnt animieren, auch wenn das System oder die Konfiguration weniger Bewegung wünscht"),
+        ("--easing", "Wie das Zählen beschleunigt und abbremst, standardmäßig linear"),
+        ("--intro", "Wie die Zahl beim Öffnen erscheint, standardmäßig count-up"),
+        ("--sound", "Beim Hochzählen ticken und am Ende oder bei einem Meilenstein läuten"),
+        ("--mute", "Töne ausschalten, auch wenn sie in der Konfiguration an sind"),
+        ("--font", "Bitmap-Schrift für die Zahlen, ein PNG der druckbaren ASCII-Zeichen in Reihen zu 16"),
+        ("--background", "Bild hinter der Zählung, ein PNG, passend zum Fenster skaliert und abgedunkelt, damit der Text lesbar bleibt"),
+        ("--background-style", "Was hinter der Zählung gezeichnet wird, ein langsam wechselnder Verlauf oder treibende Sterne statt einfarbig, Standard ist flat"),
+        ("--screensaver", "Den Monitor schwarz füllen und die Zählung langsam darüber springen lassen, damit sich ein OLED-Bildschirm, der den Zähler dauerhaft zeigt, nicht einbrennt"),
+        ("--desktop", "Das Fenster auf den Desktop legen, unter die anderen Fenster und ohne Titelleiste, mit Klicks, die hindurchgehen, wie conky, nur X11 und Windows"),
+        ("--text-scale", "Die Zahlen so viel mal größer zeichnen, das Fenster wächst mit"),
+        ("--size", "Fenstergröße, z. B. 540x180, die Zahlen werden so groß wie möglich gezeichnet, außer --text-scale ist gesetzt"),
+        ("--scale", "Das Fenster so viel mal größer machen, zusätzlich zur Skalierung des Bildschirms, für Bildschirme mit hoher DPI"),
+        ("--min-text-size", "Das Fenster so groß machen, dass der kleinste Text mindestens so viele Pixel hoch ist, zusätzlich zur Skalierung des Bildschirms"),
+        ("--position", "Das Fenster hier statt an der letzten Stelle öffnen, x,y in Pixeln von oben links auf dem Monitor oder top-left, top-right, bottom-left, bottom-right oder center"),
+        ("--monitor", "Das Fenster auf diesem Monitor statt an der letzten Stelle öffnen, aus `countup monitors`, zentriert, außer --position ist gesetzt"),
+        ("--start-hidden", "Das Fenster erst öffnen, wenn es mit der D-Bus-Methode Show gezeigt wird, zum Starten bei der Anmeldung, nur unter Linux"),
+        ("--new-instance", "Ein weiteres Fenster öffnen, auch wenn countup schon läuft, statt jenes nach vorne zu holen"),
+        ("--numerals", "Ziffern für die großen Zahlen, standardmäßig die der Sprache"),
+        ("--number-format", "Trennzeichen zwischen je drei Ziffern in den Zahlen und im Text, locale für das der Sprache, standardmäßig plain"),
+        ("--clock", "Datum und Uhrzeit in der Ecke zeigen, für einen immer laufenden Zähler"),
+        ("--calendar", "Zeigen, wie weit Jahr, Monat und Tag fortgeschritten sind, ohne ab einem Datum zu zählen"),
+        ("--month-length", "Tage in einem Monat für die Monate im Differenz-Modus, oder Kalendermonate, standardmäßig 28"),
+        ("--minimal", "Nur die Zahlen zeigen, ohne Kopfzeile und Beschriftungen, in einem kleineren Fenster"),
+        ("--high-contrast", "Auf Schwarz mit weißem Text zeichnen, oder der Farbe des Ereignisses, wenn sie noch gut lesbar ist, und ohne Hintergrundbild, Verlauf oder Sterne"),
+        ("--hours", "12- oder 24-Stunden-Zeit, für --clock und den Verlaufsmodus, standardmäßig die der Sprache"),
+        ("--capture-frames", "Bilder, die zum Speichern als PNG mit Strg+F oder bei einem Absturz behalten werden, 0 schaltet es aus, standardmäßig 120"),
+        ("--slideshow", "Jedes Ereignis aus der Konfiguration so viele Sekunden nacheinander zeigen, mit erneutem Hochzählen"),
+        ("--birthday", "Ein Alter ab diesem Geburtsdatum zeigen, mit den Tagen bis zum nächsten Geburtstag"),
+        ("--born-at", "Geburtszeit für --birthday als HH:MM, um das Alter auf die Minute und die Stunden-Meilensteine zu zeigen"),
+        ("--inclusive", "Den Starttag selbst mitzählen, damit er Tag 1 statt 0 ist"),
+        ("--exclusive", "Den Starttag nicht mitzählen, der Standard, außer in der Konfiguration geändert"),
+        ("--goal", "Einen Fortschrittsbalken zu einer Anzahl Tage oder einem Datum unter der Zählung zeigen"),
+        ("--fiscal-year", "Den Geschäftsjahr-Modus hinzufügen, für ein Geschäftsjahr ab dem 1. dieses Monats, z. B. 4 für April"),
+        ("--patterns", "Diese Tageszahlen auch als Meilensteine zählen, aus palindrome, repdigit, power-of-two und pi, z. B. palindrome,pi"),
+        ("--pair", "Die Tage bis zu diesem Ereignis oder Datum neben der Zählung zeigen, im Dual-Modus"),
+        ("--anniversary", "Im geteilten Modus auch die Tage bis zum nächsten Jahrestag des Datums zeigen"),
+        ("--leap-days", "Im geteilten Modus die 29. Februare seit dem Datum statt der Tage bis zum Jahrestag zeigen"),
+        ("--roman", "Einen Modus mit den Jahren, Monaten und Tagen in römischen Zahlen hinzufügen, nur zum Spaß"),
+        ("--forget", "Das gemerkte Datum und Ereignis vergessen"),
+        ("--headless", "Die Zahl ausgeben statt ein Fenster zu öffnen"),
+        ("--json", "Die Zahl als JSON ausgeben, schließt --headless ein"),
+        ("--statusbar", "Eine Zeile für Statusleisten ausgeben, schließt --headless ein"),
+        ("--statusbar-template", "Text für --statusbar, unterstützt {days}, {weeks}, {months}, {years}, {until}, {start}, {event} und {milestone}"),
+        ("--watch", "Weiterlaufen und die Zahl neu ausgeben, schließt --headless ein"),
+        ("--interval", "Sekunden zwischen den Aktualisierungen mit --watch"),
+        ("show", "Den Zähler öffnen, das ist der Standard"),
+        ("NAME", "Name des Ereignisses"),
+        ("show NAME", "Name eines Ereignisses, ab dem gezählt wird"),
+        ("card", "Die Zählung als großes Bild speichern, z.B. zum Teilen an einem Jahrestag"),
+        ("card NAME", "Name eines Ereignisses, ab dem gezählt wird"),
+        ("card --out", "Zu schreibende PNG-Datei"),
+        ("card --label", "Text über der Zählung, standardmäßig der Name des Ereignisses"),
+        ("check", "Mit 0 beenden, wenn heute ein Meilenstein ist, sonst mit 1, für Cronjobs und Skripte"),
+        ("check NAME", "Name eines Ereignisses, ab dem gezählt wird"),
+        ("check --milestone", "Nur diese Anzahl Tage, z.B. 1000, statt jedes Meilensteins"),
+        ("stopwatch", "Ab jetzt sekundengenau hochzählen, beim erneuten Öffnen ab demselben Zeitpunkt weiter"),
+        ("stopwatch --restart", "Wieder ab jetzt beginnen"),
+        ("add", "Ein Ereignis in der Konfigurationsdatei speichern"),
+        ("add DATE", "Datum des Ereignisses, Format jjjj-mm-tt"),
+        ("add --timezone", "Zeitzone, in der die Tage weiterzählen, z.B. Asia/Tokyo, standardmäßig UTC"),
+        ("list", "Die Ereignisse in der Konfigurationsdatei auflisten"),
+        ("remove", "Ein Ereignis aus der Konfigurationsdatei entfernen"),
+        ("reset", "Das Datum eines Ereignisses auf heute setzen"),
+        ("snooze", "Die Meilensteine eines Ereignisses eine Weile nicht läuten und keine Hooks ausführen lassen"),
+        ("snooze LENGTH", "Wie lange, ab heute, off hebt es auf"),
+        ("history", "Zeigen, wer die Ereignisse auf dem Daemon geändert hat, neueste zuerst"),
+        ("history NAME", "Nur Änderungen an diesem Ereignis zeigen"),
+        ("history --limit", "Anzahl der angezeigten Änderungen"),
+        ("conflicts", "Änderungen prüfen, die mit Änderungen auf dem Daemon kollidiert sind"),
+        ("conflicts keep-mine", "Deine Änderung übernehmen und die des Daemons ersetzen"),
+        ("conflicts keep-theirs", "Deine Änderung verwerfen"),
+        ("NUMBER", "Nummer des Konflikts, aus `countup conflicts`"),
+        ("daemon", "Die Ereignisse der Konfigurationsdatei mit anderen countup-Instanzen teilen"),
+        ("daemon --listen", "Adresse, auf der gelauscht wird, 0.0.0.0:7878 für andere Rechner"),
+        ("serve", "Die Zahl als Webseite und JSON anzeigen"),
+        ("serve NAME", "Name eines Ereignisses, ab dem gezählt wird"),
+        ("serve --date", "Datum, ab dem gezählt wird, Format jjjj-mm-tt, - liest es von stdin"),
+        ("serve --date-file", "Datei, aus der das Datum gelesen wird"),
+        ("serve --lang", "Sprache der Seite, standardmäßig die des Systems"),
+        ("serve --date-format", "Format des Datums, als strftime-Muster"),
+        ("serve --port", "Port, auf dem gelauscht wird"),
+        ("serve --bind", "Adresse, auf der gelauscht wird"),
+        ("import", "Ereignisse aus einer CSV-Datei mit Zeilen name,datum, einem ICS-Kalender oder dem CSV- oder JSON-Export einer Habit-App hinzufügen"),
+        ("import FILE", "Zu importierende CSV-, .ics- oder .json-Datei"),
+        ("import --filter", "Nur Ereignisse importieren, deren Name zu diesem Muster passt, * passt auf alles"),
+        ("export", "Die kommenden Meilensteine der Ereignisse in eine Datei schreiben, für eine Kalender-App"),
+        ("export --milestones", "Die Meilensteine und Jahrestage im nächsten Jahr"),
+        ("export --ics", "Zu schreibende ICS-Kalenderdatei, - gibt sie aus"),
+        ("auth", "Geheimnisse im Schlüsselbund des Systems verwalten"),
+        ("auth set", "Ein Geheimnis speichern, von stdin gelesen"),
+        ("auth set NAME", "Name des Geheimnisses, z.B. proxy"),
+        ("auth clear", "Ein Geheimnis löschen"),
+        ("auth clear NAME", "Name des Geheimnisses"),
+        ("monitors", "Die Monitore auflisten, mit den Nummern für --monitor"),
+        ("monitors --json", "Als JSON ausgeben"),
+        ("autostart", "countup beim Anmelden starten oder nicht mehr starten"),
+        ("autostart enable", "Den Anmeldeeintrag hinzufügen und einen früheren ersetzen"),
+        ("autostart enable ARGS", "Argumente, mit denen countup startet, nach --, z.B. -- --event Sober"),
+        ("autostart disable", "Den Anmeldeeintrag entfernen"),
+        ("selftest", "Datumsrechnung, Konfiguration, gespeicherte Dateien und Zeichnen prüfen, für Einrichtungsskripte"),
+        ("update", "Diese Programmdatei durch die neueste Version ersetzen, falls sie neuer ist"),
+        ("update --check-only", "Nur sagen, ob es eine neuere Version gibt"),
+        ("token", "Tokens für den Zugriff auf Daemon und Server dieses Rechners verwalten"),
+        ("token add", "Ein Token erstellen und eines mit gleichem Namen ersetzen"),
+        ("token add NAME", "Name des Tokens, z.B. lobby-display"),
+        ("token add --role", "Was das Token darf, viewer können nur lesen, editor auch Ereignisse hinzufügen und zurücksetzen, admin darf alles"),
+        ("token list", "Tokens auflisten"),
+        ("token remove", "Ein Token löschen"),
+        ("token remove NAME", "Name des Tokens"),
+    ],
+};
+
+const FR: CliStrings = CliStrings {
+    usage: "Utilisation",
+    arguments: "Arguments",
+    options: "Options",
+    commands: "Commandes",
+    print_help: "Afficher l'aide",
+    print_version: "Afficher la version",
+    help_subcommand: "Afficher ce message ou l'aide des commandes données",
+    error: "erreur",
+    more_info: "Pour plus d'informations, essayez '--help'.",
+    invalid_value: "valeur '{value}' invalide pour '{arg}'",
+    possible_values: "valeurs possibles",
+    unexpected_argument: "argument inattendu '{arg}'",
+    missing_required: "ces arguments obligatoires sont manquants",
+    conflict: "'{arg}' ne peut pas être utilisé avec '{other}'",
+    unknown_subcommand: "commande inconnue '{name}'",
+    need_date: "Donnez une date avec --date ou le nom d'un événement",
+    invalid_date: "Date invalide {date}, utilisez aaaa-mm-jj, aaaa/mm/jj, jj-mm-aaaa, aaaammjj ou un horodatage unix, essayés dans cet ordre",
+    invalid_date_format: "Format de date invalide : {format}",
+    to_before_from: "--to {to} est avant --from {from}",
+    no_events: "Aucun événement dans la configuration, ajoutez-en un avec countup add",
+    no_event: "Aucun événement nommé {name}",
+    event_exists: "{name} existe déjà, supprimez-le d'abord",
+    needs_daemon: "Donnez l'adresse du démon avec --connect",
+    history_needs_daemon: "L'historique est gardé par le démon, donnez son adresse avec --connect",
+    help: &[
+        ("", "Compte à partir d'une date"),
+        ("--event", "Nom d'un événement du fichier de configuration"),
+        ("--config", "Chemin du fichier de configuration, par défaut config.json dans le dossier de préférences de l'app"),
+        ("--data-dir", "Dossier des préférences, de la boîte d'envoi et des secrets (et de la configuration par défaut), par défaut le dossier de préférences de l'app"),
+        ("--prefs-dir", "Dossier du mode, de l'événement et de la date mémorisés, par ex. à côté du binaire pour une installation portable, par défaut le dossier de données. La position de la fenêtre n'est alors pas mémorisée"),
+        ("--connect", "Utiliser les événements d'un démon countup, par ex. 192.168.1.20:7878"),
+        ("--token", "Jeton pour le démon, par défaut le secret `token`"),
+        ("--verbose", "Journaliser ce qui a été lu et calculé, le chargement et l'enregistrement des préférences et les fréquences d'images sur stderr"),
+        ("--log-file", "Écrire le journal dans ce fichier au lieu de stderr, implique --verbose"),
+        ("--date", "Date à partir de laquelle compter (ou jusqu'à laquelle, si elle est future), format aaaa-mm-jj, - la lit depuis stdin"),
+        ("--date-file", "Fichier d'où lire la date, par ex. écrit par un script"),
+        ("--windows", "Ouvrir une fenêtre par événement de la configuration, chacune retient sa propre position"),
+        ("--from", "Afficher le temps entre cette date et --to, sans avancer avec les jours"),
+        ("--to", "Fin du temps affiché avec --from, aujourd'hui par défaut"),
+        ("--now", "Compter comme si c'était cette date, ou cette heure RFC 3339 comme 2024-06-01T09:30:00Z, au lieu de maintenant, pour les captures, démos et tests"),
+        ("--lang", "Langue de l'interface, par défaut celle du système"),
+        ("--date-format", "Format de la date dans l'en-tête, comme motif strftime"),
+        ("--template", "Texte de l'en-tête, accepte {years}, {months} et {days} comme en mode split, {total}, {label} et ceux de --statusbar-template"),
+        ("--term", "Démarrer en mode trimestre, nécessite un trimestre dans le fichier de configuration"),
+        ("--anim-speed", "Secondes pour compter chaque année à l'ouverture, 0 pour ne pas compter"),
+        ("--no-animation", "Afficher le nombre directement, comme --anim-speed 0"),
+        ("--reduced-motion", "Sauter le comptage et le flash au changement de jour, et garder le fond immobile, activé par défaut si le système demande moins de mouvement"),
+        ("--full-motion", "Animer comme d'habitude, même si le système ou la configuration demande moins de mouvement"),
+        ("--easing", "Comment le comptage accélère et ralentit, par défaut linear"),
+        ("--intro", "Comment le nombre apparaît à l'ouverture, par défaut count-up"),
+        ("--sound", "Faire tic-tac en comptant et sonner à la fin ou à une étape"),
+        ("--mute", "Couper les sons, même s'ils sont activés dans la configuration"),
+        ("--font", "Police bitmap pour les nombres, un PNG des caractères ASCII imprimables en lignes de 16"),
+        ("--background", "Image derrière le compte, un PNG mis à l'échelle de la fenêtre et assombri pour que le texte reste lisible"),
+        ("--background-style", "Ce qui est dessiné derrière le compte, un dégradé qui change lentement ou des étoiles qui dérivent au lieu d'un fond uni, flat par défaut"),
+        ("--screensaver", "Remplir l'écran de noir et faire rebondir lentement le compte dessus, pour qu'un écran OLED laissé sur le compteur ne marque pas"),
+        ("--desktop", "Mettre la fenêtre sur le bureau, sous les autres fenêtres et sans barre de titre, les clics passant au travers, comme conky, X11 et Windows uniquement"),
+        ("--text-scale", "Dessiner les nombres ce nombre de fois plus grands, la fenêtre s'agrandit"),
+        ("--size", "Taille de la fenêtre, par ex. 540x180, les nombres sont dessinés aussi grands que possible sauf si --text-scale est défini"),
+        ("--scale", "Agrandir la fenêtre ce nombre de fois, en plus de la mise à l'échelle de l'écran, pour les écrans à haute densité"),
+        ("--min-text-size", "Agrandir la fenêtre pour que le plus petit texte fasse au moins ce nombre de pixels de haut, en plus de la mise à l'échelle de l'écran"),
+        ("--position", "Ouvrir la fenêtre ici plutôt qu'à sa dernière place, x,y en pixels depuis le coin supérieur gauche de l'écran ou top-left, top-right, bottom-left, bottom-right ou center"),
+        ("--monitor", "Ouvrir la fenêtre sur cet écran plutôt qu'à sa dernière place, d'après `countup monitors`, centrée sauf si --position est défini"),
+        ("--start-hidden", "Ne pas ouvrir la fenêtre avant qu'elle soit affichée avec la méthode D-Bus Show, pour un lancement à la connexion, Linux uniquement"),
+        ("--new-instance", "Ouvrir une autre fenêtre même si countup est déjà lancé, au lieu de mettre celle-ci au premier plan"),
+        ("--numerals", "Chiffres des grands nombres, par défaut ceux de la langue"),
+        ("--number-format", "Séparateur entre chaque groupe de trois chiffres dans les nombres et le texte, locale pour celui de la langue, plain par défaut"),
+        ("--clock", "Afficher la date et l'heure dans le coin, pour un compteur toujours allumé"),
+        ("--calendar", "Afficher l'avancement de l'année, du mois et du jour, sans compter depuis une date"),
+        ("--month-length", "Jours dans un mois pour les mois du mode différence, ou mois du calendrier, 28 par défaut"),
+        ("--minimal", "N'afficher que les nombres, sans en-tête ni libellés, dans une fenêtre plus petite"),
+        ("--high-contrast", "Dessiner sur du noir avec du texte blanc, ou la couleur de l'événement si elle reste facile à lire, sans image de fond, dégradé ni étoiles"),
+        ("--hours", "Heures sur 12 ou 24 heures, pour --clock et le mode historique, par défaut celles de la langue"),
+        ("--capture-frames", "Images gardées pour les enregistrer en PNG avec Ctrl+F ou en cas de plantage, 0 pour désactiver, 120 par défaut"),
+        ("--slideshow", "Afficher chaque événement de la configuration tour à tour pendant ce nombre de secondes, en rejouant le décompte"),
+        ("--birthday", "Afficher un âge depuis cette date de naissance, avec les jours avant le prochain anniversaire"),
+        ("--born-at", "Heure de naissance pour --birthday au format HH:MM, pour afficher l'âge à la minute et les étapes en heures"),
+        ("--inclusive", "Compter le jour de départ lui-même, pour qu'il soit le jour 1 plutôt que 0"),
+        ("--exclusive", "Ne pas compter le jour de départ, par défaut sauf si changé dans la configuration"),
+        ("--goal", "Afficher une barre de progression vers un nombre de jours ou une date sous le compte"),
+        ("--fiscal-year", "Ajouter le mode exercice, pour un exercice commençant le 1er de ce mois, par ex. 4 pour avril"),
+        ("--patterns", "Compter aussi ces nombres de jours comme des étapes, parmi palindrome, repdigit, power-of-two et pi, par ex. palindrome,pi"),
+        ("--pair", "Afficher les jours jusqu'à cet événement ou cette date à côté du compte, en mode double"),
+        ("--anniversary", "Afficher aussi les jours avant le prochain anniversaire de la date en mode détaillé"),
+        ("--leap-days", "Afficher les 29 février depuis la date en mode détaillé, au lieu des jours avant l'anniversaire"),
+        ("--roman", "Ajouter un mode avec les années, mois et jours en chiffres romains, juste pour le plaisir"),
+        ("--forget", "Oublier la date et l'événement mémorisés"),
+        ("--headless", "Afficher le nombre au lieu d'ouvrir une fenêtre"),
+        ("--json", "Afficher le nombre en JSON, implique --headless"),
+        ("--statusbar", "Afficher une seule ligne pour les barres d'état, implique --headless"),
+        ("--statusbar-template", "Texte pour --statusbar, accepte {days}, {weeks}, {months}, {years}, {until}, {start}, {event} et {milestone}"),
+        ("--watch", "Continuer et réafficher le nombre, implique --headless"),
+        ("--interval", "Secondes entre les mises à jour avec --watch"),
+        ("show", "Ouvrir le compteur, c'est le comportement par défaut"),
+        ("NAME", "Nom de l'événement"),
+        ("show NAME", "Nom d'un événement à partir duquel compter"),
+        ("card", "Enregistrer le compte comme une grande image, p. ex. à partager pour un anniversaire"),
+        ("card NAME", "Nom d'un événement à partir duquel compter"),
+        ("card --out", "Fichier PNG à écrire"),
+        ("card --label", "Texte au-dessus du compte, le nom de l'événement par défaut"),
+        ("check", "Quitter avec 0 si aujourd'hui est une étape et 1 sinon, pour les tâches cron et les scripts"),
+        ("check NAME", "Nom d'un événement à partir duquel compter"),
+        ("check --milestone", "Seulement ce nombre de jours, p. ex. 1000, plutôt que n'importe quelle étape"),
+        ("stopwatch", "Compter à la seconde à partir de maintenant, en reprenant au même moment à la réouverture"),
+        ("stopwatch --restart", "Recommencer à partir de maintenant"),
+        ("add", "Enregistrer un événement dans le fichier de configuration"),
+        ("add DATE", "Date de l'événement, format aaaa-mm-jj"),
+        ("add --timezone", "Fuseau horaire dans lequel les jours avancent, par ex. Asia/Tokyo, par défaut UTC"),
+        ("list", "Lister les événements du fichier de configuration"),
+        ("remove", "Supprimer un événement du fichier de configuration"),
+        ("reset", "Mettre la date d'un événement à aujourd'hui"),
+        ("snooze", "Empêcher les étapes d'un événement de sonner et de lancer les hooks pendant un temps"),
+        ("snooze LENGTH", "Pour combien de temps, à partir d'aujourd'hui, off pour annuler"),
+        ("history", "Montrer qui a modifié les événements sur le démon, les plus récents d'abord"),
+        ("history NAME", "Ne montrer que les modifications de cet événement"),
+        ("history --limit", "Nombre de modifications à montrer"),
+        ("conflicts", "Revoir les modifications en conflit avec celles du démon"),
+        ("conflicts keep-mine", "Appliquer votre modification, en remplaçant celle du démon"),
+        ("conflicts keep-theirs", "Abandonner votre modification"),
+        ("NUMBER", "Numéro du conflit, d'après `countup conflicts`"),
+        ("daemon", "Partager les événements du fichier de configuration avec d'autres instances de countup"),
+        ("daemon --listen", "Adresse d'écoute, utilisez 0.0.0.0:7878 pour les autres machines"),
+        ("serve", "Afficher le nombre en page web et en JSON"),
+        ("serve NAME", "Nom d'un événement à partir duquel compter"),
+        ("serve --date", "Date à partir de laquelle compter, format aaaa-mm-jj, - la lit depuis stdin"),
+        ("serve --date-file", "Fichier d'où lire la date"),
+        ("serve --lang", "Langue de la page, par défaut celle du système"),
+        ("serve --date-format", "Format de la date, comme motif strftime"),
+        ("serve --port", "Port d'écoute"),
+        ("serve --bind", "Adresse d'écoute"),
+        ("import", "Ajouter des événements depuis un fichier CSV de lignes nom,date, un calendrier ICS ou l'export CSV ou JSON d'une app d'habitudes"),
+        ("import FILE", "Fichier CSV, .ics ou .json à importer"),
+        ("import --filter", "N'importer que les événements dont le nom correspond à ce motif, * correspond à tout"),
+        ("export", "Écrire les prochains jalons des événements dans un fichier, pour une application de calendrier"),
+        ("export --milestones", "Les jalons et anniversaires de l'année à venir"),
+        ("export --ics", "Fichier de calendrier ICS à écrire, - l'affiche"),
+        ("auth", "Gérer les secrets du trousseau du système"),
+        ("auth set", "Enregistrer un secret, lu depuis stdin"),
+        ("auth set NAME", "Nom du secret, par ex. proxy"),
+        ("auth clear", "Supprimer un secret"),
+        ("auth clear NAME", "Nom du secret"),
+        ("monitors", "Lister les écrans, avec les numéros pour --monitor"),
+        ("monitors --json", "Les afficher en JSON"),
+        ("autostart", "Lancer countup à l'ouverture de session, ou ne plus le lancer"),
+        ("autostart enable", "Ajouter l'entrée de démarrage, en remplaçant une précédente"),
+        ("autostart enable ARGS", "Arguments avec lesquels lancer countup, après --, par ex. -- --event Sober"),
+        ("autostart disable", "Supprimer l'entrée de démarrage"),
+        ("selftest", "Vérifier le calcul des dates, la configuration, les fichiers enregistrés et le dessin, pour les scripts d'installation"),
+        ("update", "Remplacer ce binaire par la dernière version, si elle est plus récente"),
+        ("update --check-only", "Indiquer seulement s'il y a une version plus récente"),
+        ("token", "Gérer les jetons d'accès au démon et au serveur de cette machine"),
+        ("token add", "Créer un jeton, en remplaçant celui du même nom"),
+        ("token add NAME", "Nom du jeton, par ex. lobby-display"),
+        ("token add --role", "Ce que le jeton permet, viewer ne peut que lire, editor peut aussi ajouter et réinitialiser des événements, admin peut tout faire"),
+        ("token list", "Lister les jetons"),
+        ("token remove", "Supprimer un jeton"),
+        ("token remove NAME", "Nom du jeton"),
+    ],
+};
+
+const IT: CliStrings = CliStrings {
+    usage: "Uso",
+    arguments: "Argomenti",
+    options: "Opzioni",
+    commands: "Comandi",
+    print_help: "Mostra l'aiuto",
+    print_version: "Mostra la versione",
+    help_subcommand: "Mostra questo messaggio o l'aiuto dei comandi indicati",
+    error: "errore",
+    more_info: "Per maggiori informazioni, prova '--help'.",
+    invalid_value: "valore '{value}' non valido per '{arg}'",
+    possible_values: "valori possibili",
+    unexpected_argument: "argomento inatteso '{arg}'",
+    missing_required: "mancano questi argomenti obbligatori",
+    conflict: "'{arg}' non può essere usato con '{other}'",
+    unknown_subcommand: "comando sconosciuto '{name}'",
+    need_date: "Indica una data con --date o il nome di un evento",
+    invalid_date: "Data non valida {date}, usa aaaa-mm-gg, aaaa/mm/gg, gg-mm-aaaa, aaaammgg o un timestamp unix, provati in questo ordine",
+    invalid_date_format: "Formato data non valido: {format}",
+    to_before_from: "--to {to} è prima di --from {from}",
+    no_events: "Nessun evento nella configurazione, aggiungine uno con countup add",
+    no_event: "Nessun evento chiamato {name}",
+    event_exists: "{name} esiste già, rimuovilo prima",
+    needs_daemon: "Indica l'indirizzo del demone con --connect",
+    history_needs_daemon: "La cronologia è tenuta dal demone, indica il suo indirizzo con --connect",
+    help: &[
+        ("", "Conta a partire da una data"),
+        ("--event", "Nome di un evento nel file di configurazione"),
+        ("--config", "Percorso del file di configurazione, di default config.json nella cartella delle preferenze dell'app"),
+        ("--data-dir", "Cartella per preferenze, posta in uscita e segreti (e la configurazione di default), di default la cartella delle preferenze dell'app"),
+        ("--prefs-dir", "Cartella per la modalità, l'evento e la data ricordati, es. accanto al binario per un'installazione portatile, di default la cartella dei dati. Con questa la posizione della finestra non viene ricordata"),
+        ("--connect", "Usa gli eventi di un demone countup, es. 192.168.1.20:7878"),
+        ("--token", "Token per il demone, di default il segreto `token`"),
+        ("--verbose", "Registra su stderr cosa è stato letto e calcolato, il caricamento e il salvataggio delle preferenze e i frame rate"),
+        ("--log-file", "Scrivi il log in questo file invece che su stderr, implica --verbose"),
+        ("--date", "Data da cui contare (o fino a cui, se è nel futuro), formato aaaa-mm-gg, - la legge da stdin"),
+        ("--date-file", "File da cui leggere la data, es. scritto da uno script"),
+        ("--windows", "Apri una finestra per ogni evento della configurazione, ognuna ricorda la propria posizione"),
+        ("--from", "Mostra il tempo tra questa data e --to, senza avanzare con i giorni"),
+        ("--to", "Fine del tempo mostrato con --from, predefinito oggi"),
+        ("--now", "Conta come se fosse questa data, o un orario RFC 3339 come 2024-06-01T09:30:00Z, invece di adesso, per screenshot, demo e test"),
+        ("--lang", "Lingua dell'interfaccia, di default quella del sistema"),
+        ("--date-format", "Formato della data nell'intestazione, come schema strftime"),
+        ("--template", "Testo dell'intestazione, supporta {years}, {months} e {days} come in modalità split, {total}, {label} e quelli di --statusbar-template"),
+        ("--term", "Avvia in modalità trimestre, serve un trimestre nel file di configurazione"),
+        ("--anim-speed", "Secondi per contare ogni anno all'apertura, 0 per saltare il conteggio"),
+        ("--no-animation", "Mostra subito il numero, come --anim-speed 0"),
+        ("--reduced-motion", "Salta il conteggio e il lampo al cambio di giorno, e tieni fermo lo sfondo, attivo di default se il sistema chiede meno movimento"),
+        ("--full-motion", "Anima come al solito, anche se il sistema o la configurazione chiede meno movimento"),
+        ("--easing", "Come il conteggio accelera e rallenta, di default linear"),
+        ("--intro", "Come appare il numero all'apertura, di default count-up"),
+        ("--sound", "Ticchetta mentre conta e suona alla fine o a un traguardo"),
+        ("--mute", "Disattiva i suoni, anche se attivi nella configurazione"),
+        ("--font", "Font bitmap per i numeri, un PNG dei caratteri ASCII stampabili in righe da 16"),
+        ("--background", "Immagine dietro il conteggio, un PNG adattato alla finestra e scurito perché il testo resti leggibile"),
+        ("--background-style", "Cosa viene disegnato dietro il conteggio, una sfumatura che cambia lentamente o stelle alla deriva invece di un colore pieno, predefinito flat"),
+        ("--screensaver", "Riempi il monitor di nero e fai rimbalzare lentamente il conteggio, così uno schermo OLED lasciato sul contatore non si brucia"),
+        ("--desktop", "Metti la finestra sul desktop, sotto le altre finestre e senza barra del titolo, con i clic che la attraversano, come conky, solo X11 e Windows"),
+        ("--text-scale", "Disegna i numeri tante volte più grandi, la finestra si allarga"),
+        ("--size", "Dimensione della finestra, ad es. 540x180, i numeri sono disegnati più grandi possibile se --text-scale non è impostato"),
+        ("--scale", "Rendi la finestra tante volte più grande, oltre al ridimensionamento dello schermo, per schermi ad alta densità"),
+        ("--min-text-size", "Rendi la finestra abbastanza grande che il testo più piccolo sia alto almeno questi pixel, oltre al ridimensionamento dello schermo"),
+        ("--position", "Apri la finestra qui invece che dove era l'ultima volta, x,y in pixel dall'angolo in alto a sinistra dello schermo oppure top-left, top-right, bottom-left, bottom-right o center"),
+        ("--monitor", "Apri la finestra su questo schermo invece che dove era l'ultima volta, da `countup monitors`, centrata a meno che non sia impostato --position"),
+        ("--start-hidden", "Non aprire la finestra finché non viene mostrata con il metodo D-Bus Show, per l'avvio all'accesso, solo Linux"),
+        ("--new-instance", "Aprire un'altra finestra anche se countup è già in esecuzione, invece di portare quella in primo piano"),
+        ("--numerals", "Cifre per i numeri grandi, di default quelle della lingua"),
+        ("--number-format", "Separatore tra ogni gruppo di tre cifre nei numeri e nel testo, locale per quello della lingua, di default plain"),
+        ("--clock", "Mostra data e ora nell'angolo, per un contatore sempre acceso"),
+        ("--calendar", "Mostra a che punto sono l'anno, il mese e il giorno, senza contare da una data"),
+        ("--month-length", "Giorni in un mese per i mesi nella modalità differenza, o mesi di calendario, 28 se non indicato"),
+        ("--minimal", "Mostra solo i numeri, senza intestazione ed etichette, in una finestra più piccola"),
+        ("--high-contrast", "Disegna su nero con testo bianco, o il colore dell'evento se resta facile da leggere, senza immagine di sfondo, sfumatura o stelle"),
+        ("--hours", "Orari a 12 o 24 ore, per --clock e la modalità cronologia, di default quelli della lingua"),
+        ("--capture-frames", "Fotogrammi tenuti per salvarli come PNG con Ctrl+F o in caso di crash, 0 per disattivare, di default 120"),
+        ("--slideshow", "Mostra a turno ogni evento della configurazione per questi secondi, ripetendo il conteggio"),
+        ("--birthday", "Mostra un'età da questa data di nascita, con i giorni al prossimo compleanno"),
+        ("--born-at", "Ora di nascita per --birthday come HH:MM, per mostrare l'età al minuto e i traguardi in ore"),
+        ("--inclusive", "Conta anche il giorno di inizio, così è il giorno 1 invece di 0"),
+        ("--exclusive", "Non contare il giorno di inizio, il default salvo modifica nella configurazione"),
+        ("--goal", "Mostra una barra di avanzamento verso un numero di giorni o una data sotto il conteggio"),
+        ("--fiscal-year", "Aggiungi la modalità anno fiscale, per un anno fiscale che inizia il 1° di questo mese, ad es. 4 per aprile"),
+        ("--patterns", "Conta anche questi numeri di giorni come traguardi, tra palindrome, repdigit, power-of-two e pi, ad es. palindrome,pi"),
+        ("--pair", "Mostra i giorni fino a questo evento o data accanto al conteggio, in modalità doppia"),
+        ("--anniversary", "Mostra anche i giorni al prossimo anniversario della data in modalità divisa"),
+        ("--leap-days", "Mostra i 29 febbraio dalla data in modalità divisa, invece dei giorni all'anniversario"),
+        ("--roman", "Aggiungi una modalità con anni, mesi e giorni in numeri romani, solo per divertimento"),
+        ("--forget", "Dimentica la data e l'evento ricordati"),
+        ("--headless", "Stampa il numero invece di aprire una finestra"),
+        ("--json", "Stampa il numero come JSON, implica --headless"),
+        ("--statusbar", "Stampa una sola riga per le barre di stato, implica --headless"),
+        ("--statusbar-template", "Testo per --statusbar, supporta {days}, {weeks}, {months}, {years}, {until}, {start}, {event} e {milestone}"),
+        ("--watch", "Continua e ristampa il numero, implica --headless"),
+        ("--interval", "Secondi tra gli aggiornamenti con --watch"),
+        ("show", "Apri il contatore, è il comportamento di default"),
+        ("NAME", "Nome dell'evento"),
+        ("show NAME", "Nome di un evento da cui contare"),
+        ("card", "Salva il conteggio come un'immagine grande, ad es. da condividere per un anniversario"),
+        ("card NAME", "Nome di un evento da cui contare"),
+        ("card --out", "File PNG da scrivere"),
+        ("card --label", "Testo sopra il conteggio, predefinito il nome dell'evento"),
+        ("check", "Esci con 0 se oggi è un traguardo e 1 altrimenti, per cron e script"),
+        ("check NAME", "Nome di un evento da cui contare"),
+        ("check --milestone", "Solo questo numero di giorni, ad es. 1000, invece di qualsiasi traguardo"),
+        ("stopwatch", "Conta al secondo da adesso, riprendendo dallo stesso momento quando viene riaperto"),
+        ("stopwatch --restart", "Ricomincia da adesso"),
+        ("add", "Salva un evento nel file di configurazione"),
+        ("add DATE", "Data dell'evento, formato aaaa-mm-gg"),
+        ("add --timezone", "Fuso orario in cui avanzano i giorni, es. Asia/Tokyo, di default UTC"),
+        ("list", "Elenca gli eventi nel file di configurazione"),
+        ("remove", "Rimuovi un evento dal file di configurazione"),
+        ("reset", "Imposta la data di un evento a oggi"),
+        ("snooze", "Non far suonare i traguardi di un evento né eseguire gli hook per un po'"),
+        ("snooze LENGTH", "Per quanto, da oggi, off per annullare"),
+        ("history", "Mostra chi ha modificato gli eventi sul demone, dal più recente"),
+        ("history NAME", "Mostra solo le modifiche a questo evento"),
+        ("history --limit", "Numero di modifiche da mostrare"),
+        ("conflicts", "Rivedi le modifiche in conflitto con quelle sul demone"),
+        ("conflicts keep-mine", "Applica la tua modifica, sostituendo quella del demone"),
+        ("conflicts keep-theirs", "Scarta la tua modifica"),
+        ("NUMBER", "Numero del conflitto, da `countup conflicts`"),
+        ("daemon", "Condividi gli eventi del file di configurazione con altre istanze di countup"),
+        ("daemon --listen", "Indirizzo su cui ascoltare, usa 0.0.0.0:7878 per altre macchine"),
+        ("serve", "Mostra il numero come pagina web e JSON"),
+        ("serve NAME", "Nome di un evento da cui contare"),
+        ("serve --date", "Data da cui contare, formato aaaa-mm-gg, - la legge da stdin"),
+        ("serve --date-file", "File da cui leggere la data"),
+        ("serve --lang", "Lingua della pagina, di default quella del sistema"),
+        ("serve --date-format", "Formato della data, come schema strftime"),
+        ("serve --port", "Porta su cui ascoltare"),
+        ("serve --bind", "Indirizzo su cui ascoltare"),
+        ("import", "Aggiungi eventi da un file CSV con righe nome,data, da un calendario ICS o dall'esportazione CSV o JSON di un'app di abitudini"),
+        ("import FILE", "File CSV, .ics o .json da importare"),
+        ("import --filter", "Importa solo gli eventi con nomi che corrispondono a questo schema, * corrisponde a tutto"),
+        ("export", "Scrivi i prossimi traguardi degli eventi in un file, per un'app di calendario"),
+        ("export --milestones", "I traguardi e gli anniversari del prossimo anno"),
+        ("export --ics", "File di calendario ICS da scrivere, - lo stampa"),
+        ("auth", "Gestisci i segreti nel portachiavi del sistema"),
+        ("auth set", "Salva un segreto, letto da stdin"),
+        ("auth set NAME", "Nome del segreto, es. proxy"),
+        ("auth clear", "Elimina un segreto"),
+        ("auth clear NAME", "Nome del segreto"),
+        ("monitors", "Elenca gli schermi, con i numeri per --monitor"),
+        ("monitors --json", "Stampali come JSON"),
+        ("autostart", "Avvia countup all'accesso, o smetti di avviarlo"),
+        ("autostart enable", "Aggiungi la voce di avvio, sostituendo quella precedente"),
+        ("autostart enable ARGS", "Argomenti con cui avviare countup, dopo --, ad es. -- --event Sober"),
+        ("autostart disable", "Rimuovi la voce di avvio"),
+        ("selftest", "Controlla il calcolo delle date, la configurazione, i file salvati e il disegno, per gli script di installazione"),
+        ("update", "Sostituisci questo binario con l'ultima versione, se è più recente"),
+        ("update --check-only", "Dì solo se c'è una versione più recente"),
+        ("token", "Gestisci i token per accedere al demone e al server di questa macchina"),
+        ("token add", "Crea un token, sostituendo quello con lo stesso nome"),
+        ("token add NAME", "Nome del token, es. lobby-display"),
+        ("token add --role", "Cosa può fare il token, viewer può solo leggere, editor può anche aggiungere e azzerare eventi, admin può fare tutto"),
+        ("token list", "Elenca i token"),
+        ("token remove", "Elimina un token"),
+        ("token remove NAME", "Nome del token"),
+    ],
+};
+
+const NL: CliStrings = CliStrings {
+    usage: "Gebruik",
+    arguments: "Argumenten",
+    options: "Opties",
+    commands: "Opdrachten",
+    print_help: "Toon hulp",
+    print_version: "Toon versie",
+    help_subcommand: "Toon dit bericht of de hulp van de gegeven opdracht(en)",
+    error: "fout",
+    more_info: "Probeer '--help' voor meer informatie.",
+    invalid_value: "ongeldige waarde '{value}' voor '{arg}'",
+    possible_values: "mogelijke waarden",
+    unexpected_argument: "onverwacht argument '{arg}'",
+    missing_required: "deze verplichte argumenten ontbreken",
+    conflict: "'{arg}' kan niet samen met '{other}' gebruikt worden",
+    unknown_subcommand: "onbekende opdracht '{name}'",
+    need_date: "Geef een datum met --date of de naam van een gebeurtenis",
+    invalid_date: "Ongeldige datum {date}, gebruik jjjj-mm-dd, jjjj/mm/dd, dd-mm-jjjj, jjjjmmdd of een unix-tijdstempel, in die volgorde geprobeerd",
+    invalid_date_format: "Ongeldig datumformaat: {format}",
+    to_before_from: "--to {to} ligt vóór --from {from}",
+    no_events: "Geen gebeurtenissen in de configuratie, voeg er een toe met countup add",
+    no_event: "Geen gebeurtenis met de naam {name}",
+    event_exists: "{name} bestaat al, verwijder het eerst",
+    needs_daemon: "Geef het adres van de daemon met --connect",
+    history_needs_daemon: "De geschiedenis staat op de daemon, geef zijn adres met --connect",
+    help: &[
+        ("", "Telt op vanaf een datum"),
+        ("--event", "Naam van een gebeurtenis in het configuratiebestand"),
+        ("--config", "Pad naar het configuratiebestand, standaard config.json in de voorkeurenmap van de app"),
+        ("--data-dir", "Map voor voorkeuren, de outbox en geheimen (en de standaardconfiguratie), standaard de voorkeurenmap van de app"),
+        ("--prefs-dir", "Map voor de onthouden modus, gebeurtenis en datum, bijv. naast het programma voor een draagbare installatie, standaard de datamap. De vensterpositie wordt dan niet onthouden"),
+        ("--connect", "Gebruik de gebeurtenissen van een countup-daemon, bijv. 192.168.1.20:7878"),
+        ("--token", "Token voor de daemon, standaard het geheim `token`"),
+        ("--verbose", "Log naar stderr wat is ingelezen en berekend, het laden en opslaan van voorkeuren en de framerates"),
+        ("--log-file", "Schrijf het log naar dit bestand in plaats van stderr, impliceert --verbose"),
+        ("--date", "Datum om vanaf te tellen (of naartoe, als die in de toekomst ligt), formaat jjjj-mm-dd, - leest hem van stdin"),
+        ("--date-file", "Bestand om de datum uit te lezen, bijv. geschreven door een script"),
+        ("--windows", "Open een venster voor elke gebeurtenis uit de configuratie, elk onthoudt zijn eigen positie"),
+        ("--from", "Toon de tijd tussen deze datum en --to, zonder mee te tellen met de dagen"),
+        ("--to", "Einde van de tijd getoond met --from, standaard vandaag"),
+        ("--now", "Tellen alsof het deze datum is, of een RFC 3339-tijd zoals 2024-06-01T09:30:00Z, in plaats van nu, voor screenshots, demo's en tests"),
+        ("--lang", "Taal van de interface, standaard die van het systeem"),
+        ("--date-format", "Formaat van de datum in de kop, als strftime-patroon"),
+        ("--template", "Tekst van de kop, ondersteunt {years}, {months} en {days} zoals in split-modus, {total}, {label} en die van --statusbar-template"),
+        ("--term", "Start in trimestermodus, vereist een trimester in het configuratiebestand"),
+        ("--anim-speed", "Seconden om elk jaar op te tellen bij het openen, 0 slaat het tellen over"),
+        ("--no-animation", "Toon het getal meteen, hetzelfde als --anim-speed 0"),
+        ("--reduced-motion", "Sla het optellen en de flits bij een nieuwe dag over en houd de achtergrond stil, standaard aan als het systeem om minder beweging vraagt"),
+        ("--full-motion", "Animeer zoals gewoonlijk, ook als het systeem of de configuratie om minder beweging vraagt"),
+        ("--easing", "Hoe het tellen versnelt en vertraagt, standaard linear"),
+        ("--intro", "Hoe het getal verschijnt bij het openen, standaard count-up"),
+        ("--sound", "Tik tijdens het optellen en klink aan het eind of bij een mijlpaal"),
+        ("--mute", "Zet geluiden uit, ook als ze in de configuratie aan staan"),
+        ("--font", "Bitmaplettertype voor de getallen, een PNG van de afdrukbare ASCII-tekens in rijen van 16"),
+        ("--background", "Afbeelding achter de telling, een PNG die in het venster past en gedimd is zodat de tekst leesbaar blijft"),
+        ("--background-style", "Wat achter de telling wordt getekend, een langzaam verschuivend verloop of drijvende sterren in plaats van effen, standaard flat"),
+        ("--screensaver", "Het scherm zwart vullen en de telling er langzaam rond laten stuiteren, zodat een OLED-scherm dat de teller blijft tonen niet inbrandt"),
+        ("--desktop", "Het venster op het bureaublad zetten, onder de andere vensters en zonder titelbalk, met klikken die erdoorheen gaan, zoals conky, alleen X11 en Windows"),
+        ("--text-scale", "Teken de getallen zoveel keer groter, het venster groeit mee"),
+        ("--size", "Venstergrootte, bijv. 540x180, de getallen worden zo groot als past getekend tenzij --text-scale is ingesteld"),
+        ("--scale", "Maak het venster zoveel keer groter, bovenop de schaal van het scherm, voor schermen met hoge DPI"),
+        ("--min-text-size", "Maak het venster zo groot dat de kleinste tekst minstens zoveel pixels hoog is, bovenop de schaal van het scherm"),
+        ("--position", "Open het venster hier in plaats van waar het de vorige keer was, x,y in pixels vanaf linksboven op het scherm of top-left, top-right, bottom-left, bottom-right of center"),
+        ("--monitor", "Open het venster op dit scherm in plaats van waar het de vorige keer was, uit `countup monitors`, gecentreerd tenzij --position is ingesteld"),
+        ("--start-hidden", "Open het venster pas als het getoond wordt met de D-Bus-methode Show, om bij het inloggen te starten, alleen Linux"),
+        ("--new-instance", "Nog een venster openen, ook als countup al draait, in plaats van dat naar voren te halen"),
+        ("--numerals", "Cijfers voor de grote getallen, standaard die van de taal"),
+        ("--number-format", "Scheidingsteken tussen elke groep van drie cijfers in de getallen en tekst, locale voor dat van de taal, standaard plain"),
+        ("--clock", "Toon de datum en tijd in de hoek, voor een teller die altijd aan staat"),
+        ("--calendar", "Toon hoe ver het jaar, de maand en de dag gevorderd zijn, zonder vanaf een datum te tellen"),
+        ("--month-length", "Dagen in een maand voor de maanden in verschilmodus, of kalendermaanden, standaard 28"),
+        ("--minimal", "Toon alleen de getallen, zonder kop en labels, in een kleiner venster"),
+        ("--high-contrast", "Teken op zwart met witte tekst, of de kleur van de gebeurtenis als die nog goed leesbaar is, zonder achtergrondafbeelding, verloop of sterren"),
+        ("--hours", "12- of 24-uurs tijden, voor --clock en de geschiedenismodus, standaard die van de taal"),
+        ("--capture-frames", "Frames om te bewaren voor opslaan als PNG met Ctrl+F of bij een crash, 0 zet het uit, standaard 120"),
+        ("--slideshow", "Toon elke gebeurtenis uit de configuratie om de beurt zo veel seconden, met opnieuw optellen"),
+        ("--birthday", "Toon een leeftijd vanaf deze geboortedatum, met de dagen tot de volgende verjaardag"),
+        ("--born-at", "Geboortetijd voor --birthday als HH:MM, om de leeftijd tot op de minuut en de uren-mijlpalen te tonen"),
+        ("--inclusive", "Tel de startdag zelf mee, zodat het dag 1 is in plaats van 0"),
+        ("--exclusive", "Tel de startdag niet mee, de standaard tenzij anders in de configuratie"),
+        ("--goal", "Toon een voortgangsbalk naar een aantal dagen of een datum onder de telling"),
+        ("--fiscal-year", "Voeg boekjaarmodus toe, voor een boekjaar dat op de 1e van deze maand begint, bijv. 4 voor april"),
+        ("--patterns", "Tel deze aantallen dagen ook als mijlpalen, uit palindrome, repdigit, power-of-two en pi, bijv. palindrome,pi"),
+        ("--pair", "Toon de dagen tot deze gebeurtenis of datum naast de telling, in dubbele modus"),
+        ("--anniversary", "Toon in de gesplitste modus ook de dagen tot de volgende verjaardag van de datum"),
+        ("--leap-days", "Toon in de gesplitste modus de 29 februari's sinds de datum, in plaats van de dagen tot de verjaardag"),
+        ("--roman", "Voeg een modus toe met de jaren, maanden en dagen in Romeinse cijfers, gewoon voor de lol"),
+        ("--forget", "Vergeet de onthouden datum en gebeurtenis"),
+        ("--headless", "Print het getal in plaats van een venster te openen"),
+        ("--json", "Print het getal als JSON, impliceert --headless"),
+        ("--statusbar", "Print één regel voor statusbalken, impliceert --headless"),
+        ("--statusbar-template", "Tekst voor --statusbar, ondersteunt {days}, {weeks}, {months}, {years}, {until}, {start}, {event} en {milestone}"),
+        ("--watch", "Blijf draaien en print het getal opnieuw, impliceert --headless"),
+        ("--interval", "Seconden tussen updates met --watch"),
+        ("show", "Open de teller, dit is de standaard"),
+        ("NAME", "Naam van de gebeurtenis"),
+        ("show NAME", "Naam van een gebeurtenis om vanaf te tellen"),
+        ("card", "De telling opslaan als een grote afbeelding, bijv. om te delen op een jubileum"),
+        ("card NAME", "Naam van een gebeurtenis om vanaf te tellen"),
+        ("card --out", "Te schrijven PNG-bestand"),
+        ("card --label", "Tekst boven de telling, standaard de naam van de gebeurtenis"),
+        ("check", "Afsluiten met 0 als vandaag een mijlpaal is en anders met 1, voor cronjobs en scripts"),
+        ("check NAME", "Naam van een gebeurtenis om vanaf te tellen"),
+        ("check --milestone", "Alleen dit aantal dagen, bijv. 1000, in plaats van elke mijlpaal"),
+        ("stopwatch", "Vanaf nu tot op de seconde optellen, bij opnieuw openen verder vanaf hetzelfde moment"),
+        ("stopwatch --restart", "Opnieuw beginnen vanaf nu"),
+        ("add", "Sla een gebeurtenis op in het configuratiebestand"),
+        ("add DATE", "Datum van de gebeurtenis, formaat jjjj-mm-dd"),
+        ("add --timezone", "Tijdzone waarin de dagen optellen, bijv. Asia/Tokyo, standaard UTC"),
+        ("list", "Toon de gebeurtenissen in het configuratiebestand"),
+        ("remove", "Verwijder een gebeurtenis uit het configuratiebestand"),
+        ("reset", "Zet de datum van een gebeurtenis op vandaag"),
+        ("snooze", "De mijlpalen van een gebeurtenis een tijdje niet laten klinken of hooks laten uitvoeren"),
+        ("snooze LENGTH", "Hoe lang, vanaf vandaag, off om het op te heffen"),
+        ("history", "Toon wie de gebeurtenissen op de daemon heeft gewijzigd, nieuwste eerst"),
+        ("history NAME", "Toon alleen wijzigingen aan deze gebeurtenis"),
+        ("history --limit", "Aantal wijzigingen om te tonen"),
+        ("conflicts", "Bekijk wijzigingen die botsten met wijzigingen op de daemon"),
+        ("conflicts keep-mine", "Pas je wijziging toe en vervang die van de daemon"),
+        ("conflicts keep-theirs", "Gooi je wijziging weg"),
+        ("NUMBER", "Nummer van het conflict, uit `countup conflicts`"),
+        ("daemon", "Deel de gebeurtenissen in het configuratiebestand met andere countup-instanties"),
+        ("daemon --listen", "Adres om op te luisteren, gebruik 0.0.0.0:7878 voor andere machines"),
+        ("serve", "Toon het getal als webpagina en JSON"),
+        ("serve NAME", "Naam van een gebeurtenis om vanaf te tellen"),
+        ("serve --date", "Datum om vanaf te tellen, formaat jjjj-mm-dd, - leest hem van stdin"),
+        ("serve --date-file", "Bestand om de datum uit te lezen"),
+        ("serve --lang", "Taal van de pagina, standaard die van het systeem"),
+        ("serve --date-format", "Formaat van de datum, als strftime-patroon"),
+        ("serve --port", "Poort om op te luisteren"),
+        ("serve --bind", "Adres om op te luisteren"),
+        ("import", "Voeg gebeurtenissen toe uit een CSV-bestand met naam,datum-regels, een ICS-agenda of de CSV- of JSON-export van een gewoonte-app"),
+        ("import FILE", "CSV-, .ics- of .json-bestand om te importeren"),
+        ("import --filter", "Importeer alleen gebeurtenissen waarvan de naam bij dit patroon past, * past op alles"),
+        ("export", "De komende mijlpalen van de gebeurtenissen naar een bestand schrijven, voor een agenda-app"),
+        ("export --milestones", "De mijlpalen en jubilea in het komende jaar"),
+        ("export --ics", "ICS-agendabestand om te schrijven, - toont het"),
+        ("auth", "Beheer geheimen in de sleutelhanger van het systeem"),
+        ("auth set", "Sla een geheim op, gelezen van stdin"),
+        ("auth set NAME", "Naam van het geheim, bijv. proxy"),
+        ("auth clear", "Verwijder een geheim"),
+        ("auth clear NAME", "Naam van het geheim"),
+        ("monitors", "Toon de schermen, met de nummers voor --monitor"),
+        ("monitors --json", "Ze als JSON tonen"),
+        ("autostart", "countup starten bij het inloggen, of daarmee stoppen"),
+        ("autostart enable", "De opstartregel toevoegen, een eerdere wordt vervangen"),
+        ("autostart enable ARGS", "Argumenten om countup mee te starten, na --, bijv. -- --event Sober"),
+        ("autostart disable", "De opstartregel verwijderen"),
+        ("selftest", "Controleer de datumberekening, configuratie, opgeslagen bestanden en het tekenen, voor installatiescripts"),
+        ("update", "Vervang dit programma door de nieuwste versie, als die nieuwer is"),
+        ("update --check-only", "Alleen zeggen of er een nieuwere versie is"),
+        ("token", "Beheer tokens voor toegang tot de daemon en server op deze machine"),
+        ("token add", "Maak een token en vervang er een met dezelfde naam"),
+        ("token add NAME", "Naam van het token, bijv. lobby-display"),
+        ("token add --role", "Wat het token mag, viewer kan alleen lezen, editor kan ook gebeurtenissen toevoegen en resetten, admin mag alles"),
+        ("token list", "Toon tokens"),
+        ("token remove", "Verwijder een token"),
+        ("token remove NAME", "Naam van het token"),
+    ],
+};
+
+const PT: CliStrings = CliStrings {
+    usage: "Utilização",
+    arguments: "Argumentos",
+    options: "Opções",
+    commands: "Comandos",
+    print_help: "Mostrar a ajuda",
+    print_version: "Mostrar a versão",
+    help_subcommand: "Mostrar esta mensagem ou a ajuda dos comandos indicados",
+    error: "erro",
+    more_info: "Para mais informações, experimente '--help'.",
+    invalid_value: "valor '{value}' inválido para '{arg}'",
+    possible_values: "valores possíveis",
+    unexpected_argument: "argumento inesperado '{arg}'",
+    missing_required: "faltam estes argumentos obrigatórios",
+    conflict: "'{arg}' não pode ser usado com '{other}'",
+    unknown_subcommand: "comando desconhecido '{name}'",
+    need_date: "Indique uma data com --date ou o nome de um evento",
+    invalid_date: "Data inválida {date}, use aaaa-mm-dd, aaaa/mm/dd, dd-mm-aaaa, aaaammdd ou um timestamp unix, tentados por esta ordem",
+    invalid_date_format: "Formato de data inválido: {format}",
+    to_before_from: "--to {to} é anterior a --from {from}",
+    no_events: "Nenhum evento na configuração, adicione um com countup add",
+    no_event: "Nenhum evento chamado {name}",
+    event_exists: "{name} já existe, remova-o primeiro",
+    needs_daemon: "Indique o endereço do daemon com --connect",
+    history_needs_daemon: "O histórico é guardado pelo daemon, indique o endereço com --connect",
+    help: &[
+        ("", "Conta a partir de uma data"),
+        ("--event", "Nome de um evento no ficheiro de configuração"),
+        ("--config", "Caminho do ficheiro de configuração, por omissão config.json na pasta de preferências da app"),
+        ("--data-dir", "Pasta para preferências, caixa de saída e segredos (e a configuração por omissão), por omissão a pasta de preferências da app"),
+        ("--prefs-dir", "Pasta para o modo, evento e data lembrados, p. ex. junto ao binário para uma instalação portátil, por omissão a pasta de dados. A posição da janela não é lembrada com ela"),
+        ("--connect", "Usar os eventos de um daemon countup, p. ex. 192.168.1.20:7878"),
+        ("--token", "Token para o daemon, por omissão o segredo `token`"),
+        ("--verbose", "Registar no stderr o que foi lido e calculado, o carregamento e gravação das preferências e as taxas de fotogramas"),
+        ("--log-file", "Escrever o registo neste ficheiro em vez do stderr, implica --verbose"),
+        ("--date", "Data a partir da qual contar (ou até à qual, se for no futuro), formato aaaa-mm-dd, - lê-a de stdin"),
+        ("--date-file", "Ficheiro de onde ler a data, p. ex. escrito por um script"),
+        ("--windows", "Abrir uma janela para cada evento da configuração, cada uma lembra a sua posição"),
+        ("--from", "Mostrar o tempo entre esta data e --to, sem avançar com os dias"),
+        ("--to", "Fim do tempo mostrado com --from, por omissão hoje"),
+        ("--now", "Contar como se fosse esta data, ou uma hora RFC 3339 como 2024-06-01T09:30:00Z, em vez de agora, para capturas, demonstrações e testes"),
+        ("--lang", "Idioma da interface, por omissão o do sistema"),
+        ("--date-format", "Formato da data no cabeçalho, como padrão strftime"),
+        ("--template", "Texto do cabeçalho, suporta {years}, {months} e {days} como no modo split, {total}, {label} e os de --statusbar-template"),
+        ("--term", "Começar no modo de período letivo, requer um período no ficheiro de configuração"),
+        ("--anim-speed", "Segundos para contar cada ano ao abrir, 0 para saltar a contagem"),
+        ("--no-animation", "Mostrar o número logo, o mesmo que --anim-speed 0"),
+        ("--reduced-motion", "Saltar a contagem e o brilho na mudança de dia, e manter o fundo parado, ligado por omissão se o sistema pedir menos movimento"),
+        ("--full-motion", "Animar como de costume, mesmo que o sistema ou a configuração peça menos movimento"),
+        ("--easing", "Como a contagem acelera e abranda, por omissão linear"),
+        ("--intro", "Como o número aparece ao abrir, por omissão count-up"),
+        ("--sound", "Fazer tique-taque ao contar e tocar no fim ou num marco"),
+        ("--mute", "Desligar os sons, mesmo que ligados na configuração"),
+        ("--font", "Fonte bitmap para os números, um PNG dos caracteres ASCII imprimíveis em linhas de 16"),
+        ("--background", "Imagem atrás da contagem, um PNG ajustado à janela e escurecido para que o texto continue legível"),
+        ("--background-style", "O que é desenhado atrás da contagem, um degradê que muda devagar ou estrelas à deriva em vez de liso, por omissão flat"),
+        ("--screensaver", "Encher o monitor de preto e fazer a contagem saltar devagar por ele, para que um ecrã OLED deixado no contador não fique marcado"),
+        ("--desktop", "Colocar a janela no ambiente de trabalho, por baixo das outras janelas e sem barra de título, com os cliques a passar através dela, como o conky, só X11 e Windows"),
+        ("--text-scale", "Desenhar os números tantas vezes maiores, a janela cresce"),
+        ("--size", "Tamanho da janela, por ex. 540x180, os números são desenhados o maior possível, a menos que --text-scale esteja definido"),
+        ("--scale", "Tornar a janela tantas vezes maior, além da escala do ecrã, para ecrãs de alta densidade"),
+        ("--min-text-size", "Tornar a janela grande o suficiente para que o texto mais pequeno tenha pelo menos estes píxeis de altura, além da escala do ecrã"),
+        ("--position", "Abrir a janela aqui em vez de onde estava da última vez, x,y em píxeis a partir do canto superior esquerdo do ecrã ou top-left, top-right, bottom-left, bottom-right ou center"),
+        ("--monitor", "Abrir a janela neste ecrã em vez de onde estava da última vez, de `countup monitors`, centrada a não ser que --position esteja definido"),
+        ("--start-hidden", "Não abrir a janela até ser mostrada com o método D-Bus Show, para arrancar no início de sessão, só em Linux"),
+        ("--new-instance", "Abrir outra janela mesmo que o countup já esteja a correr, em vez de trazer essa para a frente"),
+        ("--numerals", "Algarismos para os números grandes, por omissão os do idioma"),
+        ("--number-format", "Separador entre cada grupo de três algarismos nos números e no texto, locale para o do idioma, por omissão plain"),
+        ("--clock", "Mostrar a data e a hora no canto, para um contador sempre ligado"),
+        ("--calendar", "Mostrar quanto já passou do ano, do mês e do dia, sem contar a partir de uma data"),
+        ("--month-length", "Dias num mês para os meses no modo diferença, ou meses de calendário, 28 por omissão"),
+        ("--minimal", "Mostrar só os números, sem cabeçalho nem etiquetas, numa janela mais pequena"),
+        ("--high-contrast", "Desenhar sobre preto com texto branco, ou a cor do evento se continuar fácil de ler, sem imagem de fundo, gradiente nem estrelas"),
+        ("--hours", "Horas de 12 ou 24 horas, para --clock e o modo de histórico, por omissão as do idioma"),
+        ("--capture-frames", "Imagens guardadas para gravar como PNG com Ctrl+F ou numa falha, 0 para desligar, por omissão 120"),
+        ("--slideshow", "Mostrar cada evento da configuração à vez durante estes segundos, repetindo a contagem"),
+        ("--birthday", "Mostrar uma idade a partir desta data de nascimento, com os dias até ao próximo aniversário"),
+        ("--born-at", "Hora de nascimento para --birthday como HH:MM, para mostrar a idade ao minuto e os marcos em horas"),
+        ("--inclusive", "Contar o próprio dia de início, para ser o dia 1 em vez de 0"),
+        ("--exclusive", "Não contar o dia de início, o padrão salvo alteração na configuração"),
+        ("--goal", "Mostrar uma barra de progresso até um número de dias ou uma data por baixo da contagem"),
+        ("--fiscal-year", "Adicionar o modo ano fiscal, para um ano fiscal que começa no dia 1 deste mês, p. ex. 4 para abril"),
+        ("--patterns", "Contar também estes números de dias como marcos, entre palindrome, repdigit, power-of-two e pi, por ex. palindrome,pi"),
+        ("--pair", "Mostrar os dias até este evento ou data ao lado da contagem, no modo duplo"),
+        ("--anniversary", "Mostrar também os dias até ao próximo aniversário da data no modo dividido"),
+        ("--leap-days", "Mostrar os 29 de fevereiro desde a data no modo dividido, em vez dos dias até ao aniversário"),
+        ("--roman", "Adicionar um modo com os anos, meses e dias em numeração romana, só por diversão"),
+        ("--forget", "Esquecer a data e o evento memorizados"),
+        ("--headless", "Escrever o número em vez de abrir uma janela"),
+        ("--json", "Escrever o número como JSON, implica --headless"),
+        ("--statusbar", "Escrever uma só linha para barras de estado, implica --headless"),
+        ("--statusbar-template", "Texto para --statusbar, suporta {days}, {weeks}, {months}, {years}, {until}, {start}, {event} e {milestone}"),
+        ("--watch", "Continuar a correr e reescrever o número, implica --headless"),
+        ("--interval", "Segundos entre atualizações com --watch"),
+        ("show", "Abrir o contador, é o comportamento por omissão"),
+        ("NAME", "Nome do evento"),
+        ("show NAME", "Nome de um evento a partir do qual contar"),
+        ("card", "Guardar a contagem como uma imagem grande, p. ex. para partilhar num aniversário"),
+        ("card NAME", "Nome de um evento a partir do qual contar"),
+        ("card --out", "Ficheiro PNG a escrever"),
+        ("card --label", "Texto acima da contagem, por omissão o nome do evento"),
+        ("check", "Sair com 0 se hoje for um marco e 1 se não, para tarefas cron e scripts"),
+        ("check NAME", "Nome de um evento a partir do qual contar"),
+        ("check --milestone", "Só este número de dias, p. ex. 1000, em vez de qualquer marco"),
+        ("stopwatch", "Contar ao segundo a partir de agora, continuando do mesmo momento ao reabrir"),
+        ("stopwatch --restart", "Recomeçar a partir de agora"),
+        ("add", "Guardar um evento no ficheiro de configuração"),
+        ("add DATE", "Data do evento, formato aaaa-mm-dd"),
+        ("add --timezone", "Fuso horário em que os dias avançam, p. ex. Asia/Tokyo, por omissão UTC"),
+        ("list", "Listar os eventos no ficheiro de configuração"),
+        ("remove", "Remover um evento do ficheiro de configuração"),
+        ("reset", "Pôr a data de um evento em hoje"),
+        ("snooze", "Impedir que os marcos de um evento toquem e executem hooks durante um tempo"),
+        ("snooze LENGTH", "Por quanto tempo, a partir de hoje, off para cancelar"),
+        ("history", "Mostrar quem alterou os eventos no daemon, os mais recentes primeiro"),
+        ("history NAME", "Mostrar só as alterações a este evento"),
+        ("history --limit", "Número de alterações a mostrar"),
+        ("conflicts", "Rever edições em conflito com alterações no daemon"),
+        ("conflicts keep-mine", "Aplicar a sua edição, substituindo a alteração do daemon"),
+        ("conflicts keep-theirs", "Descartar a sua edição"),
+        ("NUMBER", "Número do conflito, de `countup conflicts`"),
+        ("daemon", "Partilhar os eventos do ficheiro de configuração com outras instâncias do countup"),
+        ("daemon --listen", "Endereço onde escutar, use 0.0.0.0:7878 para outras máquinas"),
+        ("serve", "Mostrar o número como página web e JSON"),
+        ("serve NAME", "Nome de um evento a partir do qual contar"),
+        ("serve --date", "Data a partir da qual contar, formato aaaa-mm-dd, - lê-a de stdin"),
+        ("serve --date-file", "Ficheiro de onde ler a data"),
+        ("serve --lang", "Idioma da página, por omissão o do sistema"),
+        ("serve --date-format", "Formato da data, como padrão strftime"),
+        ("serve --port", "Porta onde escutar"),
+        ("serve --bind", "Endereço onde escutar"),
+        ("import", "Adicionar eventos de um ficheiro CSV com linhas nome,data, de um calendário ICS ou da exportação CSV ou JSON de uma app de hábitos"),
+        ("import FILE", "Ficheiro CSV, .ics ou .json a importar"),
+        ("import --filter", "Importar só eventos com nomes que correspondam a este padrão, * corresponde a tudo"),
+        ("export", "Escrever os próximos marcos dos eventos num ficheiro, para uma aplicação de calendário"),
+        ("export --milestones", "Os marcos e aniversários do próximo ano"),
+        ("export --ics", "Ficheiro de calendário ICS a escrever, - mostra-o"),
+        ("auth", "Gerir segredos guardados no porta-chaves do sistema"),
+        ("auth set", "Guardar um segredo, lido de stdin"),
+        ("auth set NAME", "Nome do segredo, p. ex. proxy"),
+        ("auth clear", "Apagar um segredo"),
+        ("auth clear NAME", "Nome do segredo"),
+        ("monitors", "Listar os ecrãs, com os números para --monitor"),
+        ("monitors --json", "Mostrá-los em JSON"),
+        ("autostart", "Iniciar o countup ao entrar na sessão, ou deixar de iniciar"),
+        ("autostart enable", "Adicionar a entrada de arranque, substituindo uma anterior"),
+        ("autostart enable ARGS", "Argumentos com que iniciar o countup, depois de --, por ex. -- --event Sober"),
+        ("autostart disable", "Remover a entrada de arranque"),
+        ("selftest", "Verificar o cálculo de datas, a configuração, os ficheiros guardados e o desenho, para scripts de instalação"),
+        ("update", "Substituir este binário pela versão mais recente, se for mais nova"),
+        ("update --check-only", "Dizer apenas se há uma versão mais recente"),
+        ("token", "Gerir tokens de acesso ao daemon e ao servidor desta máquina"),
+        ("token add", "Criar um token, substituindo outro com o mesmo nome"),
+        ("token add NAME", "Nome do token, p. ex. lobby-display"),
+        ("token add --role", "O que o token pode fazer, viewer só pode ler, editor também pode adicionar e repor eventos, admin pode fazer tudo"),
+        ("token list", "Listar tokens"),
+        ("token remove", "Apagar um token"),
+        ("token remove NAME", "Nome do token"),
+    ],
+};
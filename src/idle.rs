@@ -0,0 +1,122 @@
+use chrono::Utc;
+use pixels_graphics_lib::prelude::Timing;
+use std::cell::Cell;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Time slept each frame while idle, also the longest a key press can wait to be seen
+const IDLE_SLEEP: Duration = Duration::from_millis(100);
+/// Frames stay at the full rate for this long after a key press or the mouse moving
+const AWAKE_AFTER_INPUT: Duration = Duration::from_secs(2);
+/// Time slept each time round the loop while minimized or covered, nothing's drawn then
+const HIDDEN_SLEEP: Duration = Duration::from_millis(250);
+
+/// Slows the loop down once nothing is moving and only redraws once a second, so a counter
+/// left open uses next to no CPU or GPU
+///
+/// The loop is run by pixels-graphics-lib, so while idle each frame sleeps in update and
+/// render keeps the last frame, which stays in the buffer, unless the second has changed
+///
+/// While unfocused it's idle as soon as nothing's moving, and while minimized or covered it
+/// sleeps longer and doesn't draw at all, both go back to the full rate when shown or focused
+#[derive(Debug)]
+pub struct IdleRender {
+    last_input: Instant,
+    /// Last mouse position, it's passed on every frame whether it moved or not
+    mouse: (usize, usize),
+    idle: bool,
+    focused: bool,
+    /// Minimized or covered by other windows, not every platform reports this
+    hidden: bool,
+    /// Start of the loop when last slept while hidden, render count isn't used as there may
+    /// be no renders while hidden
+    hidden_slept: Option<Instant>,
+    /// Render count when last slept, as update can run more than once per frame
+    renders: usize,
+    /// Unix time in seconds of the last frame drawn
+    drawn: Cell<Option<i64>>,
+}
+
+impl Default for IdleRender {
+    fn default() -> Self {
+        IdleRender {
+            last_input: Instant::now(),
+            mouse: (0, 0),
+            idle: false,
+            focused: true,
+            hidden: false,
+            hidden_slept: None,
+            renders: 0,
+            drawn: Cell::new(None),
+        }
+    }
+}
+
+impl IdleRender {
+    /// Back to the full frame rate for a while, i.e. after a key press
+    pub fn wake(&mut self) {
+        self.last_input = Instant::now();
+        self.drawn.set(None);
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if focused {
+            self.wake();
+        }
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.hidden = !visible;
+        if visible {
+            self.wake();
+        }
+    }
+
+    /// If frames are being slowed down, so they aren't counted as slow by [crate::budget]
+    pub fn is_idle(&self) -> bool {
+        self.idle || self.hidden
+    }
+
+    /// Wakes if the mouse has moved
+    pub fn mouse_at(&mut self, xy: (usize, usize)) {
+        if xy != self.mouse {
+            self.mouse = xy;
+            self.wake();
+        }
+    }
+
+    /// Called from update with whether anything is animating, sleeps once per frame while
+    /// idle
+    pub fn update(&mut self, timing: &Timing, animating: bool) {
+        if self.hidden {
+            if self.hidden_slept != Some(timing.now) {
+                self.hidden_slept = Some(timing.now);
+                thread::sleep(HIDDEN_SLEEP);
+            }
+            return;
+        }
+        let idle = !animating && (!self.focused || self.last_input.elapsed() >= AWAKE_AFTER_INPUT);
+        if idle != self.idle {
+            self.idle = idle;
+            self.drawn.set(None);
+        }
+        if idle && timing.renders != self.renders {
+            self.renders = timing.renders;
+            thread::sleep(IDLE_SLEEP);
+        }
+    }
+
+    /// Called from render, false if the last frame drawn is still up to date
+    pub fn should_draw(&self) -> bool {
+        if self.hidden {
+            return false;
+        }
+        let second = Utc::now().timestamp();
+        if self.idle && self.drawn.get() == Some(second) {
+            return false;
+        }
+        self.drawn.set(Some(second));
+        true
+    }
+}
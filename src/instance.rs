@@ -0,0 +1,172 @@
+//! Keeps to one window, a second launch brings the running one to the front and exits
+//!
+//! On Linux the running one is found over D-Bus, which can also switch its event. Elsewhere
+//! it's found by the process id kept in `instance.pid`, and is only brought to the front
+
+use color_eyre::Result;
+#[cfg(not(target_os = "linux"))]
+use std::path::PathBuf;
+#[cfg(not(target_os = "linux"))]
+use std::process::Command;
+use std::thread;
+use tracing::debug;
+
+/// Held by the only instance until its window closes
+pub struct Guard {
+    #[cfg(not(target_os = "linux"))]
+    lock: Option<PathBuf>,
+}
+
+/// Hands off to a running instance, bringing it to the front if `show` and switching it to
+/// `event`, none if there was one and this should exit
+#[cfg(target_os = "linux")]
+pub fn claim(event: Option<&str>, show: bool) -> Option<Guard> {
+    (!crate::dbus::hand_off(event, show)).then_some(Guard {})
+}
+
+/// Hands off to a running instance, bringing it to the front if `show`, none if there was
+/// one and this should exit
+///
+/// Failing to read or write the lock isn't an error, it's only to save duplicate windows
+#[cfg(not(target_os = "linux"))]
+pub fn claim(event: Option<&str>, show: bool) -> Option<Guard> {
+    use std::fs;
+    let Ok(dir) = crate::paths::data_dir() else {
+        return Some(Guard { lock: None });
+    };
+    let path = dir.join("instance.pid");
+    let running = fs::read_to_string(&path)
+        .ok()
+        .and_then(|pid| pid.trim().parse().ok())
+        .filter(|pid| *pid != std::process::id() && is_countup(*pid));
+    if let Some(pid) = running {
+        if event.is_some() {
+            eprintln!("The running countup can only switch event over D-Bus, on Linux");
+        }
+        if show {
+            if let Err(err) = raise(pid) {
+                eprintln!("Unable to bring the running countup to the front: {err}");
+            }
+        }
+        return None;
+    }
+    let lock = fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&path, std::process::id().to_string()))
+        .map_err(|err| debug!("Unable to write {}: {err}", path.display()))
+        .ok()
+        .map(|_| path);
+    Some(Guard { lock })
+}
+
+#[cfg(not(target_os = "linux"))]
+impl Drop for Guard {
+    fn drop(&mut self) {
+        if let Some(path) = &self.lock {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Brings this process's window to the front from a background thread, for `Show` over
+/// D-Bus, failing quietly as it's open regardless
+pub fn raise_own() {
+    thread::spawn(|| {
+        if let Err(err) = raise(std::process::id()) {
+            debug!("Unable to bring the window to the front: {err}");
+        }
+    });
+}
+
+/// Asks the window manager to activate it, as a pager so it isn't refused as stealing focus
+#[cfg(not(any(target_os = "macos", windows)))]
+fn raise(pid: u32) -> Result<()> {
+    use color_eyre::eyre::eyre;
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{ClientMessageEvent, ConnectionExt, EventMask};
+
+    let (conn, screen) = x11rb::connect(None)?;
+    let root = conn.setup().roots[screen].root;
+    let window =
+        crate::desktop::x11_window(&conn, root, pid)?.ok_or_else(|| eyre!("no window found"))?;
+    let active = conn
+        .intern_atom(false, b"_NET_ACTIVE_WINDOW")?
+        .reply()?
+        .atom;
+    let event = ClientMessageEvent::new(32, window, active, [2, x11rb::CURRENT_TIME, 0, 0, 0]);
+    conn.send_event(
+        false,
+        root,
+        EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+        event,
+    )?;
+    conn.flush()?;
+    Ok(())
+}
+
+/// Restores and focuses it with PowerShell, like [desktop::pin](crate::desktop::pin)
+#[cfg(windows)]
+fn raise(pid: u32) -> Result<()> {
+    let script = format!(
+        r#"
+Add-Type @"
+using System;
+using System.Runtime.InteropServices;
+public static class Raise {{
+    [DllImport("user32.dll")] public static extern bool ShowWindow(IntPtr window, int command);
+    [DllImport("user32.dll")] public static extern bool SetForegroundWindow(IntPtr window);
+}}
+"@
+$window = (Get-Process -Id {pid}).MainWindowHandle
+if ($window -eq [IntPtr]::Zero) {{ exit 2 }}
+[Raise]::ShowWindow($window, 9) | Out-Null
+[Raise]::SetForegroundWindow($window) | Out-Null
+"#
+    );
+    run("powershell", &["-NoProfile", "-Command", &script])
+}
+
+#[cfg(target_os = "macos")]
+fn raise(pid: u32) -> Result<()> {
+    let script = format!(
+        "tell application \"System Events\" to set frontmost of first process whose unix id is {pid} to true"
+    );
+    run("osascript", &["-e", &script])
+}
+
+#[cfg(any(target_os = "macos", windows))]
+fn run(program: &str, args: &[&str]) -> Result<()> {
+    use color_eyre::eyre::eyre;
+    let output = Command::new(program).args(args).output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(eyre!("{}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+/// So a stale lock from a crash isn't followed to whatever reused its process id
+#[cfg(windows)]
+fn is_countup(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH", "/FO", "CSV"])
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .to_lowercase()
+                .contains("countup")
+        })
+}
+
+/// So a stale lock from a crash isn't followed to whatever reused its process id
+#[cfg(all(unix, not(target_os = "linux")))]
+fn is_countup(pid: u32) -> bool {
+    Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .output()
+        .is_ok_and(|output| {
+            output.status.success()
+                && String::from_utf8_lossy(&output.stdout)
+                    .trim()
+                    .ends_with("countup")
+        })
+}
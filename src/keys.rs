@@ -0,0 +1,213 @@
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use pixels_graphics_lib::prelude::VirtualKeyCode::{self, *};
+use serde::{Deserialize, Serialize};
+
+/// Names keys are given by in the config and shown by in the help
+const NAMES: [(&str, VirtualKeyCode); 60] = [
+    ("A", A),
+    ("B", B),
+    ("C", C),
+    ("D", D),
+    ("E", E),
+    ("F", F),
+    ("G", G),
+    ("H", H),
+    ("I", I),
+    ("J", J),
+    ("K", K),
+    ("L", L),
+    ("M", M),
+    ("N", N),
+    ("O", O),
+    ("P", P),
+    ("Q", Q),
+    ("R", R),
+    ("S", S),
+    ("T", T),
+    ("U", U),
+    ("V", V),
+    ("W", W),
+    ("X", X),
+    ("Y", Y),
+    ("Z", Z),
+    ("0", Key0),
+    ("1", Key1),
+    ("2", Key2),
+    ("3", Key3),
+    ("4", Key4),
+    ("5", Key5),
+    ("6", Key6),
+    ("7", Key7),
+    ("8", Key8),
+    ("9", Key9),
+    ("F1", F1),
+    ("F2", F2),
+    ("F3", F3),
+    ("F4", F4),
+    ("F5", F5),
+    ("F6", F6),
+    ("F7", F7),
+    ("F8", F8),
+    ("F9", F9),
+    ("F10", F10),
+    ("F11", F11),
+    ("F12", F12),
+    ("Space", Space),
+    ("Enter", Return),
+    ("Tab", Tab),
+    ("Esc", Escape),
+    ("Delete", Delete),
+    ("Insert", Insert),
+    ("Home", Home),
+    ("End", End),
+    ("Up", Up),
+    ("Down", Down),
+    ("Left", Left),
+    ("Right", Right),
+];
+/// Used by the shortcuts that can't be changed, with or without Ctrl
+const FIXED: [VirtualKeyCode; 12] = [Return, End, P, I, C, E, L, H, Slash, Back, PageUp, PageDown];
+
+/// Shortcuts that can be changed, as key names like `Q` or `Ctrl+S`, unset ones keep
+/// their default
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save_frames: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quit: Option<String>,
+}
+
+impl KeysConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == KeysConfig::default()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Binding {
+    pub key: VirtualKeyCode,
+    pub ctrl: bool,
+}
+
+impl Binding {
+    const fn new(key: VirtualKeyCode) -> Self {
+        Binding { key, ctrl: false }
+    }
+
+    /// A key name with an optional `Ctrl+` before it, case doesn't matter
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let (ctrl, name) = match text.split_once('+') {
+            Some((modifier, name)) if modifier.trim().eq_ignore_ascii_case("ctrl") => {
+                (true, name.trim())
+            }
+            _ => (false, text),
+        };
+        NAMES
+            .iter()
+            .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+            .map(|(_, key)| Binding { key: *key, ctrl })
+            .ok_or_else(|| {
+                eyre!("Unknown key {text}, use a letter, digit, F1-F12, Space, Enter, Tab, Esc, Delete, Insert, Home, End or an arrow, optionally with Ctrl+")
+            })
+    }
+
+    /// As shown in the help, i.e. `Ctrl+F`
+    pub fn name(&self) -> String {
+        let name = NAMES
+            .iter()
+            .find(|(_, key)| *key == self.key)
+            .map_or("?", |(name, _)| name);
+        if self.ctrl {
+            format!("Ctrl+{name}")
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// If it's in `keys`, and Ctrl is held if it needs it
+    pub fn pressed(&self, keys: &[VirtualKeyCode], ctrl: bool) -> bool {
+        keys.contains(&self.key) && (ctrl || !self.ctrl)
+    }
+}
+
+/// The shortcuts that can be changed with `keys` in the config
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Keys {
+    pub next_mode: Binding,
+    pub restart: Binding,
+    pub save_frames: Binding,
+    pub quit: Binding,
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Keys {
+            next_mode: Binding::new(Space),
+            restart: Binding::new(R),
+            save_frames: Binding { key: F, ctrl: true },
+            quit: Binding::new(Escape),
+        }
+    }
+}
+
+impl Keys {
+    /// Fails if a key can't be parsed, is used twice or is used by a shortcut that can't be
+    /// changed
+    pub fn new(config: &KeysConfig) -> Result<Self> {
+        let defaults = Keys::default();
+        let parse = |name: &str, text: &Option<String>, default: Binding| -> Result<Binding> {
+            let Some(text) = text else {
+                return Ok(default);
+            };
+            let binding = Binding::parse(text).wrap_err_with(|| format!("keys.{name}"))?;
+            if FIXED.contains(&binding.key) {
+                return Err(eyre!(
+                    "keys.{name}: {} is already used by another shortcut",
+                    binding.name()
+                ));
+            }
+            Ok(binding)
+        };
+        let keys = Keys {
+            next_mode: parse("next_mode", &config.next_mode, defaults.next_mode)?,
+            restart: parse("restart", &config.restart, defaults.restart)?,
+            save_frames: parse("save_frames", &config.save_frames, defaults.save_frames)?,
+            quit: parse("quit", &config.quit, defaults.quit)?,
+        };
+        let bindings = keys.bindings();
+        for (i, (name, binding)) in bindings.iter().enumerate() {
+            if let Some((other, _)) = bindings[..i]
+                .iter()
+                .find(|(_, other)| other.key == binding.key)
+            {
+                return Err(eyre!(
+                    "keys.{name}: {} is already used by keys.{other}",
+                    binding.name()
+                ));
+            }
+        }
+        Ok(keys)
+    }
+
+    fn bindings(&self) -> [(&'static str, Binding); 4] {
+        [
+            ("next_mode", self.next_mode),
+            ("restart", self.restart),
+            ("save_frames", self.save_frames),
+            ("quit", self.quit),
+        ]
+    }
+
+    /// For [pixels_graphics_lib::prelude::System::action_keys]
+    pub fn all(&self) -> [VirtualKeyCode; 4] {
+        self.bindings().map(|(_, binding)| binding.key)
+    }
+}
@@ -0,0 +1,25 @@
+//! Counting and drawing, shared by the app and the web version
+//!
+//! [date], [report] and [milestones] are the same date parsing, breakdown and text the app
+//! uses, for other tools to get identical results. They follow semver, breaking changes only
+//! come with a new minor version while below 1.0. The other modules are shared with the web
+//! version and can change at any time
+
+pub mod count;
+pub mod date;
+pub mod event;
+#[cfg(all(feature = "ffi", not(target_arch = "wasm32")))]
+pub mod ffi;
+pub mod font;
+pub mod i18n;
+pub mod milestones;
+pub mod plugin;
+pub mod render;
+pub mod report;
+pub mod roman;
+pub mod term;
+pub mod units;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+pub const DEFAULT_DATE_FORMAT: &str = "%d/%m/%Y";
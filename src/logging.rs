@@ -0,0 +1,40 @@
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::Level;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::prelude::*;
+
+/// Sends the debug log to `file`, appending, or stderr if only `verbose`, nothing is logged
+/// without either
+pub fn init(verbose: bool, file: Option<&Path>) -> Result<()> {
+    match file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .wrap_err_with(|| format!("Opening log file {}", path.display()))?;
+            start(Mutex::new(file))
+        }
+        None if verbose => start(std::io::stderr),
+        None => Ok(()),
+    }
+}
+
+/// Only countup's own logs, D-Bus and the rest can be very chatty
+fn start<W>(writer: W) -> Result<()>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_filter(Targets::new().with_target("countup", Level::DEBUG));
+    tracing_subscriber::registry()
+        .with(layer)
+        .try_init()
+        .map_err(|err| eyre!("Unable to start logging: {err}"))
+}
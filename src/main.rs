@@ -1,199 +1,3370 @@
-use chrono::{DateTime, Datelike, Utc};
-use clap::{arg, command};
+mod app_prefs;
+mod autostart;
+mod budget;
+mod card;
+mod cli;
+mod commands;
+mod config;
+mod credentials;
+mod dbus;
+mod desktop;
+mod digest;
+mod editor;
+mod frames;
+mod holidays;
+mod hooks;
+mod idle;
+mod instance;
+mod keys;
+mod logging;
+mod menu;
+mod motion;
+mod mqtt;
+mod net;
+mod offline;
+mod outbox;
+mod paths;
+mod placement;
+mod recap;
+mod reload;
+mod selftest;
+mod serve;
+mod settings;
+mod shared;
+mod shutdown;
+mod sound;
+mod title;
+mod tokens;
+mod update;
+mod windows;
+
+use countup::{
+    count, date, event, font, i18n, milestones, plugin, render, report, roman, term, units,
+    DEFAULT_DATE_FORMAT,
+};
+
+use crate::app_prefs::{AppPref, AppPreferences};
+use crate::budget::FrameBudget;
+use crate::card::Card;
+use crate::config::Config;
+use crate::count::Fiscal as FiscalYear;
+use crate::count::{
+    calc_days_since, date_modes, days_to_anniversary, local_date, midnight, next_midnight,
+    Animation, CalendarProgress, Count, Goal, Intro, IsoWeeks, Mode, PreciseAge, TimeSource,
+    Timeline, Weekdays, YearProgress,
+};
+use crate::date::DatePicker;
+use crate::dbus::{Control, Status};
+use crate::digest::digest;
+use crate::editor::{Edit, Editor};
+use crate::event::{Event, PhaseProgress, Rgb, SnoozeLength};
+use crate::font::{Glyphs, NumberFont};
+use crate::hooks::{Hook, HookPayload, Hooks};
+use crate::i18n::{detect_lang, strings, Strings};
+use crate::idle::IdleRender;
+use crate::keys::Keys;
+use crate::menu::{Action, Menu};
+use crate::milestones::{Milestones, Pattern};
+use crate::mqtt::{MqttConfig, Publisher};
+use crate::placement::Position;
+use crate::plugin::{DisplayMode, External, Registry};
+use crate::recap::{is_anniversary, Recap};
+use crate::reload::FileWatch;
+use crate::render::{
+    canvas_size, render_about, render_age, render_bases, render_calendar, render_confirm_reset,
+    render_date_picker, render_diff, render_dual, render_fiscal, render_goal, render_help,
+    render_moons, render_phases, render_recap, render_roman, render_segments, render_split,
+    render_term, render_timeline, render_tminus, render_totals, render_upcoming, render_weekdays,
+    render_weeks, render_weeks_and_days, render_year, units_height, Backdrop, BackgroundStyle,
+    Clock, Heading, Palette, Reveal, Screensaver, Side, SplitRow, Texts, GOAL_HEIGHT,
+};
+use crate::report::{Counting, MonthLength, Report, Upcoming};
+use crate::roman::roman_date;
+use crate::serve::Counter;
+use crate::settings::{Settings, MAX_TEXT_SCALE};
+use crate::shared::{AuditEntry, Change, Update};
+use crate::sound::{Sound, Sounds};
+use crate::term::{Term, TermProgress};
+use crate::units::{CustomUnit, Formula, MAX_UNITS};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use clap::ArgMatches;
+use color_eyre::eyre::{eyre, WrapErr};
 use color_eyre::Result;
 use pixels_graphics_lib::prefs::WindowPreferences;
-use pixels_graphics_lib::prelude::Positioning::{LeftTop, RightTop};
-use pixels_graphics_lib::prelude::VirtualKeyCode::{Escape, Space};
+use pixels_graphics_lib::prelude::VirtualKeyCode::{
+    Back, Down, End, LControl, LShift, Left, PageDown, PageUp, RControl, RShift, Return, Right,
+    Slash, Up, C, E, H, I, L, P,
+};
 use pixels_graphics_lib::prelude::*;
+use std::fs;
+use std::io::{stdin, stdout, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::thread;
+use std::time::Instant;
+use tracing::debug;
+use Mode::*;
 
 fn main() -> Result<()> {
-    let matches = command!()
-        .arg(arg!(-d --date <DATE> "Date to count from, format yyyy-mm-dd" ))
-        .get_matches();
-
-    let default = DateTime::parse_from_str("2022-11-25T00-00-00+0000", "%Y-%m-%dT%H-%M-%S%z")
-        .expect("Default date invalid?")
-        .with_timezone(&Utc);
-
-    let (start, days) = match matches.get_one::<String>("date") {
-        None => calc_days_since(default),
-        Some(date) => {
-            let date =
-                DateTime::parse_from_str(&format!("{date}T00-00-00+0000"), "%Y-%m-%dT%H-%M-%S%z")
-                    .expect("Invalid date")
-                    .with_timezone(&Utc);
-            if date > Utc::now() {
-                panic!("Date must be in the past");
+    let matches = cli::parse();
+    logging::init(
+        matches.get_flag("verbose"),
+        matches.get_one::<PathBuf>("log-file").map(PathBuf::as_path),
+    )?;
+
+    if let Some(dir) = matches.get_one::<PathBuf>("data-dir") {
+        paths::set_data_dir(dir.clone());
+    }
+    if let Some(dir) = matches.get_one::<PathBuf>("prefs-dir") {
+        paths::set_prefs_dir(dir.clone());
+    }
+    tokens::set_client_token(matches.get_one::<String>("token").cloned());
+
+    let config_path = match matches.get_one::<PathBuf>("config") {
+        Some(path) => path.clone(),
+        None => config::default_path()?,
+    };
+
+    let remote = matches.get_one::<String>("connect").map(String::as_str);
+    debug!(config = %config_path.display(), ?remote, subcommand = ?matches.subcommand_name(), "starting");
+
+    match matches.subcommand() {
+        Some(("add", sub)) => commands::add(
+            &config_path,
+            remote,
+            sub.get_one::<String>("NAME").expect("name is required"),
+            sub.get_one::<String>("DATE").expect("date is required"),
+            sub.get_one::<Tz>("timezone").copied(),
+        ),
+        Some(("list", _)) => commands::list(&config_path, remote, now_arg(&matches)?.now()),
+        Some(("remove", sub)) => commands::remove(
+            &config_path,
+            remote,
+            sub.get_one::<String>("NAME").expect("name is required"),
+        ),
+        Some(("reset", sub)) => commands::reset(
+            &config_path,
+            remote,
+            sub.get_one::<String>("NAME").expect("name is required"),
+            now_arg(&matches)?.now(),
+        ),
+        Some(("snooze", sub)) => commands::snooze(
+            &config_path,
+            remote,
+            sub.get_one::<String>("NAME").expect("name is required"),
+            sub.get_one::<String>("LENGTH")
+                .and_then(|name| SnoozeLength::from_name(name))
+                .expect("length is required"),
+            now_arg(&matches)?.now().date_naive(),
+        ),
+        Some(("serve", sub)) => {
+            let date = date_arg(sub)?;
+            let event = sub.get_one::<String>("NAME").cloned();
+            if date.is_none() && event.is_none() {
+                return Err(eyre!(cli::text().need_date));
+            }
+            let lang = sub
+                .get_one::<String>("lang")
+                .cloned()
+                .unwrap_or_else(detect_lang);
+            let date_format = sub
+                .get_one::<String>("date-format")
+                .expect("date-format has default");
+            check_date_format(date_format)?;
+            let addr = format!(
+                "{}:{}",
+                sub.get_one::<String>("bind").expect("bind has default"),
+                sub.get_one::<u16>("port").expect("port has default")
+            );
+            //a broken config is reported on the first request, not here
+            let config = Config::load_with_env(&config_path).ok();
+            let counting = config
+                .as_ref()
+                .and_then(|config| config.counting)
+                .unwrap_or_default();
+            let month_length = config
+                .and_then(|config| config.month_length)
+                .unwrap_or_default();
+            serve::serve(
+                &addr,
+                Counter {
+                    date,
+                    event,
+                    config_path,
+                    remote: remote.map(String::from),
+                    date_format: date_format.clone(),
+                    strings: strings(&lang),
+                    counting,
+                    month_length,
+                    time: now_arg(&matches)?,
+                },
+            )
+        }
+        Some(("history", sub)) => commands::history(
+            remote,
+            sub.get_one::<String>("NAME").map(String::as_str),
+            *sub.get_one::<u64>("limit").expect("limit has default") as usize,
+        ),
+        Some(("conflicts", sub)) => match sub.subcommand() {
+            Some((keep @ ("keep-mine" | "keep-theirs"), sub)) => commands::resolve_conflict(
+                remote,
+                *sub.get_one::<u64>("NUMBER").expect("number is required") as usize,
+                keep == "keep-mine",
+            ),
+            _ => commands::conflicts(remote),
+        },
+        Some(("daemon", sub)) => shared::serve(
+            sub.get_one::<String>("listen").expect("listen has default"),
+            config_path,
+        ),
+        Some(("import", sub)) => commands::import(
+            &config_path,
+            sub.get_one::<PathBuf>("FILE").expect("file is required"),
+            sub.get_one::<String>("filter").map(String::as_str),
+        ),
+        Some(("export", sub)) => commands::export_milestones(
+            &config_path,
+            remote,
+            sub.get_one::<PathBuf>("ics").expect("ics is required"),
+        ),
+        Some(("auth", sub)) => match sub.subcommand() {
+            Some(("set", sub)) => {
+                commands::auth_set(sub.get_one::<String>("NAME").expect("name is required"))
+            }
+            Some(("clear", sub)) => {
+                commands::auth_clear(sub.get_one::<String>("NAME").expect("name is required"))
+            }
+            _ => unreachable!("auth requires a subcommand"),
+        },
+        Some(("token", sub)) => match sub.subcommand() {
+            Some(("add", sub)) => commands::token_add(
+                sub.get_one::<String>("NAME").expect("name is required"),
+                sub.get_one::<String>("role").expect("role has default"),
+            ),
+            Some(("list", _)) => commands::token_list(),
+            Some(("remove", sub)) => {
+                commands::token_remove(sub.get_one::<String>("NAME").expect("name is required"))
+            }
+            _ => unreachable!("token requires a subcommand"),
+        },
+        Some(("monitors", sub)) => commands::monitors(sub.get_flag("json")),
+        Some(("autostart", sub)) => match sub.subcommand() {
+            Some(("enable", sub)) => {
+                let args: Vec<String> = sub
+                    .get_many::<String>("ARGS")
+                    .map(|args| args.cloned().collect())
+                    .unwrap_or_default();
+                commands::autostart(true, &args)
+            }
+            _ => commands::autostart(false, &[]),
+        },
+        Some(("selftest", _)) => selftest::run(&config_path),
+        Some(("update", sub)) => {
+            //a broken config shouldn't stop a fix being installed
+            let network = Config::load_with_env(&config_path)
+                .map(|config| config.network)
+                .unwrap_or_default();
+            net::init(&network)?;
+            update::run(sub.get_flag("check-only"))
+        }
+        Some(("show", sub)) => show(sub, sub.get_one::<String>("NAME"), &config_path),
+        Some(("card", sub)) => show(sub, sub.get_one::<String>("NAME"), &config_path),
+        Some(("check", sub)) => show(sub, sub.get_one::<String>("NAME"), &config_path),
+        Some(("stopwatch", sub)) => show(sub, None, &config_path),
+        _ => show(&matches, matches.get_one::<String>("event"), &config_path),
+    }
+}
+
+/// Loads the modes from the shared libraries at `paths`, ones that can't be loaded are
+/// skipped with an error so a broken plugin doesn't stop the counter opening
+fn load_plugins(paths: &[PathBuf]) -> Registry {
+    let mut registry = Registry::default();
+    for path in paths {
+        //plugins are trusted like the binary itself, they're only loaded from the config
+        match unsafe { External::load(path) } {
+            Ok(mode) => {
+                debug!(path = %path.display(), name = mode.name(), "loaded plugin");
+                if registry.register(Box::new(mode)).is_none() {
+                    eprintln!("Too many plugins, skipping {}", path.display());
+                }
+            }
+            Err(err) => eprintln!("Unable to load mode from {}: {err}", path.display()),
+        }
+    }
+    registry
+}
+
+/// Checks each value in `units` parses, so mistakes are found before the window opens
+fn parse_units(units: &[CustomUnit]) -> Result<Vec<(String, Formula)>> {
+    if units.len() > MAX_UNITS {
+        return Err(eyre!("units: only {MAX_UNITS} fit in diff mode"));
+    }
+    units
+        .iter()
+        .map(|unit| {
+            let formula =
+                Formula::parse(&unit.value).map_err(|err| eyre!("units: {}: {err}", unit.label))?;
+            Ok((unit.label.to_uppercase(), formula))
+        })
+        .collect()
+}
+
+/// Reads the bitmap font at `path`, or the built in one if not set
+fn load_glyphs(path: Option<&PathBuf>) -> Result<Glyphs> {
+    match path {
+        Some(path) => {
+            let image = open_image(path)
+                .wrap_err_with(|| format!("Loading font from {}", path.display()))?;
+            Glyphs::from_image(&image)
+                .map_err(|err| eyre!("Invalid font {}: {err}", path.display()))
+        }
+        None => Ok(Glyphs::large()),
+    }
+}
+
+/// Opens the counter window
+fn show(matches: &ArgMatches, event_name: Option<&String>, config_path: &Path) -> Result<()> {
+    let mut config = Config::load_with_env(config_path)?;
+    let mut updates = None;
+    if let Some(addr) = matches.get_one::<String>("connect") {
+        config.events = shared::list(addr)?;
+    }
+    if matches.get_flag("windows") {
+        return windows::open(&config.events);
+    }
+    //one of the windows opened by --windows
+    let window = matches.get_one::<String>("window");
+    let event_name = window.or(event_name);
+    //only `countup check` has --milestone, it's none to check for any milestone
+    let check = matches
+        .try_get_one::<u64>("milestone")
+        .ok()
+        .map(|milestone| milestone.copied());
+    let headless = matches.get_flag("headless")
+        || matches.get_flag("json")
+        || matches.get_flag("watch")
+        || matches.contains_id("statusbar")
+        || check.is_some();
+    //only `countup card` has --out
+    let card_path = matches.try_get_one::<PathBuf>("out").ok().flatten();
+    //prefs aren't needed when headless, so a missing or read only home dir is fine
+    let mut app_prefs = match AppPreferences::load() {
+        Ok(prefs) => prefs,
+        Err(_) if headless || card_path.is_some() => AppPreferences::unsaved(),
+        Err(err) => {
+            eprintln!("{err:#}, choices won't be remembered");
+            AppPreferences::unsaved()
+        }
+    };
+    let mut saved = app_prefs.get();
+    if matches.get_flag("forget") {
+        saved.date = None;
+        saved.event = None;
+    }
+
+    let show_term = matches.get_flag("term");
+    if show_term && config.term.is_none() {
+        return Err(eyre!(
+            "No term set in {}, add one like {}",
+            config_path.display(),
+            r#"{"term": {"start": "2026-09-01", "end": "2026-12-18", "breaks": [{"start": "2026-10-26", "end": "2026-10-30"}]}}"#
+        ));
+    }
+
+    let Settings {
+        lang,
+        date_format,
+        template,
+        animation,
+        reduced_motion,
+        sound,
+        font,
+        background,
+        background_style,
+        text_scale,
+        size,
+        scale,
+        numerals,
+        number_format,
+        capture_frames,
+        hours,
+        clock,
+        minimal,
+        high_contrast,
+        counting,
+        month_length,
+        fiscal_year,
+        patterns,
+    } = Settings::resolve(matches, &config, config_path)?;
+    let date_format = &date_format;
+    let separator = number_format.separator(strings(&lang));
+    let screensaver = matches.get_flag("screensaver") && !headless && card_path.is_none();
+    //the window covers the monitor, unless --size is set
+    let size = match size {
+        None if screensaver => Some(placement::filling(
+            matches
+                .get_one::<u64>("monitor")
+                .map(|monitor| *monitor as usize),
+            scale.map_or(WindowScaling::Auto, WindowScaling::Fixed),
+        )?),
+        size => size,
+    };
+    let glyphs = load_glyphs(font.as_ref())?;
+    let backdrop = background
+        .map(|path| {
+            open_image(&path)
+                .map(Backdrop::new)
+                .wrap_err_with(|| format!("Loading background from {}", path.display()))
+        })
+        .transpose()?;
+    let slideshow = matches
+        .get_one::<u64>("slideshow")
+        .map(|secs| std::time::Duration::from_secs(*secs));
+    let settings = vec![
+        ("easing", animation.easing.name().to_string()),
+        ("anim_speed", animation.time_per_year.to_string()),
+        ("sound", if sound { "on" } else { "off" }.to_string()),
+        (
+            "reduced_motion",
+            if reduced_motion { "on" } else { "off" }.to_string(),
+        ),
+        ("lang", lang.clone()),
+        ("numerals", numerals.name().to_string()),
+        ("number_format", number_format.name().to_string()),
+        ("month_length", month_length.name().to_string()),
+        (
+            "slideshow",
+            slideshow.map_or(String::from("off"), |secs| format!("{}s", secs.as_secs())),
+        ),
+        ("hours", format!("{hours}h")),
+        ("clock", if clock { "on" } else { "off" }.to_string()),
+        ("minimal", if minimal { "on" } else { "off" }.to_string()),
+        (
+            "high_contrast",
+            if high_contrast { "on" } else { "off" }.to_string(),
+        ),
+        ("background_style", background_style.name().to_string()),
+        (
+            "patterns",
+            if patterns.is_empty() {
+                String::from("off")
             } else {
-                calc_days_since(date)
+                let names: Vec<&str> = patterns.iter().map(|pattern| pattern.name()).collect();
+                names.join(",")
+            },
+        ),
+        (
+            "scale",
+            scale.map_or(String::from("auto"), |scale| format!("{scale}x")),
+        ),
+        (
+            "size",
+            size.map_or(String::from("auto"), |(width, height)| {
+                format!("{width}x{height}")
+            }),
+        ),
+        (
+            "fiscal_year",
+            fiscal_year.map_or(String::from("off"), |month| month.to_string()),
+        ),
+    ];
+    debug!(?settings, %date_format, ?counting, "settings");
+    let data_dir = paths::data_dir().map(|dir| dir.display().to_string());
+    let about = vec![
+        ("version", env!("CARGO_PKG_VERSION").to_string()),
+        ("build", build_info()),
+        ("config", config_path.display().to_string()),
+        ("data_dir", data_dir.unwrap_or_else(|err| err.to_string())),
+        ("backend", backend()),
+    ];
+
+    let default = NaiveDate::from_ymd_opt(2022, 11, 25).expect("Default date invalid?");
+
+    let time = now_arg(matches)?;
+    let date = date_arg(matches)?;
+    let between = between_arg(matches, time)?;
+    let goal = goal_arg(matches)?;
+    //only `countup stopwatch` has --restart
+    let stopwatch =
+        matches
+            .try_get_one::<bool>("restart")
+            .ok()
+            .flatten()
+            .map(|restart| match saved.stopwatch {
+                Some(started) if !restart => started,
+                _ => time.now(),
+            });
+    debug!(?time, ?date, ?between, ?goal, ?stopwatch, "parsed dates");
+    let units = parse_units(&config.units)?;
+    let font = match text_scale {
+        Some(scale) => NumberFont::new(glyphs, scale),
+        //cards are the same whatever the window size
+        None if card_path.is_some() => NumberFont::new(glyphs, 1),
+        //leaves room to move around
+        None if screensaver => fitting_font(
+            glyphs,
+            size.map(|(width, height)| (width / 2, height / 2)),
+            minimal,
+            goal.is_some(),
+            units.len(),
+        ),
+        None => fitting_font(glyphs, size, minimal, goal.is_some(), units.len()),
+    }
+    .with_numerals(numerals);
+    let birthday = matches
+        .get_one::<String>("birthday")
+        .map(|date| parse_date(date))
+        .transpose()?;
+    let born = matches
+        .get_one::<String>("born-at")
+        .map(|time| {
+            NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .map_err(|_| eyre!(cli::text().invalid_value(time, "--born-at")))
+        })
+        .transpose()?
+        .zip(birthday)
+        .map(|(time, date)| date.and_time(time));
+    //dates and times, for the hours milestones
+    let moment_format = format!("{date_format} {}", i18n::time_format(hours));
+    let pair = matches
+        .get_one::<String>("pair")
+        .map(|target| Pair::new(target, &config.events, date_format, animation, time))
+        .transpose()?;
+    //ages are never counted from the day of birth
+    let counting = if birthday.is_some() {
+        Counting::Exclusive
+    } else {
+        counting
+    };
+
+    let event =
+        match event_name {
+            None if date.is_some()
+                || between.is_some()
+                || birthday.is_some()
+                || stopwatch.is_some() =>
+            {
+                None
             }
+            //the event may have been removed from the config since it was saved
+            None => saved
+                .event
+                .as_ref()
+                .and_then(|name| config.event(name).cloned())
+                .or_else(|| slideshow.and(config.events.first().cloned())),
+            Some(name) => Some(config.event(name).cloned().ok_or_else(|| {
+                eyre!("{} ({})", cli::text().no_event(name), config_path.display())
+            })?),
+        };
+
+    let start_day = stopwatch
+        .map(|started| started.date_naive())
+        .or(between.map(|(from, _)| from))
+        .or(birthday)
+        .or(date)
+        .or(event.as_ref().map(|event| event.date))
+        .or(saved.date)
+        .unwrap_or(default);
+    let timezone = event.as_ref().and_then(|event| event.timezone);
+    let (start, days) = match between {
+        Some((from, to)) => (
+            midnight(from, None),
+            (to - from).num_days() as usize + counting.offset(),
+        ),
+        None => {
+            let (start, days) = calc_days_since(start_day, timezone, counting, time);
+            let paused = event.as_ref().map_or(0, |event| {
+                event.paused_days(local_date(time.now(), timezone), counting)
+            });
+            (start, days.saturating_sub(paused))
         }
     };
+    //the days are still whole days, T-minus mode shows the seconds from the moment itself
+    let start = stopwatch.unwrap_or(start);
+    let end = between.map(|(_, to)| to);
+    debug!(
+        event = ?event.as_ref().map(|event| &event.name),
+        %start_day,
+        ?timezone,
+        ?birthday,
+        %start,
+        days,
+        "counting from"
+    );
+    let heading = match (end, birthday) {
+        (Some(to), _) => Heading::Between(to.format(date_format).to_string()),
+        (None, Some(_)) => Heading::Age,
+        (None, None) => Heading::Since,
+    };
+
+    if let Some(path) = card_path {
+        let today = end.unwrap_or_else(|| local_date(time.now(), timezone));
+        let start = start_day.format(date_format).to_string();
+        let event_name = event.as_ref().map(|event| event.name.clone());
+        let report = report_for(start_day, today, event_name.clone(), &heading, counting)
+            .with_milestones(event.as_ref().map_or_else(
+                || Milestones::new(&patterns),
+                |event| event.milestones(&patterns),
+            ));
+        let split = if heading == Heading::Age {
+            report::Split::between(start_day, start_day + Duration::days(days as i64))
+        } else {
+            report::Split::new(days)
+        };
+        let shown = matches.get_flag("anniversary")
+            || heading == Heading::Age
+            || event
+                .as_ref()
+                .is_some_and(|event| event.anniversary == Some(true));
+        let extra = if matches.get_flag("leap-days") {
+            Some(SplitRow::LeapDays(report::leap_days(start_day, today)))
+        } else {
+            (shown && end.is_none() && start_day <= today)
+                .then(|| SplitRow::Anniversary(days_to_anniversary(start_day, today)))
+        };
+        let mut texts = Texts::new(strings(&lang))
+            .with_heading(heading)
+            .with_minimal(minimal)
+            .with_month_length(month_length)
+            .with_separator(separator);
+        texts.set_header(
+            template
+                .as_ref()
+                .map(|template| report.fill_header(template, &start)),
+        );
+        texts.set_celebration(
+            event
+                .as_ref()
+                .filter(|_| report.days_until.is_none())
+                .and_then(|event| event.custom_milestone(report.days))
+                .cloned(),
+        );
+        texts.set_excluded(
+            event
+                .as_ref()
+                .map_or(0, |event| event.noted_pauses(today, counting)),
+        );
+        let palette = Palette::of(event.as_ref()).with_contrast(high_contrast);
+        let card = Card {
+            label: matches.get_one::<String>("label").cloned().or(event_name),
+            palette,
+            backdrop: backdrop.as_ref(),
+            background_style,
+        };
+        let reveal = Reveal {
+            intro: Intro::CountUp,
+            progress: 1.0,
+        };
+        card.save(path, canvas_size(&font, minimal), |graphics| {
+            render_split(
+                graphics, split, extra, &start, reveal, &font, palette, &texts,
+            )
+        })?;
+        println!("{}", path.display());
+        return Ok(());
+    }
+
+    if let Some(milestone) = check {
+        //nothing is printed, so cron doesn't mail it every day
+        let hit = end.is_none()
+            && start_day <= local_date(time.now(), timezone)
+            && match milestone {
+                Some(milestone) => days as u64 == milestone,
+                None => event
+                    .as_ref()
+                    .map_or_else(
+                        || Milestones::new(&patterns),
+                        |event| event.milestones(&patterns),
+                    )
+                    .milestone(days)
+                    .is_some(),
+            };
+        std::process::exit(if hit { 0 } else { 1 });
+    }
+
+    if headless {
+        //remembered choices aren't changed as headless runs are usually scripted
+        let event_name = event.as_ref().map(|event| event.name.clone());
+        let start = start_day.format(date_format).to_string();
+        let strings = strings(&lang);
+        let output = || {
+            let today = end.unwrap_or_else(|| local_date(time.now(), timezone));
+            let paused = event
+                .as_ref()
+                .map_or(0, |event| event.paused_days(today, counting));
+            let mut report = report_for(start_day, today, event_name.clone(), &heading, counting)
+                .with_milestones(event.as_ref().map_or_else(
+                    || Milestones::new(&patterns),
+                    |event| event.milestones(&patterns),
+                ))
+                .with_paused(paused)
+                .with_month_length(month_length)
+                .with_separator(separator);
+            report.fiscal = fiscal_year.map(|month| FiscalYear::new(start_day, today, month));
+            report.header = template
+                .as_ref()
+                .map(|template| report.fill_header(template, &start));
+            report.age = born.map(|born| PreciseAge::new(born, time.local().naive_local()));
+            headless_output(matches, &report, &start, &heading, &moment_format, strings)
+        };
+        if !matches.get_flag("watch") {
+            println!("{}", output()?);
+            return Ok(());
+        }
+        let interval = *matches
+            .get_one::<u64>("interval")
+            .expect("interval has default");
+        let in_place = stdout().is_terminal();
+        let mut last: Option<String> = None;
+        loop {
+            let text = output()?;
+            if in_place {
+                if let Some(last) = &last {
+                    //move back to the start of the previous output and clear it
+                    print!("\x1b[{}F\x1b[J", last.lines().count());
+                }
+                println!("{text}");
+            } else if last.as_ref() != Some(&text) {
+                println!("{text}");
+            }
+            stdout().flush()?;
+            last = Some(text);
+            thread::sleep(std::time::Duration::from_secs(interval));
+        }
+    }
+
+    //held until the window closes, the screensaver, stopwatch and --windows are meant to be
+    //extra
+    let extra = window.is_some() || screensaver || stopwatch.is_some();
+    let _instance = if !extra && !matches.get_flag("new-instance") {
+        let start_hidden = matches.get_flag("start-hidden");
+        match instance::claim(event_name.map(String::as_str), !start_hidden) {
+            Some(guard) => Some(guard),
+            None => {
+                println!("countup is already running, use --new-instance to open another");
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+    if date.is_some() {
+        saved.date = date;
+        saved.event = None;
+    }
+    if stopwatch.is_some() {
+        saved.stopwatch = stopwatch;
+    }
+    let today = time.now().date_naive();
+    let show_digest = config.weekly_digest.unwrap_or_default()
+        && saved
+            .digest_shown
+            .is_none_or(|shown| shown.iso_week() != today.iso_week());
+    if show_digest {
+        saved.digest_shown = Some(today);
+    }
+    //the windows from --windows would each overwrite the others' choices
+    if window.is_none() {
+        app_prefs.set(saved.clone());
+        app_prefs.save();
+    }
+
+    if let Some(addr) = matches.get_one::<String>("connect") {
+        updates = Some(shared::subscribe(addr.clone()));
+    }
+    net::init(&config.network)?;
+    //send anything that couldn't be delivered last time
+    thread::spawn(outbox::flush);
+    //closes the window so prefs are saved, the same as pressing escape
+    shutdown::install(|| {});
+    frames::start(capture_frames);
+
+    let term = config.term.map(|term| with_label(term, date_format));
+    //the daemon sends its own changes
+    let config_watch = updates
+        .is_none()
+        .then(|| FileWatch::new(config_path.to_path_buf()));
+
+    let mut initial_modes = vec![];
+    if stopwatch.is_some() {
+        initial_modes.push(TMinus);
+    }
+    if is_anniversary(counting.elapsed(days)) {
+        initial_modes.push(Mode::Recap);
+    }
+    if pair.is_some() {
+        initial_modes.push(Dual);
+    }
+    if show_digest {
+        initial_modes.push(Digest);
+    }
+    if show_term {
+        initial_modes.push(Term);
+    }
+    if born.is_some() {
+        initial_modes.push(Age);
+    }
+    initial_modes.extend(saved.mode);
+    if event.iter().any(|event| !event.phases.is_empty()) {
+        initial_modes.push(Phases);
+    }
 
     ui(
         days,
-        format!("{:0>2}/{:0>2}/{}", start.day(), start.month(), start.year()),
+        match stopwatch {
+            Some(started) => started
+                .with_timezone(&Local)
+                .format(&moment_format)
+                .to_string(),
+            None => start_day.format(date_format).to_string(),
+        },
         start,
+        strings(&lang),
+        Extras {
+            term,
+            event,
+            initial_modes,
+            hooks: config.hooks,
+            mqtt: config.mqtt,
+            date_format: date_format.clone(),
+            template,
+            pair,
+            fiscal_year,
+            patterns,
+            updates,
+            events: config.events,
+            animation,
+            config_watch,
+            sound,
+            font,
+            backdrop,
+            background_style,
+            size,
+            scale,
+            position: matches
+                .get_one::<Position>("position")
+                .copied()
+                .or(screensaver.then_some(Position::At(0, 0))),
+            monitor: matches
+                .get_one::<u64>("monitor")
+                .map(|monitor| *monitor as usize),
+            start_hidden: matches.get_flag("start-hidden"),
+            screensaver,
+            desktop: matches.get_flag("desktop"),
+            settings,
+            about,
+            slideshow,
+            end,
+            heading,
+            window: window.cloned(),
+            anniversary: matches.get_flag("anniversary"),
+            leap_days: matches.get_flag("leap-days"),
+            roman: matches.get_flag("roman"),
+            keys: Keys::new(&config.keys).wrap_err("Reading the keys from the config")?,
+            plugins: load_plugins(&config.plugins),
+            units,
+            hours,
+            counting,
+            clock: clock.then(|| Clock::new(date_format, hours)),
+            born,
+            minimal,
+            high_contrast,
+            reduced_motion,
+            separator,
+            month_length,
+            calendar: matches.get_flag("calendar"),
+            stopwatch: stopwatch.is_some(),
+            time,
+            goal: goal.map(|goal| {
+                let label = match goal {
+                    Goal::Days(days) => format!("{days} {}", strings(&lang).days_lower),
+                    Goal::Date(date) => date.format(date_format).to_string(),
+                };
+                (goal, label)
+            }),
+            config_path: config_path.to_path_buf(),
+            connect: matches.get_one::<String>("connect").cloned(),
+        },
+        app_prefs,
+    )
+}
+
+/// Text for `--headless`, `--json` and `--statusbar`
+fn headless_output(
+    matches: &ArgMatches,
+    report: &Report,
+    start: &str,
+    heading: &Heading,
+    moment_format: &str,
+    strings: &Strings,
+) -> Result<String> {
+    if let Some(format) = matches.get_one::<String>("statusbar") {
+        let template = matches
+            .get_one::<String>("statusbar-template")
+            .expect("statusbar-template has default");
+        let text = report.fill(template, start);
+        if format == "waybar" {
+            let json = serde_json::json!({
+                "text": text,
+                "tooltip": report_lines(report, start, heading, moment_format, strings).join("\n"),
+                "class": report.class(),
+            });
+            Ok(json.to_string())
+        } else {
+            Ok(text)
+        }
+    } else if matches.get_flag("json") {
+        //one line per update when watching
+        if matches.get_flag("watch") {
+            Ok(serde_json::to_string(report)?)
+        } else {
+            Ok(serde_json::to_string_pretty(report)?)
+        }
+    } else {
+        Ok(report_lines(report, start, heading, moment_format, strings).join("\n"))
+    }
+}
+
+/// [Report::new_with], with calendar years and months for `--birthday`
+fn report_for(
+    start: NaiveDate,
+    today: NaiveDate,
+    event: Option<String>,
+    heading: &Heading,
+    counting: Counting,
+) -> Report {
+    let mut report = Report::new_with(start, today, event, counting);
+    if *heading == Heading::Age {
+        report.split = report::Split::between(start, today);
+    }
+    debug!(
+        %start,
+        %today,
+        ?counting,
+        days = report.days,
+        weeks = report.weeks,
+        months = report.months,
+        years = report.years,
+        split = ?report.split,
+        days_until = ?report.days_until,
+        "breakdown"
+    );
+    report
+}
+
+/// [Report::lines] with the title for `heading`, and the days until the next birthday for
+/// `--birthday`, and with `--born-at` the age to the minute and when it's next
+/// [count::HOURS_MILESTONE] hours old, in `moment_format`
+fn report_lines(
+    report: &Report,
+    start: &str,
+    heading: &Heading,
+    moment_format: &str,
+    strings: &Strings,
+) -> Vec<String> {
+    let mut lines = report.lines(start, strings);
+    if report.days_until.is_some() {
+        return lines;
+    }
+    lines[0] = match &report.header {
+        Some(header) => header.clone(),
+        None => heading.title(strings, start),
+    };
+    if *heading == Heading::Age {
+        let days = days_to_anniversary(
+            report.start,
+            report.start + Duration::days(report.days as i64),
+        );
+        lines.insert(2, format!("{days} {}", strings.to_birthday));
+    }
+    if let Some(age) = report.age {
+        let [summary, milestone] = age.lines(moment_format, strings);
+        lines[1] = summary;
+        lines.push(format!("{} {}", age.total_hours, strings.total_hours));
+        lines.push(format!("{} {}", age.total_minutes, strings.total_minutes));
+        lines.push(milestone);
+    }
+    lines
+}
+
+/// Reads `--now`, a date (at midnight UTC) or an RFC 3339 time
+fn now_arg(matches: &ArgMatches) -> Result<TimeSource> {
+    let Some(now) = matches.get_one::<String>("now") else {
+        return Ok(TimeSource::System);
+    };
+    match DateTime::parse_from_rfc3339(now.trim()) {
+        Ok(time) => Ok(TimeSource::Fixed(time.with_timezone(&Utc))),
+        Err(_) => parse_date(now).map(|date| TimeSource::Fixed(midnight(date, None))),
+    }
+}
+
+/// Reads `--from` and `--to`, which defaults to today
+fn between_arg(matches: &ArgMatches, time: TimeSource) -> Result<Option<(NaiveDate, NaiveDate)>> {
+    let Some(from) = matches.get_one::<String>("from") else {
+        return Ok(None);
+    };
+    let from = parse_date(from)?;
+    let to = match matches.get_one::<String>("to") {
+        Some(to) => parse_date(to)?,
+        None => time.now().date_naive(),
+    };
+    if to < from {
+        return Err(eyre!(
+            cli::text().to_before_from(&to.to_string(), &from.to_string())
+        ));
+    }
+    Ok(Some((from, to)))
+}
+
+/// Reads `--goal`, up to 6 digits is a number of days as longer ones could be dates like
+/// `20270101`
+fn goal_arg(matches: &ArgMatches) -> Result<Option<Goal>> {
+    let Some(goal) = matches.get_one::<String>("goal") else {
+        return Ok(None);
+    };
+    match goal.parse::<usize>() {
+        Ok(0) => Err(eyre!("--goal must be at least 1 day")),
+        Ok(days) if goal.len() <= 6 => Ok(Some(Goal::Days(days))),
+        _ => parse_date(goal).map(|date| Some(Goal::Date(date))),
+    }
+}
+
+/// Reads `--date`, from stdin if it's `-`, or the first line of `--date-file`
+fn date_arg(matches: &ArgMatches) -> Result<Option<NaiveDate>> {
+    if let Some(path) = matches.get_one::<PathBuf>("date-file") {
+        let text =
+            fs::read_to_string(path).wrap_err_with(|| format!("Reading {}", path.display()))?;
+        return parse_date(text.lines().next().unwrap_or_default()).map(Some);
+    }
+    match matches.get_one::<String>("date").map(String::as_str) {
+        Some("-") => {
+            let mut line = String::new();
+            stdin()
+                .lock()
+                .read_line(&mut line)
+                .wrap_err("Reading the date from stdin")?;
+            parse_date(&line).map(Some)
+        }
+        Some(date) => parse_date(date).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Reads a date passed on the command line, see [date::parse_date] for the formats
+pub fn parse_date(date: &str) -> Result<NaiveDate> {
+    date::parse_date(date).ok_or_else(|| eyre!(cli::text().invalid_date(date.trim())))
+}
+
+pub fn check_date_format(date_format: &str) -> Result<()> {
+    if StrftimeItems::new(date_format).any(|item| item == Item::Error) {
+        return Err(eyre!(cli::text().invalid_date_format(date_format)));
+    }
+    Ok(())
+}
+
+/// Edits that fit in history mode, also used for the digest
+const HISTORY_ROWS: usize = 4;
+/// Finished streaks shown in streaks mode, the most recent
+const STREAK_ROWS: usize = 5;
+/// How long each page of the digest is shown for before moving to the next
+const DIGEST_PAGE: std::time::Duration = std::time::Duration::from_secs(8);
+/// Events listed in the right click menu, with snooze, editing, copy and quit this fills
+/// the window
+const MENU_EVENTS: usize = 5;
+/// Extra keys that close the help overlay, as only keys asked for are reported
+const ANY_KEY: [VirtualKeyCode; 29] = [
+    VirtualKeyCode::A,
+    VirtualKeyCode::B,
+    VirtualKeyCode::D,
+    VirtualKeyCode::E,
+    VirtualKeyCode::G,
+    VirtualKeyCode::J,
+    VirtualKeyCode::K,
+    VirtualKeyCode::L,
+    VirtualKeyCode::M,
+    VirtualKeyCode::N,
+    VirtualKeyCode::O,
+    VirtualKeyCode::Q,
+    VirtualKeyCode::S,
+    VirtualKeyCode::T,
+    VirtualKeyCode::U,
+    VirtualKeyCode::V,
+    VirtualKeyCode::W,
+    VirtualKeyCode::X,
+    VirtualKeyCode::Y,
+    VirtualKeyCode::Z,
+    VirtualKeyCode::Tab,
+    VirtualKeyCode::Back,
+    VirtualKeyCode::Delete,
+    VirtualKeyCode::Home,
+    VirtualKeyCode::Up,
+    VirtualKeyCode::Down,
+    VirtualKeyCode::Left,
+    VirtualKeyCode::Right,
+    VirtualKeyCode::Insert,
+];
+
+/// Keys listed in the help overlay
+fn shortcuts(strings: &Strings, keys: &Keys) -> [(String, &'static str); 11] {
+    [
+        (keys.next_mode.name(), strings.next_mode),
+        (String::from("Enter"), strings.skip),
+        (String::from("P"), strings.pause),
+        (keys.restart.name(), strings.restart),
+        (String::from("I"), strings.next_intro),
+        (String::from("C"), strings.copy_summary),
+        (String::from("E"), strings.change_date),
+        (String::from("L"), strings.edit_events),
+        (keys.save_frames.name(), strings.save_frames),
+        (String::from("A"), strings.about),
+        (keys.quit.name(), strings.quit),
+    ]
+}
+
+/// Profile, target and optional features
+fn build_info() -> String {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    let sound = if cfg!(feature = "sound") {
+        "with sound"
+    } else {
+        "no sound"
+    };
+    format!(
+        "{profile} {}-{}, {sound}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
     )
 }
 
-fn calc_days_since(date: DateTime<Utc>) -> (DateTime<Utc>, usize) {
-    let diff = Utc::now() - date;
-    (date, diff.num_days() as usize)
+/// What the window is drawn with
+fn backend() -> String {
+    let windowing = if cfg!(target_os = "linux") {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            "Wayland"
+        } else {
+            "X11"
+        }
+    } else {
+        std::env::consts::OS
+    };
+    format!("pixels (wgpu) on {windowing}")
 }
 
-const COUNT_TIME_PER_YEAR: f64 = 1.0;
-const COL_NUM: isize = 120;
-const COL_PERIOD: isize = 128;
+/// Optional data from the config file
+#[derive(Debug, Default)]
+struct Extras {
+    /// Term and its formatted dates
+    term: Option<(Term, String)>,
+    event: Option<Event>,
+    /// Modes to start in, in order of preference, if none are available the first mode is used
+    initial_modes: Vec<Mode>,
+    hooks: Hooks,
+    mqtt: Option<MqttConfig>,
+    date_format: String,
+    /// From `--template`
+    template: Option<String>,
+    pair: Option<Pair>,
+    fiscal_year: Option<u32>,
+    patterns: Vec<Pattern>,
+    /// Events from the daemon, sent whenever they change
+    updates: Option<Receiver<Update>>,
+    /// Events that can be switched to over D-Bus
+    events: Vec<Event>,
+    animation: Animation,
+    /// Config file to reload when edited
+    config_watch: Option<FileWatch>,
+    sound: bool,
+    font: NumberFont,
+    backdrop: Option<Backdrop>,
+    background_style: BackgroundStyle,
+    /// From `--size`
+    size: Option<(usize, usize)>,
+    /// From `--scale`
+    scale: Option<usize>,
+    /// From `--position` and `--monitor`
+    position: Option<Position>,
+    monitor: Option<usize>,
+    /// Wait for D-Bus to show the window, from `--start-hidden`
+    start_hidden: bool,
+    /// Fill the monitor and move the count around it, from `--screensaver`
+    screensaver: bool,
+    /// Below other windows and click-through, from `--desktop`
+    desktop: bool,
+    /// Settings that can't change while open, for the help overlay
+    settings: Vec<(&'static str, String)>,
+    /// Version, paths and so on for the about overlay
+    about: Vec<(&'static str, String)>,
+    /// How long to show each event for before switching to the next
+    slideshow: Option<std::time::Duration>,
+    /// `--to`, the count doesn't go up while this is set
+    end: Option<NaiveDate>,
+    heading: Heading,
+    /// Event this window was opened for by `--windows`
+    window: Option<String>,
+    /// Show the days until the next anniversary for any date, not just events with it set
+    anniversary: bool,
+    leap_days: bool,
+    roman: bool,
+    keys: Keys,
+    /// Modes from `plugins` in the config
+    plugins: Registry,
+    /// Extra rows for diff mode from `units` in the config
+    units: Vec<(String, Formula)>,
+    hours: u8,
+    counting: Counting,
+    clock: Option<Clock>,
+    /// Date and time of birth, from `--birthday` and `--born-at`
+    born: Option<NaiveDateTime>,
+    minimal: bool,
+    high_contrast: bool,
+    reduced_motion: bool,
+    /// From `--number-format`
+    separator: &'static str,
+    month_length: MonthLength,
+    calendar: bool,
+    stopwatch: bool,
+    time: TimeSource,
+    /// From `--goal`, with the days or date to show
+    goal: Option<(Goal, String)>,
+    config_path: PathBuf,
+    connect: Option<String>,
+}
+
+/// Term with its formatted dates, for showing in term mode, and its public holidays
+fn with_label(mut term: Term, date_format: &str) -> (Term, String) {
+    if let Some(region) = &term.holidays {
+        //without them the term is still shown, only counting the breaks
+        match holidays::between(region, term.start, term.end) {
+            Ok(dates) => term.holiday_dates = dates,
+            Err(err) => eprintln!("Unable to load holidays: {err:#}"),
+        }
+    }
+    let label = format!(
+        "{} - {}",
+        term.start.format(date_format),
+        term.end.format(date_format)
+    );
+    (term, label)
+}
+
+/// Target from `--pair`, counted down next to the count in dual mode
+#[derive(Debug)]
+struct Pair {
+    /// Event name or formatted date
+    label: String,
+    date: NaiveDate,
+    timezone: Option<Tz>,
+    palette: Palette,
+    count: Count,
+    time: TimeSource,
+}
+
+impl Pair {
+    /// `target` is the name of an event in the config or a date
+    fn new(
+        target: &str,
+        events: &[Event],
+        date_format: &str,
+        animation: Animation,
+        time: TimeSource,
+    ) -> Result<Self> {
+        let event = events
+            .iter()
+            .find(|event| event.name.eq_ignore_ascii_case(target));
+        let (label, date, timezone) = match event {
+            Some(event) => (event.name.clone(), event.date, event.timezone),
+            None => {
+                let date = parse_date(target)
+                    .wrap_err_with(|| format!("{target} isn't an event in the config"))?;
+                (date.format(date_format).to_string(), date, None)
+            }
+        };
+        let mut pair = Pair {
+            label,
+            date,
+            timezone,
+            palette: Palette::of(event),
+            count: Count::new(0, animation),
+            time,
+        };
+        pair.count.count_to(pair.days());
+        Ok(pair)
+    }
+
+    /// Days to go, 0 once it's passed
+    fn days(&self) -> usize {
+        let today = local_date(self.time.now(), self.timezone);
+        (self.date - today).num_days().max(0) as usize
+    }
+}
 
 struct Countup {
-    days: usize,
+    count: Count,
     start: String,
     start_date: DateTime<Utc>,
+    /// Timezone the days go up in, UTC if not set
+    timezone: Option<Tz>,
+    /// When the days next go up
+    next_rollover: DateTime<Utc>,
+    /// When each of the other events' days next go up, so their hooks fire at their own
+    /// midnight
+    rollovers: Vec<(Event, DateTime<Utc>)>,
     should_exit: bool,
-    current_days: usize,
-    next_inc_speed: f64,
-    next_inc: f64,
-    diff_mode: bool,
+    mode: Mode,
+    /// Modes space cycles through
+    modes: Vec<Mode>,
+    remaining: Duration,
+    strings: &'static Strings,
+    /// Term and its formatted dates
+    term: Option<(Term, String)>,
+    term_progress: Option<TermProgress>,
+    event: Option<Event>,
+    phase_progress: Option<PhaseProgress>,
+    /// Date term and phase progress were last calculated
+    progress_checked: NaiveDate,
+    prefs: AppPreferences,
+    hooks: Hooks,
+    date_format: String,
+    /// From `--template`
+    template: Option<String>,
+    /// Target counted down next to the count in dual mode, from `--pair`
+    pair: Option<Pair>,
+    /// Month the fiscal year starts in, from `--fiscal-year`
+    fiscal_year: Option<u32>,
+    /// Extra milestones, from `--patterns`
+    patterns: Vec<Pattern>,
+    /// Fiscal mode's numbers and header, rebuilt when the day changes
+    fiscal: Option<(FiscalYear, String)>,
+    /// Year mode's numbers and header, rebuilt when the day changes
+    year: Option<(YearProgress, String)>,
+    /// Upcoming mode's milestones and their dates, rebuilt when the day changes
+    upcoming: Option<(Upcoming, [String; 3])>,
+    /// Timeline mode's line and its labels, rebuilt when the day changes
+    timeline: Option<(Timeline, [String; 3])>,
+    /// The window's, with the count, rebuilt when the day changes
+    title: String,
+    /// Weekdays mode's counts and header, rebuilt when the day changes
+    weekdays: Option<(Weekdays, String)>,
+    /// Date and time of birth with age mode's header, from `--born-at`
+    born: Option<(NaiveDateTime, String)>,
+    /// Age mode's numbers and lines, rebuilt when the minute changes
+    age: Option<(PreciseAge, [String; 2])>,
+    updates: Option<Receiver<Update>>,
+    /// Recent edits from the daemon, newest first
+    history: Vec<AuditEntry>,
+    events: Vec<Event>,
+    dbus: Option<dbus::Handle>,
+    mqtt: Option<Publisher>,
+    config_watch: Option<FileWatch>,
+    /// Only set if sound is on
+    sounds: Option<Sounds>,
+    /// For the big numbers
+    font: NumberFont,
+    /// From `--background`
+    backdrop: Option<Backdrop>,
+    background_style: BackgroundStyle,
+    /// From `--size`
+    size: Option<(usize, usize)>,
+    /// For Ctrl+F, saving the last frames
+    ctrl_held: bool,
+    /// For capitals in the event editor
+    shift_held: bool,
+    overlay: Option<Overlay>,
+    /// Open event editor, drawn over the overlays
+    editor: Option<Editor>,
+    settings: Vec<(&'static str, String)>,
+    about: Vec<(&'static str, String)>,
+    /// Open right click menu
+    menu: Option<Menu>,
+    /// Kept open as on some platforms the copied text is lost when it's dropped
+    clipboard: Option<arboard::Clipboard>,
+    texts: Texts,
+    /// Date, text and colour of each digest row, only rebuilt when the events or day change
+    digest: Vec<(String, String, Color)>,
+    /// Page of [HISTORY_ROWS] digest rows shown, and when it moves on to the next
+    digest_page: (usize, Instant),
+    /// Time and text of each history row for the current event
+    history_rows: Vec<(String, String)>,
+    streaks: StreakRows,
+    slideshow: Option<std::time::Duration>,
+    /// When the slideshow moves on to the next event
+    next_slide_at: Instant,
+    /// `--to`, the count doesn't go up while this is set
+    end: Option<NaiveDate>,
+    /// Title for `--from` and `--birthday`, which also shows the split mode as an age
+    heading: Heading,
+    budget: FrameBudget,
+    idle: IdleRender,
+    /// 12 or 24 hour times in history mode
+    hours: u8,
+    /// Whether the start day is counted, from `--inclusive`
+    counting: Counting,
+    /// Date and time in the corner, from `--clock`
+    clock: Option<Clock>,
+    /// Only the numbers, from `--minimal`
+    minimal: bool,
+    /// From `--high-contrast`
+    high_contrast: bool,
+    /// Jump to the new day instead of flashing, and keep the background style still
+    reduced_motion: bool,
+    /// Between each group of three digits, from `--number-format`
+    separator: &'static str,
+    /// Only calendar mode, from `--calendar`
+    calendar: bool,
+    /// From `countup stopwatch`, until the event or date is changed
+    stopwatch: bool,
+    /// From `--now`, otherwise the system clock
+    time: TimeSource,
+    /// For the months in diff mode, from `--month-length`
+    month_length: MonthLength,
+    /// From `--goal`, with the days or date to show
+    goal: Option<(Goal, String)>,
+    /// Where resetting a streak is saved, the daemon if connected to one
+    config_path: PathBuf,
+    connect: Option<String>,
+    /// Event this window was opened for by `--windows`, it has its own position and doesn't
+    /// change the remembered choices
+    window: Option<String>,
+    /// Opened at `--position` or on `--monitor`, so the remembered position isn't changed
+    placed: bool,
+    /// Moves the count around the window, from `--screensaver`
+    screensaver: Option<Screensaver>,
+    /// From `--anniversary`, events can also turn it on
+    anniversary: bool,
+    /// Days until the next anniversary, if shown under split mode
+    to_anniversary: Option<usize>,
+    /// From `--leap-days`, shown instead of the anniversary
+    leap_days: bool,
+    /// 29 Februaries since the start, if shown under split mode
+    leap_days_since: Option<usize>,
+    /// From `--roman`, adds roman mode
+    roman: bool,
+    /// Start date in Roman numerals, for roman mode
+    roman_start: String,
+    /// Shortcuts, changed with `keys` in the config
+    keys: Keys,
+    plugins: Registry,
+    /// What the modes in `plugins` are drawn from, kept as they're drawn every frame
+    plugin_report: Option<Report>,
+}
+
+/// Panels drawn over the current mode
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Overlay {
+    /// Keys and settings
+    Help,
+    /// Version, paths and backend, opened from the help
+    About,
+    /// Asks before resetting a streak, opened with Backspace
+    ConfirmReset,
+    /// Changes the start date until closed, opened with E
+    DatePicker(DatePicker),
+}
+
+#[allow(clippy::too_many_arguments)]
+fn available_modes(
+    start_date: DateTime<Utc>,
+    days: usize,
+    has_term: bool,
+    event: Option<&Event>,
+    connected: bool,
+    has_events: bool,
+    paired: bool,
+    fiscal: bool,
+    born: bool,
+    roman: bool,
+    plugins: usize,
+    stopwatch: bool,
+    time: TimeSource,
+) -> Vec<Mode> {
+    let now = time.now();
+    let mut modes = date_modes(start_date, time);
+    if stopwatch && start_date <= now {
+        modes.insert(0, TMinus);
+    }
+    if paired && start_date <= now {
+        modes.push(Dual);
+    }
+    if fiscal && start_date <= now {
+        modes.push(Fiscal);
+    }
+    if born && start_date <= now {
+        modes.push(Age);
+    }
+    if roman && start_date <= now {
+        modes.push(Mode::Roman);
+    }
+    if has_term {
+        modes.push(Term);
+    }
+    if event.iter().any(|event| !event.phases.is_empty()) {
+        modes.push(Phases);
+    }
+    if connected && event.is_some() {
+        modes.push(History);
+    }
+    if event.iter().any(|event| !event.resets.is_empty()) {
+        modes.push(Streaks);
+    }
+    if start_date <= now && Recap::latest(days).is_some() {
+        modes.push(Mode::Recap);
+    }
+    if has_events {
+        modes.push(Digest);
+    }
+    modes.extend((0..plugins.min(u8::MAX as usize + 1)).map(|index| Mode::Plugin(index as u8)));
+    modes
 }
 
 impl Countup {
-    pub fn new(days: usize, start: String, start_date: DateTime<Utc>) -> Self {
-        let f_days = days as f64;
-        let next_inc_speed =
-            ((f_days / 365.0) * COUNT_TIME_PER_YEAR).max(COUNT_TIME_PER_YEAR) / f_days;
-        Self {
+    pub fn new(
+        days: usize,
+        start: String,
+        start_date: DateTime<Utc>,
+        strings: &'static Strings,
+        extras: Extras,
+        prefs: AppPreferences,
+    ) -> Self {
+        let remaining = start_date - extras.time.now();
+        let screensaver = extras.screensaver.then(|| {
+            Screensaver::new(window_size(
+                &extras.font,
+                extras.minimal,
+                extras.goal.is_some(),
+                extras.units.len(),
+                None,
+            ))
+        });
+        let mut modes = available_modes(
             start_date,
-            days,
+            extras.counting.elapsed(days),
+            extras.term.is_some(),
+            extras.event.as_ref(),
+            extras.updates.is_some(),
+            !extras.events.is_empty(),
+            extras.pair.is_some(),
+            extras.fiscal_year.is_some(),
+            extras.born.is_some(),
+            extras.roman,
+            extras.plugins.len(),
+            extras.stopwatch,
+            extras.time,
+        );
+        if extras.minimal {
+            modes.retain(|mode| mode.is_count());
+        }
+        if extras.calendar {
+            modes = vec![Calendar];
+        }
+        let mode = extras
+            .initial_modes
+            .into_iter()
+            .find(|mode| modes.contains(mode))
+            .unwrap_or(modes[0]);
+        let timezone = extras.event.as_ref().and_then(|event| event.timezone);
+        //the day rolls over at midnight in the event's timezone
+        let today = local_date(extras.time.now(), timezone);
+        let term_progress = extras.term.as_ref().map(|(term, _)| term.progress(today));
+        let phase_progress = extras
+            .event
+            .as_ref()
+            .map(|event| event.phase_progress(today));
+        let born = extras.born.map(|born| (born, strings.age(&start)));
+        let mut countup = Self {
+            start_date,
+            timezone,
+            next_rollover: next_midnight(extras.time.now(), timezone),
+            rollovers: vec![],
+            count: Count::new(days, extras.animation),
             start,
             should_exit: false,
-            current_days: 0,
-            next_inc_speed,
-            next_inc: 0.0,
-            diff_mode: false,
+            mode,
+            modes,
+            remaining,
+            strings,
+            term: extras.term,
+            term_progress,
+            event: extras.event,
+            phase_progress,
+            progress_checked: today,
+            prefs,
+            hooks: extras.hooks,
+            mqtt: extras.mqtt.map(Publisher::start),
+            date_format: extras.date_format,
+            template: extras.template,
+            pair: extras.pair,
+            fiscal_year: extras.fiscal_year,
+            patterns: extras.patterns,
+            fiscal: None,
+            year: None,
+            upcoming: None,
+            timeline: None,
+            title: String::new(),
+            weekdays: None,
+            born,
+            age: None,
+            updates: extras.updates,
+            history: vec![],
+            events: extras.events,
+            dbus: None,
+            config_watch: extras.config_watch,
+            sounds: extras.sound.then(sound::start).flatten(),
+            font: extras.font,
+            backdrop: extras.backdrop,
+            background_style: extras.background_style,
+            size: extras.size,
+            ctrl_held: false,
+            shift_held: false,
+            overlay: None,
+            editor: None,
+            settings: extras.settings,
+            about: extras.about,
+            menu: None,
+            clipboard: None,
+            texts: Texts::new(strings)
+                .with_heading(extras.heading.clone())
+                .with_minimal(extras.minimal)
+                .with_month_length(extras.month_length)
+                .with_separator(extras.separator)
+                .with_units(extras.units),
+            digest: vec![],
+            digest_page: (0, Instant::now() + DIGEST_PAGE),
+            history_rows: vec![],
+            streaks: StreakRows::default(),
+            slideshow: extras.slideshow,
+            next_slide_at: Instant::now() + extras.slideshow.unwrap_or_default(),
+            end: extras.end,
+            heading: extras.heading,
+            budget: FrameBudget::default(),
+            idle: IdleRender::default(),
+            hours: extras.hours,
+            counting: extras.counting,
+            clock: extras.clock,
+            minimal: extras.minimal,
+            high_contrast: extras.high_contrast,
+            reduced_motion: extras.reduced_motion,
+            separator: extras.separator,
+            calendar: extras.calendar,
+            stopwatch: extras.stopwatch,
+            time: extras.time,
+            month_length: extras.month_length,
+            goal: extras.goal,
+            config_path: extras.config_path,
+            connect: extras.connect,
+            window: extras.window,
+            placed: extras.position.is_some() || extras.monitor.is_some(),
+            screensaver,
+            anniversary: extras.anniversary,
+            to_anniversary: None,
+            leap_days: extras.leap_days,
+            leap_days_since: None,
+            roman: extras.roman,
+            roman_start: String::new(),
+            keys: extras.keys,
+            plugins: extras.plugins,
+            plugin_report: None,
+        };
+        countup.schedule_rollovers();
+        countup.refresh_rows();
+        countup.refresh_age();
+        countup.dbus = dbus::start(countup.status());
+        countup.publish();
+        countup
+    }
+
+    /// Works out when each event other than the current one next rolls over
+    fn schedule_rollovers(&mut self) {
+        let now = self.time.now();
+        let current = self.event.as_ref().map(|event| event.name.as_str());
+        self.rollovers = self
+            .events
+            .iter()
+            .filter(|event| Some(event.name.as_str()) != current)
+            .map(|event| (event.clone(), next_midnight(now, event.timezone)))
+            .collect();
+    }
+
+    /// Fires the hooks for events that aren't being shown that have reached midnight
+    fn check_rollovers(&mut self) {
+        let now = self.time.now();
+        let mut due = vec![];
+        for (event, at) in &mut self.rollovers {
+            if now >= *at {
+                *at = next_midnight(now, event.timezone);
+                due.push(event.clone());
+            }
+        }
+        for event in due {
+            let today = local_date(now, event.timezone);
+            if event.date > today {
+                continue;
+            }
+            let (_, days) = calc_days_since(event.date, event.timezone, self.counting, self.time);
+            let days = days.saturating_sub(event.paused_days(today, self.counting));
+            let start = event.date.format(&self.date_format).to_string();
+            let report = report_for(
+                event.date,
+                today,
+                Some(event.name.clone()),
+                &Heading::Since,
+                self.counting,
+            )
+            .with_paused(event.paused_days(today, self.counting))
+            .with_month_length(self.month_length)
+            .with_separator(self.separator);
+            let title = Heading::Since.title(self.strings, &start);
+            let payload = |hook, milestone| {
+                HookPayload::new(
+                    hook,
+                    &report,
+                    start.clone(),
+                    milestone,
+                    &title,
+                    self.strings,
+                )
+            };
+            self.hooks.fire(payload(Hook::DayRollover, None));
+            //the count doesn't go up while paused, so it would be the same milestone again
+            if event.is_snoozed(today) || event.is_paused(today) {
+                continue;
+            }
+            if let Some(milestone) = event.milestones(&self.patterns).milestone(days) {
+                self.hooks.fire(payload(Hook::Milestone, Some(milestone)));
+            }
+        }
+    }
+
+    /// Switches to the latest version of the current event, restarting the count if its
+    /// date has changed (i.e. it was reset on another machine)
+    fn apply_update(&mut self, update: Update) {
+        self.history = update.history;
+        self.events = update.events;
+        self.schedule_rollovers();
+        self.refresh_rows();
+        let Some(current) = &self.event else {
+            self.publish();
+            return;
+        };
+        let Some(event) = self
+            .events
+            .iter()
+            .find(|event| event.name.eq_ignore_ascii_case(&current.name))
+        else {
+            //removed, keep showing it
+            self.publish();
+            return;
+        };
+        if event == current {
+            self.publish();
+            return;
+        }
+        self.set_event(event.clone());
+    }
+
+    /// Shows `event`, restarting the count if it has a different date to the current one
+    fn set_event(&mut self, event: Event) {
+        //switching away from `--from` and `--to` goes back to counting up to today
+        let was_between = self.end.take().is_some();
+        self.stopwatch = false;
+        if self.heading != Heading::Since {
+            self.heading = Heading::Since;
+            self.born = None;
+            self.age = None;
+            self.texts = Texts::new(self.strings)
+                .with_minimal(self.minimal)
+                .with_month_length(self.month_length)
+                .with_separator(self.separator)
+                .with_units(self.texts.units().to_vec());
+        }
+        let (start_date, days) =
+            calc_days_since(event.date, event.timezone, self.counting, self.time);
+        let days = days.saturating_sub(
+            event.paused_days(local_date(self.time.now(), event.timezone), self.counting),
+        );
+        if was_between || start_date != self.start_date {
+            self.start_date = start_date;
+            self.start = event.date.format(&self.date_format).to_string();
+            self.count.count_to(days);
+            self.remaining = start_date - self.time.now();
+        }
+        self.timezone = event.timezone;
+        self.next_rollover = next_midnight(self.time.now(), event.timezone);
+        self.phase_progress = Some(event.phase_progress(event.today(self.time.now())));
+        self.event = Some(event);
+        self.schedule_rollovers();
+        self.refresh_modes();
+        self.refresh_rows();
+        self.publish();
+    }
+
+    /// Rebuilds the digest, history, streak and anniversary rows and the `--template` header,
+    /// i.e. after the events, history or day changed
+    fn refresh_rows(&mut self) {
+        let shown = self.anniversary
+            || self.heading == Heading::Age
+            || self
+                .event
+                .as_ref()
+                .is_some_and(|event| event.anniversary == Some(true));
+        let start = local_date(self.start_date, self.timezone);
+        let today = local_date(self.time.now(), self.timezone);
+        self.to_anniversary = (shown && self.end.is_none() && start <= today)
+            .then(|| days_to_anniversary(start, today));
+        self.leap_days_since = self
+            .leap_days
+            .then(|| report::leap_days(start, self.end.unwrap_or(today)));
+        let report = self.report();
+        let window_title = title::text(&report, self.strings);
+        if window_title != self.title {
+            //the first is given to the window when it's created
+            if !self.title.is_empty() {
+                title::set(window_title.clone());
+            }
+            self.title = window_title;
+        }
+        self.plugin_report = (!self.plugins.is_empty()).then(|| report.clone());
+        self.texts.set_header(report.header);
+        let celebration = self
+            .event
+            .as_ref()
+            .filter(|_| report.days_until.is_none())
+            .and_then(|event| event.custom_milestone(report.days))
+            .cloned();
+        self.texts.set_celebration(celebration);
+        let excluded = self.event.as_ref().map_or(0, |event| {
+            event.noted_pauses(self.end.unwrap_or(today), self.counting)
+        });
+        self.texts.set_excluded(excluded);
+        self.fiscal = self.fiscal_year.map(|month| {
+            let end = self.end.unwrap_or(today);
+            let fiscal = FiscalYear::new(start, end, month);
+            let started = fiscal.started.format(&self.date_format).to_string();
+            (fiscal, self.strings.fiscal(fiscal.year, &started))
+        });
+        let progress = YearProgress::new(start, self.end.unwrap_or(today));
+        let from = progress.from.format(&self.date_format).to_string();
+        self.year = Some((progress, self.strings.year_from(progress.year, &from)));
+        let upcoming = Upcoming::new(start, self.end.unwrap_or(today), report.days);
+        let dates = [upcoming.hundred, upcoming.thousand, upcoming.anniversary]
+            .map(|ahead| ahead.date.format(&self.date_format).to_string());
+        self.upcoming = Some((upcoming, dates));
+        let milestone = &report.next_milestone;
+        let timeline = Timeline::new(start, self.end.unwrap_or(today), milestone.date);
+        self.timeline = Some((
+            timeline,
+            [
+                start.format(&self.date_format).to_string(),
+                milestone.date.format(&self.date_format).to_string(),
+                milestone.name.clone(),
+            ],
+        ));
+        let weekdays = Weekdays::new(start, self.end.unwrap_or(today));
+        self.weekdays = Some((weekdays, self.strings.started_on(weekdays.start)));
+        self.roman_start = roman_date(start);
+        if let Some(pair) = &mut self.pair {
+            let days = pair.days();
+            if days != pair.count.days {
+                pair.count.set(days);
+            }
+        }
+        let day_month = i18n::day_month_format(&self.date_format);
+        self.digest = digest_rows(&self.events, self.progress_checked, &day_month);
+        if self.digest_page.0 >= self.digest_pages() {
+            self.digest_page.0 = 0;
+        }
+        self.history_rows = match &self.event {
+            Some(event) => history_rows(
+                &event.name,
+                &self.history,
+                &day_month,
+                self.hours,
+                self.strings,
+            ),
+            None => vec![],
+        };
+        self.streaks = match &self.event {
+            Some(event) => StreakRows::new(event, today, self.strings),
+            None => StreakRows::default(),
+        };
+    }
+
+    /// Years, months and days for split mode, in calendar months for `--birthday`
+    fn split(&self) -> report::Split {
+        let shown = self.count.shown();
+        if self.heading == Heading::Age {
+            let start = local_date(self.start_date, self.timezone);
+            report::Split::between(start, start + Duration::days(shown as i64))
+        } else {
+            report::Split::new(shown)
+        }
+    }
+
+    /// Recalculates the available modes, i.e. after the event or term has changed
+    fn refresh_modes(&mut self) {
+        self.modes = available_modes(
+            self.start_date,
+            self.counting.elapsed(self.count.days),
+            self.term.is_some(),
+            self.event.as_ref(),
+            self.updates.is_some(),
+            !self.events.is_empty(),
+            self.pair.is_some(),
+            self.fiscal_year.is_some(),
+            self.born.is_some(),
+            self.roman,
+            self.plugins.len(),
+            self.stopwatch,
+            self.time,
+        );
+        if self.minimal {
+            self.modes.retain(|mode| mode.is_count());
+        }
+        if self.calendar {
+            self.modes = vec![Calendar];
+        }
+        if !self.modes.contains(&self.mode) {
+            self.mode = self.modes[0];
         }
     }
+
+    /// Applies edits to the config file, if it's invalid the current settings are kept
+    fn reload_config(&mut self) {
+        let Some(path) = self.config_watch.as_ref().map(|watch| watch.path()) else {
+            return;
+        };
+        let config = match Config::load_with_env(path) {
+            Ok(config) => config,
+            Err(err) => {
+                eprintln!("Not reloading config: {err:?}");
+                return;
+            }
+        };
+        self.hooks = config.hooks;
+        match Keys::new(&config.keys) {
+            Ok(keys) => self.keys = keys,
+            Err(err) => eprintln!("Not reloading keys: {err:#}"),
+        }
+        if self.mqtt.as_ref().map(|mqtt| &mqtt.config) != config.mqtt.as_ref() {
+            self.mqtt = config.mqtt.map(Publisher::start);
+        }
+        self.term = config.term.map(|term| with_label(term, &self.date_format));
+        self.term_progress = self
+            .term
+            .as_ref()
+            .map(|(term, _)| term.progress(self.progress_checked));
+        self.events = config.events;
+        self.schedule_rollovers();
+        self.refresh_rows();
+        let updated = self.event.as_ref().and_then(|current| {
+            self.events
+                .iter()
+                .find(|event| event.name.eq_ignore_ascii_case(&current.name))
+                .filter(|event| *event != current)
+                .cloned()
+        });
+        match updated {
+            Some(event) => self.set_event(event),
+            None => {
+                self.refresh_modes();
+                self.publish();
+            }
+        }
+    }
+
+    /// Counts up from zero again, with `--pair`'s target alongside
+    fn restart_count(&mut self) {
+        self.count.restart();
+        if let Some(pair) = &mut self.pair {
+            pair.count.restart();
+        }
+    }
+
+    fn next_mode(&mut self) {
+        self.restart_count();
+        let idx = self.modes.iter().position(|mode| *mode == self.mode);
+        self.mode = self.modes[idx.map(|i| (i + 1) % self.modes.len()).unwrap_or(0)];
+        self.publish();
+    }
+
+    /// Details for the about overlay
+    fn about(&self) -> Vec<(&'static str, String)> {
+        let timezone = self.timezone.map(|timezone| timezone.name().to_string());
+        let mut about = self.about.clone();
+        about.push((
+            "timezone",
+            format!(
+                "{}, system {}",
+                timezone.unwrap_or_else(|| String::from("UTC")),
+                Local::now().format("%:z")
+            ),
+        ));
+        about
+    }
+
+    /// Settings for the help overlay, the ones that can change while open first
+    fn settings(&self) -> Vec<(&'static str, String)> {
+        let event = self.event.as_ref().map(|event| event.name.clone());
+        let timezone = self.timezone.map(|timezone| timezone.name().to_string());
+        let mut settings = vec![
+            ("mode", self.mode_name().to_lowercase()),
+            ("event", event.unwrap_or_else(|| String::from("-"))),
+            ("timezone", timezone.unwrap_or_else(|| String::from("UTC"))),
+            ("intro", self.count.intro().name().to_string()),
+            (
+                "quality",
+                if self.budget.reduced() {
+                    "reduced"
+                } else {
+                    "full"
+                }
+                .to_string(),
+            ),
+        ];
+        settings.extend(self.settings.iter().cloned());
+        settings
+    }
+
+    /// The plugin's name for its modes
+    fn mode_name(&self) -> String {
+        match self.mode {
+            Mode::Plugin(index) => self
+                .plugins
+                .get(index)
+                .map_or_else(|| String::from("Plugin"), |mode| mode.name().to_string()),
+            mode => format!("{mode:?}"),
+        }
+    }
+
+    fn status(&self) -> Status {
+        Status {
+            days: self.count.days,
+            start: self.start.clone(),
+            event: self.event.as_ref().map(|event| event.name.clone()),
+            mode: self.mode_name(),
+            events: self.events.iter().map(|event| event.name.clone()).collect(),
+        }
+    }
+
+    /// Updates what's returned over D-Bus and sent to the MQTT broker
+    fn publish(&self) {
+        if let Some(dbus) = &self.dbus {
+            dbus.publish(self.status());
+        }
+        if let Some(mqtt) = &self.mqtt {
+            mqtt.publish(&self.report());
+        }
+    }
+
+    fn control(&mut self, control: Control) {
+        match control {
+            Control::SwitchEvent(name) => self.switch_event(&name),
+            Control::NextMode => self.next_mode(),
+            //already open, so bring it to the front for a second launch
+            Control::Show => instance::raise_own(),
+            Control::Quit => self.should_exit = true,
+        }
+    }
+
+    fn switch_event(&mut self, name: &str) {
+        self.next_slide_at = Instant::now() + self.slideshow.unwrap_or_default();
+        if let Some(event) = self
+            .events
+            .iter()
+            .find(|event| event.name.eq_ignore_ascii_case(name))
+        {
+            self.set_event(event.clone());
+        }
+    }
+
+    fn is_streak(&self) -> bool {
+        self.event
+            .as_ref()
+            .is_some_and(|event| event.streak == Some(true))
+    }
+
+    /// Starts the current event again from today, the same as `countup reset`, so the old
+    /// date is kept in its resets
+    fn reset_streak(&mut self) {
+        let Some(mut event) = self.event.clone() else {
+            return;
+        };
+        let now = self.time.now();
+        if let Err(err) =
+            commands::reset(&self.config_path, self.connect.as_deref(), &event.name, now)
+        {
+            eprintln!("Unable to reset {}: {err:#}", event.name);
+            return;
+        }
+        //the config watch or daemon would catch up, but not until after a frame or two
+        event.reset(event.today(now));
+        if let Some(listed) = self
+            .events
+            .iter_mut()
+            .find(|listed| listed.name.eq_ignore_ascii_case(&event.name))
+        {
+            *listed = event.clone();
+        }
+        self.set_event(event);
+    }
+
+    /// Arrows change the date being picked, Enter counts from it and any other key closes
+    /// the picker
+    fn pick_date(&mut self, mut picker: DatePicker, keys: &[VirtualKeyCode]) {
+        if keys.contains(&Return) {
+            self.overlay = None;
+            self.set_start(picker.date);
+            return;
+        }
+        if keys.contains(&Left) || keys.contains(&Right) {
+            picker.next_field(keys.contains(&Right));
+        } else if keys.contains(&Up) || keys.contains(&Down) {
+            picker.step(keys.contains(&Up));
+        } else if !keys.is_empty() {
+            self.overlay = None;
+            return;
+        }
+        self.overlay = Some(Overlay::DatePicker(picker));
+    }
+
+    /// Counts from `date` and replays the count, for the date picker, the config isn't
+    /// changed so it's only until countup is closed, or the event is switched or edited
+    fn set_start(&mut self, date: NaiveDate) {
+        match self.event.clone() {
+            Some(mut event) => {
+                event.date = date;
+                self.set_event(event);
+            }
+            None => {
+                self.stopwatch = false;
+                let (start_date, days) = match self.end {
+                    Some(end) => (
+                        midnight(date, None),
+                        (end - date).num_days().max(0) as usize + self.counting.offset(),
+                    ),
+                    None => calc_days_since(date, self.timezone, self.counting, self.time),
+                };
+                self.start_date = start_date;
+                self.start = date.format(&self.date_format).to_string();
+                self.count.count_to(days);
+                self.remaining = start_date - self.time.now();
+                self.refresh_modes();
+                self.refresh_rows();
+                self.publish();
+            }
+        }
+        self.restart_count();
+    }
+
+    /// Opens the event editor with the current event selected
+    fn open_editor(&mut self) {
+        let current = self.event.as_ref().and_then(|current| {
+            self.events
+                .iter()
+                .position(|event| event.name.eq_ignore_ascii_case(&current.name))
+        });
+        self.overlay = None;
+        //new events don't have a timezone, so they start today in UTC
+        let today = self.time.now().date_naive();
+        self.editor = Some(Editor::new(current.unwrap_or_default(), today));
+    }
+
+    /// Saves a change from the event editor, the same as `countup add` and `countup remove`
+    fn apply_edit(&mut self, edit: Edit) {
+        let saved = |result: Result<()>| {
+            result
+                .map_err(|err| eprintln!("Unable to save the events: {err:#}"))
+                .is_ok()
+        };
+        let remote = self.connect.as_deref();
+        //the config watch or daemon would catch up, but not until after a frame or two
+        match edit {
+            Edit::Close => self.editor = None,
+            Edit::Show(name) => {
+                self.editor = None;
+                self.switch_event(&name);
+            }
+            Edit::Add { name, date } => {
+                let event = commands::new_event(&name, date, None);
+                if saved(commands::add_event(
+                    &self.config_path,
+                    remote,
+                    event.clone(),
+                )) {
+                    self.events.push(event.clone());
+                    self.editor = None;
+                    self.set_event(event);
+                }
+            }
+            Edit::Rename { from, to } => {
+                let Some(event) = self.events.iter().find(|event| event.name == from) else {
+                    return;
+                };
+                if saved(commands::rename(&self.config_path, remote, event, &to)) {
+                    for event in self.events.iter_mut().chain(&mut self.event) {
+                        if event.name == from {
+                            event.name = to.clone();
+                        }
+                    }
+                    self.schedule_rollovers();
+                    self.refresh_rows();
+                    self.publish();
+                }
+            }
+            Edit::Delete(name) => {
+                if saved(commands::remove(&self.config_path, remote, &name)) {
+                    //still shown if it's the current one, the same as when removed elsewhere
+                    self.events.retain(|event| event.name != name);
+                    self.schedule_rollovers();
+                    self.refresh_rows();
+                    self.publish();
+                }
+            }
+        }
+    }
+
+    fn digest_pages(&self) -> usize {
+        self.digest.len().div_ceil(HISTORY_ROWS).max(1)
+    }
+
+    /// Shows the next (or previous) page of the digest, wrapping around
+    fn turn_digest_page(&mut self, forward: bool) {
+        let pages = self.digest_pages();
+        let page = if forward {
+            self.digest_page.0 + 1
+        } else {
+            self.digest_page.0 + pages - 1
+        };
+        self.digest_page = (page % pages, Instant::now() + DIGEST_PAGE);
+    }
+
+    /// Shows the event after the current one and replays its count, does nothing if
+    /// there's no other event to show
+    fn next_slide(&mut self) {
+        self.next_slide_at = Instant::now() + self.slideshow.unwrap_or_default();
+        let current = self.event.as_ref().and_then(|current| {
+            self.events
+                .iter()
+                .position(|event| event.name.eq_ignore_ascii_case(&current.name))
+        });
+        let next = current.map_or(0, |idx| (idx + 1) % self.events.len().max(1));
+        if current == Some(next) {
+            return;
+        }
+        if let Some(event) = self.events.get(next).cloned() {
+            self.set_event(event);
+            self.restart_count();
+        }
+    }
+
+    /// Swaps between split and diff, or to split from any other mode
+    fn toggle_split_diff(&mut self) {
+        let mode = if self.mode == Split { Diff } else { Split };
+        if self.modes.contains(&mode) {
+            self.restart_count();
+            self.mode = mode;
+            self.publish();
+        }
+    }
+
+    /// Opens the right click menu at `x`, `y`, with the other events to switch to
+    fn open_menu(&mut self, x: usize, y: usize) {
+        let current = self.event.as_ref().map(|event| event.name.as_str());
+        let mut items: Vec<(String, Action)> = self
+            .events
+            .iter()
+            .filter(|event| Some(event.name.as_str()) != current)
+            .take(MENU_EVENTS)
+            .map(|event| (event.name.clone(), Action::SwitchEvent(event.name.clone())))
+            .collect();
+        if self.event.is_some() {
+            items.push(if self.milestones_snoozed() {
+                (
+                    self.strings.unsnooze_milestones.to_string(),
+                    Action::Snooze(SnoozeLength::Off),
+                )
+            } else {
+                (
+                    self.strings.snooze_milestones.to_string(),
+                    Action::SnoozeMenu,
+                )
+            });
+        }
+        items.push((self.strings.edit_events.to_string(), Action::EditEvents));
+        items.push((self.strings.copy.to_string(), Action::Copy));
+        items.push((self.strings.quit.to_string(), Action::Quit));
+        let canvas = window_size(
+            &self.font,
+            self.minimal,
+            self.goal.is_some(),
+            self.texts.units().len(),
+            self.size,
+        );
+        self.menu = Some(Menu::new(items, (x, y), canvas));
+    }
+
+    fn open_snooze_menu(&mut self, x: usize, y: usize) {
+        let lengths = [SnoozeLength::Day, SnoozeLength::Week, SnoozeLength::Forever];
+        let items = self
+            .strings
+            .snooze_for
+            .iter()
+            .zip(lengths)
+            .map(|(label, length)| (label.to_string(), Action::Snooze(length)))
+            .collect();
+        let canvas = window_size(
+            &self.font,
+            self.minimal,
+            self.goal.is_some(),
+            self.texts.units().len(),
+            self.size,
+        );
+        self.menu = Some(Menu::new(items, (x, y), canvas));
+    }
+
+    /// Days in the current event's pauses up to `today`, none for `--to` as there's no event
+    fn paused_days(&self, today: NaiveDate) -> usize {
+        self.event
+            .as_ref()
+            .map_or(0, |event| event.paused_days(today, self.counting))
+    }
+
+    /// `--patterns` and the current event's own milestones
+    fn milestones(&self) -> Milestones {
+        self.event.as_ref().map_or_else(
+            || Milestones::new(&self.patterns),
+            |event| event.milestones(&self.patterns),
+        )
+    }
+
+    /// If the current event's milestones are snoozed today
+    fn milestones_snoozed(&self) -> bool {
+        let today = local_date(self.time.now(), self.timezone);
+        self.event
+            .as_ref()
+            .is_some_and(|event| event.is_snoozed(today))
+    }
+
+    /// Snoozes the current event's milestones, saving it to the config or daemon
+    fn snooze(&mut self, length: SnoozeLength) {
+        let Some(mut event) = self.event.clone() else {
+            return;
+        };
+        let today = self.time.now().date_naive();
+        if let Err(err) = commands::snooze(
+            &self.config_path,
+            self.connect.as_deref(),
+            &event.name,
+            length,
+            today,
+        ) {
+            eprintln!("Unable to snooze {}: {err:#}", event.name);
+            return;
+        }
+        event.snooze = length.starting(today);
+        if let Some(listed) = self
+            .events
+            .iter_mut()
+            .find(|listed| listed.name.eq_ignore_ascii_case(&event.name))
+        {
+            *listed = event.clone();
+        }
+        self.event = Some(event);
+    }
+
+    /// The count as of today, or `--to`, with the `--template` header
+    fn report(&self) -> Report {
+        let today = self
+            .end
+            .unwrap_or_else(|| local_date(self.time.now(), self.timezone));
+        let mut report = report_for(
+            local_date(self.start_date, self.timezone),
+            today,
+            self.event.as_ref().map(|event| event.name.clone()),
+            &self.heading,
+            self.counting,
+        )
+        .with_milestones(self.milestones())
+        .with_paused(self.paused_days(today))
+        .with_month_length(self.month_length)
+        .with_separator(self.separator);
+        report.fiscal = self
+            .fiscal_year
+            .map(|month| FiscalYear::new(report.start, today, month));
+        report.header = self
+            .template
+            .as_ref()
+            .map(|template| report.fill_header(template, &self.start));
+        report.age = self.age.as_ref().map(|(age, _)| *age);
+        report
+    }
+
+    /// Dates and times, for the hours milestones
+    fn moment_format(&self) -> String {
+        format!("{} {}", self.date_format, i18n::time_format(self.hours))
+    }
+
+    /// Rebuilds age mode's numbers and lines if the minute has changed
+    fn refresh_age(&mut self) {
+        let Some((born, _)) = &self.born else {
+            return;
+        };
+        let age = PreciseAge::new(*born, self.time.local().naive_local());
+        if self.age.as_ref().is_some_and(|(shown, _)| *shown == age) {
+            return;
+        }
+        let lines = age.lines(&self.moment_format(), self.strings);
+        self.age = Some((age, lines));
+    }
+
+    /// Copies the count as text, the same as `--headless` prints
+    fn copy(&mut self) {
+        let report = self.report();
+        let text = report_lines(
+            &report,
+            &self.start,
+            &self.heading,
+            &self.moment_format(),
+            self.strings,
+        )
+        .join("\n");
+        self.set_clipboard(text);
+    }
+
+    /// Copies the count as one line, for pasting into chats
+    fn copy_summary(&mut self) {
+        let report = self.report();
+        let title = self.summary_title(&report);
+        self.set_clipboard(report.sentence(&title, self.strings));
+    }
+
+    /// Start of [Report::sentence], the same as the title
+    fn summary_title(&self, report: &Report) -> String {
+        match (&report.header, report.days_until) {
+            (_, Some(_)) => self.strings.until(&self.start),
+            (Some(header), None) => header.clone(),
+            (None, None) => self.heading.title(self.strings, &self.start),
+        }
+    }
+
+    fn set_clipboard(&mut self, text: String) {
+        let clipboard = match self.clipboard.take() {
+            Some(clipboard) => Ok(clipboard),
+            None => arboard::Clipboard::new(),
+        };
+        match clipboard.and_then(|mut clipboard| clipboard.set_text(text).map(|_| clipboard)) {
+            Ok(clipboard) => self.clipboard = Some(clipboard),
+            Err(err) => eprintln!("Unable to copy the count: {err}"),
+        }
+    }
+
+    /// Called when the count reaches the final number, by counting or skipping
+    fn count_finished(&self) {
+        self.fire(Hook::AnimationComplete, None);
+        self.play(Sound::Chime);
+    }
+
+    fn play(&self, sound: Sound) {
+        if let Some(sounds) = &self.sounds {
+            sounds.play(sound);
+        }
+    }
+
+    /// The event's colours, or black and white for `--high-contrast`
+    fn palette(&self) -> Palette {
+        Palette::of(self.event.as_ref()).with_contrast(self.high_contrast)
+    }
+
+    /// The mode and everything over it but the menu, moved around by `--screensaver`
+    fn draw(&self, graphics: &mut Graphics) {
+        let palette = self.palette().flashed(self.count.flash());
+        match self.mode {
+            Split => render_split(
+                graphics,
+                self.split(),
+                self.leap_days_since
+                    .map(SplitRow::LeapDays)
+                    .or(self.to_anniversary.map(SplitRow::Anniversary)),
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Diff => render_diff(
+                graphics,
+                self.count.shown(),
+                self.month_length.months(
+                    local_date(self.start_date, self.timezone),
+                    self.count.shown(),
+                ),
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            WeeksAndDays => render_weeks_and_days(
+                graphics,
+                self.count.shown(),
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Segments => render_segments(
+                graphics,
+                self.count.shown(),
+                window_size(
+                    &self.font,
+                    self.minimal,
+                    self.goal.is_some(),
+                    self.texts.units().len(),
+                    self.size,
+                )
+                .1 - self.goal.as_ref().map_or(0, |_| GOAL_HEIGHT),
+                palette,
+                &self.texts,
+            ),
+            TMinus => render_tminus(
+                graphics,
+                self.remaining,
+                &self.start,
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Term => {
+                if let (Some((_, label)), Some(progress)) = (&self.term, self.term_progress) {
+                    render_term(graphics, progress, label, &self.texts)
+                }
+            }
+            Phases => {
+                if let (Some(event), Some(progress)) = (&self.event, &self.phase_progress) {
+                    render_phases(
+                        graphics,
+                        event,
+                        progress,
+                        self.progress_checked,
+                        palette,
+                        &self.texts,
+                    )
+                }
+            }
+            History => render_history(graphics, &self.history_rows, self.strings),
+            Mode::Recap => {
+                if let Some(recap) = Recap::latest(self.counting.elapsed(self.count.days)) {
+                    render_recap(
+                        graphics,
+                        recap.year,
+                        recap.days,
+                        recap.milestones,
+                        &self.font,
+                        palette,
+                        &self.texts,
+                    )
+                }
+            }
+            Streaks => render_streaks(graphics, &self.streaks, palette, self.strings),
+            Fiscal => {
+                if let Some((fiscal, header)) = &self.fiscal {
+                    render_fiscal(
+                        graphics,
+                        *fiscal,
+                        header,
+                        Reveal::of(&self.count),
+                        &self.font,
+                        palette,
+                        &self.texts,
+                    )
+                }
+            }
+            Year => {
+                if let Some((progress, header)) = &self.year {
+                    render_year(
+                        graphics,
+                        *progress,
+                        header,
+                        Reveal::of(&self.count),
+                        &self.font,
+                        palette,
+                        &self.texts,
+                    )
+                }
+            }
+            Age => {
+                if let Some((age, lines)) = &self.age {
+                    let header = self.born.as_ref().map_or("", |(_, header)| header.as_str());
+                    render_age(
+                        graphics,
+                        *age,
+                        header,
+                        lines,
+                        Reveal::of(&self.count),
+                        &self.font,
+                        palette,
+                        &self.texts,
+                    )
+                }
+            }
+            Totals => {
+                let now = match self.end {
+                    Some(end) => midnight(end, self.timezone),
+                    None => self.time.now(),
+                };
+                //from the time of birth with `--born-at`
+                let start = self
+                    .born
+                    .as_ref()
+                    .and_then(|(born, _)| born.and_local_timezone(Local).earliest())
+                    .map_or(self.start_date, |born| born.with_timezone(&Utc));
+                render_totals(
+                    graphics,
+                    count::Totals::new(start, now),
+                    &self.start,
+                    Reveal::of(&self.count),
+                    palette,
+                    &self.texts,
+                )
+            }
+            Mode::Timeline => {
+                if let Some((timeline, labels)) = &self.timeline {
+                    render_timeline(
+                        graphics,
+                        *timeline,
+                        labels,
+                        Reveal::of(&self.count),
+                        palette,
+                        &self.texts,
+                    )
+                }
+            }
+            Mode::Upcoming => {
+                if let Some((upcoming, dates)) = &self.upcoming {
+                    render_upcoming(
+                        graphics,
+                        *upcoming,
+                        dates,
+                        Reveal::of(&self.count),
+                        palette,
+                        &self.texts,
+                    )
+                }
+            }
+            Mode::Weekdays => {
+                if let Some((weekdays, header)) = &self.weekdays {
+                    render_weekdays(
+                        graphics,
+                        *weekdays,
+                        header,
+                        Reveal::of(&self.count),
+                        palette,
+                        &self.texts,
+                    )
+                }
+            }
+            Mode::Bases => render_bases(
+                graphics,
+                self.count.shown(),
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Mode::Plugin(index) => {
+                if let (Some(mode), Some(report)) = (self.plugins.get(index), &self.plugin_report) {
+                    mode.render(graphics, report, palette)
+                }
+            }
+            Mode::Roman => render_roman(
+                graphics,
+                self.split(),
+                &self.roman_start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Calendar => render_calendar(
+                graphics,
+                CalendarProgress::new(self.time.local().naive_local()),
+                palette,
+                &self.texts,
+            ),
+            Moons => {
+                let today = self
+                    .end
+                    .unwrap_or_else(|| local_date(self.time.now(), self.timezone));
+                render_moons(
+                    graphics,
+                    report::Moons::new(local_date(self.start_date, self.timezone), today),
+                    &self.start,
+                    Reveal::of(&self.count),
+                    &self.font,
+                    palette,
+                    &self.texts,
+                )
+            }
+            Weeks => {
+                let today = self
+                    .end
+                    .unwrap_or_else(|| local_date(self.time.now(), self.timezone));
+                render_weeks(
+                    graphics,
+                    IsoWeeks::new(local_date(self.start_date, self.timezone), today),
+                    Reveal::of(&self.count),
+                    &self.font,
+                    palette,
+                    &self.texts,
+                )
+            }
+            Dual => {
+                if let Some(pair) = &self.pair {
+                    let since = Side {
+                        days: self.count.shown(),
+                        reveal: Reveal::of(&self.count),
+                        palette,
+                        label: self
+                            .event
+                            .as_ref()
+                            .map_or(self.start.as_str(), |event| event.name.as_str()),
+                    };
+                    let until = Side {
+                        days: pair.count.shown(),
+                        reveal: Reveal::of(&pair.count),
+                        palette: pair.palette.with_contrast(self.high_contrast),
+                        label: &pair.label,
+                    };
+                    render_dual(graphics, [since, until], &self.font, &self.texts);
+                }
+            }
+            Digest => render_digest(
+                graphics,
+                &self.digest,
+                (self.digest_page.0, self.digest_pages()),
+                self.strings,
+                &self.texts,
+            ),
+        }
+        if let Some(backdrop) = &self.backdrop {
+            backdrop.draw(graphics, palette.background);
+        }
+        //drawing it every frame is the first thing to go on a slow machine
+        if !self.budget.reduced() {
+            let seconds = if self.reduced_motion {
+                0.0
+            } else {
+                Utc::now().timestamp_millis() as f64 / 1000.0
+            };
+            self.background_style
+                .draw(graphics, palette.background, seconds);
+        }
+        if let Some((goal, label)) = &self.goal {
+            let start = local_date(self.start_date, self.timezone);
+            render_goal(
+                graphics,
+                self.count.shown(),
+                goal.days(start),
+                label,
+                palette,
+                &self.texts,
+            );
+        }
+        if let Some(clock) = &self.clock {
+            clock.render(graphics, self.time.local());
+        }
+        match self.overlay {
+            Some(Overlay::Help) => {
+                let shortcuts = shortcuts(self.strings, &self.keys);
+                let shortcuts: Vec<(&str, &str)> = shortcuts
+                    .iter()
+                    .map(|(key, action)| (key.as_str(), *action))
+                    .collect();
+                render_help(graphics, &shortcuts, &self.settings(), self.strings)
+            }
+            Some(Overlay::About) => render_about(graphics, &self.about(), self.strings),
+            Some(Overlay::ConfirmReset) => render_confirm_reset(graphics, self.strings),
+            Some(Overlay::DatePicker(picker)) => {
+                let strings = self.strings;
+                render_date_picker(
+                    graphics,
+                    &picker,
+                    strings.change_date,
+                    strings.pick_date,
+                    strings.close_help,
+                )
+            }
+            None => {}
+        }
+        if let Some(editor) = &self.editor {
+            editor.render(graphics, &self.events, &self.date_format, self.strings);
+        }
+    }
+
+    fn fire(&self, hook: Hook, milestone: Option<String>) {
+        let report = self.report();
+        self.hooks.fire(HookPayload::new(
+            hook,
+            &report,
+            self.start.clone(),
+            milestone,
+            &self.summary_title(&report),
+            self.strings,
+        ));
+    }
+}
+
+/// [canvas_size], with room for the custom units in diff mode and the goal under the modes
+/// if there is one, or `size` if that's bigger
+fn window_size(
+    font: &NumberFont,
+    minimal: bool,
+    goal: bool,
+    units: usize,
+    size: Option<(usize, usize)>,
+) -> (usize, usize) {
+    let (width, mut height) = canvas_size(font, minimal);
+    height += units_height(font, minimal, units);
+    if goal {
+        height += GOAL_HEIGHT;
+    }
+    let (min_width, min_height) = size.unwrap_or_default();
+    (width.max(min_width), height.max(min_height))
+}
+
+/// Font at the largest text scale that fits in `size`, or 1 if there isn't one
+fn fitting_font(
+    glyphs: Glyphs,
+    size: Option<(usize, usize)>,
+    minimal: bool,
+    goal: bool,
+    units: usize,
+) -> NumberFont {
+    let Some((width, height)) = size else {
+        return NumberFont::new(glyphs, 1);
+    };
+    (2..=MAX_TEXT_SCALE)
+        .rev()
+        .map(|scale| NumberFont::new(glyphs.clone(), scale))
+        .find(|font| {
+            let (font_width, font_height) = window_size(font, minimal, goal, units, None);
+            font_width <= width && font_height <= height
+        })
+        .unwrap_or_else(|| NumberFont::new(glyphs, 1))
 }
 
-fn ui(days: usize, start: String, start_date: DateTime<Utc>) -> Result<()> {
-    let system = Box::new(Countup::new(days, start, start_date));
-    run(270, 90, "Countup", system, Options::default())?;
+fn ui(
+    days: usize,
+    start: String,
+    start_date: DateTime<Utc>,
+    strings: &'static Strings,
+    extras: Extras,
+    prefs: AppPreferences,
+) -> Result<()> {
+    let (width, height) = window_size(
+        &extras.font,
+        extras.minimal,
+        extras.goal.is_some(),
+        extras.units.len(),
+        extras.size,
+    );
+    let scaling = extras
+        .scale
+        .map_or(WindowScaling::Auto, WindowScaling::Fixed);
+    if extras.start_hidden {
+        let status = Status {
+            days,
+            start: start.clone(),
+            event: extras.event.as_ref().map(|event| event.name.clone()),
+            mode: String::from("Hidden"),
+            events: extras
+                .events
+                .iter()
+                .map(|event| event.name.clone())
+                .collect(),
+        };
+        if !dbus::wait_until_shown(status) {
+            return Ok(());
+        }
+    }
+    if extras.desktop {
+        desktop::check()?;
+    }
+    if extras.position.is_some() || extras.monitor.is_some() {
+        placement::place(extras.position, extras.monitor, (width, height), scaling)?;
+    }
+    let hide_cursor = extras.screensaver;
+    let desktop = extras.desktop;
+    let countup = Countup::new(days, start, start_date, strings, extras, prefs);
+    let title = countup.title.clone();
+    let system = Box::new(countup);
+    let options = Options {
+        scaling,
+        hide_cursor,
+        ..Options::default()
+    };
+    if desktop {
+        desktop::pin();
+    }
+    run(width, height, &title, system, options)?;
     Ok(())
 }
 
 impl System for Countup {
     fn action_keys(&self) -> Vec<VirtualKeyCode> {
-        vec![Escape, Space]
+        let mut keys = vec![
+            Return, End, P, I, C, E, H, L, Slash, LControl, RControl, PageUp, PageDown, Back,
+        ];
+        keys.extend(self.keys.all());
+        if self.editor.is_some() {
+            keys.extend(Editor::keys());
+        } else if self.overlay.is_some() {
+            keys.extend(ANY_KEY);
+        }
+        keys
     }
 
     fn window_prefs(&self) -> Option<WindowPreferences> {
-        Some(WindowPreferences::new("app", "emmabritton", "countup").unwrap())
+        //they can only be in the app's pref dir, which a portable install shouldn't write to
+        if paths::has_prefs_dir() && !self.placed {
+            return None;
+        }
+        //the window still opens without them, just not where it was last time
+        let name = match &self.window {
+            _ if self.placed => String::from(placement::PREFS_NAME),
+            Some(event) => windows::prefs_name(event),
+            None => String::from("countup"),
+        };
+        //checked here as the window isn't opened at all if they can't be read when it is
+        WindowPreferences::new("app", "emmabritton", &name)
+            .and_then(|mut prefs| prefs.load().map(|_| prefs))
+            .map_err(|err| eprintln!("Unable to load window position: {err:?}"))
+            .ok()
     }
 
     fn update(&mut self, timing: &Timing) {
-        if self.current_days < self.days {
-            while self.next_inc < 0.0 && self.current_days < self.days {
-                self.current_days += 1;
-                self.next_inc += self.next_inc_speed;
+        if !self.idle.is_idle() {
+            self.budget.record(timing);
+        }
+        if let (Mode::Plugin(index), Some(report)) = (self.mode, &self.plugin_report) {
+            if let Some(mode) = self.plugins.get_mut(index) {
+                mode.update(report, timing.delta);
             }
-            self.next_inc -= timing.fixed_time_step;
-        } else {
-            let (_, day_count) = calc_days_since(self.start_date);
-            if day_count != self.days {
-                self.days = day_count;
-                self.current_days = day_count;
+        }
+        if let Some(update) = self
+            .updates
+            .as_ref()
+            .and_then(|updates| updates.try_iter().last())
+        {
+            self.apply_update(update);
+        }
+        if self
+            .config_watch
+            .as_mut()
+            .is_some_and(|watch| watch.changed())
+        {
+            self.reload_config();
+        }
+        while let Some(control) = self.dbus.as_ref().and_then(|dbus| dbus.try_recv()) {
+            self.control(control);
+        }
+        self.check_rollovers();
+        self.refresh_age();
+        if self.slideshow.is_some() && Instant::now() >= self.next_slide_at {
+            self.next_slide();
+        }
+        //the pages only turn while the digest is shown
+        if self.mode != Digest {
+            self.digest_page.1 = Instant::now() + DIGEST_PAGE;
+        } else if Instant::now() >= self.digest_page.1 {
+            self.turn_digest_page(true);
+        }
+        let today = local_date(self.time.now(), self.timezone);
+        if today != self.progress_checked {
+            self.progress_checked = today;
+            self.term_progress = self.term.as_ref().map(|(term, _)| term.progress(today));
+            self.phase_progress = self.event.as_ref().map(|event| event.phase_progress(today));
+            self.refresh_rows();
+        }
+        if let Some(pair) = &mut self.pair {
+            pair.count
+                .update((timing.now - timing.started_at).as_secs_f64());
+        }
+        if self.mode == TMinus {
+            self.remaining = self.start_date - self.time.now();
+        } else if self.count.is_counting() || self.count.is_flashing() {
+            let now = (timing.now - timing.started_at).as_secs_f64();
+            let shown = self.count.current;
+            if self.count.update(now) {
+                self.count_finished();
+            } else if self.count.current != shown {
+                if let Some(sounds) = &mut self.sounds {
+                    sounds.tick();
+                }
+            }
+        } else if self.end.is_none() && self.time.now() >= self.next_rollover {
+            self.next_rollover = next_midnight(self.time.now(), self.timezone);
+            let start = local_date(self.start_date, self.timezone);
+            let (_, day_count) = calc_days_since(start, self.timezone, self.counting, self.time);
+            let day_count = day_count
+                .saturating_sub(self.paused_days(local_date(self.time.now(), self.timezone)));
+            if day_count != self.count.days {
+                if self.reduced_motion {
+                    self.count.set(day_count);
+                } else {
+                    self.count.roll_over(day_count);
+                }
+                self.refresh_modes();
+                self.refresh_rows();
+                if is_anniversary(self.counting.elapsed(day_count)) {
+                    self.mode = Mode::Recap;
+                }
+                self.fire(Hook::DayRollover, None);
+                self.publish();
+                if let Some(milestone) = self
+                    .milestones()
+                    .milestone(day_count)
+                    .filter(|_| !self.milestones_snoozed())
+                {
+                    self.play(Sound::Chime);
+                    self.fire(Hook::Milestone, Some(milestone));
+                }
             }
         }
+        let animating = self.count.is_counting()
+            || self.count.is_flashing()
+            || self
+                .pair
+                .as_ref()
+                .is_some_and(|pair| pair.count.is_counting())
+            //the count drifts all the time, even with nobody there
+            || self.screensaver.is_some()
+            //as does the background, unless it's held still or dropped to keep up
+            || (self.background_style != BackgroundStyle::Flat
+                && !self.reduced_motion
+                && !self.budget.reduced());
+        self.idle.update(timing, animating);
     }
 
     fn render(&self, graphics: &mut Graphics) {
-        if self.diff_mode {
-            render_diff(graphics, self.current_days, &self.start)
-        } else {
-            render_split(graphics, self.current_days, &self.start);
+        if !self.idle.should_draw() {
+            return;
+        }
+        match &self.screensaver {
+            Some(screensaver) => {
+                let seconds = Utc::now().timestamp_millis() as f64 / 1000.0;
+                let background = self.palette().background;
+                screensaver.draw(graphics, background, seconds, |count| self.draw(count));
+            }
+            None => self.draw(graphics),
+        }
+        if let Some(menu) = &self.menu {
+            menu.render(graphics);
+        }
+        if !self.budget.reduced() {
+            frames::record(graphics);
+        }
+    }
+
+    fn on_visibility_changed(&mut self, visible: bool) {
+        self.idle.set_visible(visible);
+    }
+
+    fn on_focus_changed(&mut self, focused: bool) {
+        self.idle.set_focused(focused);
+    }
+
+    fn on_mouse_move(&mut self, x: usize, y: usize) {
+        self.idle.mouse_at((x, y));
+        if let Some(menu) = &mut self.menu {
+            menu.hover(x, y);
+        }
+    }
+
+    fn on_mouse_up(&mut self, x: usize, y: usize, button: MouseButton) {
+        self.idle.wake();
+        if let Some(menu) = self.menu.take() {
+            if button == MouseButton::Left {
+                match menu.click(x, y) {
+                    Some(Action::SwitchEvent(name)) => self.switch_event(&name),
+                    Some(Action::Copy) => self.copy(),
+                    Some(Action::SnoozeMenu) => self.open_snooze_menu(x, y),
+                    Some(Action::Snooze(length)) => self.snooze(length),
+                    Some(Action::EditEvents) => self.open_editor(),
+                    Some(Action::Quit) => self.should_exit = true,
+                    None => {}
+                }
+            }
+        } else if self.editor.is_some() {
+            self.editor = None;
+        } else if self.overlay.is_some() {
+            self.overlay = None;
+        } else if button == MouseButton::Left {
+            self.toggle_split_diff();
+        } else if button == MouseButton::Right {
+            self.open_menu(x, y);
         }
     }
 
+    fn on_key_down(&mut self, keys: Vec<VirtualKeyCode>) {
+        self.ctrl_held = keys.contains(&LControl) || keys.contains(&RControl);
+        self.shift_held = keys.contains(&LShift) || keys.contains(&RShift);
+    }
+
     fn on_key_pressed(&mut self, keys: Vec<VirtualKeyCode>) {
-        if keys.contains(&Escape) {
+        if !keys.is_empty() {
+            self.idle.wake();
+        }
+        if let Some(editor) = &mut self.editor {
+            if let Some(edit) = editor.key(&keys, self.shift_held, &self.events) {
+                self.apply_edit(edit);
+            }
+        } else if self.overlay == Some(Overlay::ConfirmReset) && keys.contains(&Back) {
+            self.overlay = None;
+            self.reset_streak();
+        } else if let Some(Overlay::DatePicker(picker)) = self.overlay {
+            self.pick_date(picker, &keys);
+        } else if self.overlay == Some(Overlay::Help) && keys.contains(&VirtualKeyCode::A) {
+            self.overlay = Some(Overlay::About);
+        } else if self.overlay.is_some() && !keys.is_empty() {
+            self.overlay = None;
+        } else if keys.contains(&H) || keys.contains(&Slash) {
+            self.overlay = Some(Overlay::Help);
+        } else if self.keys.save_frames.pressed(&keys, self.ctrl_held) {
+            //saving a couple of seconds of PNGs would stall the window
+            thread::spawn(|| match frames::dump() {
+                Ok(dir) => println!("Saved the last frames to {}", dir.display()),
+                Err(err) => eprintln!("Unable to save the last frames: {err:#}"),
+            });
+        } else if self.mode == Digest && (keys.contains(&PageUp) || keys.contains(&PageDown)) {
+            self.turn_digest_page(keys.contains(&PageDown));
+        } else if self.keys.quit.pressed(&keys, self.ctrl_held) {
             self.should_exit = true
-        } else if keys.contains(&Space) {
-            self.current_days = 0;
-            self.diff_mode = !self.diff_mode;
+        } else if self.keys.next_mode.pressed(&keys, self.ctrl_held) {
+            self.next_mode();
+        } else if (keys.contains(&Return) || keys.contains(&End)) && self.count.is_counting() {
+            self.count.skip();
+            if let Some(pair) = &mut self.pair {
+                pair.count.skip();
+            }
+            self.count_finished();
+        } else if keys.contains(&P) && self.count.is_counting() {
+            self.count.toggle_pause();
+            if let Some(pair) = &mut self.pair {
+                pair.count.toggle_pause();
+            }
+        } else if keys.contains(&C) {
+            self.copy_summary();
+        } else if keys.contains(&L) {
+            self.open_editor();
+        } else if keys.contains(&E) {
+            let start = local_date(self.start_date, self.timezone);
+            self.overlay = Some(Overlay::DatePicker(DatePicker::new(start)));
+        } else if self.keys.restart.pressed(&keys, self.ctrl_held) {
+            self.restart_count();
+        } else if keys.contains(&I) {
+            self.count.next_intro();
+            if let Some(pair) = &mut self.pair {
+                pair.count.next_intro();
+            }
+        } else if keys.contains(&Back) && self.is_streak() {
+            self.overlay = Some(Overlay::ConfirmReset);
+        }
+    }
+
+    fn on_window_closed(&mut self) {
+        //stops the audio thread
+        self.sounds = None;
+        outbox::persist_in_flight();
+        //calendar mode isn't kept for the next time it's opened with a date
+        if self.window.is_some() || self.calendar {
+            return;
         }
+        self.prefs.set(AppPref {
+            mode: Some(self.mode),
+            event: self.event.as_ref().map(|event| event.name.clone()),
+            ..self.prefs.get()
+        });
+        self.prefs.save();
     }
 
     fn should_exit(&self) -> bool {
-        self.should_exit
+        self.should_exit || shutdown::requested()
     }
 }
 
-fn render_split(graphics: &mut Graphics, current_days: usize, start: &str) {
+/// Rows for the digest mode, upcoming dates are brighter, dated with `day_month`
+fn digest_rows(
+    events: &[Event],
+    today: NaiveDate,
+    day_month: &str,
+) -> Vec<(String, String, Color)> {
+    digest(events, today)
+        .into_iter()
+        .map(|entry| {
+            let color = match entry.color {
+                Some(Rgb(r, g, b)) => Color::rgb(r, g, b),
+                None if entry.date > today => WHITE,
+                None => LIGHT_GRAY,
+            };
+            let text = match &entry.milestone {
+                Some(milestone) => format!("{}: {milestone}", entry.event),
+                None => entry.event.clone(),
+            };
+            (entry.date.format(day_month).to_string(), text, color)
+        })
+        .collect()
+}
+
+/// The rows on `page`, with `page/pages` in the corner if they don't all fit
+fn render_digest(
+    graphics: &mut Graphics,
+    rows: &[(String, String, Color)],
+    (page, pages): (usize, usize),
+    strings: &Strings,
+    texts: &Texts,
+) {
     graphics.clear(DARK_GRAY);
-    graphics.draw_text(
-        &format!("Since {} it's been", start),
-        Px(4, 4),
-        (LIGHT_GRAY, Large),
-    );
-    let years = current_days / 365;
-    let remaining = current_days - (years * 365);
-    let months = remaining / 28;
-    let days = remaining - (months * 28);
-    graphics.draw_text(
-        &format!("{years}"),
-        Px(COL_NUM, 24),
-        (WHITE, Large, RightTop),
-    );
-    graphics.draw_text("YEARS", Px(COL_PERIOD, 24), (LIGHT_GRAY, Large, LeftTop));
-    graphics.draw_text(
-        &format!("{months}"),
-        Px(COL_NUM, 40),
-        (WHITE, Large, RightTop),
-    );
-    graphics.draw_text("MONTHS", Px(COL_PERIOD, 40), (LIGHT_GRAY, Large, LeftTop));
-    graphics.draw_text(
-        &format!("{days}"),
-        Px(COL_NUM, 56),
-        (WHITE, Large, RightTop),
-    );
-    graphics.draw_text("DAYS", Px(COL_PERIOD, 56), (LIGHT_GRAY, Large, LeftTop));
+    graphics.draw_text(strings.digest, Px(4, 4), (LIGHT_GRAY, Large));
+    if rows.is_empty() {
+        graphics.draw_text(strings.no_digest, Px(4, 24), (WHITE, Normal));
+    }
+    if pages > 1 {
+        graphics.draw_text(
+            &texts.page(page, pages),
+            Px(graphics.width() as isize - 4, 4),
+            (LIGHT_GRAY, Normal, Positioning::RightTop),
+        );
+    }
+    let shown = rows.iter().skip(page * HISTORY_ROWS).take(HISTORY_ROWS);
+    for (i, (date, text, color)) in shown.enumerate() {
+        let y = 24 + (i as isize * 14);
+        graphics.draw_text(date, Px(4, y), (*color, Normal));
+        graphics.draw_text(text, Px(56, y), (*color, Normal));
+    }
+}
+
+/// Bars for streaks mode, sized against the best streak
+#[derive(Debug, Default)]
+struct StreakRows {
+    /// Length as text and in days, oldest first
+    rows: Vec<(String, usize)>,
+    best: usize,
+    /// Best and average, i.e. `Best 200 days   Average 87 days`
+    summary: String,
+}
+
+impl StreakRows {
+    fn new(event: &Event, today: NaiveDate, strings: &Strings) -> Self {
+        let past = event.past_streaks();
+        let current = (today - event.date).num_days().max(0) as usize;
+        let best = past
+            .iter()
+            .copied()
+            .chain([current])
+            .max()
+            .unwrap_or_default();
+        let average = past.iter().sum::<usize>() / past.len().max(1);
+        StreakRows {
+            rows: past
+                .iter()
+                .skip(past.len().saturating_sub(STREAK_ROWS))
+                .map(|days| (days.to_string(), *days))
+                .collect(),
+            best,
+            summary: format!(
+                "{}   {}",
+                strings.best_streak(best),
+                strings.average_streak(average)
+            ),
+        }
+    }
 }
 
-fn render_diff(graphics: &mut Graphics, current_days: usize, start: &str) {
+fn render_streaks(
+    graphics: &mut Graphics,
+    streaks: &StreakRows,
+    palette: Palette,
+    strings: &Strings,
+) {
+    graphics.clear(palette.background);
+    graphics.draw_text(strings.streaks, Px(4, 4), (LIGHT_GRAY, Large));
+    //room for 5 digits before the bars
+    let bar_x = 44;
+    let max_width = graphics.width() as isize - bar_x - 4;
+    for (i, (text, days)) in streaks.rows.iter().enumerate() {
+        let y = 22 + (i as isize * 10);
+        graphics.draw_text(
+            text,
+            Px(bar_x - 6, y),
+            (WHITE, Normal, Positioning::RightTop),
+        );
+        let width = (max_width * *days as isize / streaks.best.max(1) as isize).max(1);
+        let color = if *days == streaks.best {
+            palette.accent
+        } else {
+            LIGHT_GRAY
+        };
+        graphics.draw_rect(Rect::new((bar_x, y), (bar_x + width, y + 6)), fill(color));
+    }
+    graphics.draw_text(&streaks.summary, Px(4, 76), (LIGHT_GRAY, Normal));
+}
+
+/// Rows for the history mode, the latest edits to `event`, dated with `day_month`
+fn history_rows(
+    event: &str,
+    history: &[AuditEntry],
+    day_month: &str,
+    hours: u8,
+    strings: &Strings,
+) -> Vec<(String, String)> {
+    let format = format!("{day_month} {}", i18n::time_format(hours));
+    history
+        .iter()
+        .filter(|entry| {
+            entry.event.eq_ignore_ascii_case(event)
+                || matches!(&entry.change, Change::Rename { name } if name.eq_ignore_ascii_case(event))
+        })
+        .take(HISTORY_ROWS)
+        .map(|entry| {
+            let action = match entry.change {
+                Change::Add { .. } => strings.added,
+                Change::Remove => strings.removed,
+                Change::SetDate { .. } => strings.reset,
+                Change::Snooze { .. } => strings.snoozed,
+                Change::Rename { .. } => strings.renamed,
+            };
+            (
+                entry.at.with_timezone(&Local).format(&format).to_string(),
+                format!("{action} {}", entry.client),
+            )
+        })
+        .collect()
+}
+
+fn render_history(graphics: &mut Graphics, rows: &[(String, String)], strings: &Strings) {
     graphics.clear(DARK_GRAY);
-    graphics.draw_text(
-        &format!("Since {} it's been", start),
-        Px(4, 4),
-        (LIGHT_GRAY, Large),
-    );
-    let weeks = current_days / 7;
-    let months = current_days / 28;
-    let years = current_days / 365;
-    graphics.draw_text(
-        &format!("{current_days}"),
-        Px(COL_NUM, 24),
-        (WHITE, Large, RightTop),
-    );
-    graphics.draw_text("DAYS", Px(COL_PERIOD, 24), (LIGHT_GRAY, Large, LeftTop));
-    graphics.draw_text("or", Px(COL_PERIOD + 42, 29), (LIGHT_GRAY, Small, LeftTop));
-    graphics.draw_text(
-        &format!("{weeks}"),
-        Px(COL_NUM, 40),
-        (WHITE, Large, RightTop),
-    );
-    graphics.draw_text("WEEKS", Px(COL_PERIOD, 40), (LIGHT_GRAY, Large, LeftTop));
-    graphics.draw_text("or", Px(COL_PERIOD + 52, 45), (LIGHT_GRAY, Small, LeftTop));
-    graphics.draw_text(
-        &format!("{months}"),
-        Px(COL_NUM, 56),
-        (WHITE, Large, RightTop),
-    );
-    graphics.draw_text("MONTHS", Px(COL_PERIOD, 56), (LIGHT_GRAY, Large, LeftTop));
-    graphics.draw_text("or", Px(COL_PERIOD + 62, 61), (LIGHT_GRAY, Small, LeftTop));
-    graphics.draw_text(
-        &format!("{years}"),
-        Px(COL_NUM, 72),
-        (WHITE, Large, RightTop),
-    );
-    graphics.draw_text("YEARS", Px(COL_PERIOD, 72), (LIGHT_GRAY, Large, LeftTop));
+    graphics.draw_text(strings.history, Px(4, 4), (LIGHT_GRAY, Large));
+    if rows.is_empty() {
+        graphics.draw_text(strings.no_history, Px(4, 24), (WHITE, Normal));
+    }
+    //12 hour times are wider
+    let text_x = rows
+        .iter()
+        .map(|(time, _)| Normal.measure(time, WrappingStrategy::None).0 as isize)
+        .max()
+        .unwrap_or_default()
+        + 11;
+    for (i, (time, text)) in rows.iter().enumerate() {
+        let y = 24 + (i as isize * 14);
+        graphics.draw_text(time, Px(4, y), (LIGHT_GRAY, Normal));
+        graphics.draw_text(text, Px(text_x, y), (WHITE, Normal));
+    }
 }
@@ -0,0 +1,92 @@
+use crate::event::SnoozeLength;
+use pixels_graphics_lib::prelude::*;
+
+/// Height of each item, the small font plus padding
+const ITEM_HEIGHT: isize = 9;
+const PADDING: isize = 3;
+
+/// What picking a menu item does
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Action {
+    SwitchEvent(String),
+    Copy,
+    /// Opens a menu of the snooze lengths in the same place
+    SnoozeMenu,
+    Snooze(SnoozeLength),
+    /// Opens the event editor
+    EditEvents,
+    Quit,
+}
+
+/// Right click menu, opened at the mouse and moved to fit on the canvas
+#[derive(Debug, Clone)]
+pub struct Menu {
+    items: Vec<(String, Action)>,
+    /// Top left corner
+    x: isize,
+    y: isize,
+    width: isize,
+    hovered: Option<usize>,
+}
+
+impl Menu {
+    pub fn new(items: Vec<(String, Action)>, xy: (usize, usize), canvas: (usize, usize)) -> Self {
+        let width = items
+            .iter()
+            .map(|(label, _)| Small.measure(label, WrappingStrategy::None).0 as isize)
+            .max()
+            .unwrap_or_default()
+            + PADDING * 2;
+        let height = items.len() as isize * ITEM_HEIGHT + PADDING;
+        Menu {
+            x: (xy.0 as isize).min(canvas.0 as isize - width - 1).max(0),
+            y: (xy.1 as isize).min(canvas.1 as isize - height - 1).max(0),
+            width,
+            items,
+            hovered: None,
+        }
+    }
+
+    /// Index of the item at `x`, `y`, if any
+    fn item_at(&self, x: usize, y: usize) -> Option<usize> {
+        let (x, y) = (x as isize - self.x, y as isize - self.y - PADDING);
+        if x < 0 || x > self.width || y < 0 {
+            return None;
+        }
+        let idx = (y / ITEM_HEIGHT) as usize;
+        (idx < self.items.len()).then_some(idx)
+    }
+
+    pub fn hover(&mut self, x: usize, y: usize) {
+        self.hovered = self.item_at(x, y);
+    }
+
+    /// Action for the item clicked, none if the click was outside the menu
+    pub fn click(&self, x: usize, y: usize) -> Option<Action> {
+        self.item_at(x, y).map(|idx| self.items[idx].1.clone())
+    }
+
+    pub fn render(&self, graphics: &mut Graphics) {
+        let height = self.items.len() as isize * ITEM_HEIGHT + PADDING;
+        let rect = Rect::new((self.x, self.y), (self.x + self.width, self.y + height));
+        graphics.draw_rect(rect.clone(), fill(BLACK));
+        graphics.draw_rect(rect, stroke(LIGHT_GRAY));
+        for (i, (label, _)) in self.items.iter().enumerate() {
+            let y = self.y + PADDING + i as isize * ITEM_HEIGHT;
+            let color = if self.hovered == Some(i) {
+                graphics.draw_rect(
+                    Rect::new((self.x + 1, y - 1), (self.x + self.width - 1, y + 6)),
+                    fill(DARK_GRAY),
+                );
+                WHITE
+            } else {
+                LIGHT_GRAY
+            };
+            graphics.draw_text(
+                label,
+                Px(self.x + PADDING, y),
+                (color, Small, Positioning::LeftTop),
+            );
+        }
+    }
+}
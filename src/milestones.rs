@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+
+/// Returns a description if `days` is a milestone, every 100 days and each year
+pub fn milestone(days: usize) -> Option<String> {
+    if days == 0 {
+        None
+    } else if days.is_multiple_of(365) {
+        let years = days / 365;
+        Some(format!("{years} year{}", if years == 1 { "" } else { "s" }))
+    } else if days.is_multiple_of(100) {
+        Some(format!("{days} days"))
+    } else {
+        None
+    }
+}
+
+/// The next milestone after `days`, returning its day count and description
+pub fn next(days: usize) -> (usize, String) {
+    Milestones::default().next(days)
+}
+
+/// Fun day counts that can also be [Milestones], none are unless turned on with `--patterns`,
+/// only from 100 days so the first few weeks aren't full of them
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Pattern {
+    /// Reads the same backwards, i.e. 1221
+    Palindrome,
+    /// Every digit the same, i.e. 1111
+    Repdigit,
+    /// i.e. 1024
+    PowerOfTwo,
+    /// The first digits of pi, 314, 3141 and 31415
+    Pi,
+}
+
+/// Smallest day count a [Pattern] can match
+const MIN_PATTERN_DAYS: usize = 100;
+
+impl Pattern {
+    pub const NAMES: [&'static str; 4] = ["palindrome", "repdigit", "power-of-two", "pi"];
+
+    pub fn from_name(name: &str) -> Option<Pattern> {
+        match name {
+            "palindrome" => Some(Pattern::Palindrome),
+            "repdigit" => Some(Pattern::Repdigit),
+            "power-of-two" => Some(Pattern::PowerOfTwo),
+            "pi" => Some(Pattern::Pi),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Pattern::Palindrome => "palindrome",
+            Pattern::Repdigit => "repdigit",
+            Pattern::PowerOfTwo => "power-of-two",
+            Pattern::Pi => "pi",
+        }
+    }
+
+    pub fn matches(self, days: usize) -> bool {
+        if days < MIN_PATTERN_DAYS {
+            return false;
+        }
+        let digits = days.to_string();
+        match self {
+            Pattern::Palindrome => digits.chars().eq(digits.chars().rev()),
+            Pattern::Repdigit => digits.bytes().all(|digit| digit == digits.as_bytes()[0]),
+            Pattern::PowerOfTwo => days.is_power_of_two(),
+            Pattern::Pi => "31415926".starts_with(&digits),
+        }
+    }
+
+    fn describe(self, days: usize) -> String {
+        match self {
+            Pattern::Palindrome => format!("{days} days, a palindrome"),
+            Pattern::Repdigit => format!("{days} days, all the same digit"),
+            Pattern::PowerOfTwo => format!("{days} days, a power of two"),
+            Pattern::Pi => format!("{days} days, the digits of pi"),
+        }
+    }
+}
+
+/// The usual milestones, plus any [Pattern]s turned on and an event's own
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Milestones {
+    pub patterns: Vec<Pattern>,
+    /// Days and message, from [crate::event::Event::milestones]
+    pub custom: Vec<(usize, String)>,
+}
+
+impl Milestones {
+    pub fn new(patterns: &[Pattern]) -> Self {
+        Milestones {
+            patterns: patterns.to_vec(),
+            custom: vec![],
+        }
+    }
+
+    /// As [milestone], an event's own come first and a repdigit is described as that rather
+    /// than a palindrome
+    pub fn milestone(&self, days: usize) -> Option<String> {
+        self.custom
+            .iter()
+            .find(|(custom, _)| *custom == days)
+            .map(|(_, message)| message.clone())
+            .or_else(|| milestone(days))
+            .or_else(|| {
+                [
+                    Pattern::Pi,
+                    Pattern::PowerOfTwo,
+                    Pattern::Repdigit,
+                    Pattern::Palindrome,
+                ]
+                .into_iter()
+                .find(|pattern| self.patterns.contains(pattern) && pattern.matches(days))
+                .map(|pattern| pattern.describe(days))
+            })
+    }
+
+    /// As [next]
+    pub fn next(&self, days: usize) -> (usize, String) {
+        (days + 1..)
+            .find_map(|days| self.milestone(days).map(|name| (days, name)))
+            .expect("there's a milestone every 100 days")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(days: usize, pattern: Pattern) -> usize {
+        Milestones::new(&[pattern]).next(days).0
+    }
+
+    #[test]
+    fn next_pattern_day() {
+        assert_eq!(next(1200, Pattern::Palindrome), 1221);
+        assert_eq!(next(1000, Pattern::PowerOfTwo), 1024);
+        assert_eq!(next(300, Pattern::Pi), 314);
+    }
+
+    #[test]
+    fn first_of_several_patterns() {
+        assert_eq!(
+            Milestones::new(&[Pattern::Palindrome, Pattern::Repdigit]).next(1100),
+            (1111, String::from("1111 days, all the same digit"))
+        );
+    }
+
+    fn custom() -> Milestones {
+        Milestones {
+            patterns: vec![Pattern::Palindrome],
+            custom: vec![
+                (101, String::from("Halfway mark")),
+                (200, String::from("Two hundred!")),
+            ],
+        }
+    }
+
+    #[test]
+    fn custom_milestones_come_first() {
+        assert_eq!(custom().next(100), (101, String::from("Halfway mark")));
+        assert_eq!(custom().milestone(200), Some(String::from("Two hundred!")));
+    }
+}
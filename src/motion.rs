@@ -0,0 +1,68 @@
+//! The OS's reduced motion setting, for `--reduced-motion`
+//!
+//! Each desktop keeps it somewhere different, if it can't be read it's taken as not set
+
+#[cfg(not(any(target_os = "macos", windows)))]
+use crate::paths;
+#[cfg(not(any(target_os = "macos", windows)))]
+use std::fs;
+use std::process::Command;
+
+/// Output of `program` with `args`, trimmed, none if it can't be run or fails
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// GNOME's (and most GTK desktops') animations switch, then KDE's animation speed
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn os_prefers_reduced() -> bool {
+    if let Some(enabled) = output(
+        "gsettings",
+        &["get", "org.gnome.desktop.interface", "enable-animations"],
+    ) {
+        return enabled == "false";
+    }
+    paths::xdg_config_home()
+        .and_then(|dir| fs::read_to_string(dir.join("kdeglobals")).ok())
+        .is_some_and(|config| {
+            config
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("AnimationDurationFactor="))
+                .any(|factor| {
+                    factor
+                        .trim()
+                        .parse::<f64>()
+                        .is_ok_and(|factor| factor == 0.0)
+                })
+        })
+}
+
+/// Accessibility > Display > Reduce motion
+#[cfg(target_os = "macos")]
+pub fn os_prefers_reduced() -> bool {
+    output(
+        "defaults",
+        &["read", "com.apple.universalaccess", "reduceMotion"],
+    )
+    .is_some_and(|value| value == "1")
+}
+
+/// Accessibility > Visual effects > Animation effects, which also stops windows animating
+/// when minimized
+#[cfg(windows)]
+pub fn os_prefers_reduced() -> bool {
+    output(
+        "reg",
+        &[
+            "query",
+            r"HKCU\Control Panel\Desktop\WindowMetrics",
+            "/v",
+            "MinAnimate",
+        ],
+    )
+    .is_some_and(|value| value.split_whitespace().last() == Some("0"))
+}
@@ -0,0 +1,172 @@
+use crate::credentials;
+use crate::outbox::{retry, SendError};
+use countup::report::Report;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// Secret used as the password when `username` is set
+const PASSWORD_SECRET: &str = "mqtt";
+const DEFAULT_PORT: u16 = 1883;
+const DEFAULT_TOPIC: &str = "countup";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Broker to publish the count to, set as `mqtt` in the config file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MqttConfig {
+    /// `host` or `host:port`, i.e. `homeassistant.local`
+    pub broker: String,
+    /// The report is published here as JSON and the days to `<topic>/days`, defaults to
+    /// `countup`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// The password is the `mqtt` secret
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+}
+
+/// Publishes reports from a background thread so a slow broker doesn't block rendering
+pub struct Publisher {
+    pub config: MqttConfig,
+    /// Report as JSON and its days
+    sender: Sender<(String, usize)>,
+}
+
+impl Publisher {
+    pub fn start(config: MqttConfig) -> Self {
+        let (sender, receiver) = channel::<(String, usize)>();
+        let broker = config.clone();
+        thread::spawn(move || {
+            let mut last = None;
+            while let Ok(mut report) = receiver.recv() {
+                //only the newest matters, the messages are retained
+                while let Ok(newer) = receiver.try_recv() {
+                    report = newer;
+                }
+                if last.as_ref() == Some(&report) {
+                    continue;
+                }
+                match retry(|| send(&broker, &report.0, report.1)) {
+                    Ok(()) => last = Some(report),
+                    Err(err) => eprintln!("Unable to publish to {}: {err}", broker.broker),
+                }
+            }
+        });
+        Publisher { config, sender }
+    }
+
+    /// Sends `report` to the broker if it's changed since the last one
+    pub fn publish(&self, report: &Report) {
+        if let Ok(json) = serde_json::to_string(report) {
+            let _ = self.sender.send((json, report.days));
+        }
+    }
+}
+
+/// Connects, publishes the report and its days as retained messages, then disconnects,
+/// MQTT 3.1.1 at QoS 0 is simple enough not to need a client library
+fn send(config: &MqttConfig, report: &str, days: usize) -> Result<(), SendError> {
+    let addr = if config.broker.contains(':') {
+        config.broker.clone()
+    } else {
+        format!("{}:{DEFAULT_PORT}", config.broker)
+    };
+    let transient = |err: std::io::Error| SendError::Transient(format!("{addr}: {err}"));
+    let socket = addr
+        .to_socket_addrs()
+        .map_err(transient)?
+        .next()
+        .ok_or_else(|| SendError::Permanent(format!("{addr} not found")))?;
+    let mut stream = TcpStream::connect_timeout(&socket, TIMEOUT).map_err(transient)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(transient)?;
+
+    let password = config
+        .username
+        .as_ref()
+        .and_then(|_| credentials::get(PASSWORD_SECRET));
+    stream
+        .write_all(&connect(config.username.as_deref(), password.as_deref()))
+        .map_err(transient)?;
+    let mut ack = [0; 4];
+    stream.read_exact(&mut ack).map_err(transient)?;
+    match ack {
+        [0x20, 2, _, 0] => {}
+        [0x20, 2, _, code @ (4 | 5)] => {
+            return Err(SendError::Permanent(format!(
+                "{addr} refused the login ({code})"
+            )))
+        }
+        [0x20, 2, _, code] => {
+            return Err(SendError::Transient(format!(
+                "{addr} refused the connection ({code})"
+            )))
+        }
+        _ => return Err(SendError::Permanent(format!("{addr} isn't an MQTT broker"))),
+    }
+    let topic = config.topic.as_deref().unwrap_or(DEFAULT_TOPIC);
+    stream
+        .write_all(&publish(topic, report.as_bytes()))
+        .and_then(|_| {
+            stream.write_all(&publish(
+                &format!("{topic}/days"),
+                days.to_string().as_bytes(),
+            ))
+        })
+        .and_then(|_| stream.write_all(&[0xE0, 0]))
+        .map_err(transient)
+}
+
+fn connect(username: Option<&str>, password: Option<&str>) -> Vec<u8> {
+    let mut body = string(b"MQTT");
+    //protocol level 4 is 3.1.1
+    body.push(4);
+    let mut flags = 0x02;
+    if username.is_some() {
+        flags |= 0x80;
+    }
+    if password.is_some() {
+        flags |= 0x40;
+    }
+    body.push(flags);
+    //keep alive, in seconds
+    body.extend(60u16.to_be_bytes());
+    body.extend(string(format!("countup-{}", std::process::id()).as_bytes()));
+    for field in [username, password].into_iter().flatten() {
+        body.extend(string(field.as_bytes()));
+    }
+    packet(0x10, body)
+}
+
+/// Retained so dashboards get the count as soon as they subscribe
+fn publish(topic: &str, payload: &[u8]) -> Vec<u8> {
+    let mut body = string(topic.as_bytes());
+    body.extend(payload);
+    packet(0x31, body)
+}
+
+/// Fixed header with the remaining length, 7 bits at a time
+fn packet(kind: u8, body: Vec<u8>) -> Vec<u8> {
+    let mut packet = vec![kind];
+    let mut len = body.len();
+    loop {
+        let byte = (len % 128) as u8;
+        len /= 128;
+        if len == 0 {
+            packet.push(byte);
+            break;
+        }
+        packet.push(byte | 0x80);
+    }
+    packet.extend(body);
+    packet
+}
+
+/// Length prefixed
+fn string(bytes: &[u8]) -> Vec<u8> {
+    let mut string = (bytes.len() as u16).to_be_bytes().to_vec();
+    string.extend(bytes);
+    string
+}
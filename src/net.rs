@@ -0,0 +1,173 @@
+use crate::credentials;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use rustls::RootCertStore;
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::CertificateDer;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use ureq::{Agent, AgentBuilder, Proxy};
+
+/// Name of the secret holding the proxy password
+const PROXY_SECRET: &str = "proxy";
+static AGENTS: OnceLock<Agents> = OnceLock::new();
+
+/// Settings for all outgoing requests
+///
+/// By default the `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY` env vars are used,
+/// and `SSL_CERT_FILE` is used as the CA bundle
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Proxy URL, i.e. `http://user@proxy.corp:8080`, overrides the env vars
+    ///
+    /// If there's a user but no password the `proxy` secret is used as the password
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Hosts to connect to directly, overrides `NO_PROXY`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<Vec<String>>,
+    /// PEM file of CA certificates trusted in addition to the built in ones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+}
+
+impl NetworkConfig {
+    pub fn is_empty(&self) -> bool {
+        self.proxy.is_none() && self.no_proxy.is_none() && self.ca_bundle.is_none()
+    }
+}
+
+struct Agents {
+    proxied: Agent,
+    direct: Agent,
+    no_proxy: Vec<String>,
+}
+
+/// Sets up the network settings, must be called before any requests are made
+/// otherwise only the env vars are used
+pub fn init(config: &NetworkConfig) -> Result<()> {
+    let agents = build_agents(config)?;
+    AGENTS
+        .set(agents)
+        .map_err(|_| eyre!("Network already initialised"))
+}
+
+/// Agent to use for requests to `url`
+pub fn agent(url: &str) -> Agent {
+    let agents = AGENTS.get_or_init(|| {
+        build_agents(&NetworkConfig::default()).unwrap_or_else(|err| {
+            eprintln!("Invalid network settings, using defaults: {err}");
+            Agents {
+                proxied: Agent::new(),
+                direct: Agent::new(),
+                no_proxy: vec![],
+            }
+        })
+    });
+    if bypass_proxy(&agents.no_proxy, url) {
+        agents.direct.clone()
+    } else {
+        agents.proxied.clone()
+    }
+}
+
+fn build_agents(config: &NetworkConfig) -> Result<Agents> {
+    let ca_bundle = config
+        .ca_bundle
+        .clone()
+        .or_else(|| env::var_os("SSL_CERT_FILE").map(PathBuf::from));
+    let tls = match ca_bundle {
+        Some(path) => Some(tls_config(&path)?),
+        None => None,
+    };
+    let builder = || {
+        let builder = AgentBuilder::new();
+        match &tls {
+            Some(tls) => builder.tls_config(tls.clone()),
+            None => builder,
+        }
+    };
+    let direct = builder().build();
+    let proxied = match &config.proxy {
+        Some(proxy) => builder()
+            .proxy(
+                Proxy::new(with_password(proxy))
+                    .wrap_err_with(|| format!("Invalid proxy {proxy}"))?,
+            )
+            .build(),
+        None => builder().try_proxy_from_env(true).build(),
+    };
+    let no_proxy = config.no_proxy.clone().unwrap_or_else(|| {
+        env::var("NO_PROXY")
+            .or_else(|_| env::var("no_proxy"))
+            .unwrap_or_default()
+            .split(',')
+            .map(|host| host.trim().to_string())
+            .filter(|host| !host.is_empty())
+            .collect()
+    });
+    Ok(Agents {
+        proxied,
+        direct,
+        no_proxy,
+    })
+}
+
+/// Adds the `proxy` secret to `proxy` if it has a user but no password
+fn with_password(proxy: &str) -> String {
+    let (scheme, rest) = proxy.split_once("://").unwrap_or(("", proxy));
+    let Some((user, host)) = rest.split_once('@') else {
+        return proxy.to_string();
+    };
+    if user.contains(':') {
+        return proxy.to_string();
+    }
+    match credentials::get(PROXY_SECRET) {
+        Some(password) if scheme.is_empty() => format!("{user}:{password}@{host}"),
+        Some(password) => format!("{scheme}://{user}:{password}@{host}"),
+        None => proxy.to_string(),
+    }
+}
+
+/// Built in roots plus the certificates in the PEM file at `path`
+fn tls_config(path: &Path) -> Result<Arc<rustls::ClientConfig>> {
+    let mut roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|iter| iter.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| eyre!("{err}"))
+        .wrap_err_with(|| format!("Reading CA bundle {}", path.display()))?;
+    let (added, _) = roots.add_parsable_certificates(certs);
+    if added == 0 {
+        return Err(eyre!("No valid certificates in {}", path.display()));
+    }
+    let tls = rustls::ClientConfig::builder_with_provider(
+        rustls::crypto::ring::default_provider().into(),
+    )
+    .with_safe_default_protocol_versions()?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    Ok(Arc::new(tls))
+}
+
+/// Checks the host of `url` against NO_PROXY style entries, `*` matches everything and
+/// `example.com` or `.example.com` match the domain and its subdomains
+fn bypass_proxy(no_proxy: &[String], url: &str) -> bool {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let authority = without_scheme.split('/').next().unwrap_or_default();
+    let host_port = authority.rsplit('@').next().unwrap_or_default();
+    let host = if let Some(ipv6) = host_port.strip_prefix('[') {
+        ipv6.split(']').next().unwrap_or_default()
+    } else {
+        host_port.split(':').next().unwrap_or_default()
+    }
+    .to_lowercase();
+    no_proxy.iter().any(|entry| {
+        let entry = entry.trim_start_matches('.').to_lowercase();
+        entry == "*" || host == entry || host.ends_with(&format!(".{entry}"))
+    })
+}
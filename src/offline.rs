@@ -0,0 +1,263 @@
+use crate::event::Event;
+use crate::paths;
+use crate::shared::{self, Change, Edit, Request, Response, SyncResult};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::{Report, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use std::{fs, mem};
+
+/// Edits waiting to be sent to each daemon and any that conflicted, in the data dir
+const OFFLINE_FILE: &str = "offline.json";
+
+/// Guards the offline file as the GUI syncs from a background thread
+static OFFLINE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Each daemon's [State], by the address it's connected to with
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Queues {
+    daemons: BTreeMap<String, State>,
+    /// From before the state was kept for each daemon, given to the first one used
+    #[serde(flatten, skip_serializing)]
+    unsorted: State,
+}
+
+impl Queues {
+    fn daemon(&mut self, addr: &str) -> &mut State {
+        self.daemons
+            .entry(addr.to_string())
+            .or_insert_with(|| mem::take(&mut self.unsorted))
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct State {
+    /// Daemon time the events were last received
+    last_sync: Option<DateTime<Utc>>,
+    pending: Vec<Edit>,
+    conflicts: Vec<Conflict>,
+}
+
+/// Edit that wasn't applied as the daemon's version changed too
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conflict {
+    pub edit: Edit,
+    pub reason: String,
+    pub theirs: Option<Event>,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Applied,
+    /// The daemon couldn't be reached, it will be sent on the next connection
+    Queued,
+    /// Kept for review with `countup conflicts`
+    Conflict(String),
+}
+
+/// Makes `change` to the event `name` on the daemon at `addr`, queuing it if the daemon
+/// can't be reached
+pub fn edit(addr: &str, name: &str, change: Change) -> Result<Outcome> {
+    //held until it's sent, so another thread's flush can't send it and get its result
+    let _lock = lock();
+    let mut queues = read()?;
+    let state = queues.daemon(addr);
+    state.pending.push(Edit {
+        name: name.to_string(),
+        change,
+        seen_at: state.last_sync,
+        made_at: Utc::now(),
+        client: shared::client_id(),
+        force: false,
+    });
+    write(&queues)?;
+    let mut results = match send(addr, queues) {
+        Ok(results) => results,
+        Err(err) if is_unreachable(&err) => return Ok(Outcome::Queued),
+        Err(err) => return Err(err),
+    };
+    let (_, result) = results
+        .pop()
+        .ok_or_else(|| eyre!("Unexpected response from daemon"))?;
+    if !results.is_empty() {
+        report(&results);
+    }
+    match result {
+        SyncResult::Applied => Ok(Outcome::Applied),
+        SyncResult::Conflict { reason, .. } => Ok(Outcome::Conflict(reason)),
+        SyncResult::Rejected(msg) => Err(eyre!(msg)),
+    }
+}
+
+/// Sends any edits queued for `addr`, conflicts are stored for review and rejected edits
+/// are dropped
+pub fn flush(addr: &str) -> Result<Vec<(Edit, SyncResult)>> {
+    let _lock = lock();
+    send(addr, read()?)
+}
+
+/// Sends the edits queued for `addr` and saves what happened, the lock must be held
+fn send(addr: &str, mut queues: Queues) -> Result<Vec<(Edit, SyncResult)>> {
+    let state = queues.daemon(addr);
+    if state.pending.is_empty() {
+        return Ok(vec![]);
+    }
+    let edits = state.pending.clone();
+    let Response::Synced { now, results, .. } = shared::request(
+        addr,
+        &Request::Sync {
+            edits: edits.clone(),
+        },
+    )?
+    else {
+        return Err(eyre!("Unexpected response from daemon"));
+    };
+    state.pending.clear();
+    state.last_sync = Some(now);
+    let results: Vec<(Edit, SyncResult)> = edits.into_iter().zip(results).collect();
+    for (edit, result) in &results {
+        if let SyncResult::Conflict { reason, theirs } = result {
+            state.conflicts.push(Conflict {
+                edit: edit.clone(),
+                reason: reason.clone(),
+                theirs: theirs.clone(),
+            });
+        }
+    }
+    write(&queues)?;
+    Ok(results)
+}
+
+/// Prints what happened to queued edits
+pub fn report(results: &[(Edit, SyncResult)]) {
+    let applied = results
+        .iter()
+        .filter(|(_, result)| matches!(result, SyncResult::Applied))
+        .count();
+    if applied > 0 {
+        eprintln!("Sent {applied} queued edit(s)");
+    }
+    for (edit, result) in results {
+        if let SyncResult::Rejected(msg) = result {
+            eprintln!("Dropped queued edit to {}: {msg}", edit.name);
+        }
+    }
+    let conflicts = results
+        .iter()
+        .filter(|(_, result)| matches!(result, SyncResult::Conflict { .. }))
+        .count();
+    if conflicts > 0 {
+        eprintln!("{conflicts} queued edit(s) conflicted, review with `countup conflicts`");
+    }
+}
+
+/// Records that the events were received from the daemon at `addr` at its time `now`
+pub fn seen(addr: &str, now: DateTime<Utc>) {
+    let _lock = lock();
+    let result = read().and_then(|mut queues| {
+        queues.daemon(addr).last_sync = Some(now);
+        write(&queues)
+    });
+    if let Err(err) = result {
+        eprintln!("Unable to save sync time: {err}");
+    }
+}
+
+/// Fails if the queued edits can't be read
+pub fn check() -> Result<()> {
+    let _lock = lock();
+    read().map(|_| ())
+}
+
+pub fn conflicts(addr: &str) -> Result<Vec<Conflict>> {
+    let _lock = lock();
+    Ok(mem::take(&mut read()?.daemon(addr).conflicts))
+}
+
+/// Removes the conflict at `idx`, if `keep_mine` its edit is sent again ignoring the
+/// daemon's changes
+pub fn resolve(addr: &str, idx: usize, keep_mine: bool) -> Result<Outcome> {
+    let _lock = lock();
+    let mut queues = read()?;
+    let state = queues.daemon(addr);
+    if idx >= state.conflicts.len() {
+        return Err(eyre!("No conflict {}", idx + 1));
+    }
+    let mut conflict = state.conflicts.remove(idx);
+    if keep_mine {
+        conflict.edit.force = true;
+        state.pending.push(conflict.edit);
+    }
+    write(&queues)?;
+    if !keep_mine {
+        return Ok(Outcome::Applied);
+    }
+    match send(addr, queues) {
+        Ok(results) => {
+            report(&results);
+            Ok(Outcome::Applied)
+        }
+        Err(err) if is_unreachable(&err) => Ok(Outcome::Queued),
+        Err(err) => Err(err),
+    }
+}
+
+/// Connection errors mean the daemon is down or the network is unavailable
+fn is_unreachable(err: &Report) -> bool {
+    err.chain().any(|cause| cause.is::<std::io::Error>())
+}
+
+fn lock() -> MutexGuard<'static, ()> {
+    OFFLINE_LOCK.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn path() -> Result<PathBuf> {
+    let mut path = paths::data_dir()?;
+    path.push(OFFLINE_FILE);
+    Ok(path)
+}
+
+fn read() -> Result<Queues> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(Queues::default());
+    }
+    let json = fs::read_to_string(&path).wrap_err_with(|| format!("Reading {}", path.display()))?;
+    serde_json::from_str(&json).wrap_err_with(|| format!("Parsing {}", path.display()))
+}
+
+fn write(queues: &Queues) -> Result<()> {
+    let path = path()?;
+    let json = serde_json::to_string_pretty(queues)?;
+    fs::write(&path, json).wrap_err_with(|| format!("Writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_is_kept_for_each_daemon() {
+        let mut queues = Queues::default();
+        queues.daemon("10.0.0.1:7878").last_sync = Some(Utc::now());
+        assert!(queues.daemon("10.0.0.2:7878").last_sync.is_none());
+        assert!(queues.daemon("10.0.0.1:7878").last_sync.is_some());
+    }
+
+    #[test]
+    fn unsorted_state_goes_to_the_first_daemon() {
+        let mut queues: Queues =
+            serde_json::from_str(r#"{"last_sync": "2026-10-01T12:00:00Z", "pending": []}"#)
+                .unwrap();
+        assert!(queues.daemon("10.0.0.1:7878").last_sync.is_some());
+        assert!(queues.daemon("10.0.0.2:7878").last_sync.is_none());
+        let json = serde_json::to_value(&queues).unwrap();
+        assert!(json.get("last_sync").is_none());
+        assert_eq!(json["daemons"].as_object().unwrap().len(), 2);
+    }
+}
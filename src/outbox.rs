@@ -0,0 +1,188 @@
+use crate::{net, paths};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+const OUTBOX_FILE: &str = "outbox.json";
+/// Oldest messages are dropped once the queue is this long
+const MAX_QUEUED: usize = 100;
+const MAX_ATTEMPTS: u32 = 5;
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Guards the outbox file as messages are sent from multiple threads
+static OUTBOX_LOCK: Mutex<()> = Mutex::new(());
+/// Messages being sent or retried, so they can be saved if the app is closed
+static IN_FLIGHT: Mutex<Vec<(u64, Message)>> = Mutex::new(vec![]);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Outgoing network message, stored in the outbox if it can't be delivered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Message {
+    Webhook { url: String, body: Value },
+}
+
+#[derive(Debug)]
+pub enum SendError {
+    /// Might work later, i.e. no connection or a server error
+    Transient(String),
+    /// Won't ever work, i.e. a bad URL or rejected request
+    Permanent(String),
+}
+
+impl Display for SendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SendError::Transient(msg) => write!(f, "{msg} (will retry)"),
+            SendError::Permanent(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl Message {
+    fn send(&self) -> Result<(), SendError> {
+        match self {
+            Message::Webhook { url, body } => match net::agent(url).post(url).send_json(body) {
+                Ok(_) => Ok(()),
+                Err(ureq::Error::Status(code, _)) if code == 429 || code >= 500 => {
+                    Err(SendError::Transient(format!("{url} returned {code}")))
+                }
+                Err(ureq::Error::Status(code, _)) => {
+                    Err(SendError::Permanent(format!("{url} returned {code}")))
+                }
+                Err(err) => Err(SendError::Transient(err.to_string())),
+            },
+        }
+    }
+}
+
+/// Runs `action` until it succeeds, fails permanently or runs out of attempts, doubling
+/// the wait between each attempt
+pub fn retry<T>(mut action: impl FnMut() -> Result<T, SendError>) -> Result<T, SendError> {
+    let mut delay = FIRST_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        match action() {
+            Err(SendError::Transient(_)) if attempt < MAX_ATTEMPTS => {
+                thread::sleep(delay);
+                delay = (delay * 2).min(MAX_RETRY_DELAY);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Sends `message` with retries, if it still can't be sent it's queued until the next
+/// successful send or launch
+///
+/// This blocks while retrying so should be called from a background thread
+pub fn deliver(message: Message) -> Result<(), SendError> {
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    in_flight().push((id, message.clone()));
+    let result = retry(|| message.send());
+    let was_in_flight = {
+        let mut in_flight = in_flight();
+        let len = in_flight.len();
+        in_flight.retain(|(other, _)| *other != id);
+        in_flight.len() != len
+    };
+    //already queued by persist_in_flight
+    if !was_in_flight {
+        return result;
+    }
+    match result {
+        Ok(()) => {
+            flush();
+            Ok(())
+        }
+        Err(err @ SendError::Transient(_)) => {
+            enqueue(message);
+            Err(err)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Tries to send every queued message once, any that fail transiently stay queued
+pub fn flush() {
+    let queued = {
+        let _lock = OUTBOX_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let queued = read();
+        if queued.is_empty() {
+            return;
+        }
+        write(&[]);
+        queued
+    };
+    for message in queued {
+        match message.send() {
+            Ok(()) => {}
+            Err(SendError::Transient(_)) => enqueue(message),
+            Err(err) => eprintln!("Dropping queued message: {err}"),
+        }
+    }
+}
+
+/// Queues every message still being sent, for when the app is closing and the sending
+/// threads won't get to finish
+///
+/// A message may be sent twice if its current attempt succeeds
+pub fn persist_in_flight() {
+    let messages: Vec<Message> = in_flight().drain(..).map(|(_, message)| message).collect();
+    for message in messages {
+        enqueue(message);
+    }
+}
+
+fn in_flight() -> std::sync::MutexGuard<'static, Vec<(u64, Message)>> {
+    IN_FLIGHT.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn enqueue(message: Message) {
+    let _lock = OUTBOX_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let mut queued = read();
+    queued.push(message);
+    if queued.len() > MAX_QUEUED {
+        let dropped = queued.len() - MAX_QUEUED;
+        eprintln!("Outbox full, dropping {dropped} oldest message(s)");
+        queued.drain(..dropped);
+    }
+    write(&queued);
+}
+
+fn path() -> Option<PathBuf> {
+    paths::data_dir()
+        .map(|mut path| {
+            path.push(OUTBOX_FILE);
+            path
+        })
+        .ok()
+}
+
+fn read() -> Vec<Message> {
+    path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn write(queued: &[Message]) {
+    let Some(path) = path() else {
+        eprintln!("Unable to get data dir, {} message(s) lost", queued.len());
+        return;
+    };
+    let result = serde_json::to_string(queued)
+        .map_err(|err| err.to_string())
+        .and_then(|json| fs::write(&path, json).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        eprintln!("Unable to save outbox to {}: {err}", path.display());
+    }
+}
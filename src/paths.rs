@@ -0,0 +1,85 @@
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use pixels_graphics_lib::prefs::preferences::get_pref_dir;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+static PREFS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Replaces the app's pref dir, must be called before any files are read
+pub fn set_data_dir(dir: PathBuf) {
+    DATA_DIR
+        .set(dir)
+        .expect("data dir should only be set once, at startup");
+}
+
+/// If `--data-dir` was passed
+pub fn has_data_dir() -> bool {
+    DATA_DIR.get().is_some()
+}
+
+/// Replaces the data dir for the prefs only, must be called before they're loaded
+pub fn set_prefs_dir(dir: PathBuf) {
+    PREFS_DIR
+        .set(dir)
+        .expect("prefs dir should only be set once, at startup");
+}
+
+/// If `--prefs-dir` was passed
+pub fn has_prefs_dir() -> bool {
+    PREFS_DIR.get().is_some()
+}
+
+/// `$XDG_CONFIG_HOME`, or `~/.config` if it's not set, on every platform
+pub fn xdg_config_home() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::home_dir().map(|home| home.join(".config")))
+}
+
+/// `$XDG_CONFIG_HOME/countup`, or `~/.config/countup` if it's not set, on every platform
+pub fn xdg_config_dir() -> Option<PathBuf> {
+    xdg_config_home().map(|dir| dir.join("countup"))
+}
+
+/// Dir for the config, prefs, outbox and credentials, the one passed to `--data-dir` or
+/// the app's pref dir
+pub fn data_dir() -> Result<PathBuf> {
+    match DATA_DIR.get() {
+        Some(dir) => {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Creating data dir {}", dir.display()))?;
+            Ok(dir.clone())
+        }
+        None => get_pref_dir("app", "emmabritton", "countup")
+            .map_err(|err| eyre!("Unable to get pref dir: {err}, use --data-dir to set one")),
+    }
+}
+
+/// Dir for the remembered choices, the one passed to `--prefs-dir` or the data dir
+pub fn prefs_dir() -> Result<PathBuf> {
+    match PREFS_DIR.get() {
+        Some(dir) => {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Creating prefs dir {}", dir.display()))?;
+            Ok(dir.clone())
+        }
+        None => data_dir(),
+    }
+}
+
+/// Writes `contents` to `path` so it's only readable by the user, for files with secrets
+pub fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+}
@@ -0,0 +1,182 @@
+use crate::selftest;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use pixels_graphics_lib::prefs::preferences::get_pref_dir;
+use pixels_graphics_lib::WindowScaling;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::process::Command;
+use winit::event_loop::EventLoop;
+
+/// Window prefs used with `--position` and `--monitor`, so the remembered position is kept
+/// for next time
+pub const PREFS_NAME: &str = "countup-placed";
+/// Gap between the presets and the edges of the screen, room for panels and the title bar,
+/// at 1x
+const EDGE_GAP: f64 = 24.0;
+
+/// Where `--position` puts the window on the monitor
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Position {
+    /// Pixels from the top left of the monitor
+    At(i32, i32),
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+impl Position {
+    pub const PRESETS: [&'static str; 5] = [
+        "top-left",
+        "top-right",
+        "bottom-left",
+        "bottom-right",
+        "center",
+    ];
+
+    /// A preset or `x,y`
+    pub fn parse(text: &str) -> Option<Position> {
+        let position = match text.trim() {
+            "top-left" => Position::TopLeft,
+            "top-right" => Position::TopRight,
+            "bottom-left" => Position::BottomLeft,
+            "bottom-right" => Position::BottomRight,
+            "center" => Position::Center,
+            text => {
+                let (x, y) = text.split_once(',')?;
+                Position::At(x.trim().parse().ok()?, y.trim().parse().ok()?)
+            }
+        };
+        Some(position)
+    }
+}
+
+/// Position and size in physical pixels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Monitor {
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale: f64,
+    pub primary: bool,
+}
+
+/// Monitors in the order `--monitor` numbers them, this creates an event loop which can
+/// only happen once per process, so the window's own process uses [find_monitors] instead
+pub fn list() -> Result<Vec<Monitor>> {
+    //winit panics if there's no display
+    selftest::display()?;
+    let event_loop = EventLoop::new();
+    let primary = event_loop.primary_monitor();
+    let monitors = event_loop
+        .available_monitors()
+        .enumerate()
+        .map(|(i, monitor)| Monitor {
+            name: monitor.name().unwrap_or_else(|| format!("Monitor {i}")),
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+            scale: monitor.scale_factor(),
+            primary: primary.as_ref() == Some(&monitor),
+        })
+        .collect();
+    Ok(monitors)
+}
+
+/// Runs `countup monitors --json` to list the monitors from another process
+fn find_monitors() -> Result<Vec<Monitor>> {
+    selftest::display()?;
+    let exe = env::current_exe().wrap_err("Finding the countup executable")?;
+    let output = Command::new(exe)
+        .args(["monitors", "--json"])
+        .output()
+        .wrap_err("Finding the monitors")?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "Finding the monitors: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout).wrap_err("Reading the monitors")
+}
+
+/// Monitor `monitor`, or the primary one if not set
+fn find_monitor(monitor: Option<usize>) -> Result<Monitor> {
+    let mut monitors = find_monitors()?;
+    match monitor {
+        Some(index) if index < monitors.len() => Ok(monitors.swap_remove(index)),
+        Some(index) => Err(eyre!(
+            "No monitor {index}, there are {}, see `countup monitors`",
+            monitors.len()
+        )),
+        None if monitors.is_empty() => Err(eyre!("No monitors found")),
+        None => {
+            let primary = monitors.iter().position(|monitor| monitor.primary);
+            Ok(monitors.swap_remove(primary.unwrap_or_default()))
+        }
+    }
+}
+
+/// Physical pixels per canvas pixel, how pixels-graphics-lib sizes the window
+fn factor(target: &Monitor, scaling: WindowScaling) -> f64 {
+    let scale = match scaling {
+        WindowScaling::None => 1.0,
+        WindowScaling::Auto => target.scale.ceil(),
+        WindowScaling::Fixed(amount) => amount as f64,
+        WindowScaling::AutoFixed(amount) => amount as f64 + target.scale.ceil(),
+    };
+    scale * target.scale
+}
+
+/// Canvas size that fills `monitor`, or the primary monitor if not set, for `--screensaver`
+pub fn filling(monitor: Option<usize>, scaling: WindowScaling) -> Result<(usize, usize)> {
+    let target = find_monitor(monitor)?;
+    let factor = factor(&target, scaling);
+    Ok((
+        (target.width as f64 / factor) as usize,
+        (target.height as f64 / factor) as usize,
+    ))
+}
+
+/// Saves the window prefs for [PREFS_NAME] so the `canvas` opens at `position` on `monitor`,
+/// centered on the primary monitor if either isn't set
+pub fn place(
+    position: Option<Position>,
+    monitor: Option<usize>,
+    canvas: (usize, usize),
+    scaling: WindowScaling,
+) -> Result<()> {
+    let target = find_monitor(monitor)?;
+    let factor = factor(&target, scaling);
+    let width = (canvas.0 as f64 * factor).round() as i32;
+    let height = (canvas.1 as f64 * factor).round() as i32;
+    let gap = (EDGE_GAP * target.scale).round() as i32;
+    let left = target.x + gap;
+    let top = target.y + gap;
+    let right = target.x + target.width as i32 - width - gap;
+    let bottom = target.y + target.height as i32 - height - gap;
+    let (x, y) = match position.unwrap_or(Position::Center) {
+        Position::At(x, y) => (target.x + x, target.y + y),
+        Position::TopLeft => (left, top),
+        Position::TopRight => (right, top),
+        Position::BottomLeft => (left, bottom),
+        Position::BottomRight => (right, bottom),
+        Position::Center => (
+            target.x + (target.width as i32 - width) / 2,
+            target.y + (target.height as i32 - height) / 2,
+        ),
+    };
+    let dir = get_pref_dir("app", "emmabritton", PREFS_NAME)
+        .map_err(|err| eyre!("Saving the window position: {err}"))?;
+    //the format pixels-graphics-lib restores the window from
+    let prefs = serde_json::json!({
+        "window.pref": {"x": x, "y": y, "w": width, "h": height}
+    });
+    fs::write(dir.join("window.pref"), prefs.to_string()).wrap_err("Saving the window position")
+}
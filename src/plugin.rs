@@ -0,0 +1,161 @@
+//! Modes that aren't built in, i.e. a calendar heatmap, added to the ones cycled through
+//!
+//! Rust code can implement [DisplayMode] and [Registry::register] it, other languages can
+//! build a shared library with the functions in `include/countup_mode.h` for the app to load
+//! with [External::load]
+
+use crate::render::Palette;
+use crate::report::Report;
+use buffer_graphics_lib::prelude::*;
+
+/// A view over the breakdown, drawn instead of the built in modes while it's selected
+pub trait DisplayMode {
+    /// Shown in the help and sent over D-Bus as the mode
+    fn name(&self) -> &str;
+
+    /// Called every frame while it's shown, `delta` is the seconds since the last, for
+    /// animating
+    fn update(&mut self, _report: &Report, _delta: f64) {}
+
+    /// Draws over the background, the whole canvas is the mode's
+    fn render(&self, graphics: &mut Graphics, report: &Report, palette: Palette);
+}
+
+/// Modes added after the built in ones, in the order they were registered
+#[derive(Default)]
+pub struct Registry {
+    modes: Vec<Box<dyn DisplayMode>>,
+}
+
+impl std::fmt::Debug for Registry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.modes.iter().map(|mode| mode.name()))
+            .finish()
+    }
+}
+
+impl Registry {
+    /// Adds `mode`, returning its index for [Mode::Plugin](crate::count::Mode::Plugin), none
+    /// if there's already as many as that can hold
+    pub fn register(&mut self, mode: Box<dyn DisplayMode>) -> Option<u8> {
+        let index = u8::try_from(self.modes.len()).ok()?;
+        self.modes.push(mode);
+        Some(index)
+    }
+
+    pub fn get(&self, index: u8) -> Option<&dyn DisplayMode> {
+        self.modes.get(index as usize).map(|mode| mode.as_ref())
+    }
+
+    pub fn get_mut(&mut self, index: u8) -> Option<&mut Box<dyn DisplayMode>> {
+        self.modes.get_mut(index as usize)
+    }
+
+    pub fn len(&self) -> usize {
+        self.modes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modes.is_empty()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use external::External;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod external {
+    use super::DisplayMode;
+    use crate::render::Palette;
+    use crate::report::{CountupBreakdown, Report};
+    use buffer_graphics_lib::prelude::*;
+    use libloading::Library;
+    use std::cell::RefCell;
+    use std::ffi::{c_char, CStr};
+    use std::path::Path;
+
+    type NameFn = unsafe extern "C" fn() -> *const c_char;
+    type UpdateFn = unsafe extern "C" fn(*const CountupBreakdown, f64);
+    type RenderFn = unsafe extern "C" fn(*const CountupBreakdown, u32, u32, *mut u32, usize, usize);
+
+    /// A mode from a shared library, see `include/countup_mode.h`
+    pub struct External {
+        name: String,
+        update: Option<UpdateFn>,
+        render: RenderFn,
+        /// Reused between frames, cleared before each
+        pixels: RefCell<Vec<u32>>,
+        //the functions point into it, so it has to outlive them
+        _library: Library,
+    }
+
+    impl External {
+        /// Loads the library at `path`, failing if it's missing `countup_mode_name` or
+        /// `countup_mode_render`
+        ///
+        /// # Safety
+        /// Its initialisers are run, and its functions must match `include/countup_mode.h`
+        pub unsafe fn load(path: &Path) -> Result<Self, String> {
+            let library = Library::new(path).map_err(|err| err.to_string())?;
+            let name = *library
+                .get::<NameFn>(b"countup_mode_name\0")
+                .map_err(|err| err.to_string())?;
+            let render = *library
+                .get::<RenderFn>(b"countup_mode_render\0")
+                .map_err(|err| err.to_string())?;
+            let update = library
+                .get::<UpdateFn>(b"countup_mode_update\0")
+                .ok()
+                .map(|update| *update);
+            let name = name();
+            if name.is_null() {
+                return Err(String::from("countup_mode_name returned null"));
+            }
+            Ok(External {
+                name: CStr::from_ptr(name).to_string_lossy().into_owned(),
+                update,
+                render,
+                pixels: RefCell::new(vec![]),
+                _library: library,
+            })
+        }
+    }
+
+    impl DisplayMode for External {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn update(&mut self, report: &Report, delta: f64) {
+            if let Some(update) = self.update {
+                let breakdown = CountupBreakdown::from(report);
+                unsafe { update(&breakdown, delta) }
+            }
+        }
+
+        fn render(&self, graphics: &mut Graphics, report: &Report, palette: Palette) {
+            let (width, height) = (graphics.width(), graphics.height());
+            let mut pixels = self.pixels.borrow_mut();
+            pixels.clear();
+            pixels.resize(width * height, 0);
+            let breakdown = CountupBreakdown::from(report);
+            unsafe {
+                (self.render)(
+                    &breakdown,
+                    palette.background.as_i32() as u32,
+                    palette.accent.as_i32() as u32,
+                    pixels.as_mut_ptr(),
+                    width,
+                    height,
+                )
+            };
+            for (i, pixel) in pixels.iter().enumerate() {
+                let color = Color::from_i32(*pixel as i32);
+                if color.a > 0 {
+                    graphics.blend_pixel((i % width) as isize, (i / width) as isize, color);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::milestones::milestone;
+
+/// Summary of the latest completed year, shown first on each anniversary and in recap
+/// mode for the rest of the year
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Recap {
+    /// Completed year, starting at 1
+    pub year: usize,
+    pub days: usize,
+    /// Milestones passed during the year, including the anniversary itself
+    pub milestones: usize,
+}
+
+impl Recap {
+    /// Recap of the year finished most recently, none until a year has passed
+    pub fn latest(days: usize) -> Option<Self> {
+        let year = days / 365;
+        if year == 0 {
+            return None;
+        }
+        let first = (year - 1) * 365 + 1;
+        let last = year * 365;
+        Some(Recap {
+            year,
+            days: last - first + 1,
+            milestones: (first..=last)
+                .filter(|day| milestone(*day).is_some())
+                .count(),
+        })
+    }
+}
+
+pub fn is_anniversary(days: usize) -> bool {
+    days > 0 && days.is_multiple_of(365)
+}
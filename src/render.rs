@@ -0,0 +1,2136 @@
+use crate::count::{
+    CalendarProgress, Count, Fiscal, Intro, IsoWeeks, PreciseAge, Timeline, Totals, Weekdays,
+    YearProgress,
+};
+use crate::date::DatePicker;
+use crate::event::{CustomMilestone, Event, PhaseProgress, Rgb};
+use crate::font::NumberFont;
+use crate::i18n::{group_digits, time_format, Strings};
+use crate::report::{MonthLength, Moons, Split, Upcoming};
+use crate::roman::roman;
+use crate::term::TermProgress;
+use crate::units::{Counts, Formula, MAX_UNITS};
+use buffer_graphics_lib::prelude::Positioning::{CenterBottom, CenterTop, LeftTop, RightTop};
+use buffer_graphics_lib::prelude::*;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::cell::{Ref, RefCell};
+
+/// Right edge of the numbers, unless they're too wide
+const COL_NUM: isize = 120;
+const COL_PERIOD: isize = 128;
+/// Gap between the numbers and their labels
+const COL_GAP: isize = COL_PERIOD - COL_NUM;
+const ROW_GAP: usize = 6;
+const TMINUS_AMBER_DAYS: i64 = 7;
+const TMINUS_RED_DAYS: i64 = 1;
+/// Height of the strip under the modes for `--goal`
+pub const GOAL_HEIGHT: usize = 14;
+/// Height of the smallest text drawn, at scale 1
+pub const SMALLEST_TEXT: usize = Small.get_size().1;
+/// Contrast between the numbers and the background for `--high-contrast`, WCAG's AAA level
+pub const MIN_CONTRAST: f64 = 7.0;
+
+/// Colours for phases, in order and repeating
+const PHASE_COLORS: [Color; 6] = [
+    Color::rgb(66, 135, 245),
+    Color::rgb(76, 175, 80),
+    Color::rgb(255, 152, 0),
+    Color::rgb(171, 71, 188),
+    Color::rgb(0, 188, 212),
+    Color::rgb(255, 235, 59),
+];
+
+/// How far through the intro the count is, for drawing the rows of numbers
+#[derive(Debug, Copy, Clone)]
+pub struct Reveal {
+    pub intro: Intro,
+    pub progress: f64,
+}
+
+impl Reveal {
+    pub fn of(count: &Count) -> Self {
+        Reveal {
+            intro: count.intro(),
+            progress: count.progress(),
+        }
+    }
+
+    /// Rows are revealed one after another, except when counting up
+    fn row_progress(&self, row: usize, rows: usize) -> f64 {
+        (self.progress * rows as f64 - row as f64).clamp(0.0, 1.0)
+    }
+
+    /// Number to draw for `row`, cached in `slot` and only formatted again when it changes
+    fn text<'a>(
+        &self,
+        value: usize,
+        row: usize,
+        rows: usize,
+        texts: &'a Texts,
+        slot: usize,
+    ) -> Ref<'a, str> {
+        let progress = self.row_progress(row, rows);
+        let animated = matches!(self.intro, Intro::Typewriter | Intro::Slot) && progress < 1.0;
+        let frame = animated.then(|| self.progress.to_bits());
+        texts.rows[slot].get((value, frame), |_| {
+            self.format(value, progress, row, texts.separator)
+        })
+    }
+
+    fn format(&self, value: usize, progress: f64, row: usize, separator: &str) -> String {
+        let text = group_digits(value, separator);
+        match self.intro {
+            Intro::Typewriter => {
+                let shown = (text.len() as f64 * progress).ceil() as usize;
+                text[..shown].to_string()
+            }
+            Intro::Slot if progress < 1.0 => {
+                //digits stop left to right, the rest keep spinning
+                let settled = (text.len() as f64 * progress) as usize;
+                let spin = (self.progress * 60.0) as u64;
+                text.chars()
+                    .enumerate()
+                    .map(|(i, chr)| {
+                        if i < settled || !chr.is_ascii_digit() {
+                            chr
+                        } else {
+                            let digit = scramble(spin, (row * 31 + i) as u64) % 10;
+                            char::from_digit(digit as u32, 10).expect("less than 10")
+                        }
+                    })
+                    .collect()
+            }
+            _ => text,
+        }
+    }
+
+    /// While rolling, the (fractional) value for an odometer to show instead of the text
+    fn rolling(&self, value: usize) -> Option<f64> {
+        (self.intro == Intro::Odometer && self.progress < 1.0)
+            .then_some(value as f64 * self.progress)
+    }
+
+    /// Colour to draw `row` with, fading in from the background
+    fn color(&self, color: Color, background: Color, row: usize, rows: usize) -> Color {
+        match self.intro {
+            Intro::Fade => mix(background, color, self.row_progress(row, rows)),
+            _ => color,
+        }
+    }
+}
+
+/// Colours for an event's modes
+#[derive(Debug, Copy, Clone)]
+pub struct Palette {
+    pub background: Color,
+    /// The numbers
+    pub accent: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            background: DARK_GRAY,
+            accent: WHITE,
+        }
+    }
+}
+
+impl Palette {
+    /// The defaults for any colours not set
+    pub fn new(background: Option<Rgb>, accent: Option<Rgb>) -> Self {
+        let color =
+            |rgb: Option<Rgb>, default| rgb.map_or(default, |Rgb(r, g, b)| Color::rgb(r, g, b));
+        let default = Palette::default();
+        Palette {
+            background: color(background, default.background),
+            accent: color(accent, default.accent),
+        }
+    }
+
+    pub fn of(event: Option<&Event>) -> Self {
+        Palette::new(
+            event.and_then(|event| event.background),
+            event.and_then(|event| event.accent),
+        )
+    }
+
+    /// For `--high-contrast`, a black background with the accent kept if it's at least
+    /// [MIN_CONTRAST] on it, or white
+    pub fn with_contrast(self, high: bool) -> Self {
+        if !high {
+            return self;
+        }
+        let accent = if contrast(self.accent, BLACK) >= MIN_CONTRAST {
+            self.accent
+        } else {
+            WHITE
+        };
+        Palette {
+            background: BLACK,
+            accent,
+        }
+    }
+
+    /// The numbers mixed towards gold by `flash`, from [crate::count::Count::flash], or
+    /// the background if they're already close to gold
+    pub fn flashed(self, flash: f64) -> Self {
+        if flash <= 0.0 {
+            return self;
+        }
+        let distance = [
+            (self.accent.r, FLASH.r),
+            (self.accent.g, FLASH.g),
+            (self.accent.b, FLASH.b),
+        ]
+        .iter()
+        .map(|(from, to)| from.abs_diff(*to) as usize)
+        .sum::<usize>();
+        let to = if distance < 96 {
+            self.background
+        } else {
+            FLASH
+        };
+        Palette {
+            accent: mix(self.accent, to, flash.min(1.0)),
+            ..self
+        }
+    }
+}
+
+/// Colour the numbers flash when the day changes
+const FLASH: Color = Color::rgb(255, 192, 0);
+
+/// How much of the background is mixed into `--background`'s image, so the text can be read
+const BACKDROP_DIM: f64 = 0.6;
+
+/// Image drawn behind the count, scaled to fit the canvas with the rest left as the
+/// background, and dimmed towards the background
+#[derive(Debug)]
+pub struct Backdrop {
+    image: Image,
+    fitted: RefCell<Option<Fitted>>,
+}
+
+/// [Backdrop]'s image as last fitted to the canvas
+#[derive(Debug)]
+struct Fitted {
+    /// Canvas width, height and background
+    key: (usize, usize, Color),
+    /// None outside the image
+    pixels: Vec<Option<Color>>,
+}
+
+impl Backdrop {
+    pub fn new(image: Image) -> Self {
+        Backdrop {
+            image,
+            fitted: RefCell::new(None),
+        }
+    }
+
+    /// Puts the image wherever the mode left `background` showing, call after drawing the
+    /// mode but before anything on top of it
+    pub fn draw(&self, graphics: &mut Graphics, background: Color) {
+        let key = (graphics.width(), graphics.height(), background);
+        let current = matches!(&*self.fitted.borrow(), Some(fitted) if fitted.key == key);
+        if !current {
+            *self.fitted.borrow_mut() = Some(Fitted {
+                key,
+                pixels: self.fit(key.0, key.1, background),
+            });
+        }
+        let fitted = self.fitted.borrow();
+        let Some(Fitted { pixels, .. }) = &*fitted else {
+            return;
+        };
+        replace_background(graphics, background, |x, y| pixels[y * key.0 + x]);
+    }
+
+    /// Letterboxes the image in `width` by `height`, each pixel is the average of the ones
+    /// it covers so photos don't look noisy when shrunk
+    fn fit(&self, width: usize, height: usize, background: Color) -> Vec<Option<Color>> {
+        let (src_width, src_height) = (self.image.width(), self.image.height());
+        if src_width == 0 || src_height == 0 {
+            return vec![None; width * height];
+        }
+        let scale = (width as f64 / src_width as f64).min(height as f64 / src_height as f64);
+        let fit_width = ((src_width as f64 * scale).round() as usize).clamp(1, width);
+        let fit_height = ((src_height as f64 * scale).round() as usize).clamp(1, height);
+        let left = (width - fit_width) / 2;
+        let top = (height - fit_height) / 2;
+        let span = |i: usize, fit: usize, src: usize| {
+            let start = i * src / fit;
+            start..((i + 1) * src / fit).max(start + 1).min(src)
+        };
+        (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                if x < left || x >= left + fit_width || y < top || y >= top + fit_height {
+                    return None;
+                }
+                let mut sum = [0_u64; 3];
+                let mut count = 0;
+                for src_y in span(y - top, fit_height, src_height) {
+                    for src_x in span(x - left, fit_width, src_width) {
+                        let color = self.image.get_pixel(src_x, src_y);
+                        //transparent parts show the background
+                        let color = mix(background, color, color.a as f64 / 255.0);
+                        sum[0] += color.r as u64;
+                        sum[1] += color.g as u64;
+                        sum[2] += color.b as u64;
+                        count += 1;
+                    }
+                }
+                let average = Color::rgb(
+                    (sum[0] / count) as u8,
+                    (sum[1] / count) as u8,
+                    (sum[2] / count) as u8,
+                );
+                Some(mix(average, background, BACKDROP_DIM))
+            })
+            .collect()
+    }
+}
+
+/// Seconds for `--background-style gradient` to go all the way round the colours
+const GRADIENT_PERIOD: f64 = 120.0;
+/// Colours across the gradient, each pixel uses the nearest
+const GRADIENT_STEPS: usize = 64;
+/// Canvas pixels per star in `--background-style starfield`
+const STAR_SPACING: usize = 120;
+/// Pixels per second the nearest stars drift left at 1x, further ones are slower
+const STAR_SPEED: f64 = 1.5;
+
+/// What's drawn behind the count in place of the flat background
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackgroundStyle {
+    #[default]
+    Flat,
+    /// A diagonal gradient that slowly goes round the colours
+    Gradient,
+    /// Stars slowly drifting left, the nearer ones faster and brighter
+    Starfield,
+}
+
+impl BackgroundStyle {
+    pub const NAMES: [&'static str; 3] = ["flat", "gradient", "starfield"];
+
+    pub fn from_name(name: &str) -> Option<BackgroundStyle> {
+        match name {
+            "flat" => Some(BackgroundStyle::Flat),
+            "gradient" => Some(BackgroundStyle::Gradient),
+            "starfield" => Some(BackgroundStyle::Starfield),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        BackgroundStyle::NAMES[self as usize]
+    }
+
+    /// Draws wherever the mode left `background` showing, `seconds` is any steadily
+    /// increasing time, i.e. since the epoch
+    pub fn draw(self, graphics: &mut Graphics, background: Color, seconds: f64) {
+        let (width, height) = (graphics.width(), graphics.height());
+        match self {
+            BackgroundStyle::Flat => {}
+            BackgroundStyle::Gradient => {
+                let phase = (seconds / GRADIENT_PERIOD).fract();
+                let ramp: Vec<Color> = (0..GRADIENT_STEPS)
+                    .map(|step| {
+                        let along = step as f64 / (GRADIENT_STEPS - 1) as f64;
+                        mix(background, hue(phase + along / 3.0), 0.1 + along * 0.25)
+                    })
+                    .collect();
+                replace_background(graphics, background, |x, y| {
+                    let along = (x as f64 / width as f64 + y as f64 / height as f64) / 2.0;
+                    Some(ramp[(along * (GRADIENT_STEPS - 1) as f64).round() as usize])
+                });
+            }
+            BackgroundStyle::Starfield => {
+                let frame = graphics.copy_to_image();
+                //as fast across a bigger canvas as the 90 pixel high one at 1x
+                let speed = STAR_SPEED * height as f64 / 90.0;
+                for star in 0..(width * height / STAR_SPACING) as u64 {
+                    let random = |salt: u64| (scramble(star, salt) % 10_000) as f64 / 10_000.0;
+                    let depth = 1.0 + (random(0) * 3.0).floor();
+                    let x = (random(1) * width as f64 - seconds * speed * depth / 3.0)
+                        .rem_euclid(width as f64) as usize;
+                    let y = (random(2) * height as f64) as usize;
+                    let twinkle = (seconds + random(3) * 10.0).sin() * 0.1;
+                    if frame.get_pixel(x, y) == background {
+                        let brightness = 0.15 + depth * 0.15 + twinkle;
+                        graphics.set_pixel(
+                            x as isize,
+                            y as isize,
+                            mix(background, WHITE, brightness),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Fully saturated colour `turns` of the way round the colour wheel
+fn hue(turns: f64) -> Color {
+    let sector = turns.rem_euclid(1.0) * 6.0;
+    let rising = (sector.fract() * 255.0) as u8;
+    let falling = 255 - rising;
+    match sector as u8 {
+        0 => Color::rgb(255, rising, 0),
+        1 => Color::rgb(falling, 255, 0),
+        2 => Color::rgb(0, 255, rising),
+        3 => Color::rgb(0, falling, 255),
+        4 => Color::rgb(rising, 0, 255),
+        _ => Color::rgb(255, 0, falling),
+    }
+}
+
+/// Canvas pixels a second `--screensaver` moves the count across and down, different so
+/// it doesn't go round the same path
+const DRIFT_SPEED: (f64, f64) = (3.0, 2.0);
+
+/// Bounces the count around a bigger black canvas, like the DVD logo, so a screen left
+/// showing it doesn't burn in
+#[derive(Debug)]
+pub struct Screensaver {
+    /// The count is drawn here at `size` before being copied to the canvas
+    buffer: RefCell<Vec<u8>>,
+    size: (usize, usize),
+}
+
+impl Screensaver {
+    pub fn new(size: (usize, usize)) -> Self {
+        Screensaver {
+            buffer: RefCell::new(vec![0; size.0 * size.1 * 4]),
+            size,
+        }
+    }
+
+    /// Draws the count with `draw`, then puts it where it's got to after `seconds` with
+    /// black instead of `background`
+    pub fn draw(
+        &self,
+        graphics: &mut Graphics,
+        background: Color,
+        seconds: f64,
+        draw: impl FnOnce(&mut Graphics),
+    ) {
+        let (width, height) = self.size;
+        let mut buffer = self.buffer.borrow_mut();
+        let Ok(mut count) = Graphics::new(&mut buffer, width, height) else {
+            return;
+        };
+        draw(&mut count);
+        let image = count.copy_to_image();
+        let x = bounce(
+            seconds * DRIFT_SPEED.0,
+            graphics.width().saturating_sub(width),
+        );
+        let y = bounce(
+            seconds * DRIFT_SPEED.1,
+            graphics.height().saturating_sub(height),
+        );
+        graphics.clear(BLACK);
+        for py in 0..height {
+            for px in 0..width {
+                let color = image.get_pixel(px, py);
+                if color != background {
+                    graphics.set_pixel(x + px as isize, y + py as isize, color);
+                }
+            }
+        }
+    }
+}
+
+/// Goes from 0 to `room` and back again over `distance`
+fn bounce(distance: f64, room: usize) -> isize {
+    if room == 0 {
+        return 0;
+    }
+    let along = distance.rem_euclid(room as f64 * 2.0) as isize;
+    let room = room as isize;
+    if along > room {
+        room * 2 - along
+    } else {
+        along
+    }
+}
+
+/// Sets each pixel that's `background` to `pixel`'s colour for it, if any
+fn replace_background(
+    graphics: &mut Graphics,
+    background: Color,
+    pixel: impl Fn(usize, usize) -> Option<Color>,
+) {
+    let frame = graphics.copy_to_image();
+    for y in 0..frame.height() {
+        for x in 0..frame.width() {
+            if frame.get_pixel(x, y) == background {
+                if let Some(color) = pixel(x, y) {
+                    graphics.set_pixel(x as isize, y as isize, color);
+                }
+            }
+        }
+    }
+}
+
+/// Text formatted from `K`, kept until `K` changes so drawing the same thing every frame
+/// doesn't allocate
+#[derive(Debug)]
+pub struct Cached<K> {
+    text: RefCell<Option<(K, String)>>,
+}
+
+impl<K> Default for Cached<K> {
+    fn default() -> Self {
+        Cached {
+            text: RefCell::new(None),
+        }
+    }
+}
+
+impl<K: PartialEq> Cached<K> {
+    /// The text for `key`, `format` is only called if `key` is different from last time
+    pub fn get(&self, key: K, format: impl FnOnce(&K) -> String) -> Ref<'_, str> {
+        let current = matches!(&*self.text.borrow(), Some((cached, _)) if *cached == key);
+        if !current {
+            let text = format(&key);
+            *self.text.borrow_mut() = Some((key, text));
+        }
+        self.text()
+    }
+}
+
+impl<K> Cached<K> {
+    /// As [Cached::get] for keys that allocate, `is_key` checks the cached key and `make`
+    /// is only called if it returns false
+    pub fn get_by(
+        &self,
+        is_key: impl FnOnce(&K) -> bool,
+        make: impl FnOnce() -> (K, String),
+    ) -> Ref<'_, str> {
+        let current = matches!(&*self.text.borrow(), Some((cached, _)) if is_key(cached));
+        if !current {
+            *self.text.borrow_mut() = Some(make());
+        }
+        self.text()
+    }
+
+    fn text(&self) -> Ref<'_, str> {
+        Ref::map(self.text.borrow(), |text| {
+            text.as_ref().map_or("", |(_, text)| text.as_str())
+        })
+    }
+}
+
+/// Date and time in the top right corner for `--clock`, under the title so it's clear of
+/// the numbers in every mode
+#[derive(Debug)]
+pub struct Clock {
+    format: String,
+    /// Minutes since the epoch
+    text: Cached<i64>,
+}
+
+impl Clock {
+    /// `date_format` is the same as the title's, `hours` is 12 or 24
+    pub fn new(date_format: &str, hours: u8) -> Self {
+        Clock {
+            format: format!("{date_format} {}", time_format(hours)),
+            text: Cached::default(),
+        }
+    }
+
+    pub fn render(&self, graphics: &mut Graphics, now: DateTime<Local>) {
+        let text = self.text.get(now.timestamp().div_euclid(60), |_| {
+            now.format(&self.format).to_string()
+        });
+        graphics.draw_text(
+            &text,
+            Px(graphics.width() as isize - 4, 16),
+            (LIGHT_GRAY, Small, RightTop),
+        );
+    }
+}
+
+/// What the title above the count says
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub enum Heading {
+    /// Since the start, or until it if it's in the future
+    #[default]
+    Since,
+    /// Between the start and this end date, from `--from` and `--to`
+    Between(String),
+    /// An age from the birth date, from `--birthday`
+    Age,
+}
+
+impl Heading {
+    pub fn title(&self, strings: &Strings, start: &str) -> String {
+        match self {
+            Heading::Since => strings.since(start),
+            Heading::Between(end) => strings.between(start, end),
+            Heading::Age => strings.age(start),
+        }
+    }
+
+    /// Label for the days until the next anniversary in split mode
+    pub fn anniversary<'a>(&self, strings: &'a Strings) -> &'a str {
+        match self {
+            Heading::Age => strings.to_birthday,
+            _ => strings.to_anniversary,
+        }
+    }
+}
+
+fn month_note(strings: &Strings, length: MonthLength) -> String {
+    format!("{} {}", strings.month_length(length), strings.or)
+}
+
+/// The strings and text drawn by the modes, kept between frames by whatever is drawing
+/// them
+#[derive(Debug)]
+pub struct Texts {
+    strings: &'static Strings,
+    heading: Heading,
+    /// Filled `--template`, shown instead of the heading's title
+    header: Option<String>,
+    /// The event's own milestone reached today, its message is shown instead of the title
+    celebration: Option<CustomMilestone>,
+    /// Paused days noted under the title, 0 for none
+    excluded: usize,
+    excluding: Cached<usize>,
+    /// Only the numbers, from `--minimal`
+    minimal: bool,
+    /// Between each group of three digits, from `--number-format`, empty for none
+    separator: &'static str,
+    /// After the months in diff mode, i.e. `(28 d) or`
+    month_note: String,
+    since: Cached<String>,
+    until: Cached<String>,
+    year_complete: Cached<usize>,
+    /// Event and phase name
+    phase: Cached<(String, usize)>,
+    /// Diff mode's own rows then its [CustomUnit](crate::units::CustomUnit)s
+    rows: [Cached<(usize, Option<u64>)>; 4 + MAX_UNITS],
+    /// Labels and values of the extra rows in diff mode
+    units: Vec<(String, Formula)>,
+    segments: Cached<usize>,
+    /// Sign and seconds left
+    clock: Cached<(char, i64)>,
+    week: Cached<(usize, usize)>,
+    /// Page and pages, i.e. `2/3`
+    page: Cached<(usize, usize)>,
+    /// Percent of the way to the goal
+    goal: Cached<usize>,
+    /// ISO week and year of the start
+    iso_since: Cached<(u32, i32)>,
+    /// Percents of the year, month and day in calendar mode
+    percents: [Cached<usize>; 3],
+    /// Single numbers in the term and phases modes
+    numbers: [Cached<i64>; 2],
+}
+
+impl Texts {
+    pub fn new(strings: &'static Strings) -> Self {
+        Texts {
+            strings,
+            heading: Heading::Since,
+            header: None,
+            celebration: None,
+            excluded: 0,
+            excluding: Cached::default(),
+            minimal: false,
+            separator: "",
+            month_note: month_note(strings, MonthLength::default()),
+            since: Cached::default(),
+            until: Cached::default(),
+            year_complete: Cached::default(),
+            phase: Cached::default(),
+            rows: Default::default(),
+            units: vec![],
+            segments: Cached::default(),
+            clock: Cached::default(),
+            week: Cached::default(),
+            page: Cached::default(),
+            goal: Cached::default(),
+            iso_since: Cached::default(),
+            percents: Default::default(),
+            numbers: Default::default(),
+        }
+    }
+
+    pub fn with_heading(self, heading: Heading) -> Self {
+        Texts { heading, ..self }
+    }
+
+    pub fn with_minimal(self, minimal: bool) -> Self {
+        Texts { minimal, ..self }
+    }
+
+    pub fn with_separator(self, separator: &'static str) -> Self {
+        Texts { separator, ..self }
+    }
+
+    /// Only the first [MAX_UNITS] are shown
+    pub fn with_units(self, units: Vec<(String, Formula)>) -> Self {
+        Texts { units, ..self }
+    }
+
+    pub fn units(&self) -> &[(String, Formula)] {
+        &self.units
+    }
+
+    pub fn with_month_length(self, length: MonthLength) -> Self {
+        Texts {
+            month_note: month_note(self.strings, length),
+            ..self
+        }
+    }
+
+    /// Replaces the title, i.e. after the count changed
+    pub fn set_header(&mut self, header: Option<String>) {
+        if self.header != header {
+            self.header = header;
+            self.since = Cached::default();
+        }
+    }
+
+    /// Celebrates the event's own milestone in the title, i.e. on its day
+    pub fn set_celebration(&mut self, celebration: Option<CustomMilestone>) {
+        if self.celebration != celebration {
+            self.celebration = celebration;
+            self.since = Cached::default();
+        }
+    }
+
+    /// From [Event::noted_pauses](crate::event::Event::noted_pauses)
+    pub fn set_excluded(&mut self, days: usize) {
+        self.excluded = days;
+    }
+
+    /// i.e. `(excluding 14 days)`, none if there's nothing to note
+    fn excluding(&self) -> Option<Ref<'_, str>> {
+        (self.excluded > 0).then(|| {
+            self.excluding
+                .get(self.excluded, |days| self.strings.excluding(*days))
+        })
+    }
+
+    /// Colour of the title, the milestone's if celebrating
+    fn since_color(&self, palette: Palette) -> Color {
+        match &self.celebration {
+            Some(CustomMilestone {
+                color: Some(Rgb(r, g, b)),
+                ..
+            }) => Color::rgb(*r, *g, *b),
+            Some(_) => palette.accent,
+            None => LIGHT_GRAY,
+        }
+    }
+
+    fn since(&self, start: &str) -> Ref<'_, str> {
+        self.since.get_by(
+            |cached| cached == start,
+            || {
+                let title = match (&self.celebration, &self.header) {
+                    (Some(celebration), _) => celebration.message.clone(),
+                    (None, Some(header)) => header.clone(),
+                    (None, None) => self.heading.title(self.strings, start),
+                };
+                (start.to_string(), title)
+            },
+        )
+    }
+
+    fn until(&self, start: &str) -> Ref<'_, str> {
+        self.until.get_by(
+            |cached| cached == start,
+            || (start.to_string(), self.strings.until(start)),
+        )
+    }
+
+    /// `page` of `pages`, counting from 1
+    pub fn page(&self, page: usize, pages: usize) -> Ref<'_, str> {
+        self.page.get((page, pages), |(page, pages)| {
+            format!("{}/{pages}", page + 1)
+        })
+    }
+
+    fn number(&self, slot: usize, value: i64) -> Ref<'_, str> {
+        self.numbers[slot].get(value, |value| group_digits(value, self.separator))
+    }
+}
+
+/// Cheap pseudo random number, stable for the same inputs
+fn scramble(a: u64, b: u64) -> u64 {
+    let mut x = a.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ b.wrapping_add(0x632B_E59B_D9B4_E019);
+    x ^= x >> 29;
+    x = x.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x ^ (x >> 32)
+}
+
+fn mix(from: Color, to: Color, amount: f64) -> Color {
+    let channel = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * amount) as u8;
+    Color::rgb(
+        channel(from.r, to.r),
+        channel(from.g, to.g),
+        channel(from.b, to.b),
+    )
+}
+
+/// WCAG contrast ratio, from 1 for the same colour to 21 for black and white
+pub fn contrast(first: Color, second: Color) -> f64 {
+    let (first, second) = (luminance(first), luminance(second));
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// Relative luminance as WCAG defines it, 0 for black to 1 for white
+fn luminance(color: Color) -> f64 {
+    let channel = |value: u8| {
+        let value = value as f64 / 255.0;
+        if value <= 0.04045 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+}
+
+/// Height of each row of numbers, including the gap below
+fn row_height(font: &NumberFont) -> usize {
+    let (_, height) = font.measure("0");
+    height.max(Large.get_size().1) + ROW_GAP
+}
+
+/// Size of the window needed to fit the numbers in `font`, 270x90 for the built in font,
+/// `minimal` leaves out the header and labels
+pub fn canvas_size(font: &NumberFont, minimal: bool) -> (usize, usize) {
+    let (countdown, _) = font.measure("T-00000 days 00:00:00");
+    if minimal {
+        return (countdown + 8, 4 + 4 * row_height(font));
+    }
+    let height = 24 + 4 * row_height(font) + 2;
+    let (numbers, _) = font.measure("00000");
+    //room for the longest labels, i.e. `MONTHS or`
+    let rows = 4 + numbers + COL_GAP as usize + 100;
+    (rows.max(countdown + 8).max(270), height.max(90))
+}
+
+/// Extra height for diff mode to fit `units` more rows, none for `--minimal` as they're
+/// left out
+pub fn units_height(font: &NumberFont, minimal: bool, units: usize) -> usize {
+    if minimal {
+        0
+    } else {
+        units.min(MAX_UNITS) * row_height(font)
+    }
+}
+
+/// Top of a single line of numbers, in the middle of the window for `--minimal`
+fn minimal_middle(font: &NumberFont) -> isize {
+    let (_, height) = font.measure("0");
+    (4 + (4 * row_height(font)).saturating_sub(height) / 2) as isize
+}
+
+/// Rows of numbers with their labels, i.e. `12 | DAYS`, with the small text in `notes`
+/// after each label, i.e. `or`, rows past the end of `notes` have none
+fn render_rows(
+    graphics: &mut Graphics,
+    rows: &[(usize, &str)],
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    notes: &[&str],
+    texts: &Texts,
+) {
+    let text = |i: usize, value: usize| reveal.text(value, i, rows.len(), texts, i);
+    let widest = rows
+        .iter()
+        .enumerate()
+        .map(|(i, (value, _))| font.measure(&text(i, *value)).0)
+        .max()
+        .unwrap_or_default();
+    //the numbers are right aligned, so the labels move over if they don't fit
+    let col_num = if texts.minimal {
+        (graphics.width() + widest) as isize / 2
+    } else {
+        COL_NUM.max(4 + widest as isize)
+    };
+    let col_label = col_num + COL_GAP;
+    let height = row_height(font) as isize;
+    let (_, number_height) = font.measure("0");
+    let label_offset = (number_height.saturating_sub(Large.get_size().1) / 2) as isize;
+    let top = if texts.minimal { 4 } else { 24 };
+    for (i, (value, label)) in rows.iter().enumerate() {
+        let y = top + (i as isize * height);
+        let color = reveal.color(palette.accent, palette.background, i, rows.len());
+        let text = text(i, *value);
+        match reveal.rolling(*value) {
+            Some(rolling) => {
+                let digits = text.chars().filter(char::is_ascii_digit).count();
+                font.draw_odometer(
+                    graphics,
+                    rolling,
+                    digits,
+                    texts.separator,
+                    (col_num, y),
+                    color,
+                )
+            }
+            None => font.draw_right_aligned(graphics, &text, (col_num, y), color),
+        }
+        if texts.minimal {
+            continue;
+        }
+        let label_y = y + label_offset;
+        let color = reveal.color(LIGHT_GRAY, palette.background, i, rows.len());
+        graphics.draw_text(label, Px(col_label, label_y), (color, Large, LeftTop));
+        if let Some(note) = notes.get(i) {
+            let (label_width, _) = Large.measure(label, WrappingStrategy::None);
+            graphics.draw_text(
+                note,
+                Px(col_label + label_width as isize + 2, label_y + 5),
+                (color, Small, LeftTop),
+            );
+        }
+    }
+}
+
+/// Optional fourth row in split mode
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SplitRow {
+    /// Days until the next anniversary, or birthday for an age
+    Anniversary(usize),
+    /// 29 Februaries since the start, from `--leap-days`
+    LeapDays(usize),
+}
+
+/// Years, months and days, with `extra` under them if set
+#[allow(clippy::too_many_arguments)]
+pub fn render_split(
+    graphics: &mut Graphics,
+    split: Split,
+    extra: Option<SplitRow>,
+    start: &str,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    if !texts.minimal {
+        graphics.draw_text(
+            &texts.since(start),
+            Px(4, 4),
+            (texts.since_color(palette), Large),
+        );
+        draw_excluding(graphics, texts);
+    }
+    let extra = match extra {
+        Some(SplitRow::Anniversary(days)) => Some((days, texts.heading.anniversary(strings))),
+        Some(SplitRow::LeapDays(days)) => Some((days, strings.leap_days)),
+        None => None,
+    };
+    let rows = [
+        (split.years, strings.years),
+        (split.months, strings.months),
+        (split.days, strings.days),
+        extra.unwrap_or_default(),
+    ];
+    let shown = if extra.is_some() { 4 } else { 3 };
+    render_rows(graphics, &rows[..shown], reveal, font, palette, &[], texts);
+}
+
+/// Split mode in Roman numerals, `start` is already in them
+pub fn render_roman(
+    graphics: &mut Graphics,
+    split: Split,
+    start: &str,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    let numerals = [split.years, split.months, split.days].map(roman);
+    let rows = [
+        (numerals[0].as_str(), strings.years),
+        (numerals[1].as_str(), strings.months),
+        (numerals[2].as_str(), strings.days),
+    ];
+    render_text_rows(
+        graphics,
+        &texts.since(start),
+        &rows,
+        reveal,
+        font,
+        palette,
+        texts,
+    );
+}
+
+/// The total days in decimal, hexadecimal and binary, for day `0x400`
+pub fn render_bases(
+    graphics: &mut Graphics,
+    days: usize,
+    start: &str,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    let numbers = [
+        group_digits(days, texts.separator),
+        format!("0x{days:X}"),
+        format!("{days:b}"),
+    ];
+    let rows = [
+        (numbers[0].as_str(), strings.days),
+        (numbers[1].as_str(), strings.hex),
+        (numbers[2].as_str(), strings.binary),
+    ];
+    render_text_rows(
+        graphics,
+        &texts.since(start),
+        &rows,
+        reveal,
+        font,
+        palette,
+        texts,
+    );
+}
+
+/// Fewest pixels between anniversary ticks in timeline mode, long counts only tick every 5,
+/// 10 and so on years
+const MIN_TICK_GAP: f64 = 4.0;
+/// Fewest pixels between the anniversary numbers over the ticks, they're left out if closer
+const MIN_LABEL_GAP: f64 = 14.0;
+
+/// The line from the start to the next milestone, filled up to today, with a tick for each
+/// anniversary, `labels` are the start's date, the milestone's date and the milestone
+pub fn render_timeline(
+    graphics: &mut Graphics,
+    timeline: Timeline,
+    labels: &[String; 3],
+    reveal: Reveal,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    graphics.draw_text(strings.timeline, Px(4, 4), (LIGHT_GRAY, Large));
+    let (left, right) = (8, graphics.width() as isize - 8);
+    let x = |date| left + ((right - left) as f64 * timeline.position(date)) as isize;
+    let top = 40;
+    draw_bar(
+        graphics,
+        Rect::new((left, top), (right, top + 6)),
+        timeline.position(timeline.today) * reveal.progress,
+        palette,
+    );
+    let year_width =
+        (right - left) as f64 * 365.25 / (timeline.end - timeline.start).num_days().max(1) as f64;
+    let step = [1, 5, 10, 25, 50, 100]
+        .into_iter()
+        .find(|step| year_width * *step as f64 >= MIN_TICK_GAP)
+        .unwrap_or(100);
+    let numbered = year_width * step as f64 >= MIN_LABEL_GAP;
+    for (year, date) in timeline.anniversaries(step) {
+        let x = x(date);
+        graphics.draw_line((x, top - 3), (x, top + 9), LIGHT_GRAY);
+        //a notch through the bar, so they show on the filled part too
+        graphics.draw_line((x, top), (x, top + 5), palette.background);
+        if numbered {
+            graphics.draw_text(
+                &year.to_string(),
+                Px(x, top - 5),
+                (LIGHT_GRAY, Small, CenterBottom),
+            );
+        }
+    }
+    //the milestone ends the line
+    graphics.draw_line((right, top - 3), (right, top + 9), WHITE);
+    let color = reveal.color(palette.accent, palette.background, 0, 1);
+    let today = x(timeline.today);
+    graphics.draw_line((today, top - 3), (today, top + 12), color);
+    let (label_width, _) = Small.measure(strings.today, WrappingStrategy::None);
+    let half = label_width as isize / 2;
+    graphics.draw_text(
+        strings.today,
+        Px(today.clamp(left + half, right - half), top + 14),
+        (color, Small, CenterTop),
+    );
+    let top = top + 22;
+    graphics.draw_text(&labels[0], Px(left, top), (LIGHT_GRAY, Normal, LeftTop));
+    graphics.draw_text(&labels[1], Px(right, top), (WHITE, Normal, RightTop));
+    graphics.draw_text(
+        &labels[2],
+        Px(right, top + 12),
+        (palette.accent, Normal, RightTop),
+    );
+}
+
+/// The paused days under the title, for an event's `note_pauses`, in the gap above the rows
+fn draw_excluding(graphics: &mut Graphics, texts: &Texts) {
+    if let Some(excluding) = texts.excluding() {
+        graphics.draw_text(&excluding, Px(4, 16), (LIGHT_GRAY, Small));
+    }
+}
+
+/// Rows of any text, i.e. Roman numerals, with their labels, in the number font unless
+/// they're too wide for it, all the intros but typing out are only the colours
+fn render_text_rows(
+    graphics: &mut Graphics,
+    title: &str,
+    rows: &[(&str, &str)],
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    graphics.clear(palette.background);
+    //long dates like `XXVIII.VIII.MDCCCLXXXVIII` don't fit in the large font
+    let title_font = if Large.measure(title, WrappingStrategy::None).0 + 8 > graphics.width() {
+        Normal
+    } else {
+        Large
+    };
+    graphics.draw_text(title, Px(4, 4), (texts.since_color(palette), title_font));
+    draw_excluding(graphics, texts);
+    let label_width = rows
+        .iter()
+        .map(|(_, label)| Large.measure(label, WrappingStrategy::None).0)
+        .max()
+        .unwrap_or_default() as isize;
+    let widest = |measure: &dyn Fn(&str) -> usize| {
+        rows.iter()
+            .map(|(text, _)| measure(text))
+            .max()
+            .unwrap_or_default() as isize
+    };
+    let width = widest(&|text| font.measure(text).0);
+    let fits = 4 + width + COL_GAP + label_width <= graphics.width() as isize;
+    let width = if fits {
+        width
+    } else {
+        widest(&|text| Large.measure(text, WrappingStrategy::None).0)
+    };
+    let col_num = COL_NUM.max(4 + width);
+    let height = row_height(font) as isize;
+    let (_, number_height) = font.measure("0");
+    let label_offset = (number_height.saturating_sub(Large.get_size().1) / 2) as isize;
+    for (i, (text, label)) in rows.iter().enumerate() {
+        let y = 24 + i as isize * height;
+        let text = match reveal.intro {
+            Intro::Typewriter => {
+                let typed = text.len() as f64 * reveal.row_progress(i, rows.len());
+                &text[..typed.ceil() as usize]
+            }
+            _ => text,
+        };
+        let color = reveal.color(palette.accent, palette.background, i, rows.len());
+        if fits {
+            font.draw_right_aligned(graphics, text, (col_num, y), color);
+        } else {
+            graphics.draw_text(
+                text,
+                Px(col_num, y + label_offset),
+                (color, Large, RightTop),
+            );
+        }
+        let color = reveal.color(LIGHT_GRAY, palette.background, i, rows.len());
+        graphics.draw_text(
+            label,
+            Px(col_num + COL_GAP, y + label_offset),
+            (color, Large, LeftTop),
+        );
+    }
+}
+
+/// Days, weeks, months and years, `months` is in months of the length passed to
+/// [Texts::with_month_length]
+#[allow(clippy::too_many_arguments)]
+pub fn render_diff(
+    graphics: &mut Graphics,
+    current_days: usize,
+    months: usize,
+    start: &str,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    if !texts.minimal {
+        graphics.draw_text(
+            &texts.since(start),
+            Px(4, 4),
+            (texts.since_color(palette), Large),
+        );
+        draw_excluding(graphics, texts);
+    }
+    let weeks = current_days / 7;
+    let years = current_days / 365;
+    let counts = Counts {
+        days: current_days,
+        weeks,
+        months,
+        years,
+    };
+    let mut rows = vec![
+        (current_days, strings.days),
+        (weeks, strings.weeks),
+        (months, strings.months),
+        (years, strings.years),
+    ];
+    //labels are the only way to tell them apart
+    if !texts.minimal {
+        rows.extend(
+            texts
+                .units
+                .iter()
+                .take(MAX_UNITS)
+                .map(|(label, formula)| (formula.eval(counts), label.as_str())),
+        );
+    }
+    render_rows(
+        graphics,
+        &rows,
+        reveal,
+        font,
+        palette,
+        &[strings.or, strings.or, &texts.month_note],
+        texts,
+    );
+}
+
+/// `days` as whole weeks and the days left over, i.e. `62 WEEKS 3 DAYS`
+pub fn render_weeks_and_days(
+    graphics: &mut Graphics,
+    days: usize,
+    start: &str,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    if !texts.minimal {
+        graphics.draw_text(
+            &texts.since(start),
+            Px(4, 4),
+            (texts.since_color(palette), Large),
+        );
+        draw_excluding(graphics, texts);
+    }
+    let rows = [(days / 7, strings.weeks), (days % 7, strings.days)];
+    render_rows(graphics, &rows, reveal, font, palette, &[], texts);
+}
+
+/// Lunar cycles and full moons since the start
+pub fn render_moons(
+    graphics: &mut Graphics,
+    moons: Moons,
+    start: &str,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    if !texts.minimal {
+        graphics.draw_text(
+            &texts.since(start),
+            Px(4, 4),
+            (texts.since_color(palette), Large),
+        );
+        draw_excluding(graphics, texts);
+    }
+    let rows = [
+        (moons.cycles, strings.lunar_cycles),
+        (moons.full_moons, strings.full_moons),
+    ];
+    render_rows(graphics, &rows, reveal, font, palette, &[], texts);
+}
+
+/// Day and week of the year and the percent gone, over a bar the width of the window,
+/// `header` is [Strings::year_from]
+pub fn render_year(
+    graphics: &mut Graphics,
+    progress: YearProgress,
+    header: &str,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    let rows = [
+        (progress.day as usize, strings.day_of_year),
+        (progress.week as usize, strings.week_of_year),
+        (progress.percent(), strings.percent_of_year),
+    ];
+    render_rows(graphics, &rows, reveal, font, palette, &[], texts);
+    if texts.minimal {
+        return;
+    }
+    graphics.draw_text(header, Px(4, 4), (LIGHT_GRAY, Large));
+    let (width, height) = (graphics.width() as isize, graphics.height() as isize);
+    let through = progress.elapsed as f64 / progress.length.max(1) as f64;
+    draw_bar(
+        graphics,
+        Rect::new((4, height - 8), (width - 4, height - 4)),
+        through * reveal.progress,
+        palette,
+    );
+}
+
+/// Hours and minutes old, `header` is [Strings::age] and `lines` are [PreciseAge::lines]
+#[allow(clippy::too_many_arguments)]
+pub fn render_age(
+    graphics: &mut Graphics,
+    age: PreciseAge,
+    header: &str,
+    lines: &[String; 2],
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    let rows = [
+        (age.total_hours, strings.total_hours),
+        (age.total_minutes, strings.total_minutes),
+    ];
+    render_rows(graphics, &rows, reveal, font, palette, &[], texts);
+    if texts.minimal {
+        return;
+    }
+    graphics.draw_text(header, Px(4, 4), (LIGHT_GRAY, Large));
+    let top = 24 + 2 * row_height(font) as isize + 2;
+    graphics.draw_text(&lines[0], Px(4, top), (LIGHT_GRAY, Normal));
+    graphics.draw_text(&lines[1], Px(4, top + 12), (palette.accent, Normal));
+}
+
+/// Seconds down to weeks since the start in columns, counting up all together
+pub fn render_totals(
+    graphics: &mut Graphics,
+    totals: Totals,
+    start: &str,
+    reveal: Reveal,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    graphics.draw_text(
+        &texts.since(start),
+        Px(4, 4),
+        (texts.since_color(palette), Large),
+    );
+    draw_excluding(graphics, texts);
+    let rows = [
+        (totals.seconds, strings.total_seconds),
+        (totals.minutes, strings.total_minutes),
+        (totals.hours, strings.total_hours),
+        (totals.days, strings.days),
+        (totals.weeks, strings.weeks),
+    ];
+    let scale = match reveal.intro {
+        Intro::CountUp => reveal.progress,
+        _ => 1.0,
+    };
+    //always grouped, in the language's way unless `--number-format` picked one
+    let separator = match texts.separator {
+        "" => strings.thousands,
+        separator => separator,
+    };
+    let numbers = rows.map(|(value, _)| group_digits((value as f64 * scale) as u64, separator));
+    let col_num = 4 + numbers
+        .iter()
+        .map(|number| Large.measure(number, WrappingStrategy::None).0)
+        .max()
+        .unwrap_or_default() as isize;
+    let height = Large.get_size().1 as isize + 4;
+    for (i, (number, (_, label))) in numbers.iter().zip(rows).enumerate() {
+        let y = 22 + i as isize * height;
+        let color = reveal.color(palette.accent, palette.background, i, rows.len());
+        graphics.draw_text(number, Px(col_num, y), (color, Large, RightTop));
+        let color = reveal.color(LIGHT_GRAY, palette.background, i, rows.len());
+        graphics.draw_text(label, Px(col_num + COL_GAP, y), (color, Large, LeftTop));
+    }
+}
+
+/// The next round day counts and anniversary, with `dates` formatted in the same order and
+/// the days until each under them
+pub fn render_upcoming(
+    graphics: &mut Graphics,
+    upcoming: Upcoming,
+    dates: &[String; 3],
+    reveal: Reveal,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    graphics.draw_text(strings.coming_up, Px(4, 4), (LIGHT_GRAY, Large));
+    let rows = [
+        (upcoming.hundred, strings.days),
+        (upcoming.thousand, strings.days),
+        (upcoming.anniversary, strings.years),
+    ];
+    let right = graphics.width() as isize - 4;
+    let height = Large.get_size().1 as isize;
+    let spacing = height + Normal.get_size().1 as isize + 6;
+    for (i, ((ahead, label), date)) in rows.iter().zip(dates).enumerate() {
+        let y = 20 + i as isize * spacing;
+        let color = reveal.color(palette.accent, palette.background, i, rows.len());
+        graphics.draw_text(
+            &format!("{} {label}", ahead.count),
+            Px(4, y),
+            (color, Large, LeftTop),
+        );
+        let color = reveal.color(WHITE, palette.background, i, rows.len());
+        graphics.draw_text(date, Px(right, y), (color, Large, RightTop));
+        let color = reveal.color(LIGHT_GRAY, palette.background, i, rows.len());
+        graphics.draw_text(
+            &strings.in_days(ahead.days_until),
+            Px(right, y + height + 2),
+            (color, Normal, RightTop),
+        );
+    }
+}
+
+/// How many of each weekday there's been in two columns, the start's weekday brighter,
+/// `header` is [Strings::started_on]
+pub fn render_weekdays(
+    graphics: &mut Graphics,
+    weekdays: Weekdays,
+    header: &str,
+    reveal: Reveal,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    graphics.draw_text(header, Px(4, 4), (LIGHT_GRAY, Large));
+    let scale = match reveal.intro {
+        Intro::CountUp => reveal.progress,
+        _ => 1.0,
+    };
+    let numbers = weekdays
+        .counts
+        .map(|count| ((count as f64 * scale) as usize).to_string());
+    let col_num = 4 + numbers
+        .iter()
+        .map(|number| Large.measure(number, WrappingStrategy::None).0)
+        .max()
+        .unwrap_or_default() as isize;
+    let column = graphics.width() as isize / 2;
+    let height = Large.get_size().1 as isize + 4;
+    let start = weekdays.start.num_days_from_monday() as usize;
+    for (i, (number, label)) in numbers.iter().zip(strings.weekdays).enumerate() {
+        let x = (i / 4) as isize * column + col_num;
+        let y = 22 + (i % 4) as isize * height;
+        let color = reveal.color(palette.accent, palette.background, i, numbers.len());
+        graphics.draw_text(number, Px(x, y), (color, Large, RightTop));
+        let color = if i == start { WHITE } else { LIGHT_GRAY };
+        let color = reveal.color(color, palette.background, i, numbers.len());
+        graphics.draw_text(label, Px(x + COL_GAP, y + 1), (color, Normal, LeftTop));
+    }
+}
+
+/// How far through the year, month and day it is, as three bars the width of the window
+pub fn render_calendar(
+    graphics: &mut Graphics,
+    progress: CalendarProgress,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    let width = graphics.width() as isize;
+    let rows = [
+        (progress.year, strings.this_year),
+        (progress.month, strings.this_month),
+        (progress.day, strings.today),
+    ];
+    for (i, (through, label)) in rows.into_iter().enumerate() {
+        let top = 4 + i as isize * 29;
+        let percent = (through * 100.0) as usize;
+        let text = texts.percents[i].get(percent, |percent| format!("{percent}%"));
+        graphics.draw_text(label, Px(4, top), (LIGHT_GRAY, Large, LeftTop));
+        graphics.draw_text(&text, Px(width - 4, top), (WHITE, Large, RightTop));
+        draw_bar(
+            graphics,
+            Rect::new((4, top + 13), (width - 4, top + 19)),
+            through,
+            palette,
+        );
+    }
+}
+
+/// Bar for progress `through` from 0 to 1, filled from the left in the accent colour
+fn draw_bar(graphics: &mut Graphics, bar: Rect, through: f64, palette: Palette) {
+    graphics.draw_rect(bar.clone(), fill(mix(palette.background, BLACK, 0.5)));
+    let filled = (bar.width() as f64 * through.clamp(0.0, 1.0)) as isize;
+    if filled > 0 {
+        let (left, top) = (bar.left(), bar.top());
+        graphics.draw_rect(
+            Rect::new((left, top), (left + filled, bar.bottom())),
+            fill(palette.accent),
+        );
+    }
+}
+
+/// This ISO week's number and the full weeks since the start
+pub fn render_weeks(
+    graphics: &mut Graphics,
+    weeks: IsoWeeks,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    if !texts.minimal {
+        let header = texts
+            .iso_since
+            .get(weeks.start, |(week, year)| strings.iso_since(*week, *year));
+        graphics.draw_text(&header, Px(4, 4), (LIGHT_GRAY, Large));
+    }
+    let rows = [
+        (weeks.week as usize, strings.this_week),
+        (weeks.full, strings.full_weeks),
+    ];
+    render_rows(graphics, &rows, reveal, font, palette, &[], texts);
+}
+
+/// Day of the fiscal year, the days left in it and the whole fiscal years since the start,
+/// `header` is [Strings::fiscal]
+pub fn render_fiscal(
+    graphics: &mut Graphics,
+    fiscal: Fiscal,
+    header: &str,
+    reveal: Reveal,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    if !texts.minimal {
+        graphics.draw_text(header, Px(4, 4), (LIGHT_GRAY, Large));
+    }
+    let rows = [
+        (fiscal.day, strings.day_of_fiscal),
+        (fiscal.days_left, strings.days_left),
+        (fiscal.years, strings.fiscal_years),
+    ];
+    render_rows(graphics, &rows, reveal, font, palette, &[], texts);
+}
+
+/// One half of dual mode
+#[derive(Debug, Copy, Clone)]
+pub struct Side<'a> {
+    pub days: usize,
+    pub reveal: Reveal,
+    pub palette: Palette,
+    /// Event name or date
+    pub label: &'a str,
+}
+
+/// The days since the start and until `--pair`'s target side by side, each in its own
+/// colours
+pub fn render_dual(graphics: &mut Graphics, sides: [Side; 2], font: &NumberFont, texts: &Texts) {
+    let strings = texts.strings;
+    let half = graphics.width() as isize / 2;
+    let height = graphics.height() as isize;
+    let (_, number_height) = font.measure("0");
+    let captions = [strings.days_since, strings.days_until];
+    for (i, side) in sides.iter().enumerate() {
+        let x = i as isize * half;
+        graphics.draw_rect(
+            Rect::new((x, 0), (x + half, height)),
+            fill(side.palette.background),
+        );
+        let text = side.reveal.text(side.days, 0, 1, texts, i);
+        let (width, _) = font.measure(&text);
+        let xy = if texts.minimal {
+            (x + (half + width as isize) / 2, minimal_middle(font))
+        } else {
+            (x + 4 + width as isize, 24)
+        };
+        let color = side
+            .reveal
+            .color(side.palette.accent, side.palette.background, 0, 1);
+        match side.reveal.rolling(side.days) {
+            Some(rolling) => {
+                let digits = text.chars().filter(char::is_ascii_digit).count();
+                font.draw_odometer(graphics, rolling, digits, texts.separator, xy, color)
+            }
+            None => font.draw_right_aligned(graphics, &text, xy, color),
+        }
+        if texts.minimal {
+            continue;
+        }
+        graphics.draw_text(side.label, Px(x + 4, 4), (LIGHT_GRAY, Normal, LeftTop));
+        graphics.draw_text(
+            captions[i],
+            Px(x + 4, 30 + number_height as isize),
+            (LIGHT_GRAY, Large, LeftTop),
+        );
+    }
+    graphics.draw_line((half, 0), (half, height), LIGHT_GRAY);
+}
+
+/// Segments lit for each digit, bits are a to g, clockwise from the top with g in the middle
+const SEGMENT_DIGITS: [u8; 10] = [
+    0b0111111, 0b0000110, 0b1011011, 0b1001111, 0b1100110, 0b1101101, 0b1111101, 0b0000111,
+    0b1111111, 0b1101111,
+];
+
+/// Total days in seven segment digits, as big as fit in the top `area_height` pixels, the
+/// whole window unless there's a goal under them
+pub fn render_segments(
+    graphics: &mut Graphics,
+    days: usize,
+    area_height: usize,
+    palette: Palette,
+    texts: &Texts,
+) {
+    graphics.clear(palette.background);
+    let text = texts.segments.get(days, usize::to_string);
+    let digits = text.chars().filter_map(|chr| chr.to_digit(10));
+    let margin = 6;
+    let area_width = graphics.width() as isize - margin * 2;
+    let height = area_height as isize - margin * 2;
+    //digits are about half as wide as they're tall, with a quarter of that between them
+    let count = text.len() as isize;
+    let width = (height / 2).min(area_width * 4 / (count * 5 - 1));
+    let height = height.min(width * 2);
+    let gap = width / 4;
+    let thickness = (width / 5).max(2);
+    let total = count * width + (count - 1) * gap;
+    let left = margin + (area_width - total) / 2;
+    let top = (area_height as isize - height) / 2;
+    //unlit segments are faintly visible, like a real display
+    let unlit = mix(palette.background, palette.accent, 0.08);
+    for (i, digit) in digits.enumerate() {
+        let x = left + i as isize * (width + gap);
+        let lit = SEGMENT_DIGITS[digit as usize];
+        let (mid, right, bottom, t) = (top + height / 2, x + width, top + height, thickness);
+        let segments = [
+            ((x + t, top), (right - t, top + t)),
+            ((right - t, top + t), (right, mid)),
+            ((right - t, mid), (right, bottom - t)),
+            ((x + t, bottom - t), (right - t, bottom)),
+            ((x, mid), (x + t, bottom - t)),
+            ((x, top + t), (x + t, mid)),
+            ((x + t, mid - t / 2), (right - t, mid + t / 2)),
+        ];
+        for (segment, (start, end)) in segments.into_iter().enumerate() {
+            let color = if lit & (1 << segment) != 0 {
+                palette.accent
+            } else {
+                unlit
+            };
+            graphics.draw_rect(Rect::new(start, end), fill(color));
+        }
+    }
+}
+
+/// Panel over the current mode listing the keys and what they do on the left, and the
+/// current settings on the right
+pub fn render_help(
+    graphics: &mut Graphics,
+    shortcuts: &[(&str, &str)],
+    settings: &[(&str, String)],
+    strings: &Strings,
+) {
+    draw_panel(graphics, strings.shortcuts, strings.close_help);
+    let key_width = shortcuts
+        .iter()
+        .map(|(key, _)| Small.measure(key, WrappingStrategy::None).0)
+        .max()
+        .unwrap_or_default() as isize;
+    let mut left_width = 0;
+    for (i, (key, action)) in shortcuts.iter().enumerate() {
+        graphics.draw_text(key, Px(10, row(i)), (WHITE, Small, LeftTop));
+        graphics.draw_text(
+            action,
+            Px(14 + key_width, row(i)),
+            (LIGHT_GRAY, Small, LeftTop),
+        );
+        left_width = left_width.max(Small.measure(action, WrappingStrategy::None).0 as isize);
+    }
+    let name_x = (24 + key_width + left_width).max(graphics.width() as isize / 2);
+    draw_pairs(graphics, settings, name_x);
+}
+
+/// Panel over the current mode with the version, paths and so on, for bug reports
+pub fn render_about(graphics: &mut Graphics, details: &[(&str, String)], strings: &Strings) {
+    draw_panel(graphics, strings.about, strings.close_help);
+    draw_pairs(graphics, details, 10);
+}
+
+/// Panel asking whether to reset the streak, any key but Backspace cancels
+pub fn render_confirm_reset(graphics: &mut Graphics, strings: &Strings) {
+    draw_panel(graphics, strings.reset_streak, strings.close_help);
+    graphics.draw_text(
+        strings.confirm_reset,
+        Px(10, row(1)),
+        (WHITE, Small, LeftTop),
+    );
+}
+
+/// Panel with the date being picked, the selected part is underlined, and `hint` below
+pub fn render_date_picker(
+    graphics: &mut Graphics,
+    picker: &DatePicker,
+    title: &str,
+    hint: &str,
+    close: &str,
+) {
+    draw_panel(graphics, title, close);
+    let (char_width, char_height) = Large.measure("0", WrappingStrategy::None);
+    let (char_width, char_height) = (char_width as isize + 1, char_height as isize);
+    let mut x = 10;
+    for (i, (field, text)) in picker.parts().into_iter().enumerate() {
+        if i > 0 {
+            graphics.draw_text("-", Px(x, row(1)), (LIGHT_GRAY, Large, LeftTop));
+            x += char_width;
+        }
+        let width = text.len() as isize * char_width;
+        let color = if field == picker.field {
+            let underline = row(1) + char_height + 1;
+            graphics.draw_line((x, underline), (x + width - 2, underline), WHITE);
+            WHITE
+        } else {
+            LIGHT_GRAY
+        };
+        graphics.draw_text(&text, Px(x, row(1)), (color, Large, LeftTop));
+        x += width;
+    }
+    graphics.draw_text(hint, Px(10, row(4)), (LIGHT_GRAY, Small, LeftTop));
+}
+
+/// Panel listing the events for the event editor, with their dates on the right, scrolled
+/// so `selected` is shown and highlighted, and `hint` at the bottom
+pub fn render_event_list(
+    graphics: &mut Graphics,
+    rows: &[(String, String)],
+    selected: usize,
+    hint: &str,
+    strings: &Strings,
+) {
+    draw_panel(graphics, strings.edit_events, strings.escape_back);
+    let bottom = graphics.height() as isize - 16;
+    let right = graphics.width() as isize - 10;
+    //a row is left empty above the hint
+    let shown = ((bottom - row(0)) / (row(1) - row(0)) - 1).max(1) as usize;
+    let first = selected.saturating_sub(shown - 1);
+    for (i, (name, date)) in rows.iter().enumerate().skip(first).take(shown) {
+        let y = row(i - first);
+        let color = if i == selected {
+            graphics.draw_rect(Rect::new((8, y - 1), (right + 2, y + 5)), fill(DARK_GRAY));
+            WHITE
+        } else {
+            LIGHT_GRAY
+        };
+        graphics.draw_text(name, Px(10, y), (color, Small, LeftTop));
+        graphics.draw_text(date, Px(right, y), (color, Small, RightTop));
+    }
+    graphics.draw_text(hint, Px(10, bottom), (LIGHT_GRAY, Small, LeftTop));
+}
+
+/// Panel with the name being typed and a cursor after it, and `hint` below
+pub fn render_name_entry(
+    graphics: &mut Graphics,
+    title: &str,
+    name: &str,
+    hint: &str,
+    close: &str,
+) {
+    draw_panel(graphics, title, close);
+    graphics.draw_text(
+        &format!("{name}_"),
+        Px(10, row(1)),
+        (WHITE, Normal, LeftTop),
+    );
+    graphics.draw_text(hint, Px(10, row(4)), (LIGHT_GRAY, Small, LeftTop));
+}
+
+/// Top of each row of small text in a panel, a little tight so all the keys fit in the help
+fn row(i: usize) -> isize {
+    19 + i as isize * 6
+}
+
+/// Panel with `title` at the top left and how to close it at the top right
+fn draw_panel(graphics: &mut Graphics, title: &str, close: &str) {
+    let right = graphics.width() as isize - 6;
+    let bottom = graphics.height() as isize - 6;
+    graphics.draw_rect(
+        Rect::new((6, 6), (right, bottom)),
+        fill(mix(DARK_GRAY, BLACK, 0.5)),
+    );
+    graphics.draw_rect(Rect::new((6, 6), (right, bottom)), stroke(LIGHT_GRAY));
+    graphics.draw_text(title, Px(10, 10), (WHITE, Normal));
+    graphics.draw_text(close, Px(right - 4, 11), (LIGHT_GRAY, Small, RightTop));
+}
+
+/// Names and values in two columns starting at `x`, values too long for the panel are cut
+/// from the start, as the end of a path is the useful part
+fn draw_pairs(graphics: &mut Graphics, pairs: &[(&str, String)], x: isize) {
+    let (char_width, _) = Small.measure("0", WrappingStrategy::None);
+    let name_width = pairs
+        .iter()
+        .map(|(name, _)| Small.measure(name, WrappingStrategy::None).0)
+        .max()
+        .unwrap_or_default() as isize;
+    let value_x = x + name_width + 4;
+    let fits = (graphics.width() as isize - 10 - value_x).max(0) as usize / char_width;
+    for (i, (name, value)) in pairs.iter().enumerate() {
+        graphics.draw_text(name, Px(x, row(i)), (LIGHT_GRAY, Small, LeftTop));
+        let count = value.chars().count();
+        let value = if count > fits {
+            let skip = count + 2 - fits.max(2);
+            format!("..{}", value.chars().skip(skip).collect::<String>())
+        } else {
+            value.clone()
+        };
+        graphics.draw_text(&value, Px(value_x, row(i)), (WHITE, Small, LeftTop));
+    }
+}
+
+/// Days and milestones in the year that has just been completed
+pub fn render_recap(
+    graphics: &mut Graphics,
+    year: usize,
+    days: usize,
+    milestones: usize,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    let title = texts
+        .year_complete
+        .get(year, |year| strings.year_complete(*year));
+    graphics.draw_text(&title, Px(4, 4), (LIGHT_GRAY, Large));
+    let rows = [(days, strings.days), (milestones, strings.milestones)];
+    let shown = Reveal {
+        intro: Intro::CountUp,
+        progress: 1.0,
+    };
+    render_rows(graphics, &rows, shown, font, palette, &[], texts);
+}
+
+/// Progress towards `goal` days along the bottom of the window, under whatever mode is drawn,
+/// `label` is the days or date to show
+pub fn render_goal(
+    graphics: &mut Graphics,
+    days: usize,
+    goal: usize,
+    label: &str,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let (width, height) = (graphics.width() as isize, graphics.height() as isize);
+    let percent = (days * 100 / goal.max(1)).min(100);
+    let text = texts
+        .goal
+        .get(percent, |percent| texts.strings.goal(*percent, label));
+    let top = height - GOAL_HEIGHT as isize;
+    graphics.draw_text(&text, Px(4, top), (LIGHT_GRAY, Small, LeftTop));
+    draw_bar(
+        graphics,
+        Rect::new((4, top + 8), (width - 4, top + 11)),
+        days as f64 / goal.max(1) as f64,
+        palette,
+    );
+}
+
+pub fn render_tminus(
+    graphics: &mut Graphics,
+    remaining: Duration,
+    start: &str,
+    font: &NumberFont,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    if !texts.minimal {
+        //once it's passed, i.e. a stopwatch, it's counting up from it
+        let title = if remaining < Duration::zero() {
+            texts.since(start)
+        } else {
+            texts.until(start)
+        };
+        graphics.draw_text(&title, Px(4, 4), (LIGHT_GRAY, Large));
+    }
+    let (sign, color) = if remaining < Duration::zero() {
+        ('+', palette.accent)
+    } else if remaining.num_days() < TMINUS_RED_DAYS {
+        ('-', RED)
+    } else if remaining.num_days() < TMINUS_AMBER_DAYS {
+        ('-', ORANGE)
+    } else {
+        ('-', GREEN)
+    };
+    let clock = texts
+        .clock
+        .get((sign, remaining.num_seconds().abs()), |(sign, secs)| {
+            let days = secs / 86400;
+            let hours = (secs % 86400) / 3600;
+            let minutes = (secs % 3600) / 60;
+            let seconds = secs % 60;
+            format!(
+                "T{sign}{days} {} {hours:0>2}:{minutes:0>2}:{seconds:0>2}",
+                strings.days_lower
+            )
+        });
+    //right aligned so the clock stays still as the number of day digits changes
+    let right = graphics.width() as isize - 4;
+    let y = if texts.minimal {
+        minimal_middle(font)
+    } else {
+        40
+    };
+    font.draw_right_aligned(graphics, &clock, (right, y), color);
+}
+
+pub fn render_term(graphics: &mut Graphics, progress: TermProgress, dates: &str, texts: &Texts) {
+    let strings = texts.strings;
+    graphics.clear(DARK_GRAY);
+    graphics.draw_text(strings.this_term, Px(4, 4), (LIGHT_GRAY, Large));
+    match progress {
+        TermProgress::NotStarted {
+            days_until,
+            teaching_days,
+        } => {
+            graphics.draw_text(
+                &texts.number(0, days_until),
+                Px(COL_NUM, 24),
+                (WHITE, Large, RightTop),
+            );
+            graphics.draw_text(
+                strings.days_to_start,
+                Px(COL_PERIOD, 24),
+                (LIGHT_GRAY, Large, LeftTop),
+            );
+            graphics.draw_text(
+                &texts.number(1, teaching_days as i64),
+                Px(COL_NUM, 40),
+                (WHITE, Large, RightTop),
+            );
+            graphics.draw_text(
+                strings.days_left,
+                Px(COL_PERIOD, 40),
+                (LIGHT_GRAY, Large, LeftTop),
+            );
+        }
+        TermProgress::InTerm {
+            week,
+            weeks,
+            days_left,
+            on_break,
+        } => {
+            graphics.draw_text(
+                &texts
+                    .week
+                    .get((week, weeks), |(week, weeks)| format!("{week}/{weeks}")),
+                Px(COL_NUM, 24),
+                (WHITE, Large, RightTop),
+            );
+            graphics.draw_text(
+                strings.week,
+                Px(COL_PERIOD, 24),
+                (LIGHT_GRAY, Large, LeftTop),
+            );
+            graphics.draw_text(
+                &texts.number(1, days_left as i64),
+                Px(COL_NUM, 40),
+                (WHITE, Large, RightTop),
+            );
+            graphics.draw_text(
+                strings.days_left,
+                Px(COL_PERIOD, 40),
+                (LIGHT_GRAY, Large, LeftTop),
+            );
+            if on_break {
+                graphics.draw_text(
+                    strings.on_break,
+                    Px(COL_PERIOD, 56),
+                    (ORANGE, Large, LeftTop),
+                );
+            }
+        }
+        TermProgress::Over => {
+            graphics.draw_text(
+                strings.term_over,
+                Px(COL_PERIOD, 40),
+                (LIGHT_GRAY, Large, LeftTop),
+            );
+        }
+    }
+    graphics.draw_text(dates, Px(4, 80), (LIGHT_GRAY, Small, LeftTop));
+}
+
+pub fn render_phases(
+    graphics: &mut Graphics,
+    event: &Event,
+    progress: &PhaseProgress,
+    today: NaiveDate,
+    palette: Palette,
+    texts: &Texts,
+) {
+    let strings = texts.strings;
+    graphics.clear(palette.background);
+    match progress {
+        PhaseProgress::NotStarted { days_until } => {
+            graphics.draw_text(&event.name, Px(4, 4), (LIGHT_GRAY, Large));
+            graphics.draw_text(
+                &texts.number(0, *days_until),
+                Px(COL_NUM, 24),
+                (palette.accent, Large, RightTop),
+            );
+            graphics.draw_text(
+                strings.days_to_start,
+                Px(COL_PERIOD, 24),
+                (LIGHT_GRAY, Large, LeftTop),
+            );
+        }
+        PhaseProgress::InPhase {
+            idx,
+            days_in,
+            days_left,
+        } => {
+            let title = texts.phase.get_by(
+                |(name, cached)| cached == idx && *name == event.name,
+                || {
+                    let title = format!("{}: {}", event.name, event.phases[*idx].name);
+                    ((event.name.clone(), *idx), title)
+                },
+            );
+            graphics.draw_text(&title, Px(4, 4), (LIGHT_GRAY, Large));
+            graphics.draw_text(
+                &texts.number(0, *days_in),
+                Px(COL_NUM, 24),
+                (palette.accent, Large, RightTop),
+            );
+            graphics.draw_text(
+                strings.days_in,
+                Px(COL_PERIOD, 24),
+                (LIGHT_GRAY, Large, LeftTop),
+            );
+            graphics.draw_text(
+                &texts.number(1, *days_left),
+                Px(COL_NUM, 40),
+                (palette.accent, Large, RightTop),
+            );
+            graphics.draw_text(
+                strings.days_left,
+                Px(COL_PERIOD, 40),
+                (PHASE_COLORS[*idx % PHASE_COLORS.len()], Large, LeftTop),
+            );
+        }
+        PhaseProgress::Complete => {
+            graphics.draw_text(&event.name, Px(4, 4), (LIGHT_GRAY, Large));
+            graphics.draw_text(
+                strings.phases_done,
+                Px(COL_PERIOD, 32),
+                (LIGHT_GRAY, Large, LeftTop),
+            );
+        }
+    }
+    let area = Rect::new((4, 66), (266, 76));
+    render_phase_timeline(graphics, event, today, area, palette.background);
+}
+
+/// Draws each phase as a coloured block sized by its length, with a marker for today, and
+/// pauses as gaps
+fn render_phase_timeline(
+    graphics: &mut Graphics,
+    event: &Event,
+    today: NaiveDate,
+    area: Rect,
+    background: Color,
+) {
+    let ranges = event.phase_ranges();
+    let (Some((first, _)), Some((_, last))) = (ranges.first(), ranges.last()) else {
+        return;
+    };
+    let total = (*last - *first).num_days().max(1) as f64;
+    let width = area.width() as f64;
+    let x_for = |date: NaiveDate| {
+        area.left() + (((date - *first).num_days() as f64 / total).clamp(0.0, 1.0) * width) as isize
+    };
+    for (i, (start, end)) in ranges.iter().enumerate() {
+        graphics.draw_rect(
+            Rect::new((x_for(*start), area.top()), (x_for(*end), area.bottom())),
+            fill(PHASE_COLORS[i % PHASE_COLORS.len()]),
+        );
+    }
+    for pause in &event.pauses {
+        let (left, right) = (x_for(pause.start), x_for(pause.end));
+        if left < right {
+            graphics.draw_rect(
+                Rect::new((left, area.top()), (right, area.bottom())),
+                fill(background),
+            );
+        }
+    }
+    let x = x_for(today);
+    graphics.draw_line((x, area.top() - 2), (x, area.bottom() + 2), WHITE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contrast_ratios() {
+        assert_eq!(contrast(BLACK, WHITE).round(), 21.0);
+        assert_eq!(contrast(RED, RED), 1.0);
+    }
+
+    #[test]
+    fn high_contrast_accents_are_readable() {
+        for accent in [
+            WHITE,
+            LIGHT_GRAY,
+            DARK_GRAY,
+            BLUE,
+            YELLOW,
+            Color::rgb(255, 192, 0),
+        ] {
+            let palette = Palette {
+                background: DARK_GRAY,
+                accent,
+            }
+            .with_contrast(true);
+            let ratio = contrast(palette.accent, palette.background);
+            assert!(ratio >= MIN_CONTRAST, "{accent:?} was {ratio:.1}:1");
+        }
+        let palette = Palette::new(None, Some(Rgb(255, 255, 0))).with_contrast(true);
+        assert_eq!(palette.accent, YELLOW);
+    }
+}
@@ -0,0 +1,722 @@
+use crate::count::{days_to_anniversary, Fiscal, PreciseAge};
+use crate::i18n::{group_digits, Strings};
+use crate::milestones::{self, Milestones};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// The count without the UI, for `--headless`, `--json` and other tools
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub start: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    /// Total days since `start`, 0 if it's in the future
+    pub days: usize,
+    pub weeks: usize,
+    /// In months of [Report::month_length]
+    pub months: usize,
+    pub month_length: MonthLength,
+    pub years: usize,
+    /// Years, months and days as shown in split mode
+    pub split: Split,
+    pub moons: Moons,
+    /// Only set if `start` is in the future
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_until: Option<i64>,
+    pub next_milestone: NextMilestone,
+    /// From `--template`, replaces the title in the text output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    /// Only set with `--fiscal-year`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fiscal: Option<Fiscal>,
+    /// Only set with `--born-at`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<PreciseAge>,
+    /// Extra milestones, from `--patterns` and the event
+    #[serde(skip)]
+    pub milestones: Milestones,
+    /// Between each group of three digits in the text output, from `--number-format`
+    #[serde(skip)]
+    pub separator: &'static str,
+    /// The counts with [Report::separator], only set with `--number-format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formatted: Option<Formatted>,
+}
+
+/// The counts as they're shown, i.e. `12,345`
+#[derive(Debug, Clone, Serialize)]
+pub struct Formatted {
+    pub days: String,
+    pub weeks: String,
+    pub months: String,
+    pub years: String,
+}
+
+/// Whether the start day itself is counted, so the count is 1 rather than 0 on the day
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Counting {
+    #[default]
+    Exclusive,
+    Inclusive,
+}
+
+impl Counting {
+    /// Days added to the days between the dates, once the start date has been reached
+    pub fn offset(self) -> usize {
+        match self {
+            Counting::Exclusive => 0,
+            Counting::Inclusive => 1,
+        }
+    }
+
+    /// Days since the start not counting the start day, for anniversaries which are on the
+    /// same date however the days are counted
+    pub fn elapsed(self, days: usize) -> usize {
+        days.saturating_sub(self.offset())
+    }
+}
+
+/// How long a month is for the months in diff mode, the text output and `{months}`
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MonthLength {
+    #[default]
+    #[serde(rename = "28")]
+    Days28,
+    #[serde(rename = "30")]
+    Days30,
+    /// 30.44 days, the average month over four years
+    #[serde(rename = "30.44")]
+    Average,
+    /// Calendar months from the start date, so the months go up on the same day each month
+    #[serde(rename = "calendar")]
+    Calendar,
+}
+
+impl MonthLength {
+    pub const NAMES: [&'static str; 4] = ["28", "30", "30.44", "calendar"];
+
+    pub fn from_name(name: &str) -> Option<MonthLength> {
+        match name {
+            "28" => Some(MonthLength::Days28),
+            "30" => Some(MonthLength::Days30),
+            "30.44" => Some(MonthLength::Average),
+            "calendar" => Some(MonthLength::Calendar),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        MonthLength::NAMES[self as usize]
+    }
+
+    /// Whole months in the `days` after `start`
+    pub fn months(self, start: NaiveDate, days: usize) -> usize {
+        match self {
+            MonthLength::Days28 => days / 28,
+            MonthLength::Days30 => days / 30,
+            MonthLength::Average => (days as f64 / 30.436_875) as usize,
+            MonthLength::Calendar => {
+                let split = Split::between(start, start + Duration::days(days as i64));
+                split.years * 12 + split.months
+            }
+        }
+    }
+}
+
+/// Days as years of 365 days, months of 28 days and the days left over, or calendar
+/// years and months with [Split::between]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub struct Split {
+    pub years: usize,
+    pub months: usize,
+    pub days: usize,
+}
+
+impl Split {
+    pub fn new(days: usize) -> Self {
+        let years = days / 365;
+        let remaining = days - (years * 365);
+        let months = remaining / 28;
+        Split {
+            years,
+            months,
+            days: remaining - (months * 28),
+        }
+    }
+
+    /// Calendar years, months and days from `start` to `end`, as for an age, 0 if `end`
+    /// is before `start`
+    ///
+    /// Months too short for `start`'s day end on their last day, so someone born on
+    /// 29 February is a year older on 28 February in other years
+    pub fn between(start: NaiveDate, end: NaiveDate) -> Self {
+        let after = |months: u32| start.checked_add_months(Months::new(months));
+        let mut months = ((end.year() - start.year()) * 12 + end.month() as i32
+            - start.month() as i32)
+            .max(0) as u32;
+        while months > 0 && after(months).is_none_or(|date| date > end) {
+            months -= 1;
+        }
+        let days = after(months).map_or(0, |date| (end - date).num_days().max(0) as usize);
+        Split {
+            years: months as usize / 12,
+            months: months as usize % 12,
+            days,
+        }
+    }
+}
+
+/// 29 Februaries after `start`, up to and including `today`
+pub fn leap_days(start: NaiveDate, today: NaiveDate) -> usize {
+    (start.year()..=today.year())
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, 2, 29))
+        .filter(|day| *day > start && *day <= today)
+        .count()
+}
+
+/// Average days from one new moon to the next
+pub const SYNODIC_MONTH: f64 = 29.530_588_853;
+
+/// A full moon to count the others from, 2000-01-21 04:40 UTC, as days since 2000-01-01
+const FULL_MOON: f64 = 20.194;
+
+/// Lunar cycles since the start for moons mode, from the average synodic month rather than
+/// the moon's actual orbit, so a full moon can be a day out
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub struct Moons {
+    /// Whole synodic months from `start` to `today`
+    pub cycles: usize,
+    /// Full moons after the start day, up to the end of `today`
+    pub full_moons: usize,
+}
+
+impl Moons {
+    pub fn new(start: NaiveDate, today: NaiveDate) -> Self {
+        let days = (today - start).num_days().max(0);
+        //full moons before the end of `date` (in UTC), counted from FULL_MOON
+        let before_end = |date: NaiveDate| {
+            let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).expect("epoch is valid");
+            let end = (date - epoch).num_days() as f64 + 1.0;
+            ((end - FULL_MOON) / SYNODIC_MONTH).floor() as i64
+        };
+        Moons {
+            cycles: (days as f64 / SYNODIC_MONTH) as usize,
+            full_moons: (before_end(today) - before_end(start)).max(0) as usize,
+        }
+    }
+}
+
+/// A round day count or anniversary still to come
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Ahead {
+    /// Days, or years for an anniversary
+    pub count: usize,
+    pub date: NaiveDate,
+    pub days_until: usize,
+}
+
+/// The next round 100 and 1000 days and the next anniversary after today, for upcoming mode
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Upcoming {
+    pub hundred: Ahead,
+    pub thousand: Ahead,
+    pub anniversary: Ahead,
+}
+
+impl Upcoming {
+    /// `days` is the count on `today`, so the day counts are reached when the count gets
+    /// to them, anniversaries are of `start`
+    pub fn new(start: NaiveDate, today: NaiveDate, days: usize) -> Self {
+        let round = |every: usize| {
+            let count = (days / every + 1) * every;
+            Ahead {
+                count,
+                date: today + Duration::days((count - days) as i64),
+                days_until: count - days,
+            }
+        };
+        let tomorrow = today + Duration::days(1);
+        let days_until = days_to_anniversary(start, tomorrow) + 1;
+        let date = today + Duration::days(days_until as i64);
+        Upcoming {
+            hundred: round(100),
+            thousand: round(1000),
+            anniversary: Ahead {
+                count: (date.year() - start.year()).max(0) as usize,
+                date,
+                days_until,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NextMilestone {
+    pub name: String,
+    pub date: NaiveDate,
+    pub days_until: usize,
+}
+
+/// Mirrors [Report] without the strings, for the C ABI and modes from [crate::plugin]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct CountupBreakdown {
+    pub days: u64,
+    pub weeks: u64,
+    pub months: u64,
+    pub years: u64,
+    pub split_years: u64,
+    pub split_months: u64,
+    pub split_days: u64,
+    /// -1 unless the start is in the future
+    pub days_until: i64,
+    pub next_milestone_days: u64,
+    pub days_until_next_milestone: u64,
+}
+
+impl From<&Report> for CountupBreakdown {
+    fn from(report: &Report) -> Self {
+        CountupBreakdown {
+            days: report.days as u64,
+            weeks: report.weeks as u64,
+            months: report.months as u64,
+            years: report.years as u64,
+            split_years: report.split.years as u64,
+            split_months: report.split.months as u64,
+            split_days: report.split.days as u64,
+            days_until: report.days_until.unwrap_or(-1),
+            next_milestone_days: (report.next_milestone.date - report.start).num_days() as u64,
+            days_until_next_milestone: report.next_milestone.days_until as u64,
+        }
+    }
+}
+
+impl Report {
+    /// Counts from `start` to `today`, both as local dates for the event's timezone
+    pub fn new(start: NaiveDate, today: NaiveDate, event: Option<String>) -> Self {
+        Report::new_with(start, today, event, Counting::Exclusive)
+    }
+
+    /// As [Report::new], also counting the start day if `counting` is inclusive, milestones
+    /// are reached when the count gets to them
+    pub fn new_with(
+        start: NaiveDate,
+        today: NaiveDate,
+        event: Option<String>,
+        counting: Counting,
+    ) -> Self {
+        let diff = (today - start).num_days();
+        let days = if diff < 0 {
+            0
+        } else {
+            diff as usize + counting.offset()
+        };
+        let (milestone_days, name) = milestones::next(days);
+        let milestone_date = start + Duration::days(counting.elapsed(milestone_days) as i64);
+        Report {
+            start,
+            event,
+            days,
+            weeks: days / 7,
+            months: days / 28,
+            month_length: MonthLength::Days28,
+            years: days / 365,
+            split: Split::new(days),
+            moons: Moons::new(start, today),
+            days_until: (diff < 0).then_some(-diff),
+            next_milestone: NextMilestone {
+                name,
+                date: milestone_date,
+                days_until: (milestone_date - today).num_days() as usize,
+            },
+            header: None,
+            fiscal: None,
+            age: None,
+            milestones: Milestones::default(),
+            separator: "",
+            formatted: None,
+        }
+    }
+
+    /// Takes `paused` days, from [crate::event::Event::paused_days], off the count, the
+    /// next milestone is then that many days later
+    pub fn with_paused(self, paused: usize) -> Self {
+        if paused == 0 || self.days_until.is_some() {
+            return self;
+        }
+        let days = self.days.saturating_sub(paused);
+        let today =
+            self.next_milestone.date - Duration::days(self.next_milestone.days_until as i64);
+        let (milestone_days, name) = self.milestones.next(days);
+        let days_until = milestone_days - days;
+        Report {
+            days,
+            weeks: days / 7,
+            months: self.month_length.months(self.start, days),
+            years: days / 365,
+            split: Split::new(days),
+            next_milestone: NextMilestone {
+                name,
+                date: today + Duration::days(days_until as i64),
+                days_until,
+            },
+            ..self
+        }
+    }
+
+    /// Also counts `milestones`, i.e. from [crate::event::Event::milestones], before
+    /// [Report::with_paused]
+    pub fn with_milestones(self, milestones: Milestones) -> Self {
+        if milestones == Milestones::default() || self.days_until.is_some() {
+            return self;
+        }
+        let today =
+            self.next_milestone.date - Duration::days(self.next_milestone.days_until as i64);
+        let (milestone_days, name) = milestones.next(self.days);
+        let days_until = milestone_days - self.days;
+        Report {
+            next_milestone: NextMilestone {
+                name,
+                date: today + Duration::days(days_until as i64),
+                days_until,
+            },
+            milestones,
+            ..self
+        }
+    }
+
+    /// Counts the months in `length` months instead of 28 days
+    pub fn with_month_length(self, length: MonthLength) -> Self {
+        Report {
+            months: length.months(self.start, self.days),
+            month_length: length,
+            ..self
+        }
+    }
+
+    /// Groups the digits in the text output with `separator`, call after the others so
+    /// [Report::formatted] has the final counts
+    pub fn with_separator(self, separator: &'static str) -> Self {
+        let formatted = (!separator.is_empty()).then(|| Formatted {
+            days: group_digits(self.days, separator),
+            weeks: group_digits(self.weeks, separator),
+            months: group_digits(self.months, separator),
+            years: group_digits(self.years, separator),
+        });
+        Report {
+            separator,
+            formatted,
+            ..self
+        }
+    }
+
+    fn grouped(&self, value: impl ToString) -> String {
+        group_digits(value, self.separator)
+    }
+
+    /// `count` and the word for it, grouped
+    fn counted(&self, count: usize, (one, many): (&str, &str)) -> String {
+        format!(
+            "{} {}",
+            self.grouped(count),
+            if count == 1 { one } else { many }
+        )
+    }
+
+    /// Years, months and days as one line, or the days to go if `start` is in the future,
+    /// i.e. `3 YEARS, 11 MONTHS, 16 DAYS`
+    pub fn summary(&self, strings: &Strings) -> String {
+        match self.days_until {
+            Some(days_until) => format!("{} {}", self.grouped(days_until), strings.days_lower),
+            None => format!(
+                "{} {}, {} {}, {} {}",
+                self.grouped(self.split.years),
+                strings.years,
+                self.split.months,
+                strings.months,
+                self.split.days,
+                strings.days
+            ),
+        }
+    }
+
+    /// One line to paste into chats, i.e. `Since 25/11/2022 it's been 1 year, 2 months,
+    /// 3 days (430 days)`, with `title` first
+    pub fn sentence(&self, title: &str, strings: &Strings) -> String {
+        match self.days_until {
+            Some(days_until) => {
+                format!(
+                    "{title}: {}",
+                    self.counted(days_until as usize, strings.day_words)
+                )
+            }
+            None => format!(
+                "{title} {}, {}, {} ({})",
+                self.counted(self.split.years, strings.year_words),
+                self.counted(self.split.months, strings.month_words),
+                self.counted(self.split.days, strings.day_words),
+                self.counted(self.days, strings.day_words),
+            ),
+        }
+    }
+
+    /// Text version of split and diff modes, `start` is the formatted start date
+    pub fn lines(&self, start: &str, strings: &Strings) -> Vec<String> {
+        match self.days_until {
+            Some(_) => vec![strings.until(start), self.summary(strings)],
+            None => {
+                let mut lines = vec![strings.since(start), self.summary(strings)];
+                for (value, label) in [
+                    (self.days, strings.days),
+                    (self.weeks, strings.weeks),
+                    (self.months, strings.months),
+                    (self.years, strings.years),
+                ] {
+                    lines.push(format!("{} {label}", self.grouped(value)));
+                }
+                lines
+            }
+        }
+    }
+
+    /// Replaces `{days}`, `{weeks}`, `{months}`, `{years}`, `{until}`, `{start}`, `{event}`
+    /// and `{milestone}` in `template`, and `{fy}` and `{fy_day}` with `--fiscal-year`
+    pub fn fill(&self, template: &str, start: &str) -> String {
+        let fiscal = |value: fn(&Fiscal) -> String| self.fiscal.as_ref().map(value);
+        [
+            ("{days}", self.grouped(self.days)),
+            ("{weeks}", self.grouped(self.weeks)),
+            ("{months}", self.grouped(self.months)),
+            ("{years}", self.grouped(self.years)),
+            ("{until}", self.grouped(self.days_until.unwrap_or_default())),
+            ("{start}", start.to_string()),
+            ("{event}", self.event.clone().unwrap_or_default()),
+            ("{milestone}", self.next_milestone.name.clone()),
+            (
+                "{fy}",
+                fiscal(|fiscal| format!("{:02}", fiscal.year.rem_euclid(100))).unwrap_or_default(),
+            ),
+            (
+                "{fy_day}",
+                fiscal(|fiscal| fiscal.day.to_string()).unwrap_or_default(),
+            ),
+        ]
+        .iter()
+        .fold(template.to_string(), |text, (key, value)| {
+            text.replace(key, value)
+        })
+    }
+
+    /// Fills a `--template` for the title, as [Report::fill] but `{years}`, `{months}` and
+    /// `{days}` are as shown in split mode, with `{total}` for all the days and `{label}` for
+    /// the event, or `start` if there isn't one
+    pub fn fill_header(&self, template: &str, start: &str) -> String {
+        let label = self.event.as_deref().unwrap_or(start);
+        let template = [
+            ("{years}", self.grouped(self.split.years)),
+            ("{months}", self.split.months.to_string()),
+            ("{days}", self.split.days.to_string()),
+            ("{total}", self.grouped(self.days)),
+            ("{label}", label.to_string()),
+        ]
+        .iter()
+        .fold(template.to_string(), |text, (key, value)| {
+            text.replace(key, value)
+        });
+        self.fill(&template, start)
+    }
+
+    /// CSS class for waybar, `future`, `milestone` (if today is one) or `counting`
+    pub fn class(&self) -> &'static str {
+        if self.days_until.is_some() {
+            "future"
+        } else if self.milestones.milestone(self.days).is_some() {
+            "milestone"
+        } else {
+            "counting"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::strings;
+    use crate::i18n::NumberFormat;
+    use crate::milestones::Pattern;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("test dates are valid")
+    }
+
+    #[test]
+    fn counting_the_start_day() {
+        let start = date(2024, 1, 1);
+        assert_eq!(
+            Report::new_with(start, start, None, Counting::Inclusive).days,
+            1
+        );
+        assert_eq!(
+            Report::new_with(start, start, None, Counting::Exclusive).days,
+            0
+        );
+        let report = Report::new_with(start, date(2024, 3, 1), None, Counting::Inclusive);
+        assert_eq!(report.next_milestone.date, date(2024, 4, 9));
+    }
+
+    #[test]
+    fn counting_a_future_date() {
+        let report = Report::new_with(
+            date(2024, 1, 11),
+            date(2024, 1, 1),
+            None,
+            Counting::Inclusive,
+        );
+        assert_eq!(report.days, 0);
+        assert_eq!(report.days_until, Some(10));
+    }
+
+    #[test]
+    fn inclusive_anniversaries_are_on_the_date() {
+        let report = Report::new_with(
+            date(2025, 1, 1),
+            date(2026, 1, 1),
+            None,
+            Counting::Inclusive,
+        );
+        assert_eq!(report.days, 366);
+        assert_eq!(Counting::Inclusive.elapsed(report.days), 365);
+    }
+
+    #[test]
+    fn full_moons_in_a_year() {
+        let moons = Moons::new(date(2024, 1, 1), date(2024, 12, 31));
+        assert_eq!(moons.cycles, 12);
+        assert_eq!(moons.full_moons, 12);
+    }
+
+    #[test]
+    fn full_moons_on_the_day() {
+        //there was a full moon on 2024-01-25 at 17:54 UTC
+        assert_eq!(
+            Moons::new(date(2024, 1, 24), date(2024, 1, 25)).full_moons,
+            1
+        );
+        assert_eq!(
+            Moons::new(date(2024, 1, 25), date(2024, 2, 23)).full_moons,
+            0
+        );
+    }
+
+    #[test]
+    fn months_of_each_length() {
+        let report = Report::new(date(2023, 1, 1), date(2023, 12, 31), None);
+        assert_eq!(report.months, 13);
+        for (length, months) in [
+            (MonthLength::Days30, 12),
+            (MonthLength::Average, 11),
+            (MonthLength::Calendar, 11),
+        ] {
+            assert_eq!(
+                report.clone().with_month_length(length).months,
+                months,
+                "{length:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn calendar_month_to_a_shorter_month() {
+        //29 February has no 31st, so the first calendar month ends on its last day
+        let report = Report::new(date(2024, 1, 31), date(2024, 2, 29), None);
+        assert_eq!(report.with_month_length(MonthLength::Calendar).months, 1);
+    }
+
+    #[test]
+    fn paused_days_move_the_milestones() {
+        let report = Report::new(date(2024, 1, 1), date(2024, 4, 30), None).with_paused(16);
+        assert_eq!(report.days, 104);
+        assert_eq!(report.next_milestone.days_until, 96);
+        assert_eq!(report.next_milestone.date, date(2024, 8, 4));
+    }
+
+    #[test]
+    fn next_custom_milestone() {
+        let milestones = Milestones {
+            patterns: vec![Pattern::Palindrome],
+            custom: vec![(101, String::from("Halfway mark"))],
+        };
+        let report =
+            Report::new(date(2024, 1, 1), date(2024, 4, 10), None).with_milestones(milestones);
+        assert_eq!(report.next_milestone.name, "Halfway mark");
+    }
+
+    #[test]
+    fn copied_summary() {
+        let strings = strings("en");
+        let report = Report::new(date(2024, 1, 1), date(2025, 1, 2), None);
+        assert_eq!(
+            report.sentence(&strings.since("01/01/2024"), strings),
+            "Since 01/01/2024 it's been 1 year, 0 months, 2 days (367 days)"
+        );
+        let report = Report::new(date(2025, 1, 2), date(2025, 1, 1), None);
+        assert_eq!(
+            report.sentence(&strings.until("02/01/2025"), strings),
+            "Counting down to 02/01/2025: 1 day"
+        );
+    }
+
+    #[test]
+    fn upcoming_from_a_leap_day() {
+        let upcoming = Upcoming::new(date(2020, 2, 29), date(2026, 10, 14), 2419);
+        assert_eq!(upcoming.hundred.count, 2500);
+        assert_eq!(upcoming.hundred.date, date(2027, 1, 3));
+        assert_eq!(upcoming.thousand.count, 3000);
+        assert_eq!(upcoming.thousand.days_until, 581);
+        assert_eq!(upcoming.anniversary.count, 7);
+        assert_eq!(upcoming.anniversary.date, date(2027, 2, 28));
+    }
+
+    #[test]
+    fn upcoming_on_the_day() {
+        //on the day it's the one after
+        let upcoming = Upcoming::new(date(2020, 2, 29), date(2025, 2, 28), 1826);
+        assert_eq!(upcoming.anniversary.date, date(2026, 2, 28));
+        let upcoming = Upcoming::new(date(2020, 1, 1), date(2024, 12, 6), 1800);
+        assert_eq!(upcoming.hundred.count, 1900);
+    }
+
+    #[test]
+    fn leap_days_since() {
+        //the start day isn't one of them, the last day is
+        assert_eq!(leap_days(date(2020, 2, 29), date(2024, 2, 29)), 1);
+        assert_eq!(leap_days(date(1999, 12, 31), date(2026, 10, 14)), 7);
+    }
+
+    #[test]
+    fn calendar_age_from_a_leap_day() {
+        let between = |end| {
+            let split = Split::between(date(2020, 2, 29), end);
+            (split.years, split.months, split.days)
+        };
+        assert_eq!(between(date(2021, 2, 28)), (1, 0, 0));
+        assert_eq!(between(date(2024, 2, 28)), (3, 11, 30));
+        assert_eq!(between(date(2024, 2, 29)), (4, 0, 0));
+        assert_eq!(between(date(2023, 3, 1)), (3, 0, 1));
+        assert_eq!(MonthLength::Calendar.months(date(2020, 2, 29), 29), 1);
+    }
+
+    #[test]
+    fn german_digit_grouping() {
+        let separator = NumberFormat::Locale.separator(strings("de"));
+        let report =
+            Report::new(date(1990, 1, 1), date(2023, 10, 18), None).with_separator(separator);
+        assert_eq!(report.lines("01.01.1990", strings("de"))[2], "12.343 TAGE");
+        assert_eq!(
+            report.formatted.map(|formatted| formatted.days),
+            Some(String::from("12.343"))
+        );
+    }
+}
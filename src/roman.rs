@@ -0,0 +1,68 @@
+//! Roman numerals for the novelty mode, i.e. `MMXXIV`
+
+use chrono::{Datelike, NaiveDate};
+
+const NUMERALS: [(usize, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+/// `value` in Roman numerals, `N` (nulla) for 0 as there isn't a numeral for it, past 3999
+/// the `M`s keep repeating
+pub fn roman(mut value: usize) -> String {
+    if value == 0 {
+        return String::from("N");
+    }
+    let mut text = String::new();
+    for (amount, numeral) in NUMERALS {
+        while value >= amount {
+            text.push_str(numeral);
+            value -= amount;
+        }
+    }
+    text
+}
+
+/// `date` as the day, month and year, i.e. `XXV.XI.MMXXII`
+pub fn roman_date(date: NaiveDate) -> String {
+    format!(
+        "{}.{}.{}",
+        roman(date.day() as usize),
+        roman(date.month() as usize),
+        roman(date.year().max(0) as usize)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("test dates are valid")
+    }
+
+    #[test]
+    fn numerals() {
+        assert_eq!(roman(2024), "MMXXIV");
+        assert_eq!(roman(1994), "MCMXCIV");
+        assert_eq!(roman(49), "XLIX");
+        assert_eq!(roman(0), "N");
+        assert_eq!(roman(4000), "MMMM");
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(roman_date(date(2022, 11, 25)), "XXV.XI.MMXXII");
+    }
+}
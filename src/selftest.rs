@@ -0,0 +1,454 @@
+use crate::app_prefs::AppPreferences;
+use crate::config::Config;
+use crate::count::{
+    local_date, midnight, next_midnight, CalendarProgress, Fiscal, Intro, IsoWeeks, PreciseAge,
+    Timeline, Totals, Weekdays, YearProgress,
+};
+use crate::font::NumberFont;
+use crate::i18n::{detect_lang, strings};
+use crate::keys::Keys;
+use crate::milestones;
+use crate::plugin::External;
+use crate::recap::is_anniversary;
+use crate::render::{
+    canvas_size, render_age, render_bases, render_calendar, render_diff, render_dual,
+    render_fiscal, render_moons, render_roman, render_segments, render_split, render_timeline,
+    render_tminus, render_totals, render_upcoming, render_weekdays, render_weeks,
+    render_weeks_and_days, render_year, Palette, Reveal, Side, Texts,
+};
+use crate::report::{Moons, Report, Split, Upcoming};
+use crate::roman::roman_date;
+use crate::tokens::Tokens;
+use crate::{load_glyphs, offline, parse_units, paths};
+use buffer_graphics_lib::prelude::*;
+use chrono::{DateTime, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Runs every check and prints a line for each, returns an error if any failed so the
+/// exit code can be checked by provisioning scripts
+pub fn run(config_path: &Path) -> Result<()> {
+    let config = Config::load_with_env(config_path);
+    let mut checks: Vec<(&str, Result<()>)> = vec![
+        ("Leap days", leap_days()),
+        ("Year boundaries", year_boundaries()),
+        ("Daylight saving", daylight_saving()),
+        ("Timezones ahead of UTC", timezones_ahead()),
+        ("Future dates", future_dates()),
+        ("Milestones", milestone_days()),
+    ];
+    let font = match &config {
+        Ok(config) => load_glyphs(config.font.as_ref())
+            .map(|glyphs| NumberFont::new(glyphs, config.text_scale.unwrap_or(1))),
+        Err(_) => Ok(NumberFont::default()),
+    };
+    checks.push((
+        "Config",
+        config.as_ref().map(|_| ()).map_err(|err| eyre!("{err:#}")),
+    ));
+    checks.push((
+        "Keys",
+        match &config {
+            Ok(config) => Keys::new(&config.keys).map(|_| ()),
+            Err(_) => Ok(()),
+        },
+    ));
+    checks.push((
+        "Plugins",
+        match &config {
+            Ok(config) => plugins(&config.plugins),
+            Err(_) => Ok(()),
+        },
+    ));
+    checks.push((
+        "Units",
+        match &config {
+            Ok(config) => parse_units(&config.units).map(|_| ()),
+            Err(_) => Ok(()),
+        },
+    ));
+    checks.push((
+        "Font",
+        font.as_ref().map(|_| ()).map_err(|err| eyre!("{err:#}")),
+    ));
+    checks.extend([
+        ("Data dir is writable", data_dir_writable()),
+        ("Prefs", AppPreferences::load().map(|_| ())),
+        ("Tokens", Tokens::load().map(|_| ())),
+        ("Offline edits", offline::check()),
+        ("Display", display()),
+        ("Drawing", drawing(&font.unwrap_or_default())),
+    ]);
+
+    let failed = checks.iter().filter(|(_, result)| result.is_err()).count();
+    for (name, result) in &checks {
+        match result {
+            Ok(()) => println!("ok    {name}"),
+            Err(err) => println!("FAIL  {name}: {err:#}"),
+        }
+    }
+    if failed > 0 {
+        return Err(eyre!("{failed} of {} checks failed", checks.len()));
+    }
+    println!("All {} checks passed", checks.len());
+    Ok(())
+}
+
+/// Each library in `plugins` loads, see [External::load]
+fn plugins(paths: &[PathBuf]) -> Result<()> {
+    for path in paths {
+        //the loader's errors already name the file
+        unsafe { External::load(path) }.map_err(|err| eyre!(err))?;
+    }
+    Ok(())
+}
+
+fn expect<T: PartialEq + std::fmt::Debug>(what: &str, actual: T, expected: T) -> Result<()> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(eyre!("{what} was {actual:?}, expected {expected:?}"))
+    }
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("test dates are valid")
+}
+
+fn timezone(name: &str) -> Tz {
+    name.parse().expect("test timezones exist")
+}
+
+fn leap_days() -> Result<()> {
+    let report = Report::new(date(2020, 2, 28), date(2020, 3, 1), None);
+    expect("Days from 2020-02-28 to 2020-03-01", report.days, 2)?;
+    let report = Report::new(date(2020, 2, 29), date(2021, 2, 28), None);
+    expect("Days from 2020-02-29 to 2021-02-28", report.days, 365)?;
+    expect(
+        "Anniversary on 2021-02-28",
+        is_anniversary(report.days),
+        true,
+    )
+}
+
+fn year_boundaries() -> Result<()> {
+    let report = Report::new(date(2023, 12, 31), date(2024, 1, 1), None);
+    expect("Days from 2023-12-31 to 2024-01-01", report.days, 1)?;
+    let report = Report::new(date(2023, 1, 1), date(2024, 2, 5), None);
+    expect(
+        "Years, months and days for 400 days",
+        split(&report),
+        (1, 1, 7),
+    )
+}
+
+fn split(report: &Report) -> (usize, usize, usize) {
+    (report.split.years, report.split.months, report.split.days)
+}
+
+fn daylight_saving() -> Result<()> {
+    let london = Some(timezone("Europe/London"));
+    let spring = midnight(date(2024, 4, 1), london) - midnight(date(2024, 3, 31), london);
+    expect(
+        "Length of 2024-03-31 in London",
+        spring,
+        Duration::hours(23),
+    )?;
+    let autumn = midnight(date(2024, 10, 28), london) - midnight(date(2024, 10, 27), london);
+    expect(
+        "Length of 2024-10-27 in London",
+        autumn,
+        Duration::hours(25),
+    )?;
+    //Brazil's clocks went forward at midnight, so 2018-11-04 started at 1am
+    let sao_paulo = Some(timezone("America/Sao_Paulo"));
+    let skipped = midnight(date(2018, 11, 4), sao_paulo);
+    let before = utc("2018-11-03T12:00:00Z");
+    expect(
+        "Next midnight after 2018-11-03 noon in Sao Paulo is later",
+        next_midnight(before, sao_paulo) > before,
+        true,
+    )?;
+    expect(
+        "Date in Sao Paulo at the start of 2018-11-04",
+        local_date(skipped, sao_paulo) <= date(2018, 11, 4),
+        true,
+    )
+}
+
+fn timezones_ahead() -> Result<()> {
+    let auckland = Some(timezone("Pacific/Auckland"));
+    let at = utc("2024-01-01T15:00:00Z");
+    expect(
+        "Date in Auckland",
+        local_date(at, auckland),
+        date(2024, 1, 2),
+    )?;
+    expect("Date in UTC", local_date(at, None), date(2024, 1, 1))?;
+    expect(
+        "Next midnight in Auckland",
+        next_midnight(at, auckland),
+        utc("2024-01-02T11:00:00Z"),
+    )
+}
+
+fn future_dates() -> Result<()> {
+    let report = Report::new(date(2024, 1, 11), date(2024, 1, 1), None);
+    expect("Days since a future date", report.days, 0)?;
+    expect("Days until a future date", report.days_until, Some(10))
+}
+
+fn milestone_days() -> Result<()> {
+    expect(
+        "Milestone after 99 days",
+        milestones::next(99),
+        (100, String::from("100 days")),
+    )?;
+    expect(
+        "Milestone after 364 days",
+        milestones::next(364),
+        (365, String::from("1 year")),
+    )
+}
+
+fn utc(text: &str) -> DateTime<Utc> {
+    text.parse().expect("test times are valid")
+}
+
+fn data_dir_writable() -> Result<()> {
+    let path = paths::data_dir()?.join(".selftest");
+    fs::write(&path, "countup").wrap_err_with(|| format!("Writing {}", path.display()))?;
+    fs::remove_file(&path).wrap_err_with(|| format!("Removing {}", path.display()))
+}
+
+/// The window can't be opened without taking over the screen, so this only checks
+/// there's something to open it on, also checked before listing the monitors
+pub fn display() -> Result<()> {
+    if cfg!(all(unix, not(target_os = "macos")))
+        && std::env::var_os("DISPLAY").is_none()
+        && std::env::var_os("WAYLAND_DISPLAY").is_none()
+    {
+        return Err(eyre!(
+            "Neither DISPLAY nor WAYLAND_DISPLAY is set, only --headless will work"
+        ));
+    }
+    Ok(())
+}
+
+/// Draws each mode offscreen, checking something was drawn over the background
+fn drawing(font: &NumberFont) -> Result<()> {
+    let (width, height) = canvas_size(font, false);
+    let mut buffer = vec![0; width * height * 4];
+    let strings = strings(&detect_lang());
+    let reveal = Reveal {
+        intro: Intro::CountUp,
+        progress: 1.0,
+    };
+    let palette = Palette::default();
+    let texts = Texts::new(strings);
+    for name in [
+        "split",
+        "diff",
+        "weeks-and-days",
+        "segments",
+        "weeks",
+        "fiscal",
+        "moons",
+        "year",
+        "age",
+        "totals",
+        "upcoming",
+        "weekdays",
+        "roman",
+        "bases",
+        "timeline",
+        "calendar",
+        "dual",
+        "t-minus",
+    ] {
+        buffer.fill(0);
+        let mut graphics = Graphics::new(&mut buffer, width, height)
+            .map_err(|err| eyre!("Creating {width}x{height} canvas: {err:?}"))?;
+        match name {
+            "split" => render_split(
+                &mut graphics,
+                Split::new(1234),
+                None,
+                "2020-01-01",
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "diff" => render_diff(
+                &mut graphics,
+                1234,
+                44,
+                "2020-01-01",
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "weeks-and-days" => render_weeks_and_days(
+                &mut graphics,
+                1234,
+                "2020-01-01",
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "segments" => render_segments(&mut graphics, 1234, height, palette, &texts),
+            "weeks" => render_weeks(
+                &mut graphics,
+                IsoWeeks::new(date(2020, 1, 1), date(2023, 5, 17)),
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "fiscal" => render_fiscal(
+                &mut graphics,
+                Fiscal::new(date(2020, 1, 1), date(2023, 5, 17), 4),
+                "FY24, started 2023-04-01",
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "moons" => render_moons(
+                &mut graphics,
+                Moons::new(date(2020, 1, 1), date(2023, 5, 17)),
+                "2020-01-01",
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "year" => render_year(
+                &mut graphics,
+                YearProgress::new(date(2020, 1, 1), date(2023, 5, 17)),
+                "2023, from 2023-01-01",
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "age" => {
+                let born = date(2000, 1, 1)
+                    .and_hms_opt(12, 0, 0)
+                    .expect("noon is valid");
+                let age = PreciseAge::new(born, born + Duration::minutes(12_345_678));
+                render_age(
+                    &mut graphics,
+                    age,
+                    "Born 2000-01-01, you are",
+                    &age.lines("%Y-%m-%d %H:%M", strings),
+                    reveal,
+                    font,
+                    palette,
+                    &texts,
+                )
+            }
+            "totals" => render_totals(
+                &mut graphics,
+                Totals::new(utc("2020-01-01T00:00:00Z"), utc("2023-05-17T12:34:56Z")),
+                "2020-01-01",
+                reveal,
+                palette,
+                &texts,
+            ),
+            "upcoming" => render_upcoming(
+                &mut graphics,
+                Upcoming::new(date(2020, 2, 29), date(2026, 10, 14), 2419),
+                &[
+                    String::from("03/01/2027"),
+                    String::from("17/05/2028"),
+                    String::from("28/02/2027"),
+                ],
+                reveal,
+                palette,
+                &texts,
+            ),
+            "weekdays" => render_weekdays(
+                &mut graphics,
+                Weekdays::new(date(2020, 2, 29), date(2026, 10, 14)),
+                &strings.started_on(Weekday::Sat),
+                reveal,
+                palette,
+                &texts,
+            ),
+            "bases" => render_bases(
+                &mut graphics,
+                13435,
+                "2020-01-01",
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "timeline" => render_timeline(
+                &mut graphics,
+                Timeline::new(date(2020, 2, 29), date(2026, 10, 14), date(2026, 11, 20)),
+                &[
+                    String::from("29/02/2020"),
+                    String::from("20/11/2026"),
+                    String::from("2500 days"),
+                ],
+                reveal,
+                palette,
+                &texts,
+            ),
+            "roman" => render_roman(
+                &mut graphics,
+                Split::new(139_000),
+                &roman_date(date(1626, 5, 6)),
+                reveal,
+                font,
+                palette,
+                &texts,
+            ),
+            "calendar" => render_calendar(
+                &mut graphics,
+                CalendarProgress::new(
+                    date(2023, 5, 17)
+                        .and_hms_opt(12, 0, 0)
+                        .expect("noon is valid"),
+                ),
+                palette,
+                &texts,
+            ),
+            "dual" => {
+                let side = |days, label| Side {
+                    days,
+                    reveal,
+                    palette,
+                    label,
+                };
+                render_dual(
+                    &mut graphics,
+                    [side(312, "2020-01-01"), side(53, "2030-01-01")],
+                    font,
+                    &texts,
+                )
+            }
+            _ => render_tminus(
+                &mut graphics,
+                Duration::days(12),
+                "2030-01-01",
+                font,
+                palette,
+                &texts,
+            ),
+        }
+        let background = DARK_GRAY.as_array();
+        if buffer.chunks_exact(4).all(|px| px == background) {
+            return Err(eyre!("Nothing was drawn in {name} mode"));
+        }
+    }
+    Ok(())
+}
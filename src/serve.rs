@@ -0,0 +1,333 @@
+use crate::commands;
+use crate::config::Config;
+use crate::count::{local_date, TimeSource};
+use crate::event::Event;
+use crate::i18n::Strings;
+use crate::offline::{self, Outcome};
+use crate::report::{Counting, MonthLength, Report};
+use crate::shared::{self, Change};
+use crate::tokens::{Denied, Role, Tokens};
+use chrono::NaiveDate;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+/// Seconds between reloads of the HTML page
+const PAGE_REFRESH: u32 = 60;
+
+/// Name, help and value of each gauge at `/metrics`
+type Metric = (&'static str, &'static str, fn(&Report) -> i64);
+const METRICS: [Metric; 6] = [
+    ("countup_days_total", "Days since the start", |report| {
+        report.days as i64
+    }),
+    ("countup_weeks", "Whole weeks since the start", |report| {
+        report.weeks as i64
+    }),
+    (
+        "countup_months",
+        "Months since the start, of the config's month_length",
+        |report| report.months as i64,
+    ),
+    ("countup_years", "Whole years since the start", |report| {
+        report.years as i64
+    }),
+    (
+        "countup_days_until_start",
+        "Days until the start, 0 once it's passed",
+        |report| report.days_until.unwrap_or_default(),
+    ),
+    (
+        "countup_days_until_milestone",
+        "Days until the next milestone",
+        |report| report.next_milestone.days_until as i64,
+    ),
+];
+
+/// What to count from, events are looked up on every request so edits are shown
+pub struct Counter {
+    pub date: Option<NaiveDate>,
+    pub event: Option<String>,
+    pub config_path: PathBuf,
+    /// Daemon to read events from instead of the config
+    pub remote: Option<String>,
+    pub date_format: String,
+    pub strings: &'static Strings,
+    /// The config's `counting`, there's no flag for it here
+    pub counting: Counting,
+    /// The config's `month_length`
+    pub month_length: MonthLength,
+    /// `--now`, otherwise the system clock
+    pub time: TimeSource,
+}
+
+impl Counter {
+    fn events(&self) -> Result<Vec<Event>> {
+        match &self.remote {
+            Some(addr) => shared::list(addr),
+            None => Ok(Config::load_with_env(&self.config_path)?.events),
+        }
+    }
+
+    fn report(&self) -> Result<Report> {
+        let today = self.time.now().date_naive();
+        match (&self.date, &self.event) {
+            (Some(date), _) => Ok(Report::new_with(*date, today, None, self.counting)
+                .with_month_length(self.month_length)),
+            (None, Some(name)) => {
+                let event = self
+                    .events()?
+                    .into_iter()
+                    .find(|event| event.name.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| eyre!("No event named {name}"))?;
+                let today = local_date(self.time.now(), event.timezone);
+                let paused = event.paused_days(today, self.counting);
+                let milestones = event.milestones(&[]);
+                Ok(
+                    Report::new_with(event.date, today, Some(event.name), self.counting)
+                        .with_milestones(milestones)
+                        .with_paused(paused)
+                        .with_month_length(self.month_length),
+                )
+            }
+            (None, None) => Err(eyre!("No date or event set")),
+        }
+    }
+
+    fn all_events(&self) -> Result<Vec<Report>> {
+        let now = self.time.now();
+        Ok(self
+            .events()?
+            .into_iter()
+            .map(|event| {
+                let today = local_date(now, event.timezone);
+                let paused = event.paused_days(today, self.counting);
+                let milestones = event.milestones(&[]);
+                Report::new_with(event.date, today, Some(event.name), self.counting)
+                    .with_milestones(milestones)
+                    .with_paused(paused)
+                    .with_month_length(self.month_length)
+            })
+            .collect())
+    }
+
+    /// Sets the event's date to today in its timezone
+    fn reset(&self) -> Result<String> {
+        let name = self
+            .event
+            .as_ref()
+            .ok_or_else(|| eyre!("Serving a date rather than an event, nothing to reset"))?;
+        let now = self.time.now();
+        if let Some(addr) = &self.remote {
+            let today = commands::remote_today(addr, name, now);
+            return match offline::edit(addr, name, Change::SetDate { date: today })? {
+                Outcome::Applied => Ok(format!("Reset {name}")),
+                Outcome::Queued => Err(eyre!(
+                    "Unable to reach the daemon, the reset will be sent next time"
+                )),
+                Outcome::Conflict(reason) => Err(eyre!("Not reset as {reason}")),
+            };
+        }
+        let mut config = Config::load(&self.config_path)?;
+        let event = config
+            .events
+            .iter_mut()
+            .find(|event| event.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| eyre!("No event named {name}"))?;
+        event.reset(event.today(now));
+        config.save(&self.config_path)?;
+        Ok(format!("Reset {name}"))
+    }
+
+    /// Gauges for Prometheus, for every event and the served date if there is one
+    fn metrics(&self) -> Result<String> {
+        let mut reports = self.all_events()?;
+        if self.date.is_some() {
+            reports.insert(0, self.report()?);
+        }
+        let mut text = String::new();
+        for (name, help, value) in METRICS {
+            text.push_str(&format!("# HELP {name} {help}\n# TYPE {name} gauge\n"));
+            for report in &reports {
+                let labels = format!(
+                    r#"event="{}",start="{}""#,
+                    escape_label(report.event.as_deref().unwrap_or_default()),
+                    report.start
+                );
+                text.push_str(&format!("{name}{{{labels}}} {}\n", value(report)));
+            }
+        }
+        Ok(text)
+    }
+
+    fn page(&self) -> Result<String> {
+        let report = self.report()?;
+        let start = report.start.format(&self.date_format).to_string();
+        let lines: String = report
+            .lines(&start, self.strings)
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let tag = if i == 0 { "h1" } else { "p" };
+                format!("<{tag}>{}</{tag}>\n", escape_html(line))
+            })
+            .collect();
+        let title = escape_html(report.event.as_deref().unwrap_or("Countup"));
+        Ok(format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta http-equiv="refresh" content="{PAGE_REFRESH}">
+<title>{title}</title>
+<style>
+body {{ background: #444; color: #fff; font-family: monospace; text-align: center; font-size: 5vmin; }}
+h1 {{ color: #aaa; font-size: 1em; }}
+</style>
+</head>
+<body>
+{lines}</body>
+</html>
+"#
+        ))
+    }
+}
+
+/// Serves the count as an HTML page at `/` and JSON at `/count.json`, with every event
+/// at `/events.json` and gauges for Prometheus at `/metrics`, `POST /reset` resets the
+/// event
+///
+/// If there are any tokens one has to be passed as `Authorization: Bearer <TOKEN>` or
+/// `?token=<TOKEN>`
+pub fn serve(addr: &str, counter: Counter) -> Result<()> {
+    //fail now rather than on the first request
+    counter.report()?;
+    let listener = TcpListener::bind(addr).wrap_err_with(|| format!("Listening on {addr}"))?;
+    println!("Serving on http://{}", listener.local_addr()?);
+    let counter = Arc::new(counter);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let counter = counter.clone();
+                thread::spawn(move || {
+                    if let Err(err) = handle(stream, &counter) {
+                        eprintln!("Request error: {err}");
+                    }
+                });
+            }
+            Err(err) => eprintln!("Unable to accept connection: {err}"),
+        }
+    }
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream, counter: &Counter) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    //only the token is used but all headers have to be read before responding
+    let mut token = None;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("authorization") {
+                token = value.trim().strip_prefix("Bearer ").map(String::from);
+            }
+        }
+        header.clear();
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or("/"),
+    );
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    //tokens are hex so don't need decoding
+    let token = token.or_else(|| {
+        query
+            .split('&')
+            .find_map(|param| param.strip_prefix("token="))
+            .map(String::from)
+    });
+    let (status, content_type, body) = respond(method, path, token.as_deref(), counter);
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    Ok(())
+}
+
+fn respond(
+    method: &str,
+    path: &str,
+    token: Option<&str>,
+    counter: &Counter,
+) -> (&'static str, &'static str, String) {
+    let needed = match method {
+        "GET" => Role::Viewer,
+        "POST" => Role::Editor,
+        _ => {
+            return (
+                "405 Method Not Allowed",
+                "text/plain",
+                String::from("Only GET and POST are supported"),
+            )
+        }
+    };
+    let access =
+        Tokens::load().map(|tokens| tokens.role(token).and_then(|role| role.require(needed)));
+    match access {
+        Ok(Ok(())) => {}
+        Ok(Err(denied @ Denied::Role { .. })) => {
+            return ("403 Forbidden", "text/plain", denied.to_string())
+        }
+        Ok(Err(denied)) => return ("401 Unauthorized", "text/plain", denied.to_string()),
+        Err(err) => return ("500 Internal Server Error", "text/plain", err.to_string()),
+    }
+    let result = match (method, path) {
+        ("GET", "/") => counter
+            .page()
+            .map(|page| Some(("text/html; charset=utf-8", page))),
+        ("GET", "/count.json") => counter
+            .report()
+            .and_then(|report| Ok(Some(("application/json", to_json(&report)?)))),
+        ("GET", "/metrics") => counter
+            .metrics()
+            .map(|metrics| Some(("text/plain; version=0.0.4", metrics))),
+        ("GET", "/events.json") => counter
+            .all_events()
+            .and_then(|reports| Ok(Some(("application/json", to_json(&reports)?)))),
+        ("POST", "/reset") => counter.reset().map(|msg| Some(("text/plain", msg))),
+        _ => Ok(None),
+    };
+    match result {
+        Ok(Some((content_type, body))) => ("200 OK", content_type, body),
+        Ok(None) => ("404 Not Found", "text/plain", String::from("Not found")),
+        Err(err) => ("500 Internal Server Error", "text/plain", err.to_string()),
+    }
+}
+
+/// Prometheus label values can't have raw quotes, backslashes or newlines
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,277 @@
+//! Display settings, each taken from the first of: CLI, `COUNTUP_*` env var, config file,
+//! default
+//!
+//! The CLI and env var are read by clap, so only args it filled from a default are
+//! replaced by the config
+
+use crate::config::Config;
+use crate::count::{Animation, Easing, Intro};
+use crate::i18n::{detect_lang, strings, NumberFormat, Numerals};
+use crate::milestones::Pattern;
+use crate::render::{BackgroundStyle, SMALLEST_TEXT};
+use crate::report::{Counting, MonthLength};
+use crate::{check_date_format, frames, motion, DEFAULT_DATE_FORMAT};
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use std::path::{Path, PathBuf};
+
+/// Largest `--text-scale`, bigger than this won't fit on most screens
+pub const MAX_TEXT_SCALE: usize = 8;
+/// Largest `--scale`
+const MAX_SCALE: usize = 8;
+/// Smallest and largest `--min-text-size`, the smallest text at scale 1 and [MAX_SCALE]
+const MIN_TEXT_SIZES: (usize, usize) = (SMALLEST_TEXT, SMALLEST_TEXT * MAX_SCALE);
+/// Smallest `--size`, the window at text scale 1
+const MIN_SIZE: (usize, usize) = (270, 90);
+/// Largest `--size`, a 4K screen
+const MAX_SIZE: (usize, usize) = (3840, 2160);
+
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub lang: String,
+    pub date_format: String,
+    /// Header instead of the title, from `--template`
+    pub template: Option<String>,
+    pub animation: Animation,
+    /// No counting up, flashing or moving backgrounds, from `--reduced-motion` or the OS
+    pub reduced_motion: bool,
+    pub sound: bool,
+    pub font: Option<PathBuf>,
+    /// Image behind the count, from `--background`
+    pub background: Option<PathBuf>,
+    pub background_style: BackgroundStyle,
+    /// None if not set, then it's the largest that fits in `size`, or 1
+    pub text_scale: Option<usize>,
+    /// Width and height of the window, from `--size`, it's still made big enough to fit
+    pub size: Option<(usize, usize)>,
+    /// Window pixels per canvas pixel, on top of the screen's, None to only use the screen's,
+    /// raised to fit `--min-text-size`
+    pub scale: Option<usize>,
+    pub numerals: Numerals,
+    pub number_format: NumberFormat,
+    pub capture_frames: usize,
+    /// 12 or 24 hour times
+    pub hours: u8,
+    /// Date and time in the corner
+    pub clock: bool,
+    /// Only the numbers
+    pub minimal: bool,
+    /// Black background and readable colours, without images or effects behind the count
+    pub high_contrast: bool,
+    pub counting: Counting,
+    pub month_length: MonthLength,
+    /// Month the fiscal year starts in, 1 to 12
+    pub fiscal_year: Option<u32>,
+    /// Extra milestones
+    pub patterns: Vec<Pattern>,
+}
+
+/// Value of `id` if passed on the command line or as an env var, not if it's clap's default
+fn arg<T: Clone + Send + Sync + 'static>(matches: &ArgMatches, id: &str) -> Option<T> {
+    match matches.value_source(id) {
+        Some(ValueSource::CommandLine | ValueSource::EnvVariable) => matches.get_one(id).cloned(),
+        _ => None,
+    }
+}
+
+impl Settings {
+    /// Reads and checks the settings, `config_path` is only used in errors
+    pub fn resolve(matches: &ArgMatches, config: &Config, config_path: &Path) -> Result<Self> {
+        let lang = arg::<String>(matches, "lang")
+            .or_else(|| config.lang.clone())
+            .unwrap_or_else(detect_lang);
+
+        let date_format = arg::<String>(matches, "date-format")
+            .or_else(|| config.date_format.clone())
+            .unwrap_or_else(|| DEFAULT_DATE_FORMAT.to_string());
+        check_date_format(&date_format)?;
+
+        let mut animation = Animation::default();
+        if let Some(secs) = arg::<f64>(matches, "anim-speed").or(config.anim_speed) {
+            if !secs.is_finite() || secs < 0.0 {
+                return Err(eyre!(
+                    "Invalid anim_speed in {}: {secs}, must be 0 or more",
+                    config_path.display()
+                ));
+            }
+            animation.time_per_year = secs;
+        }
+        if let Some(easing) = arg::<String>(matches, "easing")
+            .and_then(|name| Easing::from_name(&name))
+            .or(config.easing)
+        {
+            animation.easing = easing;
+        }
+        if let Some(intro) = arg::<String>(matches, "intro")
+            .and_then(|name| Intro::from_name(&name))
+            .or(config.intro)
+        {
+            animation.intro = intro;
+        }
+        let reduced_motion = if matches.get_flag("full-motion") {
+            false
+        } else {
+            matches.get_flag("reduced-motion")
+                || config
+                    .reduced_motion
+                    .unwrap_or_else(motion::os_prefers_reduced)
+        };
+        if matches.get_flag("no-animation") || reduced_motion {
+            animation.time_per_year = 0.0;
+        }
+
+        let sound = !matches.get_flag("mute")
+            && (matches.get_flag("sound") || config.sound.unwrap_or_default());
+
+        let text_scale = arg::<u64>(matches, "text-scale")
+            .map(|scale| scale as usize)
+            .or(config.text_scale);
+        if let Some(text_scale) = text_scale.filter(|scale| !(1..=MAX_TEXT_SCALE).contains(scale)) {
+            return Err(eyre!(
+                "Invalid text_scale in {}: {text_scale}, must be 1 to {MAX_TEXT_SCALE}",
+                config_path.display()
+            ));
+        }
+
+        let size = arg::<String>(matches, "size").or_else(|| config.size.clone());
+        let size = size
+            .map(|size| {
+                parse_size(&size).ok_or_else(|| {
+                    eyre!(
+                        "Invalid size in {}: {size}, must be WIDTHxHEIGHT from {}x{} to {}x{}",
+                        config_path.display(),
+                        MIN_SIZE.0,
+                        MIN_SIZE.1,
+                        MAX_SIZE.0,
+                        MAX_SIZE.1
+                    )
+                })
+            })
+            .transpose()?;
+
+        let scale = arg::<u64>(matches, "scale")
+            .map(|scale| scale as usize)
+            .or(config.scale);
+        if let Some(scale) = scale.filter(|scale| !(1..=MAX_SCALE).contains(scale)) {
+            return Err(eyre!(
+                "Invalid scale in {}: {scale}, must be 1 to {MAX_SCALE}",
+                config_path.display()
+            ));
+        }
+
+        let min_text_size = arg::<u64>(matches, "min-text-size")
+            .map(|size| size as usize)
+            .or(config.min_text_size);
+        let (smallest, largest) = MIN_TEXT_SIZES;
+        if let Some(size) = min_text_size.filter(|size| !(smallest..=largest).contains(size)) {
+            return Err(eyre!(
+                "Invalid min_text_size in {}: {size}, must be {smallest} to {largest}",
+                config_path.display()
+            ));
+        }
+        //the text can only be made bigger by scaling the whole window, as the layouts are
+        //in pixels
+        let scale = match min_text_size {
+            Some(size) => Some(scale.unwrap_or(1).max(size.div_ceil(SMALLEST_TEXT))),
+            None => scale,
+        };
+
+        let numerals = arg::<String>(matches, "numerals")
+            .and_then(|name| Numerals::from_name(&name))
+            .or(config.numerals)
+            .unwrap_or(strings(&lang).numerals);
+
+        let hours = arg::<String>(matches, "hours")
+            .and_then(|hours| hours.parse().ok())
+            .or(config.hours)
+            .unwrap_or(strings(&lang).hours);
+        if hours != 12 && hours != 24 {
+            return Err(eyre!(
+                "Invalid hours in {}: {hours}, must be 12 or 24",
+                config_path.display()
+            ));
+        }
+
+        let fiscal_year = arg::<u64>(matches, "fiscal-year")
+            .map(|month| month as u32)
+            .or(config.fiscal_year);
+        if let Some(month) = fiscal_year.filter(|month| !(1..=12).contains(month)) {
+            return Err(eyre!(
+                "Invalid fiscal_year in {}: {month}, must be a month from 1 to 12",
+                config_path.display()
+            ));
+        }
+
+        let counting = if matches.get_flag("inclusive") {
+            Counting::Inclusive
+        } else if matches.get_flag("exclusive") {
+            Counting::Exclusive
+        } else {
+            config.counting.unwrap_or_default()
+        };
+
+        let high_contrast =
+            matches.get_flag("high-contrast") || config.high_contrast.unwrap_or_default();
+
+        Ok(Settings {
+            date_format,
+            template: arg(matches, "template").or_else(|| config.template.clone()),
+            animation,
+            reduced_motion,
+            sound,
+            font: arg(matches, "font").or_else(|| config.font.clone()),
+            background: arg(matches, "background")
+                .or_else(|| config.background_image.clone())
+                .filter(|_| !high_contrast),
+            background_style: arg::<String>(matches, "background-style")
+                .and_then(|name| BackgroundStyle::from_name(&name))
+                .or(config.background_style)
+                .filter(|_| !high_contrast)
+                .unwrap_or_default(),
+            text_scale,
+            size,
+            scale,
+            numerals,
+            number_format: arg::<String>(matches, "number-format")
+                .and_then(|name| NumberFormat::from_name(&name))
+                .or(config.number_format)
+                .unwrap_or_default(),
+            capture_frames: arg::<u64>(matches, "capture-frames")
+                .map(|count| count as usize)
+                .or(config.capture_frames)
+                .unwrap_or(frames::DEFAULT_CAPACITY),
+            hours,
+            clock: matches.get_flag("clock") || config.clock.unwrap_or_default(),
+            minimal: matches.get_flag("minimal") || config.minimal.unwrap_or_default(),
+            high_contrast,
+            counting,
+            month_length: arg::<String>(matches, "month-length")
+                .and_then(|name| MonthLength::from_name(&name))
+                .or(config.month_length)
+                .unwrap_or_default(),
+            fiscal_year,
+            patterns: match arg::<String>(matches, "patterns") {
+                Some(_) => matches
+                    .get_many::<String>("patterns")
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|name| Pattern::from_name(name))
+                    .collect(),
+                None => config.patterns.clone(),
+            },
+            lang,
+        })
+    }
+}
+
+/// `WIDTHxHEIGHT`, i.e. `540x180`, if it's within [MIN_SIZE] and [MAX_SIZE]
+fn parse_size(text: &str) -> Option<(usize, usize)> {
+    let (width, height) = text.trim().split_once(['x', 'X'])?;
+    let size = (width.trim().parse().ok()?, height.trim().parse().ok()?);
+    let fits = |(width, height): (usize, usize)| {
+        (MIN_SIZE.0..=MAX_SIZE.0).contains(&width) && (MIN_SIZE.1..=MAX_SIZE.1).contains(&height)
+    };
+    fits(size).then_some(size)
+}
@@ -0,0 +1,841 @@
+use crate::config::Config;
+use crate::event::{Event, Snooze};
+use crate::tokens::{self, Role, Tokens};
+use crate::{offline, paths, shutdown};
+use chrono::{DateTime, NaiveDate, Utc};
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const SUBSCRIBER_TIMEOUT: Duration = Duration::from_secs(5);
+/// When each event field was last changed, in the data dir
+const TIMES_FILE: &str = "daemon_times.json";
+/// Every applied edit, one JSON entry per line, in the data dir
+const AUDIT_FILE: &str = "audit.jsonl";
+/// Entries sent to subscribers with the events
+const SUBSCRIBER_HISTORY: usize = 20;
+/// Bytes of the audit file read at a time, from the end back, for history requests
+const HISTORY_BLOCK: u64 = 64 * 1024;
+/// Taken before the daemon's lock is released, so entries are written outside it but still
+/// in the order they were applied
+static AUDIT_ORDER: Mutex<()> = Mutex::new(());
+/// Edits are written to disk at most this long after they were made, so a burst of edits
+/// is saved once
+const SAVE_DELAY: Duration = Duration::from_secs(1);
+
+/// Sent by clients as a line of JSON, each is answered with a [Response]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Request {
+    List,
+    /// Applies the edits in order, any that conflict with changes made since the client
+    /// last synced aren't applied
+    Sync {
+        edits: Vec<Edit>,
+    },
+    /// Keeps the connection open and sends the events after every change
+    Subscribe,
+    /// Most recent edits, newest first
+    History {
+        event: Option<String>,
+        limit: usize,
+    },
+}
+
+/// Request with the client's token, see [tokens]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Envelope {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(flatten)]
+    request: Request,
+}
+
+impl Envelope {
+    fn new(request: Request) -> Self {
+        Envelope {
+            token: tokens::client_token().map(String::from),
+            request,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    Events {
+        events: Vec<Event>,
+        /// Daemon's time, clients use this to say what they've seen
+        now: DateTime<Utc>,
+        /// Recent edits, only sent to subscribers
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        history: Vec<AuditEntry>,
+    },
+    History(Vec<AuditEntry>),
+    Synced {
+        events: Vec<Event>,
+        now: DateTime<Utc>,
+        /// One for each edit sent, in the same order
+        results: Vec<SyncResult>,
+    },
+    Error(String),
+    /// Sent to subscribers when the daemon is stopped
+    ShuttingDown,
+}
+
+/// Change to an event, made on a client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Edit {
+    pub name: String,
+    pub change: Change,
+    /// Daemon time the client last received the events, changes after this conflict
+    pub seen_at: Option<DateTime<Utc>>,
+    /// Client time, only for showing to the user
+    pub made_at: DateTime<Utc>,
+    /// Who made the edit, see [client_id]
+    #[serde(default)]
+    pub client: String,
+    /// Apply even if it conflicts, used once the user has reviewed it
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl Edit {
+    /// Role needed for the daemon to apply this, reading only needs [Role::Viewer]
+    fn required_role(&self) -> Role {
+        match self.change {
+            _ if self.force => Role::Admin,
+            Change::Remove => Role::Admin,
+            Change::Add { .. }
+            | Change::SetDate { .. }
+            | Change::Snooze { .. }
+            | Change::Rename { .. } => Role::Editor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    Add {
+        event: Event,
+    },
+    Remove,
+    /// Set the date, i.e. resetting to the day the edit was made
+    SetDate {
+        date: NaiveDate,
+    },
+    /// Snooze the milestones, or unsnooze them if None
+    Snooze {
+        snooze: Option<Snooze>,
+    },
+    /// Give the event a new name, keeping everything else about it
+    Rename {
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncResult {
+    Applied,
+    /// The same fields were changed on the daemon since the client last synced
+    Conflict {
+        reason: String,
+        /// Daemon's version of the event, if it still exists
+        theirs: Option<Event>,
+    },
+    /// Would never work, i.e. the event doesn't exist
+    Rejected(String),
+}
+
+/// Edit applied by the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Daemon time it was applied
+    pub at: DateTime<Utc>,
+    pub client: String,
+    /// IP address the edit came from
+    pub addr: String,
+    pub event: String,
+    pub change: Change,
+}
+
+/// Name sent with edits, `COUNTUP_CLIENT_ID` or the hostname
+pub fn client_id() -> String {
+    env::var("COUNTUP_CLIENT_ID")
+        .ok()
+        .or_else(|| {
+            process::Command::new("hostname")
+                .output()
+                .ok()
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+        })
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from("unknown"))
+}
+
+/// When the fields of an event were last changed on the daemon, missing times mean it
+/// hasn't changed since the daemon started tracking
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct FieldTimes {
+    date: Option<DateTime<Utc>>,
+    phases: Option<DateTime<Utc>>,
+    removed: Option<DateTime<Utc>>,
+}
+
+impl FieldTimes {
+    fn added(now: DateTime<Utc>) -> Self {
+        FieldTimes {
+            date: Some(now),
+            phases: Some(now),
+            removed: None,
+        }
+    }
+
+    fn changed_since(time: Option<DateTime<Utc>>, seen_at: Option<DateTime<Utc>>) -> bool {
+        match (time, seen_at) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(time), Some(seen_at)) => time > seen_at,
+        }
+    }
+}
+
+struct Daemon {
+    config_path: PathBuf,
+    events: Vec<Event>,
+    /// By lower case event name
+    times: BTreeMap<String, FieldTimes>,
+    subscribers: Vec<TcpStream>,
+    /// When the oldest edit not yet written to disk was made
+    unsaved: Option<Instant>,
+    /// Newest first, up to [SUBSCRIBER_HISTORY], so subscribers aren't sent them from disk
+    recent: VecDeque<AuditEntry>,
+}
+
+impl Daemon {
+    fn find(&self, name: &str) -> Option<usize> {
+        self.events
+            .iter()
+            .position(|event| event.name.eq_ignore_ascii_case(name))
+    }
+
+    fn apply(&mut self, edit: Edit, now: DateTime<Utc>) -> SyncResult {
+        let key = edit.name.to_lowercase();
+        let times = self.times.get(&key).cloned().unwrap_or_default();
+        let changed = |time| !edit.force && FieldTimes::changed_since(time, edit.seen_at);
+        let idx = self.find(&edit.name);
+        let current = idx.map(|idx| self.events[idx].clone());
+        let conflict = |reason: String| SyncResult::Conflict {
+            reason,
+            theirs: current.clone(),
+        };
+        match (edit.change, idx) {
+            (Change::Add { event }, Some(idx)) => {
+                if self.events[idx] == event {
+                    return SyncResult::Applied;
+                }
+                if changed(times.date) || changed(times.phases) {
+                    return conflict(format!("{} was also added on the daemon", event.name));
+                }
+                if !edit.force {
+                    return SyncResult::Rejected(format!(
+                        "{} already exists, remove it first",
+                        event.name
+                    ));
+                }
+                self.events[idx] = event;
+                self.times.insert(key, FieldTimes::added(now));
+            }
+            (Change::Add { event }, None) => {
+                self.events.push(event);
+                self.times.insert(key, FieldTimes::added(now));
+            }
+            (Change::Remove, None) => return SyncResult::Applied,
+            (Change::Remove, Some(idx)) => {
+                if changed(times.date) || changed(times.phases) {
+                    return conflict(format!("{} was changed on the daemon", edit.name));
+                }
+                self.events.remove(idx);
+                self.times.entry(key).or_default().removed = Some(now);
+            }
+            (Change::SetDate { date }, Some(idx)) => {
+                if self.events[idx].date == date {
+                    return SyncResult::Applied;
+                }
+                if changed(times.date) {
+                    return conflict(format!("{}'s date was changed on the daemon", edit.name));
+                }
+                self.events[idx].date = date;
+                self.times.entry(key).or_default().date = Some(now);
+            }
+            (Change::SetDate { date }, None) => {
+                if changed(times.removed) {
+                    return conflict(format!("{} was removed on the daemon", edit.name));
+                }
+                if !edit.force {
+                    return SyncResult::Rejected(format!("No event named {}", edit.name));
+                }
+                //keeping a reset of an event that was removed brings it back
+                self.events.push(Event {
+                    name: edit.name,
+                    date,
+                    phases: vec![],
+                    pauses: vec![],
+                    timezone: None,
+                    resets: vec![],
+                    background: None,
+                    accent: None,
+                    anniversary: None,
+                    streak: None,
+                    note_pauses: None,
+                    snooze: None,
+                    milestones: vec![],
+                });
+                self.times.insert(key, FieldTimes::added(now));
+            }
+            //the latest snooze always wins, it's never worth a conflict
+            (Change::Snooze { snooze }, Some(idx)) => self.events[idx].snooze = snooze,
+            (Change::Snooze { .. }, None) => {
+                return SyncResult::Rejected(format!("No event named {}", edit.name));
+            }
+            (Change::Rename { name }, Some(idx)) => {
+                if self.events[idx].name == name {
+                    return SyncResult::Applied;
+                }
+                //only changing the case is fine
+                if self.find(&name).is_some_and(|other| other != idx) {
+                    return SyncResult::Rejected(format!("{name} already exists"));
+                }
+                self.events[idx].name = name.clone();
+                //a rename doesn't change the other fields, so their times go with it
+                if let Some(times) = self.times.remove(&key) {
+                    self.times.insert(name.to_lowercase(), times);
+                }
+            }
+            (Change::Rename { .. }, None) => {
+                if changed(times.removed) {
+                    return conflict(format!("{} was removed on the daemon", edit.name));
+                }
+                return SyncResult::Rejected(format!("No event named {}", edit.name));
+            }
+        }
+        SyncResult::Applied
+    }
+
+    fn record(&mut self, entry: AuditEntry) {
+        self.recent.push_front(entry);
+        self.recent.truncate(SUBSCRIBER_HISTORY);
+    }
+
+    fn history(&self) -> Vec<AuditEntry> {
+        self.recent.iter().cloned().collect()
+    }
+
+    fn events_response(&self, now: DateTime<Utc>, history: Vec<AuditEntry>) -> Response {
+        Response::Events {
+            events: self.events.clone(),
+            now,
+            history,
+        }
+    }
+
+    /// Saves any unwritten edits and tells subscribers the daemon is stopping, holding the
+    /// lock while this is called means no edit is half saved
+    fn close(&mut self) {
+        if let Err(err) = self.save() {
+            eprintln!("Unable to save events: {err:#}");
+        }
+        let line = to_line(&Response::ShuttingDown);
+        for mut stream in self.subscribers.drain(..) {
+            let _ = stream.write_all(line.as_bytes());
+        }
+    }
+
+    /// Sends the events to every subscriber, dropping any that have disconnected, they're
+    /// written to disk by [Daemon::save] once [SAVE_DELAY] has passed
+    fn changed(&mut self, now: DateTime<Utc>) {
+        self.unsaved.get_or_insert_with(Instant::now);
+        let line = to_line(&self.events_response(now, self.history()));
+        self.subscribers
+            .retain_mut(|stream| stream.write_all(line.as_bytes()).is_ok());
+    }
+
+    /// Writes the events to the config file (keeping the other settings), does nothing if
+    /// there are no unwritten edits
+    fn save(&mut self) -> Result<()> {
+        if self.unsaved.is_none() {
+            return Ok(());
+        }
+        let mut config = Config::load(&self.config_path)?;
+        config.events = self.events.clone();
+        config.save(&self.config_path)?;
+        save_times(&self.times)?;
+        self.unsaved = None;
+        Ok(())
+    }
+
+    /// If edits have been waiting long enough to be written
+    fn save_due(&self) -> bool {
+        self.unsaved
+            .is_some_and(|since| since.elapsed() >= SAVE_DELAY)
+    }
+}
+
+/// Saves edits in the background once [SAVE_DELAY] has passed, failures are kept unsaved to
+/// be retried
+fn write_behind(daemon: Arc<Mutex<Daemon>>) {
+    thread::spawn(move || loop {
+        thread::sleep(SAVE_DELAY / 4);
+        let mut daemon = daemon.lock().unwrap_or_else(|err| err.into_inner());
+        if daemon.save_due() {
+            if let Err(err) = daemon.save() {
+                eprintln!("Unable to save events: {err:#}");
+            }
+        }
+    });
+}
+
+fn times_path() -> Result<PathBuf> {
+    let mut path = paths::data_dir()?;
+    path.push(TIMES_FILE);
+    Ok(path)
+}
+
+fn load_times() -> Result<BTreeMap<String, FieldTimes>> {
+    let path = times_path()?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let json = fs::read_to_string(&path).wrap_err_with(|| format!("Reading {}", path.display()))?;
+    serde_json::from_str(&json).wrap_err_with(|| format!("Parsing {}", path.display()))
+}
+
+fn save_times(times: &BTreeMap<String, FieldTimes>) -> Result<()> {
+    let path = times_path()?;
+    let json = serde_json::to_string_pretty(times)?;
+    fs::write(&path, json).wrap_err_with(|| format!("Writing {}", path.display()))
+}
+
+fn audit_path() -> Result<PathBuf> {
+    let mut path = paths::data_dir()?;
+    path.push(AUDIT_FILE);
+    Ok(path)
+}
+
+fn audit(entries: &[AuditEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let path = audit_path()?;
+    let lines: String = entries.iter().map(to_line).collect();
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .wrap_err_with(|| format!("Writing {}", path.display()))
+}
+
+/// Up to `limit` of the newest entries, for `event` if set
+fn read_history(event: Option<&str>, limit: usize) -> Result<Vec<AuditEntry>> {
+    let path = audit_path()?;
+    let mut file = match File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err).wrap_err_with(|| format!("Opening {}", path.display())),
+    };
+    newest_entries(&mut file, event, limit, HISTORY_BLOCK)
+        .wrap_err_with(|| format!("Reading {}", path.display()))
+}
+
+/// Reads `audit` back from the end `block` bytes at a time until there's `limit` entries,
+/// so a long history isn't read in full for the last few
+fn newest_entries(
+    audit: &mut (impl Read + Seek),
+    event: Option<&str>,
+    limit: usize,
+    block: u64,
+) -> Result<Vec<AuditEntry>> {
+    let mut entries = vec![];
+    let mut end = audit.seek(SeekFrom::End(0))?;
+    //the start of the last line read, cut off by the start of the block
+    let mut partial = vec![];
+    while end > 0 && entries.len() < limit {
+        let start = end.saturating_sub(block);
+        let mut bytes = vec![0; (end - start) as usize];
+        audit.seek(SeekFrom::Start(start))?;
+        audit.read_exact(&mut bytes)?;
+        bytes.append(&mut partial);
+        let mut lines: Vec<&[u8]> = bytes.split(|byte| *byte == b'\n').collect();
+        if start > 0 {
+            partial = lines.remove(0).to_vec();
+        }
+        entries.extend(
+            lines
+                .into_iter()
+                .rev()
+                .filter_map(|line| serde_json::from_slice::<AuditEntry>(line).ok())
+                .filter(|entry| event.is_none_or(|name| entry.event.eq_ignore_ascii_case(name)))
+                .take(limit - entries.len()),
+        );
+        end = start;
+    }
+    Ok(entries)
+}
+
+fn to_line<T: Serialize>(value: &T) -> String {
+    let mut line = serde_json::to_string(value).expect("requests and responses are serializable");
+    line.push('\n');
+    line
+}
+
+/// Runs the shared event store, the events are read from and saved to `config_path`
+pub fn serve(addr: &str, config_path: PathBuf) -> Result<()> {
+    let events = Config::load(&config_path)?.events;
+    let listener = TcpListener::bind(addr).wrap_err_with(|| format!("Listening on {addr}"))?;
+    println!(
+        "Sharing {} event(s) on {}",
+        events.len(),
+        listener.local_addr()?
+    );
+    let daemon = Arc::new(Mutex::new(Daemon {
+        config_path,
+        events,
+        times: load_times()?,
+        subscribers: vec![],
+        unsaved: None,
+        recent: read_history(None, SUBSCRIBER_HISTORY)?.into(),
+    }));
+    write_behind(daemon.clone());
+    let closing = daemon.clone();
+    shutdown::install(move || {
+        closing
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .close();
+        println!("Stopped");
+        process::exit(0);
+    });
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let daemon = daemon.clone();
+                thread::spawn(move || {
+                    if let Err(err) = handle(stream, daemon) {
+                        eprintln!("Client error: {err}");
+                    }
+                });
+            }
+            Err(err) => eprintln!("Unable to accept connection: {err}"),
+        }
+    }
+    Ok(())
+}
+
+fn handle(stream: TcpStream, daemon: Arc<Mutex<Daemon>>) -> Result<()> {
+    let mut writer = stream.try_clone()?;
+    let addr = stream.peer_addr()?.ip().to_string();
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let now = Utc::now();
+        let response = match serde_json::from_str::<Envelope>(&line) {
+            Ok(envelope) => {
+                let role = match authorize(envelope.token.as_deref()) {
+                    Ok(role) => role,
+                    Err(msg) => {
+                        writer.write_all(to_line(&Response::Error(msg)).as_bytes())?;
+                        continue;
+                    }
+                };
+                let lock = || daemon.lock().unwrap_or_else(|err| err.into_inner());
+                match envelope.request {
+                    Request::List => lock().events_response(now, vec![]),
+                    Request::Subscribe => {
+                        let subscriber = writer.try_clone()?;
+                        //so a stalled client can't hold up the others
+                        subscriber.set_write_timeout(Some(SUBSCRIBER_TIMEOUT))?;
+                        let mut daemon = lock();
+                        daemon.subscribers.push(subscriber);
+                        daemon.events_response(now, daemon.history())
+                    }
+                    //read from the file without the lock, it's only ever appended to
+                    Request::History { event, limit } => {
+                        Response::History(read_history(event.as_deref(), limit)?)
+                    }
+                    Request::Sync { edits } => {
+                        let mut daemon = lock();
+                        let mut applied = vec![];
+                        let mut results = vec![];
+                        for edit in edits {
+                            if let Err(denied) = role.require(edit.required_role()) {
+                                results.push(SyncResult::Rejected(denied.to_string()));
+                                continue;
+                            }
+                            let before = daemon.events.clone();
+                            let entry = AuditEntry {
+                                at: now,
+                                client: edit.client.clone(),
+                                addr: addr.clone(),
+                                event: edit.name.clone(),
+                                change: edit.change.clone(),
+                            };
+                            results.push(daemon.apply(edit, now));
+                            //no-ops (i.e. resetting twice) aren't recorded
+                            if daemon.events != before {
+                                daemon.record(entry.clone());
+                                applied.push(entry);
+                            }
+                        }
+                        if !applied.is_empty() {
+                            daemon.changed(now);
+                        }
+                        let response = Response::Synced {
+                            events: daemon.events.clone(),
+                            now,
+                            results,
+                        };
+                        let _order = AUDIT_ORDER.lock().unwrap_or_else(|err| err.into_inner());
+                        drop(daemon);
+                        //the edits are applied and sent to subscribers by now, failing the
+                        //response would only have the client send them again
+                        if let Err(err) = audit(&applied) {
+                            eprintln!("Unable to record edits: {err:#}");
+                        }
+                        response
+                    }
+                }
+            }
+            Err(err) => Response::Error(format!("Invalid request: {err}")),
+        };
+        writer.write_all(to_line(&response).as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Role of the client's token, tokens are reloaded for every request so changes apply
+/// without restarting the daemon
+fn authorize(token: Option<&str>) -> Result<Role, String> {
+    let tokens = Tokens::load().map_err(|err| format!("Unable to load tokens: {err}"))?;
+    tokens.role(token).map_err(|denied| denied.to_string())
+}
+
+/// Sends `request` to the daemon at `addr`
+pub fn request(addr: &str, request: &Request) -> Result<Response> {
+    let mut stream = TcpStream::connect(addr)
+        .wrap_err_with(|| format!("Connecting to countup daemon at {addr}"))?;
+    stream.write_all(to_line(&Envelope::new(request.clone())).as_bytes())?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    match serde_json::from_str(&line).wrap_err("Invalid response from daemon")? {
+        Response::Error(msg) => Err(eyre!(msg)),
+        response => Ok(response),
+    }
+}
+
+/// Gets the events from the daemon at `addr`
+pub fn list(addr: &str) -> Result<Vec<Event>> {
+    match request(addr, &Request::List)? {
+        Response::Events { events, now, .. } => {
+            offline::seen(addr, now);
+            Ok(events)
+        }
+        _ => Err(eyre!("Unexpected response from daemon")),
+    }
+}
+
+/// Gets up to `limit` of the newest edits from the daemon at `addr`, for `event` if set
+pub fn history(addr: &str, event: Option<&str>, limit: usize) -> Result<Vec<AuditEntry>> {
+    let request = Request::History {
+        event: event.map(String::from),
+        limit,
+    };
+    match self::request(addr, &request)? {
+        Response::History(entries) => Ok(entries),
+        _ => Err(eyre!("Unexpected response from daemon")),
+    }
+}
+
+/// Events and recent edits, sent to subscribers after every change
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub events: Vec<Event>,
+    pub history: Vec<AuditEntry>,
+}
+
+/// Receives the events from the daemon at `addr` every time they change, reconnecting if
+/// the connection is lost
+pub fn subscribe(addr: String) -> Receiver<Update> {
+    let (sender, receiver) = channel();
+    thread::spawn(move || loop {
+        match listen(&addr, &sender) {
+            //the app has closed
+            Ok(()) => return,
+            Err(err) => eprintln!("Lost connection to {addr}: {err}, retrying"),
+        }
+        thread::sleep(RECONNECT_DELAY);
+    });
+    receiver
+}
+
+fn listen(addr: &str, sender: &Sender<Update>) -> Result<()> {
+    let mut stream = TcpStream::connect(addr)?;
+    //send anything edited while disconnected
+    offline::report(&offline::flush(addr)?);
+    stream.write_all(to_line(&Envelope::new(Request::Subscribe)).as_bytes())?;
+    for line in BufReader::new(stream).lines() {
+        match serde_json::from_str(&line?)? {
+            Response::Events {
+                events,
+                now,
+                history,
+            } => {
+                offline::seen(addr, now);
+                if sender.send(Update { events, history }).is_err() {
+                    return Ok(());
+                }
+            }
+            //i.e. the token was rejected
+            Response::Error(msg) => return Err(eyre!(msg)),
+            Response::ShuttingDown => return Err(eyre!("daemon stopped")),
+            _ => {}
+        }
+    }
+    Err(eyre!("connection closed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn daemon(names: &[&str]) -> Daemon {
+        Daemon {
+            config_path: PathBuf::new(),
+            events: names
+                .iter()
+                .map(|name| {
+                    serde_json::from_value(serde_json::json!({"name": name, "date": "2020-06-01"}))
+                        .unwrap()
+                })
+                .collect(),
+            times: BTreeMap::new(),
+            subscribers: vec![],
+            unsaved: None,
+            recent: VecDeque::new(),
+        }
+    }
+
+    fn rename(from: &str, to: &str) -> Edit {
+        Edit {
+            name: from.to_string(),
+            change: Change::Rename {
+                name: to.to_string(),
+            },
+            seen_at: None,
+            made_at: Utc::now(),
+            client: String::from("test"),
+            force: false,
+        }
+    }
+
+    #[test]
+    fn rename_keeps_the_event() {
+        let mut daemon = daemon(&["Wedding"]);
+        let now = Utc::now();
+        daemon
+            .times
+            .insert(String::from("wedding"), FieldTimes::added(now));
+        let edit = rename("wedding", "Anniversary");
+        assert_eq!(edit.required_role(), Role::Editor);
+        assert!(matches!(daemon.apply(edit, now), SyncResult::Applied));
+        assert_eq!(daemon.events.len(), 1);
+        assert_eq!(daemon.events[0].name, "Anniversary");
+        assert_eq!(daemon.events[0].date.to_string(), "2020-06-01");
+        assert!(daemon.times.contains_key("anniversary"));
+        assert!(!daemon.times.contains_key("wedding"));
+    }
+
+    #[test]
+    fn rename_only_changing_the_case() {
+        let mut daemon = daemon(&["wedding"]);
+        assert!(matches!(
+            daemon.apply(rename("wedding", "Wedding"), Utc::now()),
+            SyncResult::Applied
+        ));
+        assert_eq!(daemon.events[0].name, "Wedding");
+    }
+
+    #[test]
+    fn rename_onto_another_event_is_rejected() {
+        let mut daemon = daemon(&["Wedding", "Anniversary"]);
+        assert!(matches!(
+            daemon.apply(rename("Wedding", "anniversary"), Utc::now()),
+            SyncResult::Rejected(_)
+        ));
+        assert_eq!(daemon.events[0].name, "Wedding");
+        assert!(matches!(
+            daemon.apply(rename("Birthday", "Party"), Utc::now()),
+            SyncResult::Rejected(_)
+        ));
+    }
+
+    fn entry(event: &str) -> AuditEntry {
+        AuditEntry {
+            at: Utc::now(),
+            client: String::from("test"),
+            addr: String::from("127.0.0.1"),
+            event: event.to_string(),
+            change: Change::Remove,
+        }
+    }
+
+    fn events(entries: &[AuditEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.event.as_str()).collect()
+    }
+
+    #[test]
+    fn newest_entries_from_the_end() {
+        let names = ["a", "b", "c", "d", "e", "f"];
+        let lines: String = names.iter().map(|name| to_line(&entry(name))).collect();
+        let mut audit = Cursor::new(lines.into_bytes());
+        //blocks much smaller than a line, so every line is split across them
+        for block in [16, 100, HISTORY_BLOCK] {
+            let newest = newest_entries(&mut audit, None, 4, block).unwrap();
+            assert_eq!(events(&newest), ["f", "e", "d", "c"], "{block}");
+            let all = newest_entries(&mut audit, None, 10, block).unwrap();
+            assert_eq!(events(&all), ["f", "e", "d", "c", "b", "a"], "{block}");
+            let one = newest_entries(&mut audit, Some("B"), 10, block).unwrap();
+            assert_eq!(events(&one), ["b"], "{block}");
+        }
+        let mut empty = Cursor::new(vec![]);
+        assert!(newest_entries(&mut empty, None, 4, 16).unwrap().is_empty());
+    }
+
+    #[test]
+    fn subscribers_get_the_newest_history() {
+        let mut daemon = daemon(&[]);
+        for idx in 0..SUBSCRIBER_HISTORY + 5 {
+            daemon.record(entry(&idx.to_string()));
+        }
+        let history = daemon.history();
+        assert_eq!(history.len(), SUBSCRIBER_HISTORY);
+        assert_eq!(history[0].event, (SUBSCRIBER_HISTORY + 4).to_string());
+        assert_eq!(history[SUBSCRIBER_HISTORY - 1].event, "5");
+    }
+}
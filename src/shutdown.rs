@@ -0,0 +1,28 @@
+use crate::outbox;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Runs `on_signal` on Ctrl-C, SIGTERM or SIGHUP (or the console closing on Windows), a
+/// second signal exits straight away
+///
+/// `on_signal` is run on its own thread
+pub fn install(mut on_signal: impl FnMut() + Send + 'static) {
+    let result = ctrlc::set_handler(move || {
+        if REQUESTED.swap(true, Ordering::SeqCst) {
+            eprintln!("Forcing exit");
+            outbox::persist_in_flight();
+            process::exit(130);
+        }
+        on_signal();
+    });
+    if let Err(err) = result {
+        eprintln!("Unable to handle signals, state may be lost if killed: {err}");
+    }
+}
+
+/// If a signal has been received, i.e. for the window to close itself
+pub fn requested() -> bool {
+    REQUESTED.load(Ordering::SeqCst)
+}
@@ -0,0 +1,90 @@
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+/// Shortest gap between ticks, so fast counts tick instead of buzzing
+const TICK_GAP: Duration = Duration::from_millis(60);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Sound {
+    /// Played as the count rolls up
+    Tick,
+    /// Played when the count finishes or a milestone is reached
+    Chime,
+}
+
+/// Plays sounds on a background thread, dropping it stops the thread
+pub struct Sounds {
+    sender: Sender<Sound>,
+    last_tick: Option<Instant>,
+}
+
+impl Sounds {
+    pub fn play(&self, sound: Sound) {
+        //the thread ends if there's no audio output, so it doesn't matter if this fails
+        let _ = self.sender.send(sound);
+    }
+
+    /// Plays a tick, unless one was played very recently
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_tick
+            .is_some_and(|last| now.duration_since(last) < TICK_GAP)
+        {
+            return;
+        }
+        self.last_tick = Some(now);
+        self.play(Sound::Tick);
+    }
+}
+
+/// Starts the audio thread, only available when built with the `sound` feature
+///
+/// Not having any audio output isn't an error as the counter works without it
+#[cfg(feature = "sound")]
+pub fn start() -> Option<Sounds> {
+    use rodio::source::SineWave;
+    use rodio::{OutputStream, Source};
+    use std::sync::mpsc::channel;
+
+    fn note(freq: f32, millis: u64, volume: f32) -> impl Source<Item = f32> + Send {
+        SineWave::new(freq)
+            .take_duration(Duration::from_millis(millis))
+            .fade_in(Duration::from_millis(5))
+            .amplify(volume)
+    }
+
+    let (sender, receiver) = channel();
+    std::thread::spawn(move || {
+        //the stream has to stay on the thread that made it
+        let (_stream, handle) = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(err) => {
+                eprintln!("Sound is off, no audio output: {err}");
+                return;
+            }
+        };
+        for sound in receiver {
+            let result = match sound {
+                Sound::Tick => handle.play_raw(note(1760.0, 12, 0.05)),
+                Sound::Chime => handle.play_raw(
+                    note(880.0, 150, 0.1)
+                        .mix(note(1318.5, 300, 0.1).delay(Duration::from_millis(120))),
+                ),
+            };
+            if let Err(err) = result {
+                eprintln!("Unable to play sound: {err}");
+            }
+        }
+    });
+    Some(Sounds {
+        sender,
+        last_tick: None,
+    })
+}
+
+#[cfg(not(feature = "sound"))]
+pub fn start() -> Option<Sounds> {
+    eprintln!("Sound is off, countup was built without the sound feature");
+    None
+}
@@ -0,0 +1,93 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// A school term or semester, only weekdays outside of breaks count as teaching days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Term {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub breaks: Vec<DateRange>,
+    /// Country or region code, i.e. `GB` or `GB-SCT`, whose public holidays aren't
+    /// teaching days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holidays: Option<String>,
+    /// The holidays' dates, filled in by the app as they're downloaded
+    #[serde(skip)]
+    pub holiday_dates: Vec<NaiveDate>,
+}
+
+/// Inclusive range of dates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TermProgress {
+    NotStarted {
+        days_until: i64,
+        teaching_days: usize,
+    },
+    InTerm {
+        /// 1 based, only weeks with at least one teaching day are counted
+        week: usize,
+        weeks: usize,
+        /// Includes today if it's a teaching day
+        days_left: usize,
+        on_break: bool,
+    },
+    Over,
+}
+
+impl Term {
+    pub fn is_teaching_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            && self.start <= date
+            && date <= self.end
+            && !self.breaks.iter().any(|range| range.contains(date))
+            && !self.holiday_dates.contains(&date)
+    }
+
+    fn teaching_days(&self) -> Vec<NaiveDate> {
+        self.start
+            .iter_days()
+            .take_while(|date| *date <= self.end)
+            .filter(|date| self.is_teaching_day(*date))
+            .collect()
+    }
+
+    pub fn progress(&self, today: NaiveDate) -> TermProgress {
+        let days = self.teaching_days();
+        if today < self.start {
+            return TermProgress::NotStarted {
+                days_until: (self.start - today).num_days(),
+                teaching_days: days.len(),
+            };
+        }
+        if today > self.end {
+            return TermProgress::Over;
+        }
+        let mut weeks: Vec<(i32, u32)> = days
+            .iter()
+            .map(|date| (date.iso_week().year(), date.iso_week().week()))
+            .collect();
+        weeks.dedup();
+        let this_week = (today.iso_week().year(), today.iso_week().week());
+        //during a break week show the last week taught
+        let week = weeks.iter().filter(|week| **week <= this_week).count();
+        TermProgress::InTerm {
+            week: week.max(1),
+            weeks: weeks.len(),
+            days_left: days.iter().filter(|date| **date >= today).count(),
+            on_break: !weeks.contains(&this_week) || self.breaks.iter().any(|b| b.contains(today)),
+        }
+    }
+}
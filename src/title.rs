@@ -0,0 +1,148 @@
+//! The count in the window title, so it's readable from the taskbar and alt-tab
+//!
+//! The window library only sets the title when the window is created, so later days are
+//! set from outside it like [desktop::pin](crate::desktop::pin), on X11 (and XWayland) and
+//! Windows. Elsewhere the title stays as it was when opened
+
+use countup::i18n::Strings;
+use countup::report::Report;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// How long to keep looking for the window, it may not be open yet on the first day
+const FIND_FOR: Duration = Duration::from_secs(10);
+const FIND_EVERY: Duration = Duration::from_millis(200);
+
+/// i.e. "Countup — Wedding — 432 days" or "Countup — in 12 days"
+pub fn text(report: &Report, strings: &Strings) -> String {
+    let count = match report.days_until {
+        Some(days) => strings.in_days(days.max(0) as usize),
+        None => strings.counted(report.days, strings.day_words),
+    };
+    match &report.event {
+        Some(event) => format!("Countup — {event} — {count}"),
+        None => format!("Countup — {count}"),
+    }
+}
+
+/// Changes the open window's title from a background thread, failing quietly as it's
+/// only a nicety
+pub fn set(title: String) {
+    thread::spawn(move || {
+        let start = Instant::now();
+        loop {
+            match try_set(&title) {
+                Ok(true) => return,
+                Ok(false) if start.elapsed() < FIND_FOR => thread::sleep(FIND_EVERY),
+                Ok(false) => return debug!("Unable to set the window title: no window found"),
+                Err(err) => return debug!("Unable to set the window title: {err}"),
+            }
+        }
+    });
+}
+
+/// False if the window isn't open yet
+#[cfg(not(any(target_os = "macos", windows)))]
+fn try_set(title: &str) -> color_eyre::Result<bool> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt, PropMode};
+
+    let (conn, screen) = x11rb::connect(None)?;
+    let root = conn.setup().roots[screen].root;
+    let Some(window) = crate::desktop::x11_window(&conn, root, std::process::id())? else {
+        return Ok(false);
+    };
+    let name = conn.intern_atom(false, b"_NET_WM_NAME")?.reply()?.atom;
+    let utf8 = conn.intern_atom(false, b"UTF8_STRING")?.reply()?.atom;
+    let bytes = title.as_bytes();
+    conn.change_property(
+        PropMode::REPLACE,
+        window,
+        name,
+        utf8,
+        8,
+        bytes.len() as u32,
+        bytes,
+    )?;
+    //for window managers that don't read the UTF-8 one, which is Latin-1
+    let latin: Vec<u8> = title
+        .chars()
+        .map(|char| u8::try_from(char).unwrap_or(b'-'))
+        .collect();
+    conn.change_property(
+        PropMode::REPLACE,
+        window,
+        AtomEnum::WM_NAME,
+        AtomEnum::STRING,
+        8,
+        latin.len() as u32,
+        &latin,
+    )?;
+    conn.flush()?;
+    Ok(true)
+}
+
+/// Sets the title with PowerShell, like [desktop::pin](crate::desktop::pin), it's passed
+/// through the environment so it needn't be escaped
+#[cfg(windows)]
+fn try_set(title: &str) -> color_eyre::Result<bool> {
+    use color_eyre::eyre::eyre;
+    use std::process::Command;
+    let script = format!(
+        r#"
+Add-Type @"
+using System;
+using System.Runtime.InteropServices;
+public static class Title {{
+    [DllImport("user32.dll", CharSet = CharSet.Unicode)] public static extern bool SetWindowText(IntPtr window, string text);
+}}
+"@
+$window = (Get-Process -Id {}).MainWindowHandle
+if ($window -eq [IntPtr]::Zero) {{ exit 2 }}
+[Title]::SetWindowText($window, $env:COUNTUP_TITLE) | Out-Null
+"#,
+        std::process::id()
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", &script])
+        .env("COUNTUP_TITLE", title)
+        .output()?;
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(2) => Ok(false),
+        _ => Err(eyre!("{}", String::from_utf8_lossy(&output.stderr).trim())),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn try_set(_title: &str) -> color_eyre::Result<bool> {
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use countup::i18n::strings;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("test dates are valid")
+    }
+
+    #[test]
+    fn title_with_an_event() {
+        let report = Report::new(
+            date(2023, 1, 1),
+            date(2024, 3, 7),
+            Some(String::from("Wedding")),
+        );
+        assert_eq!(text(&report, strings("en")), "Countup — Wedding — 431 days");
+    }
+
+    #[test]
+    fn title_counting_down() {
+        let report = Report::new(date(2024, 1, 2), date(2024, 1, 1), None);
+        assert_eq!(text(&report, strings("en")), "Countup — in 1 day");
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{credentials, paths};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Tokens accepted by the daemon and `serve`, in the data dir
+const TOKENS_FILE: &str = "tokens.json";
+/// Name of the secret used when `--token` isn't passed
+pub const CREDENTIAL: &str = "token";
+/// Random bytes in each token, hex encoded
+const TOKEN_BYTES: usize = 24;
+
+static CLIENT_TOKEN: OnceLock<Option<String>> = OnceLock::new();
+
+/// In order of access, each role can do everything the previous one can
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Read the count, events and history
+    Viewer,
+    /// Add and reset events
+    Editor,
+    /// Remove events and override conflicts
+    Admin,
+}
+
+impl Role {
+    pub const NAMES: [&'static str; 3] = ["viewer", "editor", "admin"];
+
+    pub fn from_name(name: &str) -> Option<Role> {
+        match name {
+            "viewer" => Some(Role::Viewer),
+            "editor" => Some(Role::Editor),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+
+    pub fn require(self, needed: Role) -> Result<(), Denied> {
+        if self >= needed {
+            Ok(())
+        } else {
+            Err(Denied::Role { has: self, needed })
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Role::NAMES[*self as usize])
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Token {
+    pub name: String,
+    pub role: Role,
+    pub token: String,
+    pub created: DateTime<Utc>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Denied {
+    Missing,
+    Invalid,
+    Role { has: Role, needed: Role },
+}
+
+impl Display for Denied {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Denied::Missing => write!(f, "A token is required"),
+            Denied::Invalid => write!(f, "Unknown token"),
+            Denied::Role { has, needed } => {
+                write!(f, "Needs {needed} access, this token only has {has} access")
+            }
+        }
+    }
+}
+
+/// Tokens that can be used to access this machine's daemon and server
+#[derive(Debug, Default)]
+pub struct Tokens(Vec<Token>);
+
+impl Tokens {
+    /// Reads the tokens file, a missing file means there are no tokens
+    pub fn load() -> Result<Self> {
+        let path = path()?;
+        if !path.exists() {
+            return Ok(Tokens::default());
+        }
+        let json =
+            fs::read_to_string(&path).wrap_err_with(|| format!("Reading {}", path.display()))?;
+        serde_json::from_str(&json)
+            .map(Tokens)
+            .wrap_err_with(|| format!("Parsing {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = path()?;
+        paths::write_private(&path, &serde_json::to_string_pretty(&self.0)?)
+            .wrap_err_with(|| format!("Writing {}", path.display()))
+    }
+
+    pub fn list(&self) -> &[Token] {
+        &self.0
+    }
+
+    /// Role of the `presented` token, without any tokens access isn't restricted
+    pub fn role(&self, presented: Option<&str>) -> Result<Role, Denied> {
+        if self.0.is_empty() {
+            return Ok(Role::Admin);
+        }
+        let presented = presented.ok_or(Denied::Missing)?;
+        self.0
+            .iter()
+            .find(|token| constant_time_eq(&token.token, presented))
+            .map(|token| token.role)
+            .ok_or(Denied::Invalid)
+    }
+
+    /// Creates a new token called `name`, replacing any with the same name
+    pub fn add(name: &str, role: Role) -> Result<Token> {
+        let mut bytes = [0; TOKEN_BYTES];
+        getrandom::getrandom(&mut bytes).map_err(|err| eyre!("Generating token: {err}"))?;
+        let token = Token {
+            name: name.to_string(),
+            role,
+            token: bytes.iter().map(|byte| format!("{byte:02x}")).collect(),
+            created: Utc::now(),
+        };
+        let mut tokens = Tokens::load()?;
+        tokens.0.retain(|existing| existing.name != name);
+        tokens.0.push(token.clone());
+        tokens.save()?;
+        Ok(token)
+    }
+
+    /// Returns false if there's no token called `name`
+    pub fn remove(name: &str) -> Result<bool> {
+        let mut tokens = Tokens::load()?;
+        let len = tokens.0.len();
+        tokens.0.retain(|token| token.name != name);
+        if tokens.0.len() == len {
+            return Ok(false);
+        }
+        tokens.save()?;
+        Ok(true)
+    }
+}
+
+/// Compares the whole string so the time taken doesn't reveal how much of a token matched
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn path() -> Result<PathBuf> {
+    let mut path = paths::data_dir()?;
+    path.push(TOKENS_FILE);
+    Ok(path)
+}
+
+/// Sets the token sent to daemons, from `--token`, must be called before any requests
+pub fn set_client_token(token: Option<String>) {
+    if token.is_some() {
+        CLIENT_TOKEN
+            .set(token)
+            .expect("client token should only be set once, at startup");
+    }
+}
+
+/// Token sent to daemons, the one passed to `--token` or the `token` secret
+pub fn client_token() -> Option<&'static str> {
+    CLIENT_TOKEN
+        .get_or_init(|| credentials::get(CREDENTIAL))
+        .as_deref()
+}
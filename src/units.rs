@@ -0,0 +1,174 @@
+//! Extra rows for diff mode from the config, i.e. `{"label": "COFFEES", "value": "days * 3"}`
+//!
+//! Values are [rhai] expressions using `days`, `weeks`, `months` and `years`. Only a single
+//! expression is accepted, not statements or loops, the engine can't load modules, has no I/O
+//! and stops after [MAX_OPERATIONS], so a config can't hang the window or reach outside it
+
+use rhai::{Dynamic, Engine, EvalAltResult, Scope, AST, INT};
+use serde::{Deserialize, Serialize};
+
+/// Longest value that can be parsed, more than any joke needs
+const MAX_LENGTH: usize = 200;
+/// Deepest brackets and calls can be nested, so parsing can't overflow the stack
+const MAX_DEPTH: usize = 32;
+/// Most steps a value can take to work out, far more than any arithmetic needs
+const MAX_OPERATIONS: u64 = 10_000;
+/// Rows diff mode has room for under its own four
+pub const MAX_UNITS: usize = 4;
+pub const VARIABLES: [&str; 4] = ["days", "weeks", "months", "years"];
+
+thread_local! {
+    /// Shared by every formula on the thread, creating one registers all the functions
+    static ENGINE: Engine = engine();
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_expr_depths(MAX_DEPTH, MAX_DEPTH)
+        .set_max_call_levels(MAX_DEPTH)
+        .set_max_string_size(MAX_LENGTH)
+        .set_max_array_size(MAX_LENGTH)
+        .set_max_map_size(MAX_LENGTH)
+        .on_print(|_| {})
+        .on_debug(|_, _, _| {});
+    engine
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CustomUnit {
+    pub label: String,
+    pub value: String,
+}
+
+/// The count the variables in a [Formula] are from, as shown
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Counts {
+    pub days: usize,
+    pub weeks: usize,
+    pub months: usize,
+    pub years: usize,
+}
+
+/// A compiled [CustomUnit::value]
+#[derive(Debug, Clone)]
+pub struct Formula {
+    text: String,
+    ast: AST,
+}
+
+impl PartialEq for Formula {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Formula {
+    /// Also works it out once, as unknown names and values that aren't numbers are only
+    /// found by running it, dividing by 0 and the like are fine as they're shown as 0
+    pub fn parse(text: &str) -> Result<Self, String> {
+        if text.len() > MAX_LENGTH {
+            return Err(format!("longer than {MAX_LENGTH} characters"));
+        }
+        let ast = ENGINE
+            .with(|engine| engine.compile_expression(text))
+            .map_err(|err| err.to_string())?;
+        let formula = Formula {
+            text: text.to_string(),
+            ast,
+        };
+        let ones = Counts {
+            days: 1,
+            weeks: 1,
+            months: 1,
+            years: 1,
+        };
+        match formula.run(ones) {
+            Ok(value) => number(&value).map(|_| formula),
+            Err(err) if matches!(*err, EvalAltResult::ErrorArithmetic(..)) => Ok(formula),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    /// Rounded down, negative and impossible values (i.e. dividing by zero) are 0
+    pub fn eval(&self, counts: Counts) -> usize {
+        match self.run(counts).ok().and_then(|value| number(&value).ok()) {
+            Some(value) if value.is_finite() => value.max(0.0) as usize,
+            _ => 0,
+        }
+    }
+
+    fn run(&self, counts: Counts) -> Result<Dynamic, Box<EvalAltResult>> {
+        let mut scope = Scope::new();
+        let values = [counts.days, counts.weeks, counts.months, counts.years];
+        for (name, value) in VARIABLES.into_iter().zip(values) {
+            scope.push_constant(name, value as INT);
+        }
+        ENGINE.with(|engine| engine.eval_ast_with_scope(&mut scope, &self.ast))
+    }
+}
+
+fn number(value: &Dynamic) -> Result<f64, String> {
+    value
+        .as_int()
+        .map(|value| value as f64)
+        .or_else(|_| value.as_float())
+        .map_err(|kind| format!("it's a {kind}, not a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNTS: Counts = Counts {
+        days: 100,
+        weeks: 14,
+        months: 3,
+        years: 0,
+    };
+
+    fn eval(text: &str) -> Result<usize, String> {
+        Formula::parse(text).map(|formula| formula.eval(COUNTS))
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(eval("days * 24 * 60"), Ok(144000));
+        assert_eq!(eval("(weeks + 1) * 2 ** 2"), Ok(60));
+        assert_eq!(eval("days / 3"), Ok(33));
+        assert_eq!(eval("days / 8.0"), Ok(12));
+        assert_eq!(eval("days % 7"), Ok(2));
+        assert_eq!(eval("-(-days)"), Ok(100));
+        assert_eq!(eval("if years > 0 { days } else { weeks }"), Ok(14));
+    }
+
+    #[test]
+    fn impossible_values_are_zero() {
+        assert_eq!(eval("months - days"), Ok(0));
+        assert_eq!(eval("days / years"), Ok(0));
+        assert_eq!(eval("days / (days - 1)"), Ok(1));
+        assert_eq!(eval("9223372036854775807 + days"), Ok(0));
+    }
+
+    #[test]
+    fn invalid_values() {
+        for bad in [
+            "",
+            "days *",
+            "(days",
+            "days)",
+            "hours * 2",
+            "days; 1",
+            "let x = 1",
+            "\"coffee\"",
+            "loop { }",
+            "import \"file\" as f",
+            "print(days)",
+        ] {
+            assert!(eval(bad).is_err(), "{bad:?} parsed");
+        }
+        assert!(eval(&"(".repeat(100)).is_err());
+        assert!(eval(&"days + ".repeat(50)).is_err());
+    }
+}
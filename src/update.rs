@@ -0,0 +1,215 @@
+use crate::net;
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const LATEST: &str = "https://api.github.com/repos/emmabritton/countup/releases/latest";
+/// Downloads that need unpacking, only bare binaries can be swapped in
+const ARCHIVES: [&str; 5] = [".zip", ".tar.gz", ".tgz", ".tar.xz", CHECKSUM];
+/// Published next to each binary, `sha256sum` output for it
+const CHECKSUM: &str = ".sha256";
+/// Far more than a checksum file needs
+const CHECKSUM_SIZE: u64 = 1024;
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    html_url: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    size: u64,
+    browser_download_url: String,
+}
+
+/// Checks the latest GitHub release and, unless `check_only`, replaces this binary with its
+/// download for this OS and architecture if it's newer
+pub fn run(check_only: bool) -> Result<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let release: Release = net::agent(LATEST)
+        .get(LATEST)
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .wrap_err("Checking for a new release")?
+        .into_json()
+        .wrap_err("Reading the latest release")?;
+    let latest = release.tag_name.trim_start_matches('v');
+    if version(latest) <= version(current) {
+        println!("countup {current} is the latest version");
+        return Ok(());
+    }
+    if check_only {
+        println!("countup {latest} is out (this is {current}), run `countup update` to install it");
+        return Ok(());
+    }
+    let asset = release
+        .assets
+        .iter()
+        .find(|asset| is_for_here(&asset.name))
+        .ok_or_else(|| {
+            eyre!(
+                "No download for {} {} in {}, get it from {}",
+                env::consts::OS,
+                env::consts::ARCH,
+                release.tag_name,
+                release.html_url
+            )
+        })?;
+    let checksum_name = format!("{}{CHECKSUM}", asset.name);
+    let checksum = release
+        .assets
+        .iter()
+        .find(|other| other.name == checksum_name)
+        .ok_or_else(|| {
+            eyre!(
+                "No {checksum_name} in {} to check the download with, get it from {}",
+                release.tag_name,
+                release.html_url
+            )
+        })?;
+    let exe = env::current_exe()
+        .and_then(fs::canonicalize)
+        .wrap_err("Finding this binary")?;
+    let bytes = download(asset, asset.size)?;
+    if bytes.len() as u64 != asset.size {
+        return Err(eyre!(
+            "Downloaded {} bytes of {}, expected {}",
+            bytes.len(),
+            asset.name,
+            asset.size
+        ));
+    }
+    let sums = download(checksum, CHECKSUM_SIZE)?;
+    verify(&bytes, &String::from_utf8_lossy(&sums))
+        .wrap_err_with(|| format!("Checking {} against {checksum_name}", asset.name))?;
+    replace(&exe, &bytes)?;
+    println!("Updated countup from {current} to {latest}, restart it to use the new version");
+    Ok(())
+}
+
+/// Up to one more byte than `limit`, so a download that's too big can be spotted
+fn download(asset: &Asset, limit: u64) -> Result<Vec<u8>> {
+    let mut bytes = vec![];
+    net::agent(&asset.browser_download_url)
+        .get(&asset.browser_download_url)
+        .call()
+        .wrap_err_with(|| format!("Downloading {}", asset.name))?
+        .into_reader()
+        .take(limit + 1)
+        .read_to_end(&mut bytes)
+        .wrap_err_with(|| format!("Downloading {}", asset.name))?;
+    Ok(bytes)
+}
+
+/// Fails unless `bytes` have the SHA-256 at the start of `sums`, as written by `sha256sum`
+fn verify(bytes: &[u8], sums: &str) -> Result<()> {
+    let expected = sums
+        .split_whitespace()
+        .next()
+        .filter(|hash| hash.len() == 64 && hash.chars().all(|char| char.is_ascii_hexdigit()))
+        .ok_or_else(|| eyre!("no SHA-256 in it"))?;
+    let actual: String = Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(eyre!("its SHA-256 is {actual}, expected {expected}"))
+    }
+}
+
+/// Major, minor and patch, missing or invalid parts are 0
+fn version(text: &str) -> (u64, u64, u64) {
+    let mut parts = text
+        .split(['.', '-', '+'])
+        .map(|part| part.parse().unwrap_or_default());
+    (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+    )
+}
+
+/// A bare binary for this OS and architecture, i.e. `countup-macos-x86_64`
+fn is_for_here(name: &str) -> bool {
+    let name = name.to_lowercase();
+    let os: &[&str] = match env::consts::OS {
+        "macos" => &["macos", "darwin", "apple"],
+        "windows" => &["windows", "win64"],
+        os => &[os],
+    };
+    let arch = match env::consts::ARCH {
+        "x86_64" => ["x86_64", "amd64"],
+        "aarch64" => ["aarch64", "arm64"],
+        arch => [arch, arch],
+    };
+    os.iter().any(|os| name.contains(os))
+        && arch.iter().any(|arch| name.contains(arch))
+        && !ARCHIVES.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Writes the new binary next to `exe` then swaps them, the old one is moved aside first as
+/// Windows won't overwrite a running binary but will rename it
+fn replace(exe: &Path, bytes: &[u8]) -> Result<()> {
+    let sibling = |ext: &str| -> PathBuf {
+        let mut name = exe.file_name().unwrap_or_default().to_os_string();
+        name.push(ext);
+        exe.with_file_name(name)
+    };
+    let new = sibling(".new");
+    let old = sibling(".old");
+    //left behind by the last update on Windows
+    let _ = fs::remove_file(&old);
+    fs::write(&new, bytes).wrap_err_with(|| format!("Writing {}", new.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&new, fs::Permissions::from_mode(0o755))
+            .wrap_err_with(|| format!("Making {} executable", new.display()))?;
+    }
+    fs::rename(exe, &old).wrap_err_with(|| format!("Moving {} aside", exe.display()))?;
+    if let Err(err) = fs::rename(&new, exe) {
+        let _ = fs::rename(&old, exe);
+        return Err(err).wrap_err_with(|| format!("Replacing {}", exe.display()));
+    }
+    //still running so it can't be deleted on Windows, it's removed by the next update
+    let _ = fs::remove_file(&old);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_against_sha256sum_output() {
+        let sums = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9  countup-macos-x86_64\n";
+        assert!(verify(b"hello world", sums).is_ok());
+        assert!(verify(b"hello world!", sums).is_err());
+        assert!(verify(b"hello world", "").is_err());
+        assert!(verify(b"hello world", "not-a-hash countup").is_err());
+    }
+
+    #[test]
+    fn release_binaries_match_their_platform() {
+        let binary = match (env::consts::OS, env::consts::ARCH) {
+            ("macos", "x86_64") => "countup-macos-x86_64",
+            ("windows", "x86_64") => "countup-windows-x86_64.exe",
+            ("linux", "x86_64") => "countup-linux-x86_64",
+            _ => return,
+        };
+        assert!(is_for_here(binary));
+        assert!(!is_for_here(&format!("{binary}.sha256")));
+        assert!(!is_for_here("countup-mac.tar.gz"));
+        assert!(!is_for_here("countup-windows.zip"));
+    }
+}
@@ -0,0 +1,389 @@
+//! Counter drawn to a canvas, the date and options are read from the page's URL,
+//! i.e. `index.html?date=2022-11-25&lang=de&format=%25Y-%25m-%25d`
+
+use crate::count::{
+    calc_days_since, date_modes, Animation, Count, IsoWeeks, Mode, TimeSource, Timeline, Totals,
+    Weekdays, YearProgress,
+};
+use crate::date::parse_date;
+use crate::event::Rgb;
+use crate::font::NumberFont;
+use crate::i18n::{strings, NumberFormat, Numerals, Strings};
+use crate::render::{
+    render_bases, render_diff, render_help, render_moons, render_segments, render_split,
+    render_timeline, render_tminus, render_totals, render_upcoming, render_weekdays, render_weeks,
+    render_weeks_and_days, render_year, Palette, Reveal, Texts,
+};
+use crate::report::{Counting, MonthLength, Moons, Report, Split, Upcoming};
+use crate::DEFAULT_DATE_FORMAT;
+use buffer_graphics_lib::Graphics;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, Utc};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{Clamped, JsCast};
+use web_sys::{
+    console, CanvasRenderingContext2d, HtmlCanvasElement, ImageData, KeyboardEvent,
+    UrlSearchParams, Window,
+};
+
+/// Same size as the window in the app, the page scales it up
+const WIDTH: usize = 270;
+const HEIGHT: usize = 90;
+/// Id of the canvas to draw to
+const CANVAS_ID: &str = "countup";
+
+type FrameCallback = Closure<dyn FnMut(f64)>;
+
+struct Web {
+    count: Count,
+    start: String,
+    start_date: DateTime<Utc>,
+    date_format: String,
+    remaining: Duration,
+    mode: Mode,
+    modes: Vec<Mode>,
+    strings: &'static Strings,
+    font: NumberFont,
+    palette: Palette,
+    texts: Texts,
+    /// Showing the keys and settings over the current mode
+    help: bool,
+    /// Settings from the URL, for the help overlay
+    settings: Vec<(&'static str, String)>,
+    buffer: Vec<u8>,
+    context: CanvasRenderingContext2d,
+}
+
+impl Web {
+    fn next_mode(&mut self) {
+        self.count.restart();
+        let idx = self.modes.iter().position(|mode| *mode == self.mode);
+        self.mode = self.modes[idx.map(|i| (i + 1) % self.modes.len()).unwrap_or(0)];
+    }
+
+    /// `now` is in seconds
+    fn update(&mut self, now: f64) {
+        if self.mode == Mode::TMinus {
+            self.remaining = self.start_date - Utc::now();
+        } else if self.count.is_counting() || self.count.is_flashing() {
+            self.count.update(now);
+        } else {
+            let (_, days) = calc_days_since(
+                self.start_date.date_naive(),
+                None,
+                Counting::Exclusive,
+                TimeSource::System,
+            );
+            if days != self.count.days {
+                self.count.roll_over(days);
+            }
+        }
+    }
+
+    fn render(&mut self) -> Result<(), JsValue> {
+        let mut graphics = Graphics::new(&mut self.buffer, WIDTH, HEIGHT)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        let palette = self.palette.flashed(self.count.flash());
+        match self.mode {
+            Mode::TMinus => render_tminus(
+                &mut graphics,
+                self.remaining,
+                &self.start,
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Mode::Segments => render_segments(
+                &mut graphics,
+                self.count.shown(),
+                HEIGHT,
+                palette,
+                &self.texts,
+            ),
+            Mode::Diff => render_diff(
+                &mut graphics,
+                self.count.shown(),
+                MonthLength::default().months(self.start_date.date_naive(), self.count.shown()),
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Mode::WeeksAndDays => render_weeks_and_days(
+                &mut graphics,
+                self.count.shown(),
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Mode::Year => {
+                let progress =
+                    YearProgress::new(self.start_date.date_naive(), Utc::now().date_naive());
+                let from = progress.from.format(&self.date_format).to_string();
+                render_year(
+                    &mut graphics,
+                    progress,
+                    &self.strings.year_from(progress.year, &from),
+                    Reveal::of(&self.count),
+                    &self.font,
+                    palette,
+                    &self.texts,
+                )
+            }
+            Mode::Moons => render_moons(
+                &mut graphics,
+                Moons::new(self.start_date.date_naive(), Utc::now().date_naive()),
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Mode::Weeks => render_weeks(
+                &mut graphics,
+                IsoWeeks::new(self.start_date.date_naive(), Utc::now().date_naive()),
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            Mode::Totals => render_totals(
+                &mut graphics,
+                Totals::new(self.start_date, Utc::now()),
+                &self.start,
+                Reveal::of(&self.count),
+                palette,
+                &self.texts,
+            ),
+            Mode::Upcoming => {
+                let upcoming = Upcoming::new(
+                    self.start_date.date_naive(),
+                    Utc::now().date_naive(),
+                    self.count.days,
+                );
+                let dates = [upcoming.hundred, upcoming.thousand, upcoming.anniversary]
+                    .map(|ahead| ahead.date.format(&self.date_format).to_string());
+                render_upcoming(
+                    &mut graphics,
+                    upcoming,
+                    &dates,
+                    Reveal::of(&self.count),
+                    palette,
+                    &self.texts,
+                )
+            }
+            Mode::Weekdays => {
+                let weekdays = Weekdays::new(self.start_date.date_naive(), Utc::now().date_naive());
+                render_weekdays(
+                    &mut graphics,
+                    weekdays,
+                    &self.strings.started_on(weekdays.start),
+                    Reveal::of(&self.count),
+                    palette,
+                    &self.texts,
+                )
+            }
+            Mode::Timeline => {
+                let (start, today) = (self.start_date.date_naive(), Utc::now().date_naive());
+                let milestone = Report::new(start, today, None).next_milestone;
+                let labels = [
+                    self.start.clone(),
+                    milestone.date.format(&self.date_format).to_string(),
+                    milestone.name,
+                ];
+                render_timeline(
+                    &mut graphics,
+                    Timeline::new(start, today, milestone.date),
+                    &labels,
+                    Reveal::of(&self.count),
+                    palette,
+                    &self.texts,
+                )
+            }
+            Mode::Bases => render_bases(
+                &mut graphics,
+                self.count.shown(),
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+            _ => render_split(
+                &mut graphics,
+                Split::new(self.count.shown()),
+                None,
+                &self.start,
+                Reveal::of(&self.count),
+                &self.font,
+                palette,
+                &self.texts,
+            ),
+        }
+        if self.help {
+            let shortcuts = [
+                ("Space", self.strings.next_mode),
+                ("Enter", self.strings.skip),
+                ("P", self.strings.pause),
+                ("R", self.strings.restart),
+                ("I", self.strings.next_intro),
+            ];
+            let mut settings = vec![
+                ("mode", format!("{:?}", self.mode).to_lowercase()),
+                ("intro", self.count.intro().name().to_string()),
+            ];
+            settings.extend(self.settings.iter().cloned());
+            render_help(&mut graphics, &shortcuts, &settings, self.strings);
+        }
+        let image = ImageData::new_with_u8_clamped_array_and_sh(
+            Clamped(&self.buffer),
+            WIDTH as u32,
+            HEIGHT as u32,
+        )?;
+        self.context.put_image_data(&image, 0.0, 0.0)
+    }
+}
+
+#[wasm_bindgen(start)]
+pub fn start() -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let params = UrlSearchParams::new_with_str(&window.location().search()?)?;
+    let date = params
+        .get("date")
+        .ok_or("Add the date to the URL, i.e. ?date=2022-11-25")?;
+    let date = parse_date(&date).ok_or_else(|| {
+        format!("Invalid date {date}, use yyyy-mm-dd, yyyy/mm/dd, dd-mm-yyyy, yyyymmdd or a unix timestamp")
+    })?;
+    let lang = params
+        .get("lang")
+        .or_else(|| window.navigator().language())
+        .unwrap_or_default();
+    let lang = lang.split('-').next().unwrap_or_default().to_lowercase();
+    let date_format = params
+        .get("format")
+        .unwrap_or_else(|| DEFAULT_DATE_FORMAT.to_string());
+    if StrftimeItems::new(&date_format).any(|item| item == Item::Error) {
+        return Err(format!("Invalid date format: {date_format}").into());
+    }
+    let strings = strings(&lang);
+    let numerals = match params.get("numerals") {
+        Some(name) => Numerals::from_name(&name).ok_or_else(|| {
+            format!(
+                "Invalid numerals {name}, must be one of {:?}",
+                Numerals::NAMES
+            )
+        })?,
+        None => strings.numerals,
+    };
+    let number_format = match params.get("number-format") {
+        Some(name) => NumberFormat::from_name(&name).ok_or_else(|| {
+            format!(
+                "Invalid number format {name}, must be one of {:?}",
+                NumberFormat::NAMES
+            )
+        })?,
+        None => NumberFormat::default(),
+    };
+    //the same colours as an event in the config, `#` must be URL encoded as `%23`
+    let color = |key: &str| -> Result<Option<Rgb>, JsValue> {
+        params
+            .get(key)
+            .map(|text| text.parse::<Rgb>())
+            .transpose()
+            .map_err(JsValue::from)
+    };
+    let palette = Palette::new(color("background")?, color("accent")?);
+
+    let canvas: HtmlCanvasElement = window
+        .document()
+        .and_then(|document| document.get_element_by_id(CANVAS_ID))
+        .ok_or_else(|| format!("No element with id {CANVAS_ID}"))?
+        .dyn_into()?;
+    canvas.set_width(WIDTH as u32);
+    canvas.set_height(HEIGHT as u32);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or("Canvas 2D not supported")?
+        .dyn_into()?;
+
+    let (start_date, days) = calc_days_since(date, None, Counting::Exclusive, TimeSource::System);
+    let modes = date_modes(start_date, TimeSource::System);
+    let web = Rc::new(RefCell::new(Web {
+        count: Count::new(days, Animation::default()),
+        start: start_date.format(&date_format).to_string(),
+        start_date,
+        date_format: date_format.clone(),
+        remaining: start_date - Utc::now(),
+        mode: modes[0],
+        modes,
+        strings,
+        font: NumberFont::default().with_numerals(numerals),
+        palette,
+        texts: Texts::new(strings).with_separator(number_format.separator(strings)),
+        help: false,
+        settings: vec![
+            ("lang", lang.clone()),
+            ("numerals", numerals.name().to_string()),
+            ("number_format", number_format.name().to_string()),
+            ("format", date_format.clone()),
+        ],
+        buffer: vec![0; WIDTH * HEIGHT * 4],
+        context,
+    }));
+
+    //the same keys as the app, clicking also swaps modes
+    let keys = web.clone();
+    let on_key = Closure::<dyn FnMut(KeyboardEvent)>::new(move |event: KeyboardEvent| {
+        let mut web = keys.borrow_mut();
+        if web.help {
+            web.help = false;
+            return;
+        }
+        match event.key().as_str() {
+            " " => {
+                event.prevent_default();
+                web.next_mode();
+            }
+            "Enter" | "End" => web.count.skip(),
+            "p" | "P" => web.count.toggle_pause(),
+            "r" | "R" => web.count.restart(),
+            "i" | "I" => web.count.next_intro(),
+            "?" | "h" | "H" => web.help = true,
+            _ => {}
+        }
+    });
+    window.add_event_listener_with_callback("keydown", on_key.as_ref().unchecked_ref())?;
+    on_key.forget();
+    let clicks = web.clone();
+    let on_click = Closure::<dyn FnMut()>::new(move || clicks.borrow_mut().next_mode());
+    canvas.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())?;
+    on_click.forget();
+
+    //the callback has to request the next frame with itself
+    let frame: Rc<RefCell<Option<FrameCallback>>> = Rc::new(RefCell::new(None));
+    let next_frame = frame.clone();
+    *frame.borrow_mut() = Some(Closure::new(move |now: f64| {
+        let mut web = web.borrow_mut();
+        web.update(now / 1000.0);
+        if let Err(err) = web.render() {
+            console::error_1(&err);
+        }
+        if let (Some(window), Some(callback)) = (web_sys::window(), next_frame.borrow().as_ref()) {
+            if let Err(err) = request_frame(&window, callback) {
+                console::error_1(&err);
+            }
+        }
+    }));
+    request_frame(&window, frame.borrow().as_ref().expect("just set"))?;
+    Ok(())
+}
+
+fn request_frame(window: &Window, callback: &FrameCallback) -> Result<(), JsValue> {
+    window.request_animation_frame(callback.as_ref().unchecked_ref())?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::event::Event;
+use crate::{cli, shutdown};
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use std::env;
+use std::process::Command;
+
+/// Opens a window for each event by running countup again with the same arguments, as
+/// the window library can only run one window per process
+///
+/// Waits for every window to close, signals are ignored here as each window handles them
+/// itself, saving its position first
+pub fn open(events: &[Event]) -> Result<()> {
+    if events.is_empty() {
+        return Err(eyre!(cli::text().no_events));
+    }
+    let exe = env::current_exe().wrap_err("Finding the countup executable")?;
+    let args: Vec<String> = env::args()
+        .skip(1)
+        .filter(|arg| arg != "--windows")
+        .collect();
+    shutdown::install(|| {});
+    let mut children = vec![];
+    for event in events {
+        let child = Command::new(&exe)
+            .args(&args)
+            .args(["--window", &event.name])
+            .spawn()
+            .wrap_err_with(|| format!("Opening the window for {}", event.name))?;
+        children.push(child);
+    }
+    for mut child in children {
+        child.wait()?;
+    }
+    Ok(())
+}
+
+/// Name for the window position prefs of the window opened for `event`
+pub fn prefs_name(event: &str) -> String {
+    let slug: String = event
+        .chars()
+        .map(|chr| {
+            if chr.is_alphanumeric() {
+                chr.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("countup-{slug}")
+}